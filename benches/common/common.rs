@@ -45,25 +45,25 @@ pub fn print_table(cashflows: &[Cashflow], market_data: &Rc<Vec<MarketData>>) {
         "{:10} | {:10} | {:10} | {:10}| {:10}",
         "Date", "Amount", "DF", "FWD", "FX"
     );
-    for (cf, md) in cashflows.iter().zip(market_data.deref()) {
+    for (cf, md) in cashflows.iter().zip(&**market_data) {
         let date = format!("{:10}", cf.payment_date());
 
         let amount = cf.amount().map_or_else(
             |_| "None      ".to_string(),
-            |amt| format!("{:10.2}", amt),
+            |amt| format!("{amt:10.2}"),
         );
 
         let df = md
             .df()
-            .map_or_else(|_| "None      ".to_string(), |df| format!("{:10.2}", df));
+            .map_or_else(|_| "None      ".to_string(), |df| format!("{df:10.2}"));
 
         let fx = md
             .fx()
-            .map_or_else(|_| "None      ".to_string(), |fx| format!("{:10.2}", fx));
+            .map_or_else(|_| "None      ".to_string(), |fx| format!("{fx:10.2}"));
 
         let fwd = md
             .fwd()
-            .map_or_else(|_| "None      ".to_string(), |fwd| format!("{:9.3}", fwd));
+            .map_or_else(|_| "None      ".to_string(), |fwd| format!("{fwd:9.3}"));
 
         println!("{date} | {amount} | {df} | {fwd} | {fx}");
     }
@@ -1,6 +1,6 @@
 //! Benchmark for fixed rate pricing calculations.
 extern crate rustatlas;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator, ParallelSliceMut};
 use rustatlas::{
     cashflows::cashflow::Side,
     currencies::enums::Currency,
@@ -23,7 +23,7 @@ use rustatlas::{
 use std::sync::Arc;
 mod common;
 use crate::common::common::create_store;
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_main, Criterion};
 
 fn npv(instruments: &mut [FixedRateInstrument]) -> Result<f64, Box<dyn std::error::Error>> {
     let store = Arc::new(create_store()?);
@@ -35,7 +35,7 @@ fn npv(instruments: &mut [FixedRateInstrument]) -> Result<f64, Box<dyn std::erro
     for inst in instruments.iter_mut() {
         indexer
             .visit(inst)
-            .map_err(|e| format!("IndexingVisitor failed: {}", e))?;
+            .map_err(|e| format!("IndexingVisitor failed: {e}"))?;
     }
 
     let model = SimpleModel::new(&store);
@@ -47,7 +47,7 @@ fn npv(instruments: &mut [FixedRateInstrument]) -> Result<f64, Box<dyn std::erro
     for inst in instruments.iter() {
         let inst_npv = npv_visitor
             .visit(inst)
-            .map_err(|e| format!("NPVConstVisitor failed: {}", e))?;
+            .map_err(|e| format!("NPVConstVisitor failed: {e}"))?;
         total_npv += inst_npv;
     }
 
@@ -89,7 +89,7 @@ fn multiple() -> Result<(), Box<dyn std::error::Error>> {
     // Process instruments in parallel chunks
     instruments.par_rchunks_mut(1000).for_each(|chunk| {
         if let Err(e) = npv(chunk) {
-            eprintln!("Error processing chunk: {}", e);
+            eprintln!("Error processing chunk: {e}");
         }
     });
 
@@ -100,10 +100,19 @@ fn multiple() -> Result<(), Box<dyn std::error::Error>> {
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("multiple", |b| {
         b.iter(|| {
-            multiple().expect("benchmark failed")
-        })
+            if let Err(e) = multiple() {
+                panic!("benchmark failed: {e}");
+            }
+        });
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+/// Benchmark group of the fixed rate pricing calculations.
+mod group {
+    use super::criterion_benchmark;
+    use criterion::criterion_group;
+
+    criterion_group!(benches, criterion_benchmark);
+}
+
+criterion_main!(group::benches);
\ No newline at end of file
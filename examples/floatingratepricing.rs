@@ -44,7 +44,7 @@ fn starting_today_pricing() -> AppResult<()> {
     let indexer = IndexingVisitor::new();
     indexer
         .visit(&mut instrument)
-        .map_err(|e| format!("IndexingVisitor failed: {}", e))?;
+        .map_err(|e| format!("IndexingVisitor failed: {e}"))?;
 
     let model = SimpleModel::new(&market_store);
     let data = model.gen_market_data(&indexer.request())?;
@@ -58,11 +58,11 @@ fn starting_today_pricing() -> AppResult<()> {
     let npv = npv_visitor.visit(&instrument)?;
 
     print_separator();
-    println!("NPV: {}", npv);
+    println!("NPV: {npv}");
 
     let par_visitor = ParValueConstVisitor::new(&data);
     let par_value = par_visitor.visit(&instrument)?;
-    println!("Par Value: {}", par_value);
+    println!("Par Value: {par_value}");
 
     Ok(())
 }
@@ -89,7 +89,7 @@ fn already_started_pricing() -> AppResult<()> {
     let indexer = IndexingVisitor::new();
     indexer
         .visit(&mut instrument)
-        .map_err(|e| format!("IndexingVisitor failed: {}", e))?;
+        .map_err(|e| format!("IndexingVisitor failed: {e}"))?;
 
     let model = SimpleModel::new(&market_store);
     let data = model.gen_market_data(&indexer.request())?;
@@ -103,17 +103,17 @@ fn already_started_pricing() -> AppResult<()> {
     let npv = npv_visitor.visit(&instrument)?;
 
     print_separator();
-    println!("NPV: {}", npv);
+    println!("NPV: {npv}");
 
     Ok(())
 }
 
 fn main() {
     if let Err(e) = starting_today_pricing() {
-        eprintln!("Error in starting_today_pricing: {}", e);
+        eprintln!("Error in starting_today_pricing: {e}");
     }
 
     if let Err(e) = already_started_pricing() {
-        eprintln!("Error in already_started_pricing: {}", e);
+        eprintln!("Error in already_started_pricing: {e}");
     }
 }
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::{cell::RefCell, collections::BTreeMap};
 use crate::{
     cashflows::{cashflow::Side, traits::Payable},
     core::traits::HasCurrency,
-    currencies::enums::Currency,
+    currencies::{enums::Currency, money::Money},
     time::date::Date,
     utils::errors::{AtlasError, Result},
     visitors::traits::HasCashflows,
@@ -84,6 +84,38 @@ impl CashAccount {
         Ok(())
     }
 
+    /// Adds cash flows from a map of dates to `Money`. Nothing is added if any flow is in a
+    /// currency other than the account currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account currency is not set or does not match the currency of
+    /// the flows.
+    pub fn add_flows_from_money_map(&self, map: &BTreeMap<Date, Money>) -> Result<()> {
+        let account_currency = self.currency()?;
+        map.values().try_for_each(|money| -> Result<()> {
+            Money::zero(account_currency).checked_add(*money)?;
+            Ok(())
+        })?;
+        let mut amount_map = self.amount.borrow_mut();
+        for (date, money) in map {
+            let entry = amount_map.entry(*date).or_insert(0.0);
+            *entry += money.amount();
+        }
+        Ok(())
+    }
+
+    /// Adds a cash flow given as `Money` at a specific date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account currency is not set or does not match the currency of
+    /// the flow.
+    pub fn add_money_flow(&self, date: Date, money: Money) -> Result<()> {
+        let money = Money::zero(self.currency()?).checked_add(money)?;
+        self.add_flows_from_new_position(date, money.amount())
+    }
+
     /// Adds a cash flow from a new position at a specific date.
     ///
     /// # Errors
@@ -127,6 +159,20 @@ impl CashAccount {
         }
         Ok(cash_account)
     }
+
+    /// Returns the cumulative cash account evolution as `Money` in the account currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account currency is not set.
+    pub fn money_evolution(&self, evals_dates: Vec<Date>) -> Result<BTreeMap<Date, Money>> {
+        let currency = self.currency()?;
+        Ok(self
+            .cash_account_evolution(evals_dates)?
+            .into_iter()
+            .map(|(date, amount)| (date, Money::new(amount, currency)))
+            .collect())
+    }
 }
 
 impl Default for CashAccount {
@@ -253,4 +299,25 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_money_flows() -> Result<()> {
+        let cash_account = CashAccount::new().with_currency(Currency::USD);
+        let date = Date::new(2020, 1, 1);
+        cash_account.add_money_flow(date, Money::new(100.0, Currency::USD))?;
+        assert!(cash_account
+            .add_money_flow(date, Money::new(90_000.0, Currency::CLP))
+            .is_err());
+
+        let map = BTreeMap::from([
+            (Date::new(2020, 7, 1), Money::new(50.0, Currency::USD)),
+            (Date::new(2021, 1, 1), Money::new(45_000.0, Currency::CLP)),
+        ]);
+        assert!(cash_account.add_flows_from_money_map(&map).is_err());
+        assert_eq!(cash_account.amount.borrow().len(), 1);
+
+        let evolution = cash_account.money_evolution(vec![date, Date::new(2020, 7, 1)])?;
+        assert_eq!(evolution[&date], Money::new(100.0, Currency::USD));
+        Ok(())
+    }
 }
@@ -50,6 +50,7 @@ pub struct Portfolio {
     position_type: Option<PositionType>,
     rate_type: Option<RateType>,
     currency: Option<Currency>,
+    account_type: Option<AccountType>,
     instruments: Vec<Instrument>,
 }
 
@@ -66,6 +67,7 @@ impl Portfolio {
             position_type: None,
             rate_type: None,
             currency: None,
+            account_type: None,
             instruments: Vec::new(),
         }
     }
@@ -112,6 +114,12 @@ impl Portfolio {
         self.currency
     }
 
+    /// Returns the portfolio account type.
+    #[must_use]
+    pub const fn account_type(&self) -> Option<AccountType> {
+        self.account_type
+    }
+
     /// Sets the portfolio currency.
     #[must_use]
     pub const fn with_currency(mut self, currency: Currency) -> Self {
@@ -161,6 +169,13 @@ impl Portfolio {
         self
     }
 
+    /// Sets the portfolio account type.
+    #[must_use]
+    pub const fn with_account_type(mut self, account_type: AccountType) -> Self {
+        self.account_type = Some(account_type);
+        self
+    }
+
     /// Sets the portfolio instruments.
     #[must_use]
     pub fn with_instruments(mut self, instruments: Vec<Instrument>) -> Self {
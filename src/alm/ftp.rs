@@ -0,0 +1,792 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::enums::Portfolio;
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        simplecashflow::SimpleCashflow,
+        traits::Payable,
+    },
+    core::{marketstore::MarketStore, meta::MarketData, traits::HasCurrency},
+    currencies::enums::Currency,
+    instruments::{
+        fixedrateinstrument::{BondAccrual, FixedRateInstrument},
+        floatingrateinstrument::FloatingRateInstrument,
+        instrument::Instrument,
+    },
+    models::{simplemodel::SimpleModel, traits::Model},
+    rates::{
+        indexstore::ReadIndex, interestrate::RateDefinition, traits::HasReferenceDate,
+        yieldtermstructure::compositetermstructure::CompositeTermStructure,
+    },
+    time::{date::Date, enums::TimeUnit, period::Period},
+    utils::errors::{AtlasError, Result},
+    visitors::{
+        durationconstvisitor::DurationConstVisitor,
+        fixingvisitor::FixingVisitor,
+        indexingvisitor::IndexingVisitor,
+        parvaluevisitor::ParValueConstVisitor,
+        traits::{ConstVisit, HasCashflows, Visit},
+    },
+};
+
+/// # `FtpMethod`
+/// How the funds transfer price of a position is taken from the FTP curve.
+///
+/// ## Variants
+/// * `MatchedMaturity` - The par rate on the FTP curve of the cashflows of the position.
+/// * `DurationMatched` - The rate of the FTP curve at the duration of the position.
+/// * `SinglePool` - The rate of the FTP curve at the same tenor for every position.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FtpMethod {
+    /// Par rate of the cashflow profile.
+    MatchedMaturity,
+    /// Rate at the duration.
+    DurationMatched,
+    /// Rate at a single tenor.
+    SinglePool(Period),
+}
+
+/// # `FtpPosition`
+/// The funds transfer price of a position and its margin.
+///
+/// Rates of fixed rate positions are all-in rates, while those of floating rate positions are
+/// spreads over their index. The premiums are what each add-on curve adds to the FTP rate.
+#[derive(Clone, Debug)]
+pub struct FtpPosition {
+    portfolio: usize,
+    id: Option<String>,
+    side: Side,
+    currency: Currency,
+    outstanding: f64,
+    client_rate: f64,
+    base_rate: f64,
+    liquidity_premium: f64,
+    optionality_premium: f64,
+}
+
+impl FtpPosition {
+    /// Returns the index of the portfolio of the position.
+    #[must_use]
+    pub const fn portfolio(&self) -> usize {
+        self.portfolio
+    }
+
+    /// Returns the id of the instrument.
+    #[must_use]
+    pub fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+
+    /// Returns the side of the instrument.
+    #[must_use]
+    pub const fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Returns the currency of the instrument.
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the outstanding notional after the reference date.
+    #[must_use]
+    pub const fn outstanding(&self) -> f64 {
+        self.outstanding
+    }
+
+    /// Returns the rate or spread paid by the client.
+    #[must_use]
+    pub const fn client_rate(&self) -> f64 {
+        self.client_rate
+    }
+
+    /// Returns the FTP rate given by the base curve.
+    #[must_use]
+    pub const fn base_rate(&self) -> f64 {
+        self.base_rate
+    }
+
+    /// Returns the liquidity premium.
+    #[must_use]
+    pub const fn liquidity_premium(&self) -> f64 {
+        self.liquidity_premium
+    }
+
+    /// Returns the optionality premium.
+    #[must_use]
+    pub const fn optionality_premium(&self) -> f64 {
+        self.optionality_premium
+    }
+
+    /// Returns the FTP rate, the base rate plus the premiums.
+    #[must_use]
+    pub fn ftp_rate(&self) -> f64 {
+        self.base_rate + self.liquidity_premium + self.optionality_premium
+    }
+
+    /// Returns the commercial margin: the client rate over the FTP rate for assets and the FTP
+    /// rate over the client rate for liabilities.
+    #[must_use]
+    pub fn margin(&self) -> f64 {
+        self.side.sign() * (self.client_rate - self.ftp_rate())
+    }
+}
+
+/// # `FtpAggregate`
+/// The funds transfer prices of a group of positions, averaged with their outstanding in the
+/// local currency of the market.
+#[derive(Clone, Debug, Default)]
+pub struct FtpAggregate {
+    outstanding: f64,
+    client_rate: f64,
+    base_rate: f64,
+    liquidity_premium: f64,
+    optionality_premium: f64,
+    margin: f64,
+}
+
+impl FtpAggregate {
+    fn new(market_store: &MarketStore, positions: &[&FtpPosition]) -> Result<Self> {
+        let mut aggregate = Self::default();
+        for position in positions {
+            let weight =
+                position.outstanding / market_store.get_exchange_rate(position.currency, None)?;
+            aggregate.outstanding += weight;
+            aggregate.client_rate += weight * position.client_rate;
+            aggregate.base_rate += weight * position.base_rate;
+            aggregate.liquidity_premium += weight * position.liquidity_premium;
+            aggregate.optionality_premium += weight * position.optionality_premium;
+            aggregate.margin += weight * position.margin();
+        }
+        if aggregate.outstanding > 0.0 {
+            let outstanding = aggregate.outstanding;
+            aggregate.client_rate /= outstanding;
+            aggregate.base_rate /= outstanding;
+            aggregate.liquidity_premium /= outstanding;
+            aggregate.optionality_premium /= outstanding;
+            aggregate.margin /= outstanding;
+        }
+        Ok(aggregate)
+    }
+
+    /// Returns the outstanding in the local currency.
+    #[must_use]
+    pub const fn outstanding(&self) -> f64 {
+        self.outstanding
+    }
+
+    /// Returns the average client rate.
+    #[must_use]
+    pub const fn client_rate(&self) -> f64 {
+        self.client_rate
+    }
+
+    /// Returns the average base rate.
+    #[must_use]
+    pub const fn base_rate(&self) -> f64 {
+        self.base_rate
+    }
+
+    /// Returns the average liquidity premium.
+    #[must_use]
+    pub const fn liquidity_premium(&self) -> f64 {
+        self.liquidity_premium
+    }
+
+    /// Returns the average optionality premium.
+    #[must_use]
+    pub const fn optionality_premium(&self) -> f64 {
+        self.optionality_premium
+    }
+
+    /// Returns the average FTP rate.
+    #[must_use]
+    pub fn ftp_rate(&self) -> f64 {
+        self.base_rate + self.liquidity_premium + self.optionality_premium
+    }
+
+    /// Returns the average commercial margin.
+    #[must_use]
+    pub const fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    /// Returns the annual margin amount in the local currency.
+    #[must_use]
+    pub fn margin_amount(&self) -> f64 {
+        self.outstanding * self.margin
+    }
+}
+
+/// # `FtpReport`
+/// The funds transfer prices of every position, of every portfolio and of the whole book.
+#[derive(Clone, Debug)]
+pub struct FtpReport {
+    positions: Vec<FtpPosition>,
+    portfolios: Vec<FtpAggregate>,
+    total: FtpAggregate,
+}
+
+impl FtpReport {
+    /// Returns the positions, in the order of the portfolios and their instruments.
+    #[must_use]
+    pub fn positions(&self) -> &[FtpPosition] {
+        &self.positions
+    }
+
+    /// Returns the aggregate of each portfolio, in the order they were given.
+    #[must_use]
+    pub fn portfolios(&self) -> &[FtpAggregate] {
+        &self.portfolios
+    }
+
+    /// Returns the aggregate of all the positions.
+    #[must_use]
+    pub const fn total(&self) -> &FtpAggregate {
+        &self.total
+    }
+}
+
+/// # `FtpEngine`
+/// Assigns funds transfer prices to the positions of a book on the reference date of the
+/// market store.
+///
+/// The FTP curve is an index of the market store. The liquidity and optionality add-on curves
+/// are indices whose term structures hold the premiums as zero rates; each is stacked on the
+/// curves before it with a `CompositeTermStructure`, and its premium is the change of the FTP
+/// rate it causes.
+///
+/// With `MatchedMaturity`, the cashflows paid after the reference date are priced at par on
+/// the outstanding notional, disbursed on the reference date if the position started before.
+/// Fixed rate positions get a par rate and floating rate ones a par spread over their index,
+/// which keeps forecasting their coupons. With the other methods the FTP rate is the forward
+/// rate of the curve from the reference date to the duration or pool tenor, quoted with the
+/// rate definition of the position; for floating rate positions the forward rate of their
+/// index over the same term is subtracted from it.
+///
+/// ## Parameters
+/// * `market_store` - The market store
+/// * `ftp_curve_id` - The index of the FTP curve
+/// * `method` - The FTP method, defaults to `MatchedMaturity`
+/// * `liquidity_curve_id` - The index of the liquidity premium curve
+/// * `optionality_curve_id` - The index of the optionality premium curve
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+/// use std::sync::{Arc, RwLock};
+///
+/// let reference_date = Date::new(2024, 1, 1);
+/// let mut market_store = MarketStore::new(reference_date, Currency::USD);
+/// let curve = FlatForwardTermStructure::new(reference_date, 0.04, RateDefinition::default());
+/// let index = IborIndex::new(reference_date).with_term_structure(Arc::new(curve));
+/// market_store
+///     .mut_index_store()
+///     .add_index(0, Arc::new(RwLock::new(index)))
+///     .unwrap();
+///
+/// let loan = MakeFixedRateInstrument::new()
+///     .with_start_date(reference_date)
+///     .with_end_date(Date::new(2026, 1, 1))
+///     .with_payment_frequency(Frequency::Annual)
+///     .with_rate_value(0.06)
+///     .with_rate_definition(RateDefinition::default())
+///     .with_notional(100.0)
+///     .with_side(Side::Receive)
+///     .with_currency(Currency::USD)
+///     .with_discount_curve_id(Some(0))
+///     .bullet()
+///     .build()
+///     .unwrap();
+/// let portfolio =
+///     Portfolio::new().with_instruments(vec![Instrument::FixedRateInstrument(loan)]);
+///
+/// let report = FtpEngine::new(&market_store, 0).run(&[portfolio]).unwrap();
+/// let position = &report.positions()[0];
+/// assert!((position.ftp_rate() - 0.04).abs() < 1e-3);
+/// assert!((position.margin() - (0.06 - position.ftp_rate())).abs() < 1e-12);
+/// ```
+pub struct FtpEngine<'a> {
+    market_store: &'a MarketStore,
+    ftp_curve_id: usize,
+    method: FtpMethod,
+    liquidity_curve_id: Option<usize>,
+    optionality_curve_id: Option<usize>,
+}
+
+impl<'a> FtpEngine<'a> {
+    /// Creates a new `FtpEngine`.
+    #[must_use]
+    pub const fn new(market_store: &'a MarketStore, ftp_curve_id: usize) -> Self {
+        Self {
+            market_store,
+            ftp_curve_id,
+            method: FtpMethod::MatchedMaturity,
+            liquidity_curve_id: None,
+            optionality_curve_id: None,
+        }
+    }
+
+    /// Sets the FTP method.
+    #[must_use]
+    pub const fn with_method(mut self, method: FtpMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Sets the index of the liquidity premium curve.
+    #[must_use]
+    pub const fn with_liquidity_curve_id(mut self, liquidity_curve_id: usize) -> Self {
+        self.liquidity_curve_id = Some(liquidity_curve_id);
+        self
+    }
+
+    /// Sets the index of the optionality premium curve.
+    #[must_use]
+    pub const fn with_optionality_curve_id(mut self, optionality_curve_id: usize) -> Self {
+        self.optionality_curve_id = Some(optionality_curve_id);
+        self
+    }
+
+    /// Returns the FTP method.
+    #[must_use]
+    pub const fn method(&self) -> FtpMethod {
+        self.method
+    }
+
+    /// Computes the funds transfer prices of the positions of the portfolios.
+    ///
+    /// # Errors
+    /// Returns an error if a curve is not found, an instrument is neither fixed nor floating
+    /// rate, or a position cannot be priced.
+    pub fn run(&self, portfolios: &[Portfolio]) -> Result<FtpReport> {
+        let (market_store, curve_ids) = self.ftp_market_store()?;
+        let mut positions = Vec::new();
+        for (i, portfolio) in portfolios.iter().enumerate() {
+            for instrument in portfolio.instruments() {
+                positions.push(self.position(&market_store, &curve_ids, i, instrument)?);
+            }
+        }
+        let portfolio_aggregates = (0..portfolios.len())
+            .map(|i| {
+                let members: Vec<&FtpPosition> =
+                    positions.iter().filter(|p| p.portfolio == i).collect();
+                FtpAggregate::new(self.market_store, &members)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let total = FtpAggregate::new(self.market_store, &positions.iter().collect::<Vec<_>>())?;
+        Ok(FtpReport {
+            positions,
+            portfolios: portfolio_aggregates,
+            total,
+        })
+    }
+
+    /// Returns a market store with the FTP curve with each add-on stacked on it, together
+    /// with the ids of the base curve, the curve with the liquidity premium and the curve
+    /// with both premiums.
+    fn ftp_market_store(&self) -> Result<(MarketStore, [usize; 3])> {
+        let mut market_store = self.market_store.clone();
+        let mut curve_ids = [self.ftp_curve_id; 3];
+        let add_ons = [self.liquidity_curve_id, self.optionality_curve_id];
+        for (i, add_on) in add_ons.iter().enumerate() {
+            let Some(add_on) = add_on else {
+                curve_ids[i + 1] = curve_ids[i];
+                continue;
+            };
+            let index = {
+                let base = market_store.get_index(curve_ids[i])?;
+                let base = base.read_index()?;
+                let spread = self.market_store.get_index(*add_on)?;
+                let spread = spread.read_index()?.term_structure()?;
+                base.relinked_to(Arc::new(CompositeTermStructure::new(
+                    spread,
+                    base.term_structure()?,
+                )))
+            };
+            let id = market_store.index_store().next_available_id();
+            market_store.mut_index_store().add_index(id, index)?;
+            curve_ids[i + 1] = id;
+        }
+        Ok((market_store, curve_ids))
+    }
+
+    fn position(
+        &self,
+        market_store: &MarketStore,
+        curve_ids: &[usize; 3],
+        portfolio: usize,
+        instrument: &Instrument,
+    ) -> Result<FtpPosition> {
+        let reference_date = market_store.reference_date();
+        let (side, client_rate, rate_definition) = match instrument {
+            Instrument::FixedRateInstrument(fri) => {
+                (fri.side(), fri.rate().rate(), fri.rate().rate_definition())
+            }
+            Instrument::FloatingRateInstrument(fri) => {
+                (fri.side(), fri.spread(), fri.rate_definition())
+            }
+            Instrument::HybridRateInstrument(_) | Instrument::DoubleRateInstrument(_) => {
+                return Err(AtlasError::InvalidValueErr(
+                    "FTP is only supported for fixed and floating rate instruments".to_string(),
+                ))
+            }
+        };
+        let profile = outstanding_profile(instrument, reference_date)?;
+
+        let rates = match self.method {
+            FtpMethod::MatchedMaturity => curve_ids
+                .iter()
+                .map(|id| par_value(market_store, &profile, *id))
+                .collect::<Result<Vec<f64>>>()?,
+            FtpMethod::DurationMatched | FtpMethod::SinglePool(_) => {
+                let term = if let FtpMethod::SinglePool(tenor) = self.method {
+                    reference_date + tenor
+                } else {
+                    let duration = duration(market_store, &profile, curve_ids[0])?;
+                    // truncation is intended, the term is measured in whole days
+                    #[allow(clippy::cast_possible_truncation)]
+                    let days = (duration * 365.0).round() as i32;
+                    reference_date + Period::new(days.max(1), TimeUnit::Days)
+                };
+                let index_rate = match instrument.forecast_curve_id() {
+                    Some(id) => {
+                        forward_rate(market_store, id, reference_date, term, rate_definition)?
+                    }
+                    None => 0.0,
+                };
+                curve_ids
+                    .iter()
+                    .map(|id| {
+                        Ok(
+                            forward_rate(market_store, *id, reference_date, term, rate_definition)?
+                                - index_rate,
+                        )
+                    })
+                    .collect::<Result<Vec<f64>>>()?
+            }
+        };
+
+        Ok(FtpPosition {
+            portfolio,
+            id: instrument.id(),
+            side,
+            currency: instrument.currency()?,
+            outstanding: outstanding(instrument, reference_date)?,
+            client_rate,
+            base_rate: rates[0],
+            liquidity_premium: rates[1] - rates[0],
+            optionality_premium: rates[2] - rates[1],
+        })
+    }
+}
+
+/// Returns the notional redeemed after the reference date.
+fn outstanding(instrument: &Instrument, reference_date: Date) -> Result<f64> {
+    instrument
+        .cashflows()
+        .iter()
+        .filter(|cf| matches!(cf, Cashflow::Redemption(_)) && cf.payment_date() > reference_date)
+        .try_fold(0.0, |acc, cf| Ok(acc + cf.amount()?))
+}
+
+/// Returns the instrument with the cashflows paid after the reference date and, if it started
+/// before, its outstanding notional disbursed on the reference date.
+fn outstanding_profile(instrument: &Instrument, reference_date: Date) -> Result<Instrument> {
+    let mut cashflows: Vec<Cashflow> = instrument
+        .cashflows()
+        .iter()
+        .filter(|cf| cf.payment_date() > reference_date)
+        .copied()
+        .collect();
+    let future_disbursements = cashflows
+        .iter()
+        .filter(|cf| matches!(cf, Cashflow::Disbursement(_)))
+        .try_fold(0.0, |acc, cf| Ok::<f64, AtlasError>(acc + cf.amount()?))?;
+    let disbursement = outstanding(instrument, reference_date)? - future_disbursements;
+    if disbursement > 0.0 {
+        let side = instrument
+            .side()
+            .ok_or(AtlasError::ValueNotSetErr("Side".to_string()))?;
+        cashflows.push(Cashflow::Disbursement(
+            SimpleCashflow::new(reference_date, instrument.currency()?, side.inverse())
+                .with_amount(disbursement),
+        ));
+    }
+
+    Ok(match instrument {
+        Instrument::FixedRateInstrument(fri) => {
+            Instrument::FixedRateInstrument(FixedRateInstrument::new(
+                fri.start_date(),
+                fri.end_date(),
+                fri.notional(),
+                fri.rate(),
+                fri.payment_frequency(),
+                cashflows,
+                fri.structure(),
+                fri.side(),
+                fri.currency()?,
+                fri.discount_curve_id(),
+                fri.id(),
+                fri.issue_date(),
+                fri.yield_rate(),
+            ))
+        }
+        Instrument::FloatingRateInstrument(fri) => {
+            Instrument::FloatingRateInstrument(FloatingRateInstrument::new(
+                fri.start_date(),
+                fri.end_date(),
+                fri.notional(),
+                fri.spread(),
+                fri.side(),
+                cashflows,
+                fri.payment_frequency(),
+                fri.rate_definition(),
+                fri.structure(),
+                fri.currency()?,
+                fri.discount_curve_id(),
+                fri.forecast_curve_id(),
+                fri.id(),
+                fri.issue_date(),
+            ))
+        }
+        Instrument::HybridRateInstrument(_) | Instrument::DoubleRateInstrument(_) => {
+            instrument.clone()
+        }
+    })
+}
+
+/// Returns the instrument discounted with the given curve, indexed and fixed.
+fn fixed_on_curve(
+    market_store: &MarketStore,
+    instrument: &Instrument,
+    curve_id: usize,
+) -> Result<(Instrument, Vec<MarketData>)> {
+    let mut instrument = instrument.clone();
+    instrument.set_discount_curve_id(curve_id);
+    let indexing_visitor = IndexingVisitor::new();
+    indexing_visitor.visit(&mut instrument)?;
+    let data = SimpleModel::new(market_store).gen_market_data(&indexing_visitor.request())?;
+    FixingVisitor::new(&data).visit(&mut instrument)?;
+    Ok((instrument, data))
+}
+
+/// Returns the par rate or spread of the instrument discounted with the given curve.
+fn par_value(market_store: &MarketStore, instrument: &Instrument, curve_id: usize) -> Result<f64> {
+    let (instrument, data) = fixed_on_curve(market_store, instrument, curve_id)?;
+    let par_visitor = ParValueConstVisitor::new(&data);
+    match &instrument {
+        Instrument::FixedRateInstrument(fri) => par_visitor.visit(fri),
+        Instrument::FloatingRateInstrument(fri) => par_visitor.visit(fri),
+        Instrument::HybridRateInstrument(_) | Instrument::DoubleRateInstrument(_) => {
+            Err(AtlasError::InvalidValueErr(
+                "Par value is only supported for fixed and floating rate instruments".to_string(),
+            ))
+        }
+    }
+}
+
+/// Returns the duration of the instrument discounted with the given curve.
+fn duration(market_store: &MarketStore, instrument: &Instrument, curve_id: usize) -> Result<f64> {
+    let (instrument, data) = fixed_on_curve(market_store, instrument, curve_id)?;
+    DurationConstVisitor::new(&data).visit(&instrument)
+}
+
+/// Returns the forward rate of the index between two dates.
+fn forward_rate(
+    market_store: &MarketStore,
+    index_id: usize,
+    start_date: Date,
+    end_date: Date,
+    rate_definition: RateDefinition,
+) -> Result<f64> {
+    market_store
+        .get_index(index_id)?
+        .read_index()?
+        .forward_rate(
+            start_date,
+            end_date,
+            rate_definition.compounding(),
+            rate_definition.frequency(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruments::{
+            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument,
+        },
+        rates::enums::Compounding,
+        time::{daycounter::DayCounter, enums::Frequency},
+        utils::testing::add_flat_index,
+    };
+
+    fn rate_definition() -> RateDefinition {
+        RateDefinition::new(
+            DayCounter::Actual365,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )
+    }
+
+    fn market_store() -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 1, 1);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        for (id, rate) in [(0, 0.04), (1, 0.005), (2, 0.002)] {
+            add_flat_index(&mut market_store, id, rate, rate_definition())?;
+        }
+        Ok(market_store)
+    }
+
+    fn fixed(start_date: Date, side: Side, rate: f64) -> Result<Instrument> {
+        Ok(Instrument::FixedRateInstrument(
+            MakeFixedRateInstrument::new()
+                .with_start_date(start_date)
+                .with_end_date(Date::new(2029, 1, 1))
+                .with_payment_frequency(Frequency::Annual)
+                .with_rate_value(rate)
+                .with_rate_definition(rate_definition())
+                .with_notional(100.0)
+                .with_side(side)
+                .with_currency(Currency::USD)
+                .with_discount_curve_id(Some(0))
+                .bullet()
+                .build()?,
+        ))
+    }
+
+    #[test]
+    fn test_matched_maturity() -> Result<()> {
+        let market_store = market_store()?;
+        let new_loan = fixed(Date::new(2024, 1, 1), Side::Receive, 0.07)?;
+        let seasoned_loan = fixed(Date::new(2022, 1, 1), Side::Receive, 0.03)?;
+        let floating_loan = Instrument::FloatingRateInstrument(
+            MakeFloatingRateInstrument::new()
+                .with_start_date(Date::new(2024, 1, 1))
+                .with_end_date(Date::new(2027, 1, 1))
+                .with_payment_frequency(Frequency::Annual)
+                .with_rate_definition(rate_definition())
+                .with_spread(0.01)
+                .with_notional(50.0)
+                .with_side(Side::Receive)
+                .with_currency(Currency::USD)
+                .with_discount_curve_id(Some(0))
+                .with_forecast_curve_id(Some(0))
+                .bullet()
+                .build()?,
+        );
+        let portfolio =
+            Portfolio::new().with_instruments(vec![new_loan, seasoned_loan, floating_loan]);
+
+        let report = FtpEngine::new(&market_store, 0)
+            .with_liquidity_curve_id(1)
+            .with_optionality_curve_id(2)
+            .run(&[portfolio])?;
+        let positions = report.positions();
+
+        for position in &positions[..2] {
+            assert!((position.outstanding() - 100.0).abs() < 1e-12);
+            assert!((position.base_rate() - 0.04).abs() < 1e-6);
+            assert!((position.liquidity_premium() - 0.005).abs() < 1e-6);
+            assert!((position.optionality_premium() - 0.002).abs() < 1e-6);
+        }
+        assert!((positions[0].margin() - 0.023).abs() < 1e-6);
+        assert!((positions[1].margin() + 0.017).abs() < 1e-6);
+
+        // the floating loan is priced as a spread over its index, the FTP curve itself
+        assert!(positions[2].base_rate().abs() < 1e-6);
+        assert!((positions[2].ftp_rate() - 0.007).abs() < 1e-4);
+        assert!((positions[2].margin() - 0.003).abs() < 1e-4);
+
+        let total = report.total();
+        assert!((total.outstanding() - 250.0).abs() < 1e-12);
+        let margin_amount = positions
+            .iter()
+            .map(|p| p.outstanding() * p.margin())
+            .sum::<f64>();
+        assert!((total.margin_amount() - margin_amount).abs() < 1e-9);
+        assert!((report.portfolios()[0].margin() - total.margin()).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration_matched_and_pool() -> Result<()> {
+        let market_store = market_store()?;
+        let deposit = fixed(Date::new(2024, 1, 1), Side::Pay, 0.03)?;
+        let portfolio = Portfolio::new().with_instruments(vec![deposit]);
+
+        let report = FtpEngine::new(&market_store, 0)
+            .with_method(FtpMethod::DurationMatched)
+            .with_liquidity_curve_id(1)
+            .run(std::slice::from_ref(&portfolio))?;
+        let position = &report.positions()[0];
+        assert!((position.base_rate() - 0.04).abs() < 1e-12);
+        assert!((position.liquidity_premium() - 0.005).abs() < 1e-12);
+        assert!(position.optionality_premium().abs() < 1e-12);
+        assert!((position.margin() - 0.015).abs() < 1e-12);
+
+        let report = FtpEngine::new(&market_store, 0)
+            .with_method(FtpMethod::SinglePool(Period::new(3, TimeUnit::Months)))
+            .run(&[portfolio])?;
+        let position = &report.positions()[0];
+        assert!((position.ftp_rate() - 0.04).abs() < 1e-12);
+        assert!((position.margin() - 0.01).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_currency_pool() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 1);
+        let mut market_store = MarketStore::new(reference_date, Currency::CLP);
+        add_flat_index(&mut market_store, 0, 0.04, rate_definition())?;
+        market_store.mut_exchange_rate_store().add_exchange_rate(
+            Currency::CLP,
+            Currency::USD,
+            800.0,
+        );
+        let loan = |currency: Currency, notional: f64, rate: f64| -> Result<Instrument> {
+            Ok(Instrument::FixedRateInstrument(
+                MakeFixedRateInstrument::new()
+                    .with_start_date(Date::new(2024, 1, 1))
+                    .with_end_date(Date::new(2029, 1, 1))
+                    .with_payment_frequency(Frequency::Annual)
+                    .with_rate_value(rate)
+                    .with_rate_definition(rate_definition())
+                    .with_notional(notional)
+                    .with_side(Side::Receive)
+                    .with_currency(currency)
+                    .with_discount_curve_id(Some(0))
+                    .bullet()
+                    .build()?,
+            ))
+        };
+        let portfolio = Portfolio::new().with_instruments(vec![
+            loan(Currency::USD, 100.0, 0.07)?,
+            loan(Currency::CLP, 40_000.0, 0.03)?,
+        ]);
+
+        let report = FtpEngine::new(&market_store, 0)
+            .with_method(FtpMethod::SinglePool(Period::new(3, TimeUnit::Months)))
+            .run(&[portfolio])?;
+
+        // USD per CLP, the USD outstanding is worth 80,000 CLP
+        let rate = market_store.get_exchange_rate(Currency::USD, None)?;
+        let usd_outstanding = 100.0 / rate;
+        assert!((usd_outstanding - 80_000.0).abs() < 1e-9);
+
+        let total = report.total();
+        assert!((total.outstanding() - (usd_outstanding + 40_000.0)).abs() < 1e-9);
+        let client_rate = 0.07f64.mul_add(usd_outstanding, 0.03 * 40_000.0) / 120_000.0;
+        assert!((total.client_rate() - client_rate).abs() < 1e-12);
+        assert!((total.ftp_rate() - 0.04).abs() < 1e-12);
+        assert!((total.margin() - (client_rate - 0.04)).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,499 @@
+use super::enums::{AccountType, Portfolio};
+use crate::{
+    cashflows::{
+        cashflow::Cashflow,
+        traits::{InterestAccrual, Payable},
+    },
+    core::traits::HasCurrency,
+    currencies::enums::Currency,
+    time::{date::Date, daycounter::DayCounter, period::Period},
+    utils::errors::Result,
+    visitors::traits::HasCashflows,
+};
+
+/// # `GapRowKey`
+/// The attributes of the portfolios aggregated in a row of a `GapTable`. The currency is the
+/// one of the portfolio or, if it is not set, the one of the cashflows.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct GapRowKey {
+    segment: Option<String>,
+    area: Option<String>,
+    product_family: Option<String>,
+    currency: Option<Currency>,
+    account_type: Option<AccountType>,
+}
+
+impl GapRowKey {
+    fn new(portfolio: &Portfolio, currency: Currency) -> Self {
+        Self {
+            segment: portfolio.segment(),
+            area: portfolio.area(),
+            product_family: portfolio.product_family(),
+            currency: Some(portfolio.currency().unwrap_or(currency)),
+            account_type: portfolio.account_type(),
+        }
+    }
+
+    /// Returns the segment.
+    #[must_use]
+    pub fn segment(&self) -> Option<&str> {
+        self.segment.as_deref()
+    }
+
+    /// Returns the area.
+    #[must_use]
+    pub fn area(&self) -> Option<&str> {
+        self.area.as_deref()
+    }
+
+    /// Returns the product family.
+    #[must_use]
+    pub fn product_family(&self) -> Option<&str> {
+        self.product_family.as_deref()
+    }
+
+    /// Returns the currency.
+    #[must_use]
+    pub const fn currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    /// Returns the account type.
+    #[must_use]
+    pub const fn account_type(&self) -> Option<AccountType> {
+        self.account_type
+    }
+}
+
+/// # `GapRow`
+/// A row of a `GapTable`: the gap of each bucket, the cumulative gap and the sensitivity of
+/// the value of each bucket to the shock of the report.
+#[derive(Clone, Debug)]
+pub struct GapRow {
+    key: GapRowKey,
+    gaps: Vec<f64>,
+    cumulative_gaps: Vec<f64>,
+    sensitivities: Vec<f64>,
+}
+
+impl GapRow {
+    /// Returns the attributes of the row.
+    #[must_use]
+    pub const fn key(&self) -> &GapRowKey {
+        &self.key
+    }
+
+    /// Returns the gap of each bucket.
+    #[must_use]
+    pub fn gaps(&self) -> &[f64] {
+        &self.gaps
+    }
+
+    /// Returns the gap accumulated up to each bucket.
+    #[must_use]
+    pub fn cumulative_gaps(&self) -> &[f64] {
+        &self.cumulative_gaps
+    }
+
+    /// Returns the change of value of each bucket under the shock of the report.
+    #[must_use]
+    pub fn sensitivities(&self) -> &[f64] {
+        &self.sensitivities
+    }
+
+    /// Returns the change of value of the row under the shock of the report.
+    #[must_use]
+    pub fn total_sensitivity(&self) -> f64 {
+        self.sensitivities.iter().sum()
+    }
+}
+
+/// # `GapTable`
+/// A gap report: one row by combination of portfolio attributes, over the buckets of the
+/// report.
+#[derive(Clone, Debug)]
+pub struct GapTable {
+    buckets: Vec<(Period, Period)>,
+    rows: Vec<GapRow>,
+}
+
+impl GapTable {
+    /// Returns the buckets.
+    #[must_use]
+    pub fn buckets(&self) -> &[(Period, Period)] {
+        &self.buckets
+    }
+
+    /// Returns the rows, in the order their attributes first appear in the portfolios.
+    #[must_use]
+    pub fn rows(&self) -> &[GapRow] {
+        &self.rows
+    }
+
+    /// Returns the row with the given attributes.
+    #[must_use]
+    pub fn row(&self, key: &GapRowKey) -> Option<&GapRow> {
+        self.rows.iter().find(|row| row.key() == key)
+    }
+
+    /// Returns the sum of all rows. Rows in different currencies are added as they are.
+    #[must_use]
+    pub fn total(&self) -> GapRow {
+        let mut gaps = vec![0.0; self.buckets.len()];
+        let mut sensitivities = vec![0.0; self.buckets.len()];
+        for row in &self.rows {
+            for (i, (gap, sensitivity)) in row.gaps.iter().zip(&row.sensitivities).enumerate() {
+                gaps[i] += gap;
+                sensitivities[i] += sensitivity;
+            }
+        }
+        GapRow {
+            key: GapRowKey::default(),
+            cumulative_gaps: cumulative(&gaps),
+            gaps,
+            sensitivities,
+        }
+    }
+}
+
+/// # `GapReport`
+/// Builds repricing and contractual liquidity gap tables from portfolios. Amounts are signed
+/// by the side of the cashflows, so assets add and liabilities subtract, and are slotted into
+/// the bucket `[start, end)` counted from the reference date. The sensitivity of a bucket is
+/// the change of value of its gap when rates move by the shock, using the middle of the
+/// bucket as its duration.
+///
+/// It assumes that the cashflows of the instruments have already been indexed and fixed.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the report
+/// * `buckets` - The buckets, as periods from the reference date
+/// * `shock` - The rate shock of the sensitivities, defaults to one basis point
+/// * `include_today_cashflows` - Whether to include cashflows paid on the reference date
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let instrument = MakeFixedRateInstrument::new()
+///     .with_start_date(Date::new(2024, 1, 2))
+///     .with_end_date(Date::new(2025, 1, 2))
+///     .with_payment_frequency(Frequency::Semiannual)
+///     .with_rate_value(0.05)
+///     .with_rate_definition(RateDefinition::default())
+///     .with_notional(100.0)
+///     .with_side(Side::Receive)
+///     .with_currency(Currency::USD)
+///     .bullet()
+///     .build()
+///     .unwrap();
+/// let portfolio = Portfolio::new()
+///     .with_account_type(AccountType::Asset)
+///     .with_instruments(vec![Instrument::FixedRateInstrument(instrument)]);
+///
+/// let report = GapReport::new(
+///     Date::new(2024, 1, 2),
+///     vec![
+///         (Period::new(0, TimeUnit::Days), Period::new(6, TimeUnit::Months)),
+///         (Period::new(6, TimeUnit::Months), Period::new(2, TimeUnit::Years)),
+///     ],
+/// );
+/// let table = report.repricing_gap(&[portfolio]).unwrap();
+/// assert_eq!(table.rows()[0].gaps(), &[0.0, 100.0]);
+/// ```
+pub struct GapReport {
+    reference_date: Date,
+    buckets: Vec<(Period, Period)>,
+    shock: f64,
+    include_today_cashflows: bool,
+}
+
+impl GapReport {
+    /// Creates a new `GapReport`.
+    #[must_use]
+    pub const fn new(reference_date: Date, buckets: Vec<(Period, Period)>) -> Self {
+        Self {
+            reference_date,
+            buckets,
+            shock: 0.0001,
+            include_today_cashflows: false,
+        }
+    }
+
+    /// Sets the rate shock of the sensitivities.
+    #[must_use]
+    pub const fn with_shock(mut self, shock: f64) -> Self {
+        self.shock = shock;
+        self
+    }
+
+    /// Sets whether cashflows paid on the reference date are included.
+    #[must_use]
+    pub const fn with_include_today_cashflows(mut self, include_today_cashflows: bool) -> Self {
+        self.include_today_cashflows = include_today_cashflows;
+        self
+    }
+
+    /// Returns the repricing gap: the outstanding notionals slotted by the date they reprice,
+    /// their maturity for fixed rate notionals and the next reset of their rate for floating
+    /// rate ones.
+    ///
+    /// # Errors
+    /// Returns an error if the currency, amount or accrual dates of a cashflow are not set.
+    pub fn repricing_gap(&self, portfolios: &[Portfolio]) -> Result<GapTable> {
+        self.build_table(portfolios, |instrument| {
+            let repricing_date = self.next_repricing_date(instrument)?;
+            let mut amounts = Vec::new();
+            for cf in instrument {
+                if let Cashflow::Redemption(redemption) = cf {
+                    let payment_date = redemption.payment_date();
+                    let date = repricing_date.map_or(payment_date, |date| date.min(payment_date));
+                    amounts.push((date, cf.currency()?, cf.amount()? * cf.side().sign()));
+                }
+            }
+            Ok(amounts)
+        })
+    }
+
+    /// Returns the contractual liquidity gap: every cashflow slotted by its payment date.
+    ///
+    /// # Errors
+    /// Returns an error if the currency or amount of a cashflow are not set.
+    pub fn liquidity_gap(&self, portfolios: &[Portfolio]) -> Result<GapTable> {
+        self.build_table(portfolios, |instrument| {
+            instrument
+                .iter()
+                .map(|cf| {
+                    Ok((
+                        cf.payment_date(),
+                        cf.currency()?,
+                        cf.amount()? * cf.side().sign(),
+                    ))
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the first date after the reference date on which a floating rate resets.
+    fn next_repricing_date(&self, cashflows: &[Cashflow]) -> Result<Option<Date>> {
+        let mut next_date: Option<Date> = None;
+        for cf in cashflows {
+            if let Cashflow::FloatingRateCoupon(_) | Cashflow::OvernightIndexedCoupon(_) = cf {
+                let start_date = cf.accrual_start_date()?;
+                let date = if start_date > self.reference_date {
+                    start_date
+                } else {
+                    cf.accrual_end_date()?
+                };
+                if date > self.reference_date {
+                    next_date = Some(next_date.map_or(date, |next| next.min(date)));
+                }
+            }
+        }
+        Ok(next_date)
+    }
+
+    fn build_table<F>(&self, portfolios: &[Portfolio], slotted_amounts: F) -> Result<GapTable>
+    where
+        F: Fn(&[Cashflow]) -> Result<Vec<(Date, Currency, f64)>>,
+    {
+        let mut keys: Vec<GapRowKey> = Vec::new();
+        let mut gaps: Vec<Vec<f64>> = Vec::new();
+        for portfolio in portfolios {
+            for instrument in portfolio.instruments() {
+                for (date, currency, amount) in slotted_amounts(instrument.cashflows())? {
+                    if date < self.reference_date
+                        || date == self.reference_date && !self.include_today_cashflows
+                    {
+                        continue;
+                    }
+                    let Some(bucket) = self.buckets.iter().position(|(start, end)| {
+                        date >= self.reference_date + *start && date < self.reference_date + *end
+                    }) else {
+                        continue;
+                    };
+                    let key = GapRowKey::new(portfolio, currency);
+                    if !keys.contains(&key) {
+                        keys.push(key.clone());
+                        gaps.push(vec![0.0; self.buckets.len()]);
+                    }
+                    let row = keys.iter().position(|k| *k == key).unwrap_or_default();
+                    gaps[row][bucket] += amount;
+                }
+            }
+        }
+
+        let durations: Vec<f64> = self
+            .buckets
+            .iter()
+            .map(|(start, end)| {
+                let time = |period: Period| {
+                    DayCounter::Actual365
+                        .year_fraction(self.reference_date, self.reference_date + period)
+                };
+                0.5 * (time(*start) + time(*end))
+            })
+            .collect();
+        let rows = keys
+            .into_iter()
+            .zip(gaps)
+            .map(|(key, gaps)| GapRow {
+                key,
+                cumulative_gaps: cumulative(&gaps),
+                sensitivities: gaps
+                    .iter()
+                    .zip(&durations)
+                    .map(|(gap, duration)| -gap * duration * self.shock)
+                    .collect(),
+                gaps,
+            })
+            .collect();
+        Ok(GapTable {
+            buckets: self.buckets.clone(),
+            rows,
+        })
+    }
+}
+
+/// Returns the running sum of the gaps.
+fn cumulative(gaps: &[f64]) -> Vec<f64> {
+    gaps.iter()
+        .scan(0.0, |total, gap| {
+            *total += gap;
+            Some(*total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        instruments::{
+            instrument::Instrument, makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument,
+        },
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::interestrate::RateDefinition,
+        time::enums::{Frequency, TimeUnit},
+        utils::testing::flat_market_store,
+        visitors::{fixingvisitor::FixingVisitor, indexingvisitor::IndexingVisitor, traits::Visit},
+    };
+
+    fn portfolios() -> Result<Vec<Portfolio>> {
+        let reference_date = Date::new(2024, 1, 2);
+        let market_store = flat_market_store(reference_date, 0.04)?;
+
+        let loan = MakeFixedRateInstrument::new()
+            .with_start_date(reference_date)
+            .with_end_date(Date::new(2026, 1, 2))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_value(0.05)
+            .with_rate_definition(RateDefinition::default())
+            .with_notional(100.0)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .build()?;
+        let mut deposit = Instrument::FloatingRateInstrument(
+            MakeFloatingRateInstrument::new()
+                .with_start_date(reference_date)
+                .with_end_date(Date::new(2027, 1, 2))
+                .with_payment_frequency(Frequency::Quarterly)
+                .with_rate_definition(RateDefinition::default())
+                .with_spread(0.0)
+                .with_notional(80.0)
+                .with_currency(Currency::USD)
+                .with_side(Side::Pay)
+                .with_discount_curve_id(Some(0))
+                .with_forecast_curve_id(Some(0))
+                .bullet()
+                .build()?,
+        );
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut deposit)?;
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+        FixingVisitor::new(&data).visit(&mut deposit)?;
+
+        Ok(vec![
+            Portfolio::new()
+                .with_area("Retail".to_string())
+                .with_account_type(AccountType::Asset)
+                .with_instruments(vec![Instrument::FixedRateInstrument(loan)]),
+            Portfolio::new()
+                .with_area("Treasury".to_string())
+                .with_account_type(AccountType::Liability)
+                .with_instruments(vec![deposit]),
+        ])
+    }
+
+    fn report() -> GapReport {
+        GapReport::new(
+            Date::new(2024, 1, 2),
+            vec![
+                (
+                    Period::new(0, TimeUnit::Days),
+                    Period::new(3, TimeUnit::Months),
+                ),
+                (
+                    Period::new(3, TimeUnit::Months),
+                    Period::new(1, TimeUnit::Years),
+                ),
+                (
+                    Period::new(1, TimeUnit::Years),
+                    Period::new(5, TimeUnit::Years),
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_repricing_gap() -> Result<()> {
+        let table = report().repricing_gap(&portfolios()?)?;
+        assert_eq!(table.rows().len(), 2);
+        let loan = &table.rows()[0];
+        assert_eq!(loan.key().area(), Some("Retail"));
+        assert_eq!(loan.key().currency(), Some(Currency::USD));
+        assert_eq!(loan.gaps(), &[0.0, 0.0, 100.0]);
+
+        // the deposit reprices on its first reset, three months from now
+        let deposit = &table.rows()[1];
+        assert_eq!(deposit.key().account_type(), Some(AccountType::Liability));
+        assert_eq!(deposit.gaps(), &[0.0, -80.0, 0.0]);
+
+        let total = table.total();
+        assert_eq!(total.cumulative_gaps(), &[0.0, -80.0, 20.0]);
+        let duration = 0.5
+            * (DayCounter::Actual365.year_fraction(Date::new(2024, 1, 2), Date::new(2025, 1, 2))
+                + DayCounter::Actual365
+                    .year_fraction(Date::new(2024, 1, 2), Date::new(2029, 1, 2)));
+        assert!((loan.total_sensitivity() / duration + 0.01).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_liquidity_gap() -> Result<()> {
+        let portfolios = portfolios()?;
+        let table = report().liquidity_gap(&portfolios)?;
+        for (portfolio, row) in portfolios.iter().zip(table.rows()) {
+            // the disbursements are paid today
+            let expected = portfolio
+                .instruments()
+                .iter()
+                .flat_map(HasCashflows::cashflows)
+                .filter(|cf| cf.payment_date() > Date::new(2024, 1, 2))
+                .map(|cf| Ok(cf.amount()? * cf.side().sign()))
+                .sum::<Result<f64>>()?;
+            let total: f64 = row.gaps().iter().sum();
+            assert!((total - expected).abs() < 1e-12);
+            assert!((row.cumulative_gaps()[2] - expected).abs() < 1e-12);
+        }
+
+        // interest of the loan is paid every six months
+        let loan = &table.rows()[0];
+        assert!(loan.gaps()[0].abs() < 1e-12);
+        assert!(loan.gaps()[1] > 0.0 && loan.gaps()[1] < 5.0);
+        Ok(())
+    }
+}
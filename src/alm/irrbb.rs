@@ -0,0 +1,1023 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    enums::Portfolio, positiongenerator::RolloverStrategy,
+    rolloversimulationengine::RolloverSimulationEngine,
+};
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        traits::{InterestAccrual, Payable},
+    },
+    core::{marketstore::MarketStore, traits::HasCurrency},
+    currencies::enums::Currency,
+    instruments::instrument::Instrument,
+    models::{simplemodel::SimpleModel, traits::Model},
+    rates::{
+        enums::Compounding,
+        indexstore::ReadIndex,
+        interestrate::InterestRate,
+        traits::{HasReferenceDate, YieldProvider},
+        yieldtermstructure::traits::{AdvanceTermStructureInTime, YieldTermStructureTrait},
+    },
+    time::{
+        date::Date,
+        daycounter::DayCounter,
+        enums::{Frequency, TimeUnit},
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+    visitors::{
+        fixingvisitor::FixingVisitor,
+        indexingvisitor::IndexingVisitor,
+        traits::{HasCashflows, Visit},
+    },
+};
+
+/// Decay, in years, of the short rate shock with maturity.
+const SHORT_DECAY: f64 = 4.0;
+/// Weight given to gains in other currencies when aggregating losses.
+const GAIN_WEIGHT: f64 = 0.5;
+
+/// # `IrrbbScenario`
+/// The six standardised interest rate shock scenarios of the Basel IRRBB standard. Short and
+/// long shocks decay with maturity as `S_s e^{-t/4}` and `S_l (1 - e^{-t/4})`, and the
+/// steepener and flattener combine them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IrrbbScenario {
+    /// Parallel shock up.
+    ParallelUp,
+    /// Parallel shock down.
+    ParallelDown,
+    /// Short rates down and long rates up.
+    Steepener,
+    /// Short rates up and long rates down.
+    Flattener,
+    /// Short rates shock up.
+    ShortUp,
+    /// Short rates shock down.
+    ShortDown,
+}
+
+impl IrrbbScenario {
+    /// Returns the six scenarios.
+    #[must_use]
+    pub const fn all() -> [Self; 6] {
+        [
+            Self::ParallelUp,
+            Self::ParallelDown,
+            Self::Steepener,
+            Self::Flattener,
+            Self::ShortUp,
+            Self::ShortDown,
+        ]
+    }
+
+    /// Returns the shift of the zero rate with maturity `t`, in years, for the given shock
+    /// sizes.
+    #[must_use]
+    pub fn zero_rate_shift(&self, sizes: &IrrbbShockSizes, t: f64) -> f64 {
+        let decay = (-t / SHORT_DECAY).exp();
+        let short = sizes.short * decay;
+        let long = sizes.long * (1.0 - decay);
+        match self {
+            Self::ParallelUp => sizes.parallel,
+            Self::ParallelDown => -sizes.parallel,
+            Self::Steepener => 0.9f64.mul_add(long, -0.65 * short),
+            Self::Flattener => 0.8f64.mul_add(short, -0.6 * long),
+            Self::ShortUp => short,
+            Self::ShortDown => -short,
+        }
+    }
+}
+
+/// # `IrrbbShockSizes`
+/// The parallel, short and long shock sizes of a currency, as absolute rates.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct IrrbbShockSizes {
+    parallel: f64,
+    short: f64,
+    long: f64,
+}
+
+impl IrrbbShockSizes {
+    /// Creates a new `IrrbbShockSizes`.
+    #[must_use]
+    pub const fn new(parallel: f64, short: f64, long: f64) -> Self {
+        Self {
+            parallel,
+            short,
+            long,
+        }
+    }
+
+    /// Returns the shock sizes given for the currency in the Basel IRRBB standard.
+    ///
+    /// # Errors
+    /// Returns an error if the standard does not calibrate the currency.
+    pub fn bcbs(currency: Currency) -> Result<Self> {
+        let (parallel, short, long) = match currency {
+            Currency::AUD => (300, 450, 200),
+            Currency::BRL | Currency::INR | Currency::MXN | Currency::ZAR => (400, 500, 300),
+            Currency::CAD | Currency::SEK | Currency::USD => (200, 300, 150),
+            Currency::CHF => (100, 150, 100),
+            Currency::CNY | Currency::GBP => (250, 300, 150),
+            Currency::EUR | Currency::HKD => (200, 250, 100),
+            Currency::IDR => (400, 500, 350),
+            Currency::JPY => (100, 100, 100),
+            Currency::KRW => (300, 400, 200),
+            _ => {
+                return Err(AtlasError::NotFoundErr(format!(
+                    "IRRBB shock sizes for currency {currency:?}"
+                )))
+            }
+        };
+        let basis_points = |size: i32| f64::from(size) / 10_000.0;
+        Ok(Self::new(
+            basis_points(parallel),
+            basis_points(short),
+            basis_points(long),
+        ))
+    }
+
+    /// Returns the parallel shock size.
+    #[must_use]
+    pub const fn parallel(&self) -> f64 {
+        self.parallel
+    }
+
+    /// Returns the short rate shock size.
+    #[must_use]
+    pub const fn short(&self) -> f64 {
+        self.short
+    }
+
+    /// Returns the long rate shock size.
+    #[must_use]
+    pub const fn long(&self) -> f64 {
+        self.long
+    }
+}
+
+/// # `PostShockFloor`
+/// A floor on shocked zero rates that increases linearly with maturity up to zero. Rates
+/// already below the floor before the shock are floored at their own level instead. The
+/// default starts at -150 basis points and increases 3 basis points per year.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PostShockFloor {
+    rate: f64,
+    annual_increase: f64,
+}
+
+impl PostShockFloor {
+    /// Creates a new `PostShockFloor` with its level at the immediate maturity and its annual
+    /// increase.
+    #[must_use]
+    pub const fn new(rate: f64, annual_increase: f64) -> Self {
+        Self {
+            rate,
+            annual_increase,
+        }
+    }
+
+    /// Returns the floor for the maturity `t`, in years.
+    #[must_use]
+    pub const fn floor(&self, t: f64) -> f64 {
+        self.annual_increase.mul_add(t, self.rate).min(0.0)
+    }
+}
+
+impl Default for PostShockFloor {
+    fn default() -> Self {
+        Self::new(-0.015, 0.0003)
+    }
+}
+
+/// # `ShockedTermStructure`
+/// Term structure that applies an `IrrbbScenario` to the continuously compounded zero rates
+/// of a base curve, measured with `Actual365` from the date of the shock, and then the
+/// post-shock floor. Forward rates are those of the base curve, with its own conventions, plus
+/// the forward rates implied by the shock. An advanced curve keeps the shocked forward rates.
+///
+/// ## Parameters
+/// * `base_curve` - The curve being shocked
+/// * `scenario` - The shock scenario
+/// * `sizes` - The shock sizes of the currency of the curve
+/// * `floor` - The post-shock floor, if any
+#[derive(Clone)]
+pub struct ShockedTermStructure {
+    base_curve: Arc<dyn YieldTermStructureTrait>,
+    scenario: IrrbbScenario,
+    sizes: IrrbbShockSizes,
+    floor: Option<PostShockFloor>,
+    shock_date: Date,
+    reference_date: Date,
+}
+
+impl ShockedTermStructure {
+    /// Creates a new `ShockedTermStructure` that shocks `base_curve` from its reference date.
+    #[must_use]
+    pub fn new(
+        base_curve: Arc<dyn YieldTermStructureTrait>,
+        scenario: IrrbbScenario,
+        sizes: IrrbbShockSizes,
+        floor: Option<PostShockFloor>,
+    ) -> Self {
+        let shock_date = base_curve.reference_date();
+        Self {
+            base_curve,
+            scenario,
+            sizes,
+            floor,
+            shock_date,
+            reference_date: shock_date,
+        }
+    }
+
+    /// Returns the shock scenario.
+    #[must_use]
+    pub const fn scenario(&self) -> IrrbbScenario {
+        self.scenario
+    }
+
+    /// Returns the discount factor from the date of the shock.
+    fn shocked_discount_factor(&self, date: Date) -> Result<f64> {
+        let base_discount_factor = self.base_curve.discount_factor(date)?;
+        let t = DayCounter::Actual365.year_fraction(self.shock_date, date);
+        if t <= 0.0 {
+            return Ok(base_discount_factor);
+        }
+        let zero_rate = -base_discount_factor.ln() / t;
+        let mut shocked_rate = zero_rate + self.scenario.zero_rate_shift(&self.sizes, t);
+        if let Some(floor) = self.floor {
+            shocked_rate = shocked_rate.max(zero_rate.min(floor.floor(t)));
+        }
+        Ok((-shocked_rate * t).exp())
+    }
+
+    /// Returns the ratio of the shocked to the base discount factor from the date of the shock.
+    fn shock_factor(&self, date: Date) -> Result<f64> {
+        Ok(self.shocked_discount_factor(date)? / self.base_curve.discount_factor(date)?)
+    }
+}
+
+impl HasReferenceDate for ShockedTermStructure {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl YieldProvider for ShockedTermStructure {
+    fn discount_factor(&self, date: Date) -> Result<f64> {
+        if date < self.reference_date {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date
+            )));
+        }
+        Ok(self.shocked_discount_factor(date)?
+            / self.shocked_discount_factor(self.reference_date)?)
+    }
+
+    fn forward_rate(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        comp: Compounding,
+        freq: Frequency,
+    ) -> Result<f64> {
+        if start_date < self.reference_date {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {start_date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date
+            )));
+        }
+        let base_forward = self
+            .base_curve
+            .forward_rate(start_date, end_date, comp, freq)?;
+        let shock_comp_factor = self.shock_factor(start_date)? / self.shock_factor(end_date)?;
+        let t = DayCounter::Actual365.year_fraction(start_date, end_date);
+        let shock_forward =
+            InterestRate::implied_rate(shock_comp_factor, DayCounter::Actual365, comp, freq, t)?
+                .rate();
+        Ok(base_forward + shock_forward)
+    }
+}
+
+impl AdvanceTermStructureInTime for ShockedTermStructure {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        self.advance_to_date(self.reference_date + period)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        Ok(Arc::new(Self {
+            reference_date: date,
+            ..self.clone()
+        }))
+    }
+}
+
+impl YieldTermStructureTrait for ShockedTermStructure {}
+
+/// # `IrrbbScenarioResult`
+/// The change of the economic value of equity (EVE) and of the net interest income (NII) of a
+/// portfolio in one scenario, by currency. Losses are aggregated in the local currency of the
+/// market store, adding the losses of each currency and offsetting half of the gains.
+#[derive(Clone, Debug)]
+pub struct IrrbbScenarioResult {
+    scenario: IrrbbScenario,
+    delta_eve: HashMap<Currency, f64>,
+    delta_nii: HashMap<Currency, f64>,
+    eve_loss: f64,
+    nii_loss: f64,
+}
+
+impl IrrbbScenarioResult {
+    /// Returns the scenario.
+    #[must_use]
+    pub const fn scenario(&self) -> IrrbbScenario {
+        self.scenario
+    }
+
+    /// Returns the change of EVE by currency, shocked minus base.
+    #[must_use]
+    pub const fn delta_eve(&self) -> &HashMap<Currency, f64> {
+        &self.delta_eve
+    }
+
+    /// Returns the change of NII by currency, shocked minus base.
+    #[must_use]
+    pub const fn delta_nii(&self) -> &HashMap<Currency, f64> {
+        &self.delta_nii
+    }
+
+    /// Returns the aggregated loss of EVE, zero if the scenario is a gain.
+    #[must_use]
+    pub const fn eve_loss(&self) -> f64 {
+        self.eve_loss
+    }
+
+    /// Returns the aggregated loss of NII, zero if the scenario is a gain.
+    #[must_use]
+    pub const fn nii_loss(&self) -> f64 {
+        self.nii_loss
+    }
+}
+
+/// # `IrrbbResults`
+/// The base EVE and NII of a portfolio and their changes in each scenario.
+#[derive(Clone, Debug)]
+pub struct IrrbbResults {
+    base_eve: HashMap<Currency, f64>,
+    base_nii: HashMap<Currency, f64>,
+    scenarios: Vec<IrrbbScenarioResult>,
+}
+
+impl IrrbbResults {
+    /// Returns the base EVE by currency.
+    #[must_use]
+    pub const fn base_eve(&self) -> &HashMap<Currency, f64> {
+        &self.base_eve
+    }
+
+    /// Returns the base NII by currency.
+    #[must_use]
+    pub const fn base_nii(&self) -> &HashMap<Currency, f64> {
+        &self.base_nii
+    }
+
+    /// Returns the results of each scenario.
+    #[must_use]
+    pub fn scenarios(&self) -> &[IrrbbScenarioResult] {
+        &self.scenarios
+    }
+
+    /// Returns the result of a scenario.
+    #[must_use]
+    pub fn scenario(&self, scenario: IrrbbScenario) -> Option<&IrrbbScenarioResult> {
+        self.scenarios.iter().find(|r| r.scenario() == scenario)
+    }
+
+    /// Returns the scenario with the largest loss of EVE.
+    #[must_use]
+    pub fn worst_eve(&self) -> Option<&IrrbbScenarioResult> {
+        self.scenarios
+            .iter()
+            .max_by(|a, b| a.eve_loss().total_cmp(&b.eve_loss()))
+    }
+
+    /// Returns the scenario with the largest loss of NII.
+    #[must_use]
+    pub fn worst_nii(&self) -> Option<&IrrbbScenarioResult> {
+        self.scenarios
+            .iter()
+            .max_by(|a, b| a.nii_loss().total_cmp(&b.nii_loss()))
+    }
+}
+
+/// # `IrrbbEngine`
+/// Computes the standardised IRRBB measures of a portfolio. For each scenario, the indices of
+/// every currency are shocked with the sizes of that currency and:
+///
+/// - EVE is the value of the cashflows paid after the reference date, slotted into the time
+///   buckets of the standard and discounted at their midpoints with the curve of the currency
+///   (`IndexStore::get_currency_curve`). Floating coupons are fixed in each scenario.
+/// - NII is the interest accrued over the horizon by the portfolio and, for currencies with
+///   rollover strategies, by the positions that replace its redemptions, generated with a
+///   `RolloverSimulationEngine`. Redemptions of assets are rolled over with the strategies that
+///   receive and those of liabilities with the strategies that pay.
+///
+/// ## Parameters
+/// * `market_store` - The base market
+/// * `portfolio` - The portfolio
+/// * `currency_indices` - The indices shocked for each currency, defaults to the currency curve
+/// * `shock_sizes` - The shock sizes of each currency, defaults to `IrrbbShockSizes::bcbs`
+/// * `floor` - The post-shock floor, defaults to `PostShockFloor::default`
+/// * `rollover_strategies` - The rollover strategies of each currency
+/// * `horizon` - The NII horizon, defaults to 12 months
+pub struct IrrbbEngine<'a> {
+    market_store: &'a MarketStore,
+    portfolio: &'a Portfolio,
+    currency_indices: HashMap<Currency, Vec<usize>>,
+    shock_sizes: HashMap<Currency, IrrbbShockSizes>,
+    floor: Option<PostShockFloor>,
+    rollover_strategies: HashMap<Currency, Vec<RolloverStrategy>>,
+    horizon: Period,
+}
+
+impl<'a> IrrbbEngine<'a> {
+    /// Creates a new `IrrbbEngine`.
+    #[must_use]
+    pub fn new(market_store: &'a MarketStore, portfolio: &'a Portfolio) -> Self {
+        Self {
+            market_store,
+            portfolio,
+            currency_indices: HashMap::new(),
+            shock_sizes: HashMap::new(),
+            floor: Some(PostShockFloor::default()),
+            rollover_strategies: HashMap::new(),
+            horizon: Period::new(12, TimeUnit::Months),
+        }
+    }
+
+    /// Sets the indices shocked for a currency. Each index must belong to one currency.
+    #[must_use]
+    pub fn with_currency_indices(mut self, currency: Currency, indices: Vec<usize>) -> Self {
+        self.currency_indices.insert(currency, indices);
+        self
+    }
+
+    /// Sets the shock sizes of a currency.
+    #[must_use]
+    pub fn with_shock_sizes(mut self, currency: Currency, sizes: IrrbbShockSizes) -> Self {
+        self.shock_sizes.insert(currency, sizes);
+        self
+    }
+
+    /// Sets the post-shock floor, `None` to leave shocked rates unfloored.
+    #[must_use]
+    pub const fn with_floor(mut self, floor: Option<PostShockFloor>) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Sets the rollover strategies of a currency.
+    #[must_use]
+    pub fn with_rollover_strategies(
+        mut self,
+        currency: Currency,
+        strategies: Vec<RolloverStrategy>,
+    ) -> Self {
+        self.rollover_strategies.insert(currency, strategies);
+        self
+    }
+
+    /// Sets the NII horizon.
+    #[must_use]
+    pub const fn with_horizon(mut self, horizon: Period) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Returns the currencies of the cashflows of the portfolio.
+    ///
+    /// # Errors
+    /// Returns an error if the currency of a cashflow is not set.
+    pub fn currencies(&self) -> Result<Vec<Currency>> {
+        let mut currencies = Vec::new();
+        for cf in self
+            .portfolio
+            .instruments()
+            .iter()
+            .flat_map(HasCashflows::cashflows)
+        {
+            let currency = cf.currency()?;
+            if !currencies.contains(&currency) {
+                currencies.push(currency);
+            }
+        }
+        Ok(currencies)
+    }
+
+    /// Returns the market store with the indices of every currency of the portfolio shocked
+    /// in the given scenario.
+    ///
+    /// # Errors
+    /// Returns an error if a currency has no curve or shock sizes, or an index cannot be found.
+    pub fn shocked_market_store(&self, scenario: IrrbbScenario) -> Result<MarketStore> {
+        let mut market_store = self.market_store.clone();
+        for currency in self.currencies()? {
+            let sizes = match self.shock_sizes.get(&currency) {
+                Some(sizes) => *sizes,
+                None => IrrbbShockSizes::bcbs(currency)?,
+            };
+            for id in self.shocked_indices(currency)? {
+                let shocked_index = {
+                    let index = self.market_store.get_index(id)?;
+                    let index = index.read_index()?;
+                    let curve = ShockedTermStructure::new(
+                        index.term_structure()?,
+                        scenario,
+                        sizes,
+                        self.floor,
+                    );
+                    index.relinked_to(Arc::new(curve))
+                };
+                market_store
+                    .mut_index_store()
+                    .replace_index(id, shocked_index)?;
+            }
+        }
+        Ok(market_store)
+    }
+
+    /// Computes EVE and NII in the base market and in the six scenarios.
+    ///
+    /// # Errors
+    /// Returns an error if a market cannot be shocked, or the portfolio cannot be fixed, valued
+    /// or rolled over in it.
+    pub fn run(&self) -> Result<IrrbbResults> {
+        let base_eve = self.eve(self.market_store)?;
+        let base_nii = self.nii(self.market_store)?;
+        let scenarios = IrrbbScenario::all()
+            .into_iter()
+            .map(|scenario| -> Result<IrrbbScenarioResult> {
+                let market_store = self.shocked_market_store(scenario)?;
+                let change = |shocked: HashMap<Currency, f64>, base: &HashMap<Currency, f64>| {
+                    shocked
+                        .into_iter()
+                        .map(|(currency, value)| {
+                            (
+                                currency,
+                                value - base.get(&currency).copied().unwrap_or(0.0),
+                            )
+                        })
+                        .collect::<HashMap<Currency, f64>>()
+                };
+                let delta_eve = change(self.eve(&market_store)?, &base_eve);
+                let delta_nii = change(self.nii(&market_store)?, &base_nii);
+                Ok(IrrbbScenarioResult {
+                    scenario,
+                    eve_loss: self.aggregated_loss(&delta_eve)?,
+                    nii_loss: self.aggregated_loss(&delta_nii)?,
+                    delta_eve,
+                    delta_nii,
+                })
+            })
+            .collect::<Result<Vec<IrrbbScenarioResult>>>()?;
+        Ok(IrrbbResults {
+            base_eve,
+            base_nii,
+            scenarios,
+        })
+    }
+
+    fn shocked_indices(&self, currency: Currency) -> Result<Vec<usize>> {
+        match self.currency_indices.get(&currency) {
+            Some(indices) => Ok(indices.clone()),
+            None => Ok(vec![self
+                .market_store
+                .index_store()
+                .get_currency_curve(currency)?]),
+        }
+    }
+
+    /// Returns the instruments of the portfolio fixed in the given market.
+    fn fixed_instruments(
+        market_store: &MarketStore,
+        instruments: &[Instrument],
+    ) -> Result<Vec<Instrument>> {
+        let mut instruments = instruments.to_vec();
+        let indexing_visitor = IndexingVisitor::new();
+        instruments
+            .iter_mut()
+            .try_for_each(|inst| indexing_visitor.visit(inst))?;
+        let data = SimpleModel::new(market_store).gen_market_data(&indexing_visitor.request())?;
+        let fixing_visitor = FixingVisitor::new(&data);
+        instruments
+            .iter_mut()
+            .try_for_each(|inst| fixing_visitor.visit(inst))?;
+        Ok(instruments)
+    }
+
+    fn eve(&self, market_store: &MarketStore) -> Result<HashMap<Currency, f64>> {
+        let reference_date = market_store.reference_date();
+        let buckets = time_buckets(reference_date);
+        let instruments = Self::fixed_instruments(market_store, self.portfolio.instruments())?;
+        let mut slotted: HashMap<Currency, Vec<f64>> = HashMap::new();
+        for cf in instruments.iter().flat_map(HasCashflows::cashflows) {
+            let payment_date = cf.payment_date();
+            if payment_date <= reference_date {
+                continue;
+            }
+            let bucket = buckets
+                .iter()
+                .position(|(end, _)| end.is_none_or(|end| payment_date <= end))
+                .unwrap_or(buckets.len() - 1);
+            let amounts = slotted
+                .entry(cf.currency()?)
+                .or_insert_with(|| vec![0.0; buckets.len()]);
+            amounts[bucket] += cf.amount()? * cf.side().sign();
+        }
+        slotted
+            .into_iter()
+            .map(|(currency, amounts)| {
+                let id = market_store.index_store().get_currency_curve(currency)?;
+                let index = market_store.get_index(id)?;
+                let index = index.read_index()?;
+                let eve = buckets
+                    .iter()
+                    .zip(&amounts)
+                    .map(|((_, midpoint), amount)| Ok(amount * index.discount_factor(*midpoint)?))
+                    .sum::<Result<f64>>()?;
+                Ok((currency, eve))
+            })
+            .collect()
+    }
+
+    fn nii(&self, market_store: &MarketStore) -> Result<HashMap<Currency, f64>> {
+        let start_date = market_store.reference_date();
+        let end_date = start_date + self.horizon;
+        let mut instruments = Self::fixed_instruments(market_store, self.portfolio.instruments())?;
+
+        // positions replacing the redemptions within the horizon
+        for (currency, strategies) in &self.rollover_strategies {
+            for side in [Side::Receive, Side::Pay] {
+                let mut redemptions = BTreeMap::new();
+                for cf in instruments.iter().flat_map(HasCashflows::cashflows) {
+                    if let Cashflow::Redemption(redemption) = cf {
+                        let payment_date = redemption.payment_date();
+                        if redemption.side() == side
+                            && cf.currency()? == *currency
+                            && payment_date >= start_date
+                            && payment_date < end_date
+                        {
+                            *redemptions.entry(payment_date).or_insert(0.0) +=
+                                redemption.amount()?;
+                        }
+                    }
+                }
+                let side_strategies: Vec<RolloverStrategy> = strategies
+                    .iter()
+                    .filter(|strategy| strategy.side() == side)
+                    .cloned()
+                    .collect();
+                if redemptions.is_empty() || side_strategies.is_empty() {
+                    continue;
+                }
+                let mut new_positions = RolloverSimulationEngine::new(
+                    market_store,
+                    redemptions,
+                    *currency,
+                    self.horizon,
+                )
+                .run(&side_strategies)?;
+                instruments.append(&mut new_positions);
+            }
+        }
+
+        let mut nii = HashMap::new();
+        for cf in instruments.iter().flat_map(HasCashflows::cashflows) {
+            if let Cashflow::Disbursement(_) | Cashflow::Redemption(_) = cf {
+                continue;
+            }
+            *nii.entry(cf.currency()?).or_insert(0.0) +=
+                cf.accrued_amount(start_date, end_date)? * cf.side().sign();
+        }
+        Ok(nii)
+    }
+
+    /// Aggregates the changes of each currency into a loss in the local currency.
+    fn aggregated_loss(&self, changes: &HashMap<Currency, f64>) -> Result<f64> {
+        let (mut losses, mut gains) = (0.0, 0.0);
+        for (currency, change) in changes {
+            let change = change / self.market_store.get_exchange_rate(*currency, None)?;
+            if change < 0.0 {
+                losses -= change;
+            } else {
+                gains += change;
+            }
+        }
+        Ok(GAIN_WEIGHT.mul_add(-gains, losses).max(0.0))
+    }
+}
+
+/// Returns the end date and the midpoint date of the time buckets of the standard.
+fn time_buckets(reference_date: Date) -> Vec<(Option<Date>, Date)> {
+    let months = [
+        1, 3, 6, 9, 12, 18, 24, 36, 48, 60, 72, 84, 96, 108, 120, 180, 240,
+    ];
+    let midpoints = [
+        0.0417, 0.1667, 0.375, 0.625, 0.875, 1.25, 1.75, 2.5, 3.5, 4.5, 5.5, 6.5, 7.5, 8.5, 9.5,
+        12.5, 17.5, 25.0,
+    ];
+    let midpoint_date = |years: f64| {
+        #[allow(clippy::cast_possible_truncation)]
+        let days = (years * 365.0).round() as i32;
+        reference_date + Period::new(days, TimeUnit::Days)
+    };
+    let mut buckets = vec![(
+        Some(reference_date + Period::new(1, TimeUnit::Days)),
+        midpoint_date(0.0028),
+    )];
+    for (i, midpoint) in midpoints.iter().enumerate() {
+        let end = months
+            .get(i)
+            .map(|months| reference_date + Period::new(*months, TimeUnit::Months));
+        buckets.push((end, midpoint_date(*midpoint)));
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruments::{
+            instrument::RateType, makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument, traits::Structure,
+        },
+        rates::{
+            interestrate::RateDefinition,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        utils::testing::add_flat_index,
+    };
+
+    const fn continuous() -> RateDefinition {
+        RateDefinition::new(
+            DayCounter::Actual365,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )
+    }
+
+    fn flat_curve(rate: f64) -> Arc<dyn YieldTermStructureTrait> {
+        Arc::new(FlatForwardTermStructure::new(
+            Date::new(2024, 1, 2),
+            rate,
+            continuous(),
+        ))
+    }
+
+    fn zero_rate(curve: &dyn YieldTermStructureTrait, date: Date) -> Result<f64> {
+        let t = DayCounter::Actual365.year_fraction(curve.reference_date(), date);
+        Ok(-curve.discount_factor(date)?.ln() / t)
+    }
+
+    fn fixed_rate_instrument(
+        end_date: Date,
+        rate: f64,
+        notional: f64,
+        side: Side,
+    ) -> Result<Instrument> {
+        Ok(Instrument::FixedRateInstrument(
+            MakeFixedRateInstrument::new()
+                .with_start_date(Date::new(2024, 1, 2))
+                .with_end_date(end_date)
+                .with_payment_frequency(Frequency::Semiannual)
+                .with_rate(InterestRate::new(
+                    rate,
+                    Compounding::Simple,
+                    Frequency::Annual,
+                    DayCounter::Actual360,
+                ))
+                .with_notional(notional)
+                .with_discount_curve_id(Some(0))
+                .with_side(side)
+                .with_currency(Currency::USD)
+                .bullet()
+                .build()?,
+        ))
+    }
+
+    #[test]
+    fn test_scenario_shapes() -> Result<()> {
+        let sizes = IrrbbShockSizes::bcbs(Currency::USD)?;
+        assert_eq!(sizes, IrrbbShockSizes::new(0.02, 0.03, 0.015));
+        assert!(IrrbbShockSizes::bcbs(Currency::CLP).is_err());
+
+        let shift = |scenario: IrrbbScenario, t: f64| scenario.zero_rate_shift(&sizes, t);
+        assert!((shift(IrrbbScenario::ParallelDown, 10.0) + 0.02).abs() < 1e-15);
+        assert!((shift(IrrbbScenario::ShortUp, 0.0) - 0.03).abs() < 1e-15);
+        assert!((shift(IrrbbScenario::Steepener, 0.0) + 0.0195).abs() < 1e-15);
+        assert!((shift(IrrbbScenario::Flattener, 0.0) - 0.024).abs() < 1e-15);
+        assert!(shift(IrrbbScenario::ShortDown, 200.0).abs() < 1e-15);
+        assert!((shift(IrrbbScenario::Steepener, 200.0) - 0.0135).abs() < 1e-15);
+        assert!((shift(IrrbbScenario::Flattener, 200.0) + 0.009).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_post_shock_floor() -> Result<()> {
+        let sizes = IrrbbShockSizes::bcbs(Currency::USD)?;
+        let floor = Some(PostShockFloor::default());
+        let date = Date::new(2025, 1, 1);
+
+        // the shocked rate stops at the floor
+        let curve =
+            ShockedTermStructure::new(flat_curve(0.005), IrrbbScenario::ParallelDown, sizes, floor);
+        assert!((zero_rate(&curve, date)? - (-0.015 + 0.0003)).abs() < 1e-12);
+
+        // rates below the floor are not moved down
+        let curve =
+            ShockedTermStructure::new(flat_curve(-0.02), IrrbbScenario::ParallelDown, sizes, floor);
+        assert!((zero_rate(&curve, date)? + 0.02).abs() < 1e-12);
+        let curve =
+            ShockedTermStructure::new(flat_curve(-0.02), IrrbbScenario::ParallelUp, sizes, floor);
+        assert!(zero_rate(&curve, date)?.abs() < 1e-12);
+
+        // an advanced curve keeps the shocked forward rates
+        let curve =
+            ShockedTermStructure::new(flat_curve(0.03), IrrbbScenario::ShortUp, sizes, None);
+        let advanced = curve.advance_to_date(Date::new(2024, 7, 2))?;
+        let forward = |curve: &dyn YieldTermStructureTrait| {
+            curve.forward_rate(
+                Date::new(2024, 7, 2),
+                Date::new(2026, 1, 2),
+                Compounding::Continuous,
+                Frequency::Annual,
+            )
+        };
+        assert!((forward(advanced.as_ref())? - forward(&curve)?).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_shock_floater() -> Result<()> {
+        // a curve quoted with other conventions than the shocks
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        add_flat_index(
+            &mut market_store,
+            0,
+            0.03,
+            RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Annual,
+            ),
+        )?;
+        let curve = market_store.get_index(0)?.read_index()?.term_structure()?;
+        market_store
+            .mut_index_store()
+            .add_currency_curve(Currency::USD, 0);
+
+        let sizes = IrrbbShockSizes::new(0.0, 0.0, 0.0);
+        let shocked = ShockedTermStructure::new(curve.clone(), IrrbbScenario::ShortUp, sizes, None);
+        let (start, end) = (Date::new(2024, 7, 2), Date::new(2025, 1, 2));
+        let forward = |curve: &dyn YieldTermStructureTrait| {
+            curve.forward_rate(start, end, Compounding::Simple, Frequency::Annual)
+        };
+        assert!((forward(&shocked)? - forward(curve.as_ref())?).abs() < 1e-12);
+
+        let floater = MakeFloatingRateInstrument::new()
+            .with_start_date(reference_date)
+            .with_end_date(Date::new(2027, 1, 2))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_definition(RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Annual,
+            ))
+            .with_spread(0.0)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::USD)
+            .with_side(Side::Receive)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        let portfolio =
+            Portfolio::new().with_instruments(vec![Instrument::FloatingRateInstrument(floater)]);
+        let results = IrrbbEngine::new(&market_store, &portfolio)
+            .with_shock_sizes(Currency::USD, sizes)
+            .run()?;
+        for scenario in IrrbbScenario::all() {
+            let result = results
+                .scenario(scenario)
+                .ok_or(AtlasError::NotFoundErr(format!("{scenario:?} scenario")))?;
+            assert!(result.delta_eve()[&Currency::USD].abs() < 1e-6);
+            assert!(result.delta_nii()[&Currency::USD].abs() < 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_eve_and_nii() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        add_flat_index(&mut market_store, 0, 0.03, continuous())?;
+        market_store
+            .mut_index_store()
+            .add_currency_curve(Currency::USD, 0);
+
+        // a five year fixed rate loan funded with six month deposits
+        let portfolio = Portfolio::new().with_instruments(vec![
+            fixed_rate_instrument(Date::new(2029, 1, 2), 0.04, 1_000_000.0, Side::Receive)?,
+            fixed_rate_instrument(Date::new(2024, 7, 2), 0.03, 900_000.0, Side::Pay)?,
+        ]);
+        let deposits = RolloverStrategy::new(
+            1.0,
+            Structure::Bullet,
+            Frequency::Semiannual,
+            Period::new(6, TimeUnit::Months),
+            Side::Pay,
+            RateType::Fixed,
+            RateDefinition::default(),
+            0,
+            None,
+        );
+        let results = IrrbbEngine::new(&market_store, &portfolio)
+            .with_rollover_strategies(Currency::USD, vec![deposits])
+            .run()?;
+
+        let base_eve = results.base_eve()[&Currency::USD];
+        assert!(base_eve > 0.0);
+        let parallel_up = results
+            .scenario(IrrbbScenario::ParallelUp)
+            .ok_or(AtlasError::NotFoundErr("Parallel up scenario".to_string()))?;
+        let parallel_down =
+            results
+                .scenario(IrrbbScenario::ParallelDown)
+                .ok_or(AtlasError::NotFoundErr(
+                    "Parallel down scenario".to_string(),
+                ))?;
+        assert!(parallel_up.delta_eve()[&Currency::USD] < 0.0);
+        assert!(parallel_down.delta_eve()[&Currency::USD] > 0.0);
+        assert_eq!(
+            results.worst_eve().map(IrrbbScenarioResult::scenario),
+            Some(IrrbbScenario::ParallelUp)
+        );
+        assert!((parallel_up.eve_loss() + parallel_up.delta_eve()[&Currency::USD]).abs() < 1e-9);
+
+        // the deposits are rolled over for the last six months at rates 200 basis points higher
+        let delta_nii = parallel_up.delta_nii()[&Currency::USD];
+        assert!((delta_nii / (-900_000.0 * 0.02 * 0.5) - 1.0).abs() < 0.1);
+        assert!(parallel_down.delta_nii()[&Currency::USD] > 0.0);
+        assert!(results
+            .worst_nii()
+            .is_some_and(|worst| worst.nii_loss() >= parallel_up.nii_loss()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_losses_in_local_currency() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::CLP);
+        add_flat_index(&mut market_store, 0, 0.03, continuous())?;
+        market_store
+            .mut_index_store()
+            .add_currency_curve(Currency::USD, 0);
+        market_store.mut_exchange_rate_store().add_exchange_rate(
+            Currency::CLP,
+            Currency::USD,
+            800.0,
+        );
+
+        let portfolio = Portfolio::new().with_instruments(vec![fixed_rate_instrument(
+            Date::new(2029, 1, 2),
+            0.04,
+            1_000_000.0,
+            Side::Receive,
+        )?]);
+        let results = IrrbbEngine::new(&market_store, &portfolio).run()?;
+        let parallel_up = results
+            .scenario(IrrbbScenario::ParallelUp)
+            .ok_or(AtlasError::NotFoundErr("Parallel up scenario".to_string()))?;
+
+        // USD per CLP
+        let rate = market_store.get_exchange_rate(Currency::USD, None)?;
+        assert!((rate - 1.0 / 800.0).abs() < 1e-15);
+        let delta_eve = parallel_up.delta_eve()[&Currency::USD];
+        assert!(delta_eve < 0.0);
+        assert!((parallel_up.eve_loss() + delta_eve / rate).abs() < 1e-6);
+        assert!((parallel_up.eve_loss() + delta_eve * 800.0).abs() < 1e-6);
+        Ok(())
+    }
+}
@@ -2,9 +2,23 @@
 pub mod cashaccount;
 /// Enumeration types module.
 pub mod enums;
+/// Funds transfer pricing module.
+pub mod ftp;
+/// Repricing and liquidity gap reports module.
+pub mod gap;
+/// Basel interest rate risk in the banking book module.
+pub mod irrbb;
+/// Monte Carlo exposure engine module.
+pub mod montecarloengine;
+/// Net interest income projection module.
+pub mod niiengine;
+/// Non-maturity deposit behavioural model module.
+pub mod nonmaturitydeposit;
 /// NPV engine module for net present value calculations.
 pub mod npvengine;
 /// Position generator module.
 pub mod positiongenerator;
+/// Loan prepayment models module.
+pub mod prepayment;
 /// Rollover simulation engine module.
 pub mod rolloversimulationengine;
@@ -0,0 +1,666 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    cashflows::{cashflow::Cashflow, traits::RequiresFixingRate},
+    core::{
+        meta::{MarketData, MarketRequest},
+        traits::Registrable,
+    },
+    instruments::instrument::Instrument,
+    models::traits::StochasticModel,
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+    visitors::{
+        indexingvisitor::IndexingVisitor,
+        npvbydateconstvisitor::NPVByDateConstVisitor,
+        traits::{ConstVisit, HasCashflows, Visit},
+    },
+};
+
+/// # `ExposureProfile`
+/// The exposure of an instrument or a netting set on the dates of a simulation.
+///
+/// ## Parameters
+/// * `dates` - The dates of the simulation
+/// * `expected_exposure` - The mean of the positive part of the value on each date
+/// * `discounted_expected_exposure` - The mean of the positive part of the value on each date,
+///   divided by the numeraire
+/// * `quantiles` - The quantiles of the potential future exposure
+/// * `potential_future_exposure` - For each quantile, the quantile of the positive part of the
+///   value on each date
+/// * `expected_positive_exposure` - The average of the expected exposure over the simulation,
+///   weighted by the time between dates
+#[derive(Clone, Debug)]
+pub struct ExposureProfile {
+    dates: Vec<Date>,
+    expected_exposure: Vec<f64>,
+    discounted_expected_exposure: Vec<f64>,
+    quantiles: Vec<f64>,
+    potential_future_exposure: Vec<Vec<f64>>,
+    expected_positive_exposure: f64,
+}
+
+impl ExposureProfile {
+    /// Returns the dates of the simulation.
+    #[must_use]
+    pub fn dates(&self) -> &[Date] {
+        &self.dates
+    }
+
+    /// Returns the expected exposure on each date.
+    #[must_use]
+    pub fn expected_exposure(&self) -> &[f64] {
+        &self.expected_exposure
+    }
+
+    /// Returns the expected exposure on each date, discounted with the numeraire of the model.
+    #[must_use]
+    pub fn discounted_expected_exposure(&self) -> &[f64] {
+        &self.discounted_expected_exposure
+    }
+
+    /// Returns the quantiles of the potential future exposure.
+    #[must_use]
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+
+    /// Returns the potential future exposure on each date at the given quantile, if it was
+    /// computed.
+    #[must_use]
+    pub fn potential_future_exposure(&self, quantile: f64) -> Option<&[f64]> {
+        self.quantiles
+            .iter()
+            .position(|q| (q - quantile).abs() < f64::EPSILON)
+            .map(|i| self.potential_future_exposure[i].as_slice())
+    }
+
+    /// Returns the expected positive exposure.
+    #[must_use]
+    pub const fn expected_positive_exposure(&self) -> f64 {
+        self.expected_positive_exposure
+    }
+}
+
+/// # `ExposureResults`
+/// The exposure profiles computed by a `MonteCarloEngine`.
+#[derive(Clone, Debug)]
+pub struct ExposureResults {
+    instruments: Vec<ExposureProfile>,
+    netting_sets: HashMap<String, ExposureProfile>,
+}
+
+impl ExposureResults {
+    /// Returns the profile of each instrument, in the order they were given.
+    #[must_use]
+    pub fn instruments(&self) -> &[ExposureProfile] {
+        &self.instruments
+    }
+
+    /// Returns the profile of each netting set.
+    #[must_use]
+    pub const fn netting_sets(&self) -> &HashMap<String, ExposureProfile> {
+        &self.netting_sets
+    }
+
+    /// Returns the profile of a netting set.
+    #[must_use]
+    pub fn netting_set(&self, name: &str) -> Option<&ExposureProfile> {
+        self.netting_sets.get(name)
+    }
+}
+
+/// Values of the instruments and numeraire on each date of a path.
+struct PathValues {
+    values: Vec<Vec<f64>>,
+    numeraires: Vec<f64>,
+}
+
+/// # `MonteCarloEngine`
+/// The `MonteCarloEngine` simulates the state variables of a `StochasticModel` on a grid of
+/// dates and values a portfolio in each state, to compute exposure profiles for counterparty
+/// and earnings at risk.
+///
+/// Paths are generated and valued in parallel with rayon. The random numbers of each path are
+/// drawn from its own generator, seeded from the seed of the engine and the index of the path,
+/// so results do not depend on the number of threads.
+///
+/// In each state, floating coupons are fixed with the forward rates seen in that state until
+/// their rate is known: from the first date of the path on or after their fixing date (the end
+/// of the observation period for overnight indexed coupons), they keep that fixing for the
+/// rest of the path.
+///
+/// ## Parameters
+/// * `model` - The model whose state variables are simulated
+/// * `dates` - The dates of the simulation, increasing and after the initial state
+/// * `paths` - The number of paths
+/// * `seed` - The seed of the random number generators, defaults to 42
+/// * `quantiles` - The quantiles of the potential future exposure, defaults to 0.95
+/// * `netting_sets` - The indices of the instruments of each netting set
+pub struct MonteCarloEngine<'a, M: StochasticModel> {
+    model: &'a M,
+    dates: Vec<Date>,
+    paths: usize,
+    seed: u64,
+    quantiles: Vec<f64>,
+    netting_sets: HashMap<String, Vec<usize>>,
+}
+
+impl<'a, M: StochasticModel> MonteCarloEngine<'a, M> {
+    /// Creates a new `MonteCarloEngine`.
+    #[must_use]
+    pub fn new(model: &'a M, dates: Vec<Date>, paths: usize) -> Self {
+        Self {
+            model,
+            dates,
+            paths,
+            seed: 42,
+            quantiles: vec![0.95],
+            netting_sets: HashMap::new(),
+        }
+    }
+
+    /// Sets the seed of the random number generators.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the quantiles of the potential future exposure.
+    #[must_use]
+    pub fn with_quantiles(mut self, quantiles: Vec<f64>) -> Self {
+        self.quantiles = quantiles;
+        self
+    }
+
+    /// Adds a netting set with the indices of its instruments in the portfolio.
+    #[must_use]
+    pub fn with_netting_set(mut self, name: String, instruments: Vec<usize>) -> Self {
+        self.netting_sets.insert(name, instruments);
+        self
+    }
+
+    /// Returns the dates of the simulation.
+    #[must_use]
+    pub fn dates(&self) -> &[Date] {
+        &self.dates
+    }
+
+    /// Returns the number of paths.
+    #[must_use]
+    pub const fn paths(&self) -> usize {
+        self.paths
+    }
+
+    /// Simulates the states of one path on each date of the simulation.
+    ///
+    /// # Errors
+    /// Returns an error if the dates are not increasing and after the initial state, or if the
+    /// model cannot evolve its state.
+    pub fn simulate_path(&self, path: usize) -> Result<Vec<M::State>> {
+        let initial_state = self.model.initial_state();
+        let initial_date = self.model.state_date(&initial_state);
+        if self
+            .dates
+            .first()
+            .is_some_and(|first| *first < initial_date)
+            || self.dates.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Simulation dates must be increasing and not before {initial_date}"
+            )));
+        }
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(path as u64));
+        let mut normals = vec![0.0; self.model.factors()];
+        let mut state = initial_state;
+        self.dates
+            .iter()
+            .map(|date| {
+                for normal in &mut normals {
+                    *normal = gen_normal(&mut rng);
+                }
+                state = self.model.evolve(&state, *date, &normals)?;
+                Ok(state.clone())
+            })
+            .collect()
+    }
+
+    /// Simulates the states of all paths.
+    ///
+    /// # Errors
+    /// Returns an error if any path cannot be simulated.
+    pub fn simulate(&self) -> Result<Vec<Vec<M::State>>> {
+        (0..self.paths)
+            .into_par_iter()
+            .map(|path| self.simulate_path(path))
+            .collect()
+    }
+
+    /// Generates the market data of a path on each of its dates.
+    ///
+    /// # Errors
+    /// Returns an error if the model cannot generate the requested data in any state.
+    pub fn gen_path_market_data(
+        &self,
+        path: &[M::State],
+        market_request: &[MarketRequest],
+    ) -> Result<Vec<Vec<MarketData>>> {
+        path.iter()
+            .map(|state| self.model.gen_state_market_data(state, market_request))
+            .collect()
+    }
+
+    /// Indexes the instruments, values them on every path and date and returns the exposure
+    /// profiles of each instrument and netting set. The value of an instrument in a state is the
+    /// value on the date of the state of the cashflows paid after it.
+    ///
+    /// # Errors
+    /// Returns an error if the quantiles are not in (0, 1), a netting set refers to a missing
+    /// instrument, or indexing, simulation, fixing or valuation fails.
+    pub fn run(&self, instruments: &mut [Instrument]) -> Result<ExposureResults> {
+        if self.paths == 0 || self.dates.is_empty() {
+            return Err(AtlasError::InvalidValueErr(
+                "Monte Carlo simulation needs at least one path and one date".to_string(),
+            ));
+        }
+        if self.quantiles.iter().any(|q| *q <= 0.0 || *q >= 1.0) {
+            return Err(AtlasError::InvalidValueErr(
+                "Exposure quantiles must be between 0 and 1".to_string(),
+            ));
+        }
+        if let Some((name, _)) = self
+            .netting_sets
+            .iter()
+            .find(|(_, members)| members.iter().any(|i| *i >= instruments.len()))
+        {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Netting set {name} refers to a missing instrument"
+            )));
+        }
+
+        let indexing_visitor = IndexingVisitor::new();
+        instruments
+            .iter_mut()
+            .try_for_each(|inst| indexing_visitor.visit(inst))?;
+        let requests = indexing_visitor.request();
+        let portfolio: &[Instrument] = instruments;
+
+        let paths = (0..self.paths)
+            .into_par_iter()
+            .map(|path| self.value_path(path, portfolio, &requests))
+            .collect::<Result<Vec<PathValues>>>()?;
+
+        let instrument_profiles = (0..portfolio.len())
+            .map(|i| self.profile(&paths, &|path, date| path.values[date][i]))
+            .collect();
+        let netting_set_profiles = self
+            .netting_sets
+            .iter()
+            .map(|(name, members)| {
+                let value = |path: &PathValues, date: usize| {
+                    members.iter().map(|i| path.values[date][*i]).sum()
+                };
+                (name.clone(), self.profile(&paths, &value))
+            })
+            .collect();
+        Ok(ExposureResults {
+            instruments: instrument_profiles,
+            netting_sets: netting_set_profiles,
+        })
+    }
+
+    /// Values the instruments on each date of a path.
+    fn value_path(
+        &self,
+        path: usize,
+        portfolio: &[Instrument],
+        requests: &[MarketRequest],
+    ) -> Result<PathValues> {
+        let states = self.simulate_path(path)?;
+        let mut instruments = portfolio.to_vec();
+        let mut known_fixings: Vec<Vec<bool>> = instruments
+            .iter()
+            .map(|inst| vec![false; inst.cashflows().len()])
+            .collect();
+        let mut values = Vec::with_capacity(states.len());
+        let mut numeraires = Vec::with_capacity(states.len());
+        for (date, state) in self.dates.iter().zip(&states) {
+            let data = self.model.gen_state_market_data(state, requests)?;
+            let npv_visitor = NPVByDateConstVisitor::new(*date, &data, false);
+            let date_values = instruments
+                .iter_mut()
+                .zip(&mut known_fixings)
+                .map(|(inst, known)| -> Result<f64> {
+                    fix_on_path(inst, known, &data, *date)?;
+                    Ok(npv_visitor.visit(inst)?.values().sum())
+                })
+                .collect::<Result<Vec<f64>>>()
+                .map_err(|e| {
+                    AtlasError::EvaluationErr(format!(
+                        "An error was found while valuing path {path} on {date}: {e}"
+                    ))
+                })?;
+            values.push(date_values);
+            numeraires.push(self.model.gen_state_numerarie(state)?);
+        }
+        Ok(PathValues { values, numeraires })
+    }
+
+    /// Computes the exposure profile of a value across all paths.
+    #[allow(clippy::cast_precision_loss)]
+    fn profile(
+        &self,
+        paths: &[PathValues],
+        value: &dyn Fn(&PathValues, usize) -> f64,
+    ) -> ExposureProfile {
+        let n = paths.len() as f64;
+        let mut expected_exposure = Vec::with_capacity(self.dates.len());
+        let mut discounted_expected_exposure = Vec::with_capacity(self.dates.len());
+        let mut potential_future_exposure = vec![Vec::new(); self.quantiles.len()];
+        for date in 0..self.dates.len() {
+            let mut exposures: Vec<f64> = paths
+                .iter()
+                .map(|path| value(path, date).max(0.0))
+                .collect();
+            expected_exposure.push(exposures.iter().sum::<f64>() / n);
+            discounted_expected_exposure.push(
+                paths
+                    .iter()
+                    .zip(&exposures)
+                    .map(|(path, exposure)| exposure / path.numeraires[date])
+                    .sum::<f64>()
+                    / n,
+            );
+            exposures.sort_by(f64::total_cmp);
+            for (pfe, quantile) in potential_future_exposure.iter_mut().zip(&self.quantiles) {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let rank = (quantile * n).ceil() as usize;
+                pfe.push(exposures[rank.clamp(1, exposures.len()) - 1]);
+            }
+        }
+
+        // the first date is weighted by the time elapsed since the initial state
+        let initial_date = self.model.state_date(&self.model.initial_state());
+        let mut weighted = 0.0;
+        let mut previous = initial_date;
+        for (date, exposure) in self.dates.iter().zip(&expected_exposure) {
+            weighted += exposure * DayCounter::Actual365.year_fraction(previous, *date);
+            previous = *date;
+        }
+        let horizon = DayCounter::Actual365.year_fraction(initial_date, previous);
+        let expected_positive_exposure = if horizon > 0.0 {
+            weighted / horizon
+        } else {
+            expected_exposure[0]
+        };
+
+        ExposureProfile {
+            dates: self.dates.clone(),
+            expected_exposure,
+            discounted_expected_exposure,
+            quantiles: self.quantiles.clone(),
+            potential_future_exposure,
+            expected_positive_exposure,
+        }
+    }
+}
+
+/// Fixes the floating coupons of an instrument with the market data of a state on `date`,
+/// except those whose rate was known on an earlier date of the path, which keep their fixing.
+/// `known` flags, for each cashflow, whether its rate is known.
+fn fix_on_path(
+    instrument: &mut Instrument,
+    known: &mut [bool],
+    market_data: &[MarketData],
+    date: Date,
+) -> Result<()> {
+    for (cf, known) in instrument.mut_cashflows().iter_mut().zip(known) {
+        let known_date = match cf {
+            Cashflow::FloatingRateCoupon(coupon) => coupon.fixing_date(),
+            Cashflow::OvernightIndexedCoupon(coupon) => coupon.observation_period().1,
+            _ => continue,
+        };
+        if *known {
+            continue;
+        }
+        let id = cf.id()?;
+        let cf_market_data = market_data.get(id).ok_or(AtlasError::NotFoundErr(format!(
+            "Market data for cashflow with id {id}"
+        )))?;
+        cf.set_fixing_rate(cf_market_data.fwd()?);
+        *known = known_date <= date;
+    }
+    Ok(())
+}
+
+/// Draws a standard normal number with the Box-Muller transform.
+fn gen_normal(rng: &mut StdRng) -> f64 {
+    // 1 - u is in (0, 1], so its logarithm is finite
+    let radius = (-2.0 * (1.0 - rng.gen::<f64>()).ln()).sqrt();
+    radius * (2.0 * PI * rng.gen::<f64>()).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::{cashflow::Side, traits::Payable},
+        currencies::enums::Currency,
+        instruments::{
+            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument,
+        },
+        models::hullwhitemodel::HullWhiteModel,
+        rates::{
+            enums::Compounding,
+            interestrate::{InterestRate, RateDefinition},
+            traits::{HasReferenceDate, YieldProvider},
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::enums::Frequency,
+        utils::testing::flat_market_store,
+        visitors::fixingvisitor::FixingVisitor,
+    };
+
+    fn fixed_rate_instrument(side: Side) -> Result<Instrument> {
+        let rate = InterestRate::new(
+            0.04,
+            Compounding::Simple,
+            Frequency::Annual,
+            DayCounter::Thirty360,
+        );
+        Ok(Instrument::FixedRateInstrument(
+            MakeFixedRateInstrument::new()
+                .with_start_date(Date::new(2024, 1, 2))
+                .with_end_date(Date::new(2029, 1, 2))
+                .with_payment_frequency(Frequency::Semiannual)
+                .with_rate(rate)
+                .with_notional(1_000_000.0)
+                .with_discount_curve_id(Some(0))
+                .with_side(side)
+                .with_currency(Currency::USD)
+                .bullet()
+                .build()?,
+        ))
+    }
+
+    #[test]
+    fn test_deterministic_exposure() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let model = HullWhiteModel::new(&market_store, 0, 0.05, 0.0);
+        let dates = vec![Date::new(2025, 1, 2), Date::new(2027, 1, 2)];
+        let mut instruments = vec![
+            fixed_rate_instrument(Side::Receive)?,
+            fixed_rate_instrument(Side::Pay)?,
+        ];
+        let results = MonteCarloEngine::new(&model, dates.clone(), 10)
+            .with_quantiles(vec![0.5, 0.99])
+            .with_netting_set("hedged".to_string(), vec![0, 1])
+            .run(&mut instruments)?;
+
+        let curve = FlatForwardTermStructure::new(
+            market_store.reference_date(),
+            0.03,
+            RateDefinition::default(),
+        );
+        let receive = &results.instruments()[0];
+        for (i, date) in dates.iter().enumerate() {
+            let mut expected = 0.0;
+            for cashflow in instruments[0].cashflows() {
+                if cashflow.payment_date() > *date {
+                    expected += cashflow.amount()?
+                        * curve.discount_factor(cashflow.payment_date())?
+                        / curve.discount_factor(*date)?;
+                }
+            }
+            let exposure = receive.expected_exposure()[i];
+            assert!((exposure - expected).abs() < 1e-6);
+            assert!(
+                (receive.potential_future_exposure(0.99).unwrap_or_default()[i] - expected).abs()
+                    < 1e-6
+            );
+            assert!(results.instruments()[1].expected_exposure()[i].abs() < 1e-12);
+        }
+        let hedged = results
+            .netting_set("hedged")
+            .ok_or(AtlasError::NotFoundErr("Netting set hedged".to_string()))?;
+        assert!(hedged
+            .expected_exposure()
+            .iter()
+            .all(|exposure| exposure.abs() < 1e-6));
+        assert!(receive.expected_positive_exposure() > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stochastic_exposure() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let model = HullWhiteModel::new(&market_store, 0, 0.05, 0.01);
+        let dates = vec![
+            Date::new(2024, 7, 2),
+            Date::new(2025, 1, 2),
+            Date::new(2026, 1, 2),
+            Date::new(2028, 1, 2),
+        ];
+        let floating = MakeFloatingRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 2))
+            .with_end_date(Date::new(2029, 1, 2))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_definition(RateDefinition::default())
+            .with_spread(0.0)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::USD)
+            .with_side(Side::Pay)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        let mut instruments = vec![
+            fixed_rate_instrument(Side::Receive)?,
+            Instrument::FloatingRateInstrument(floating),
+        ];
+        let engine = MonteCarloEngine::new(&model, dates, 500)
+            .with_quantiles(vec![0.95, 0.99])
+            .with_netting_set("swap".to_string(), vec![0, 1]);
+        let results = engine.run(&mut instruments)?;
+        let swap = results
+            .netting_set("swap")
+            .ok_or(AtlasError::NotFoundErr("Netting set swap".to_string()))?;
+
+        let pfe_95 = swap.potential_future_exposure(0.95).unwrap_or_default();
+        let pfe_99 = swap.potential_future_exposure(0.99).unwrap_or_default();
+        for (i, exposure) in swap.expected_exposure().iter().enumerate() {
+            assert!(*exposure > 0.0);
+            assert!(pfe_95[i] >= *exposure && pfe_99[i] >= pfe_95[i]);
+            assert!(swap.discounted_expected_exposure()[i] < *exposure);
+            // netting cannot increase the exposure
+            let gross: f64 = results
+                .instruments()
+                .iter()
+                .map(|profile| profile.expected_exposure()[i])
+                .sum();
+            assert!(*exposure <= gross);
+        }
+
+        // the same seed gives the same paths
+        let again = engine.run(&mut instruments)?;
+        assert_eq!(
+            again
+                .netting_set("swap")
+                .map(ExposureProfile::expected_exposure),
+            Some(swap.expected_exposure())
+        );
+        assert!(engine
+            .with_quantiles(vec![1.0])
+            .run(&mut instruments)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixings_kept_on_path() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let model = HullWhiteModel::new(&market_store, 0, 0.05, 0.01);
+        let dates = vec![
+            Date::new(2024, 4, 2),
+            Date::new(2024, 7, 2),
+            Date::new(2024, 10, 2),
+        ];
+        let floating = MakeFloatingRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 2))
+            .with_end_date(Date::new(2026, 1, 2))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_definition(RateDefinition::default())
+            .with_spread(0.0)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::USD)
+            .with_side(Side::Receive)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        let mut instrument = Instrument::FloatingRateInstrument(floating);
+        let indexing_visitor = IndexingVisitor::new();
+        indexing_visitor.visit(&mut instrument)?;
+        let engine = MonteCarloEngine::new(&model, dates.clone(), 1);
+        let path = engine.simulate_path(0)?;
+        let data = engine.gen_path_market_data(&path, &indexing_visitor.request())?;
+
+        // the second coupon accrues from 2024-07-02, when it is fixed
+        let amount = |inst: &Instrument, coupon: Date| -> Result<f64> {
+            inst.cashflows()
+                .iter()
+                .find_map(|cf| match cf {
+                    Cashflow::FloatingRateCoupon(c) if c.fixing_date() == coupon => {
+                        Some(c.amount())
+                    }
+                    _ => None,
+                })
+                .ok_or(AtlasError::NotFoundErr(format!(
+                    "Coupon fixing on {coupon}"
+                )))?
+        };
+        let mut known = vec![false; instrument.cashflows().len()];
+        let mut amounts = Vec::new();
+        for (date, date_data) in dates.iter().zip(&data) {
+            fix_on_path(&mut instrument, &mut known, date_data, *date)?;
+            amounts.push((
+                amount(&instrument, dates[1])?,
+                amount(&instrument, Date::new(2025, 1, 2))?,
+            ));
+        }
+        assert!((amounts[2].0 - amounts[1].0).abs() < 1e-12);
+        assert!((amounts[1].0 - amounts[0].0).abs() > 1e-6);
+        assert!((amounts[2].1 - amounts[1].1).abs() > 1e-6);
+
+        // re-fixing in the last state would have changed the coupon
+        let mut refixed = instrument.clone();
+        FixingVisitor::new(&data[2]).visit(&mut refixed)?;
+        assert!((amount(&refixed, dates[1])? - amounts[2].0).abs() > 1e-6);
+        Ok(())
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    enums::{AccountType, Portfolio},
+    positiongenerator::RolloverStrategy,
+    rolloversimulationengine::{GrowthMode, RolloverSimulationEngine},
+};
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        traits::Payable,
+    },
+    core::{marketstore::MarketStore, traits::HasCurrency},
+    currencies::enums::Currency,
+    instruments::instrument::Instrument,
+    models::{simplemodel::SimpleModel, traits::Model},
+    rates::traits::HasReferenceDate,
+    time::{date::Date, enums::TimeUnit, period::Period},
+    utils::errors::{AtlasError, Result},
+    visitors::{
+        accruedamountconstvisitor::AccruedAmountConstVisitor,
+        fixingvisitor::FixingVisitor,
+        indexingvisitor::IndexingVisitor,
+        traits::{ConstVisit, HasCashflows, Visit},
+    },
+};
+
+/// # `BalanceSheetAssumption`
+/// What happens to the notional redeemed by the positions during the projection.
+///
+/// ## Variants
+/// * `Static` - The balance sheet runs off: redemptions are not replaced.
+/// * `ConstantBalance` - Every redemption is replaced by new positions of the same amount.
+/// * `Growth` - New positions keep the balance growing at the given annual rate.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BalanceSheetAssumption {
+    /// Run-off balance sheet.
+    Static,
+    /// Redemptions rolled over.
+    ConstantBalance,
+    /// Balance growing at an annual rate.
+    Growth(f64),
+}
+
+/// # `NIIRow`
+/// The monthly interest income and expense of the positions of an account type and currency.
+#[derive(Clone, Debug)]
+pub struct NIIRow {
+    account_type: Option<AccountType>,
+    currency: Currency,
+    interest_income: Vec<f64>,
+    interest_expense: Vec<f64>,
+}
+
+impl NIIRow {
+    /// Returns the account type of the portfolios of the row.
+    #[must_use]
+    pub const fn account_type(&self) -> Option<AccountType> {
+        self.account_type
+    }
+
+    /// Returns the currency of the row.
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the interest accrued by the positions that receive, per month.
+    #[must_use]
+    pub fn interest_income(&self) -> &[f64] {
+        &self.interest_income
+    }
+
+    /// Returns the interest accrued by the positions that pay, per month.
+    #[must_use]
+    pub fn interest_expense(&self) -> &[f64] {
+        &self.interest_expense
+    }
+
+    /// Returns the net interest income per month.
+    #[must_use]
+    pub fn nii(&self) -> Vec<f64> {
+        self.interest_income
+            .iter()
+            .zip(&self.interest_expense)
+            .map(|(income, expense)| income - expense)
+            .collect()
+    }
+
+    /// Returns the net interest income over the horizon.
+    #[must_use]
+    pub fn total_nii(&self) -> f64 {
+        self.nii().iter().sum()
+    }
+}
+
+/// # `NIIProjection`
+/// The net interest income of a book in a market, per month, account type and currency.
+#[derive(Clone, Debug)]
+pub struct NIIProjection {
+    periods: Vec<(Date, Date)>,
+    rows: Vec<NIIRow>,
+}
+
+impl NIIProjection {
+    /// Returns the start and end dates of the months.
+    #[must_use]
+    pub fn periods(&self) -> &[(Date, Date)] {
+        &self.periods
+    }
+
+    /// Returns the rows, in the order their account type and currency first appear.
+    #[must_use]
+    pub fn rows(&self) -> &[NIIRow] {
+        &self.rows
+    }
+
+    /// Returns the row of an account type and currency.
+    #[must_use]
+    pub fn row(&self, account_type: Option<AccountType>, currency: Currency) -> Option<&NIIRow> {
+        self.rows
+            .iter()
+            .find(|row| row.account_type == account_type && row.currency == currency)
+    }
+
+    /// Returns the net interest income of a currency per month.
+    #[must_use]
+    pub fn nii(&self, currency: Currency) -> Vec<f64> {
+        let mut nii = vec![0.0; self.periods.len()];
+        for row in self.rows.iter().filter(|row| row.currency == currency) {
+            for (total, value) in nii.iter_mut().zip(row.nii()) {
+                *total += value;
+            }
+        }
+        nii
+    }
+
+    /// Returns the net interest income of a currency over the horizon.
+    #[must_use]
+    pub fn total_nii(&self, currency: Currency) -> f64 {
+        self.nii(currency).iter().sum()
+    }
+}
+
+/// # `NIIResults`
+/// The NII projections of the base market and of each shocked market.
+#[derive(Clone, Debug)]
+pub struct NIIResults {
+    base: NIIProjection,
+    scenarios: Vec<(String, NIIProjection)>,
+}
+
+impl NIIResults {
+    /// Returns the projection in the base market.
+    #[must_use]
+    pub const fn base(&self) -> &NIIProjection {
+        &self.base
+    }
+
+    /// Returns the projections in the shocked markets, in the order they were added.
+    #[must_use]
+    pub fn scenarios(&self) -> &[(String, NIIProjection)] {
+        &self.scenarios
+    }
+
+    /// Returns the projection in a shocked market.
+    #[must_use]
+    pub fn scenario(&self, name: &str) -> Option<&NIIProjection> {
+        self.scenarios
+            .iter()
+            .find(|(scenario, _)| scenario == name)
+            .map(|(_, projection)| projection)
+    }
+}
+
+/// # `NIIEngine`
+/// Projects the monthly net interest income of a book over a horizon.
+///
+/// In each market, floating coupons are fixed with the forward rates of their indices and the
+/// interest accrued day by day is collected with an `AccruedAmountConstVisitor`. Unless the
+/// balance sheet is static, the redemptions of each portfolio are replaced with new positions
+/// generated by a `RolloverSimulationEngine` with the strategies of their currency and side, so
+/// that assets are rolled over into assets and liabilities into liabilities. The new positions
+/// keep the account type of the portfolio they replace.
+///
+/// ## Parameters
+/// * `market_store` - The base market
+/// * `portfolios` - The portfolios of the book
+/// * `horizon` - The horizon of the projection, defaults to 12 months
+/// * `assumption` - The balance sheet assumption, defaults to `Static`
+/// * `rollover_strategies` - The rollover strategies of each currency
+/// * `scenarios` - The shocked markets, by name
+pub struct NIIEngine<'a> {
+    market_store: &'a MarketStore,
+    portfolios: &'a [Portfolio],
+    horizon: Period,
+    assumption: BalanceSheetAssumption,
+    rollover_strategies: HashMap<Currency, Vec<RolloverStrategy>>,
+    scenarios: Vec<(String, &'a MarketStore)>,
+}
+
+impl<'a> NIIEngine<'a> {
+    /// Creates a new `NIIEngine`.
+    #[must_use]
+    pub fn new(market_store: &'a MarketStore, portfolios: &'a [Portfolio]) -> Self {
+        Self {
+            market_store,
+            portfolios,
+            horizon: Period::new(12, TimeUnit::Months),
+            assumption: BalanceSheetAssumption::Static,
+            rollover_strategies: HashMap::new(),
+            scenarios: Vec::new(),
+        }
+    }
+
+    /// Sets the horizon of the projection.
+    #[must_use]
+    pub const fn with_horizon(mut self, horizon: Period) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Sets the balance sheet assumption.
+    #[must_use]
+    pub const fn with_assumption(mut self, assumption: BalanceSheetAssumption) -> Self {
+        self.assumption = assumption;
+        self
+    }
+
+    /// Sets the strategies used to roll over the redemptions of a currency.
+    #[must_use]
+    pub fn with_rollover_strategies(
+        mut self,
+        currency: Currency,
+        strategies: Vec<RolloverStrategy>,
+    ) -> Self {
+        self.rollover_strategies.insert(currency, strategies);
+        self
+    }
+
+    /// Adds a shocked market to project the NII in.
+    #[must_use]
+    pub fn with_scenario(mut self, name: String, market_store: &'a MarketStore) -> Self {
+        self.scenarios.push((name, market_store));
+        self
+    }
+
+    /// Returns the start and end dates of the months of the horizon.
+    #[must_use]
+    pub fn periods(&self) -> Vec<(Date, Date)> {
+        let reference_date = self.market_store.reference_date();
+        let end_date = reference_date + self.horizon;
+        let mut periods = Vec::new();
+        let mut start_date = reference_date;
+        let mut month = 1;
+        while start_date < end_date {
+            let date = (reference_date + Period::new(month, TimeUnit::Months)).min(end_date);
+            periods.push((start_date, date));
+            start_date = date;
+            month += 1;
+        }
+        periods
+    }
+
+    /// Projects the NII in the base market and in every shocked market.
+    ///
+    /// # Errors
+    /// Returns an error if the positions cannot be fixed, rolled over or accrued in a market.
+    pub fn run(&self) -> Result<NIIResults> {
+        let base = self.project(self.market_store)?;
+        let scenarios = self
+            .scenarios
+            .iter()
+            .map(|(name, market_store)| Ok((name.clone(), self.project(market_store)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(NIIResults { base, scenarios })
+    }
+
+    /// Projects the NII in a market.
+    ///
+    /// # Errors
+    /// Returns an error if the positions cannot be fixed, rolled over or accrued.
+    pub fn project(&self, market_store: &MarketStore) -> Result<NIIProjection> {
+        let reference_date = market_store.reference_date();
+        let periods = self.periods();
+        let mut rows: Vec<NIIRow> = Vec::new();
+        for portfolio in self.portfolios {
+            let mut instruments = fixed_instruments(market_store, portfolio.instruments())?;
+            let mut new_positions = self.rolled_over_positions(market_store, &instruments)?;
+            instruments.append(&mut new_positions);
+
+            let mut visitors: Vec<(Currency, Side, AccruedAmountConstVisitor)> = Vec::new();
+            for instrument in &instruments {
+                let currency = instrument.currency()?;
+                let side = instrument
+                    .side()
+                    .ok_or(AtlasError::ValueNotSetErr("Side".to_string()))?;
+                if !visitors
+                    .iter()
+                    .any(|(c, s, _)| *c == currency && *s == side)
+                {
+                    visitors.push((
+                        currency,
+                        side,
+                        AccruedAmountConstVisitor::new(reference_date, self.horizon)?,
+                    ));
+                }
+                if let Some((_, _, visitor)) = visitors
+                    .iter()
+                    .find(|(c, s, _)| *c == currency && *s == side)
+                {
+                    visitor.visit(instrument)?;
+                }
+            }
+
+            for (currency, side, visitor) in visitors {
+                let account_type = portfolio.account_type();
+                if !rows
+                    .iter()
+                    .any(|row| row.account_type == account_type && row.currency == currency)
+                {
+                    rows.push(NIIRow {
+                        account_type,
+                        currency,
+                        interest_income: vec![0.0; periods.len()],
+                        interest_expense: vec![0.0; periods.len()],
+                    });
+                }
+                let row = rows
+                    .iter_mut()
+                    .find(|row| row.account_type == account_type && row.currency == currency)
+                    .ok_or(AtlasError::NotFoundErr("NII row".to_string()))?;
+                let amounts = match side {
+                    Side::Receive => &mut row.interest_income,
+                    Side::Pay => &mut row.interest_expense,
+                };
+                for (date, amount) in visitor.accrued_amounts()? {
+                    if let Some(month) = periods
+                        .iter()
+                        .position(|(start, end)| date > *start && date <= *end)
+                    {
+                        amounts[month] += amount;
+                    }
+                }
+            }
+        }
+        Ok(NIIProjection { periods, rows })
+    }
+
+    /// Returns the positions that replace the redemptions of the instruments.
+    fn rolled_over_positions(
+        &self,
+        market_store: &MarketStore,
+        instruments: &[Instrument],
+    ) -> Result<Vec<Instrument>> {
+        let growth_rate = match self.assumption {
+            BalanceSheetAssumption::Static => return Ok(Vec::new()),
+            BalanceSheetAssumption::ConstantBalance => 0.0,
+            BalanceSheetAssumption::Growth(rate) => rate,
+        };
+        let reference_date = market_store.reference_date();
+        let mut new_positions = Vec::new();
+        for (currency, strategies) in &self.rollover_strategies {
+            for side in [Side::Receive, Side::Pay] {
+                let mut redemptions = BTreeMap::new();
+                for cf in instruments.iter().flat_map(HasCashflows::cashflows) {
+                    if let Cashflow::Redemption(redemption) = cf {
+                        let payment_date = redemption.payment_date();
+                        if redemption.side() == side
+                            && cf.currency()? == *currency
+                            && payment_date >= reference_date
+                        {
+                            *redemptions.entry(payment_date).or_insert(0.0) +=
+                                redemption.amount()?;
+                        }
+                    }
+                }
+                let side_strategies: Vec<RolloverStrategy> = strategies
+                    .iter()
+                    .filter(|strategy| strategy.side() == side)
+                    .cloned()
+                    .collect();
+                if redemptions.is_empty() || side_strategies.is_empty() {
+                    continue;
+                }
+                // the balance grows with time, not only when positions are redeemed
+                let growth_mode = if growth_rate == 0.0 {
+                    GrowthMode::PaidAmount
+                } else {
+                    GrowthMode::Annual
+                };
+                let mut positions = RolloverSimulationEngine::new(
+                    market_store,
+                    redemptions,
+                    *currency,
+                    self.horizon,
+                )
+                .with_growth_mode(growth_mode)
+                .with_growth_rate(growth_rate)
+                .run(&side_strategies)?;
+                new_positions.append(&mut positions);
+            }
+        }
+        Ok(new_positions)
+    }
+}
+
+/// Returns the instruments indexed and fixed with the forward rates of the market.
+fn fixed_instruments(
+    market_store: &MarketStore,
+    instruments: &[Instrument],
+) -> Result<Vec<Instrument>> {
+    let mut instruments = instruments.to_vec();
+    let indexing_visitor = IndexingVisitor::new();
+    instruments
+        .iter_mut()
+        .try_for_each(|inst| indexing_visitor.visit(inst))?;
+    let data = SimpleModel::new(market_store).gen_market_data(&indexing_visitor.request())?;
+    let fixing_visitor = FixingVisitor::new(&data);
+    instruments
+        .iter_mut()
+        .try_for_each(|inst| fixing_visitor.visit(inst))?;
+    Ok(instruments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruments::{
+            instrument::RateType, makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument, traits::Structure,
+        },
+        rates::interestrate::RateDefinition,
+        time::enums::Frequency,
+        utils::testing::flat_market_store,
+    };
+
+    fn portfolios(loan_end_date: Date) -> Result<Vec<Portfolio>> {
+        let loan = MakeFixedRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 1))
+            .with_end_date(loan_end_date)
+            .with_payment_frequency(Frequency::Monthly)
+            .with_rate_value(0.06)
+            .with_rate_definition(RateDefinition::default())
+            .with_notional(100.0)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .with_discount_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        let deposit = MakeFloatingRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 1))
+            .with_end_date(Date::new(2026, 1, 1))
+            .with_payment_frequency(Frequency::Monthly)
+            .with_rate_definition(RateDefinition::default())
+            .with_spread(0.0)
+            .with_notional(80.0)
+            .with_side(Side::Pay)
+            .with_currency(Currency::USD)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        Ok(vec![
+            Portfolio::new()
+                .with_account_type(AccountType::Asset)
+                .with_instruments(vec![Instrument::FixedRateInstrument(loan)]),
+            Portfolio::new()
+                .with_account_type(AccountType::Liability)
+                .with_instruments(vec![Instrument::FloatingRateInstrument(deposit)]),
+        ])
+    }
+
+    #[test]
+    fn test_static_projection() -> Result<()> {
+        let base = flat_market_store(Date::new(2024, 1, 1), 0.04)?;
+        let shocked = flat_market_store(Date::new(2024, 1, 1), 0.05)?;
+        let portfolios = portfolios(Date::new(2026, 1, 1))?;
+        let results = NIIEngine::new(&base, &portfolios)
+            .with_scenario("Up".to_string(), &shocked)
+            .run()?;
+
+        let projection = results.base();
+        assert_eq!(projection.periods().len(), 12);
+        let asset = projection
+            .row(Some(AccountType::Asset), Currency::USD)
+            .ok_or(AtlasError::NotFoundErr("Asset row".to_string()))?;
+        let liability = projection
+            .row(Some(AccountType::Liability), Currency::USD)
+            .ok_or(AtlasError::NotFoundErr("Liability row".to_string()))?;
+
+        // 31 days of interest in January at 6% and 4%, on an Actual360 basis
+        assert!((asset.interest_income()[0] - 100.0 * 0.06 * 31.0 / 360.0).abs() < 1e-9);
+        assert!((liability.interest_expense()[0] - 80.0 * 0.04 * 31.0 / 360.0).abs() < 1e-9);
+        let income: f64 = asset.interest_income().iter().sum();
+        let expense: f64 = liability.interest_expense().iter().sum();
+        assert!((income - 100.0 * 0.06 * 366.0 / 360.0).abs() < 1e-9);
+        assert!((projection.total_nii(Currency::USD) - (income - expense)).abs() < 1e-9);
+
+        // only the floating deposit reprices in the shocked market
+        let shocked = results
+            .scenario("Up")
+            .ok_or(AtlasError::NotFoundErr("Up scenario".to_string()))?;
+        let shocked_asset = shocked
+            .row(Some(AccountType::Asset), Currency::USD)
+            .ok_or(AtlasError::NotFoundErr("Asset row".to_string()))?;
+        assert!((shocked_asset.total_nii() - asset.total_nii()).abs() < 1e-12);
+        assert!(shocked.total_nii(Currency::USD) < projection.total_nii(Currency::USD));
+        Ok(())
+    }
+
+    #[test]
+    fn test_balance_sheet_assumptions() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 1), 0.04)?;
+        let portfolios = portfolios(Date::new(2024, 7, 1))?;
+        let strategies = vec![RolloverStrategy::new(
+            1.0,
+            Structure::Bullet,
+            Frequency::Monthly,
+            Period::new(1, TimeUnit::Years),
+            Side::Receive,
+            RateType::Fixed,
+            RateDefinition::default(),
+            0,
+            None,
+        )];
+        let income = |assumption| -> Result<Vec<f64>> {
+            let projection = NIIEngine::new(&market_store, &portfolios)
+                .with_assumption(assumption)
+                .with_rollover_strategies(Currency::USD, strategies.clone())
+                .project(&market_store)?;
+            Ok(projection
+                .row(Some(AccountType::Asset), Currency::USD)
+                .ok_or(AtlasError::NotFoundErr("Asset row".to_string()))?
+                .interest_income()
+                .to_vec())
+        };
+
+        let run_off = income(BalanceSheetAssumption::Static)?;
+        assert!(run_off[5] > 0.0);
+        assert!(run_off[6..].iter().all(|income| income.abs() < 1e-12));
+
+        // the redeemed loan is replaced by a loan at the 4% par rate
+        let constant = income(BalanceSheetAssumption::ConstantBalance)?;
+        assert!((constant[5] - run_off[5]).abs() < 1e-12);
+        assert!((constant[7] / (100.0 * 31.0 / 360.0) - 0.04).abs() < 1e-3);
+
+        let growth = income(BalanceSheetAssumption::Growth(0.1))?;
+        assert!(growth[7] > constant[7]);
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+
+use super::positiongenerator::{PositionGenerator, RolloverStrategy};
+use crate::{
+    cashflows::cashflow::Side,
+    core::marketstore::MarketStore,
+    currencies::enums::Currency,
+    instruments::{instrument::Instrument, instrument::RateType, traits::Structure},
+    rates::{interestrate::RateDefinition, traits::HasReferenceDate},
+    time::{
+        date::Date,
+        daycounter::DayCounter,
+        enums::{Frequency, TimeUnit},
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+/// # `DecayProfile`
+/// How the core balance of a non-maturity deposit runs off with time.
+///
+/// ## Variants
+/// * `Exponential` - The balance decays at a constant annual rate, so the fraction left after
+///   `t` years is `e^{-rate * t}`.
+/// * `RunOff` - The fraction left at each tenor, interpolated linearly in time and flat after
+///   the last tenor. The balance is whole at the reference date.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum DecayProfile {
+    /// Constant annual decay rate.
+    Exponential(f64),
+    /// Fractions of the balance left at increasing tenors.
+    RunOff(Vec<(Period, f64)>),
+}
+
+impl DecayProfile {
+    /// Returns the fraction of the balance left on `date`.
+    #[must_use]
+    pub fn survival(&self, reference_date: Date, date: Date) -> f64 {
+        let time = |date: Date| DayCounter::Actual365.year_fraction(reference_date, date);
+        let t = time(date);
+        if t <= 0.0 {
+            return 1.0;
+        }
+        match self {
+            Self::Exponential(rate) => (-rate * t).exp(),
+            Self::RunOff(points) => {
+                let (mut previous_time, mut previous_fraction) = (0.0, 1.0);
+                for (tenor, fraction) in points {
+                    let tenor_time = time(reference_date + *tenor);
+                    if t <= tenor_time {
+                        let weight = (t - previous_time) / (tenor_time - previous_time);
+                        return weight.mul_add(fraction - previous_fraction, previous_fraction);
+                    }
+                    (previous_time, previous_fraction) = (tenor_time, *fraction);
+                }
+                previous_fraction
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Exponential(rate) if *rate < 0.0 => Err(AtlasError::InvalidValueErr(
+                "Decay rate must not be negative".to_string(),
+            )),
+            Self::RunOff(points) => {
+                let mut previous = (0.0, 1.0);
+                for (tenor, fraction) in points {
+                    let tenor_time = tenor.period_in_year();
+                    if tenor_time <= previous.0 || *fraction > previous.1 || *fraction < 0.0 {
+                        return Err(AtlasError::InvalidValueErr(
+                            "Run-off tenors must be increasing and fractions decreasing between 1 and 0"
+                                .to_string(),
+                        ));
+                    }
+                    previous = (tenor_time, *fraction);
+                }
+                Ok(())
+            }
+            Self::Exponential(_) => Ok(()),
+        }
+    }
+}
+
+/// # `NonMaturityDeposit`
+/// A behavioural model of a balance without contractual maturity, such as demand deposits. The
+/// balance is split into a volatile part, withdrawn at the shortest tenor, and a core part that
+/// runs off following a `DecayProfile`.
+///
+/// The replicating portfolio represents the balance with fixed rate bullet tranches at par,
+/// generated with a `PositionGenerator`: the volatile part goes in a tranche at the volatile
+/// tenor, the core that runs off between two tenors in the tranche of the later one, and the
+/// core left after the last tenor in the longest tranche. The tranches are ordinary instruments, so they can be used in any report.
+///
+/// ## Parameters
+/// * `balance` - The current balance
+/// * `currency` - The currency of the balance
+/// * `core_ratio` - The fraction of the balance that is stable
+/// * `decay` - The run-off profile of the core balance
+/// * `side` - The side of the tranches, defaults to `Pay` for deposits
+/// * `discount_curve_id` - The curve used to price the tranches at par, defaults to 0
+/// * `rate_definition` - The rate definition of the tranches
+/// * `payment_frequency` - The payment frequency of the tranches, defaults to `Once`
+/// * `volatile_tenor` - The tenor of the volatile tranche, defaults to one day
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let deposit = NonMaturityDeposit::new(
+///     1_000.0,
+///     Currency::USD,
+///     0.6,
+///     DecayProfile::Exponential(0.2),
+/// )
+/// .unwrap();
+/// assert!((deposit.volatile_balance() - 400.0).abs() < 1e-12);
+///
+/// let reference_date = Date::new(2024, 1, 1);
+/// let core = deposit.core_balance_at(reference_date, reference_date);
+/// assert!((core - 600.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NonMaturityDeposit {
+    balance: f64,
+    currency: Currency,
+    core_ratio: f64,
+    decay: DecayProfile,
+    side: Side,
+    discount_curve_id: usize,
+    rate_definition: RateDefinition,
+    payment_frequency: Frequency,
+    volatile_tenor: Period,
+}
+
+impl NonMaturityDeposit {
+    /// Creates a new `NonMaturityDeposit`.
+    ///
+    /// # Errors
+    /// Returns an error if the balance is negative, the core ratio is not between 0 and 1 or
+    /// the decay profile is invalid.
+    pub fn new(
+        balance: f64,
+        currency: Currency,
+        core_ratio: f64,
+        decay: DecayProfile,
+    ) -> Result<Self> {
+        if balance < 0.0 || !(0.0..=1.0).contains(&core_ratio) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Invalid non-maturity deposit balance ({balance}) or core ratio ({core_ratio})"
+            )));
+        }
+        decay.validate()?;
+        Ok(Self {
+            balance,
+            currency,
+            core_ratio,
+            decay,
+            side: Side::Pay,
+            discount_curve_id: 0,
+            rate_definition: RateDefinition::default(),
+            payment_frequency: Frequency::Once,
+            volatile_tenor: Period::new(1, TimeUnit::Days),
+        })
+    }
+
+    /// Sets the side of the tranches.
+    #[must_use]
+    pub const fn with_side(mut self, side: Side) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Sets the curve used to price the tranches.
+    #[must_use]
+    pub const fn with_discount_curve_id(mut self, discount_curve_id: usize) -> Self {
+        self.discount_curve_id = discount_curve_id;
+        self
+    }
+
+    /// Sets the rate definition of the tranches.
+    #[must_use]
+    pub const fn with_rate_definition(mut self, rate_definition: RateDefinition) -> Self {
+        self.rate_definition = rate_definition;
+        self
+    }
+
+    /// Sets the payment frequency of the tranches.
+    #[must_use]
+    pub const fn with_payment_frequency(mut self, payment_frequency: Frequency) -> Self {
+        self.payment_frequency = payment_frequency;
+        self
+    }
+
+    /// Sets the tenor of the volatile tranche.
+    #[must_use]
+    pub const fn with_volatile_tenor(mut self, volatile_tenor: Period) -> Self {
+        self.volatile_tenor = volatile_tenor;
+        self
+    }
+
+    /// Returns the balance.
+    #[must_use]
+    pub const fn balance(&self) -> f64 {
+        self.balance
+    }
+
+    /// Returns the currency.
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the decay profile of the core balance.
+    #[must_use]
+    pub const fn decay(&self) -> &DecayProfile {
+        &self.decay
+    }
+
+    /// Returns the stable part of the balance.
+    #[must_use]
+    pub fn core_balance(&self) -> f64 {
+        self.balance * self.core_ratio
+    }
+
+    /// Returns the part of the balance that can be withdrawn at any time.
+    #[must_use]
+    pub fn volatile_balance(&self) -> f64 {
+        self.balance - self.core_balance()
+    }
+
+    /// Returns the core balance left on `date`.
+    #[must_use]
+    pub fn core_balance_at(&self, reference_date: Date, date: Date) -> f64 {
+        self.core_balance() * self.decay.survival(reference_date, date)
+    }
+
+    /// Returns the strategies of the tranches of the replicating portfolio, with weights as
+    /// fractions of the balance. Tranches with no weight are left out.
+    ///
+    /// # Errors
+    /// Returns an error if the tenors are empty, not increasing or not longer than the
+    /// volatile tenor.
+    pub fn replicating_strategies(
+        &self,
+        reference_date: Date,
+        tenors: &[Period],
+    ) -> Result<Vec<RolloverStrategy>> {
+        let dates: Vec<Date> = tenors.iter().map(|tenor| reference_date + *tenor).collect();
+        if dates.is_empty()
+            || dates[0] <= reference_date + self.volatile_tenor
+            || dates.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Replicating tenors must be increasing and longer than the volatile tenor"
+                    .to_string(),
+            ));
+        }
+        let survival = |date: Date| self.core_ratio * self.decay.survival(reference_date, date);
+        let mut weights = vec![(self.volatile_tenor, 1.0 - self.core_ratio)];
+        let mut previous = self.core_ratio;
+        for (tenor, date) in tenors.iter().zip(&dates) {
+            let left = survival(*date);
+            weights.push((*tenor, previous - left));
+            previous = left;
+        }
+        if let Some((_, weight)) = weights.last_mut() {
+            *weight += previous;
+        }
+        Ok(weights
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0.0)
+            .map(|(tenor, weight)| {
+                RolloverStrategy::new(
+                    weight,
+                    Structure::Bullet,
+                    self.payment_frequency,
+                    tenor,
+                    self.side,
+                    RateType::Fixed,
+                    self.rate_definition,
+                    self.discount_curve_id,
+                    None,
+                )
+            })
+            .collect())
+    }
+
+    /// Generates the tranches of the replicating portfolio at par rates on the reference date
+    /// of the market store.
+    ///
+    /// # Errors
+    /// Returns an error if the tenors are invalid or a tranche cannot be priced.
+    pub fn replicating_portfolio(
+        &self,
+        market_store: &MarketStore,
+        tenors: &[Period],
+    ) -> Result<Vec<Instrument>> {
+        let strategies = self.replicating_strategies(market_store.reference_date(), tenors)?;
+        let generator = PositionGenerator::new(self.currency, strategies.clone())
+            .with_market_store(market_store)
+            .with_amount(self.balance);
+        strategies
+            .iter()
+            .map(|strategy| generator.generate_position(strategy))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alm::{enums::Portfolio, gap::GapReport},
+        models::{simplemodel::SimpleModel, traits::Model},
+        utils::testing::flat_market_store,
+        visitors::{
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    #[test]
+    fn test_decay_profiles() {
+        let reference_date = Date::new(2024, 1, 2);
+        let date = reference_date + Period::new(2, TimeUnit::Years);
+        let t = DayCounter::Actual365.year_fraction(reference_date, date);
+
+        let exponential = DecayProfile::Exponential(0.1);
+        assert!((exponential.survival(reference_date, reference_date) - 1.0).abs() < 1e-12);
+        assert!((exponential.survival(reference_date, date) - (-0.1 * t).exp()).abs() < 1e-12);
+
+        let run_off = DecayProfile::RunOff(vec![
+            (Period::new(1, TimeUnit::Years), 0.8),
+            (Period::new(3, TimeUnit::Years), 0.4),
+        ]);
+        let one_year = reference_date + Period::new(1, TimeUnit::Years);
+        let three_years = reference_date + Period::new(3, TimeUnit::Years);
+        assert!((run_off.survival(reference_date, one_year) - 0.8).abs() < 1e-12);
+        assert!((run_off.survival(reference_date, three_years) - 0.4).abs() < 1e-12);
+        assert!((run_off.survival(reference_date, date) - 0.6).abs() < 1e-3);
+        assert!(
+            (run_off.survival(
+                reference_date,
+                three_years + Period::new(1, TimeUnit::Years)
+            ) - 0.4)
+                .abs()
+                < 1e-12
+        );
+
+        let increasing = DecayProfile::RunOff(vec![
+            (Period::new(1, TimeUnit::Years), 0.5),
+            (Period::new(2, TimeUnit::Years), 0.7),
+        ]);
+        assert!(NonMaturityDeposit::new(100.0, Currency::USD, 0.5, increasing).is_err());
+        assert!(NonMaturityDeposit::new(100.0, Currency::USD, 1.5, exponential).is_err());
+    }
+
+    #[test]
+    fn test_replicating_portfolio() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.04)?;
+        let reference_date = market_store.reference_date();
+        let deposit =
+            NonMaturityDeposit::new(1_000.0, Currency::USD, 0.7, DecayProfile::Exponential(0.2))?
+                .with_volatile_tenor(Period::new(1, TimeUnit::Days));
+        let tenors = [
+            Period::new(1, TimeUnit::Years),
+            Period::new(3, TimeUnit::Years),
+        ];
+
+        let strategies = deposit.replicating_strategies(reference_date, &tenors)?;
+        let total_weight: f64 = strategies.iter().map(RolloverStrategy::weight).sum();
+        assert!((total_weight - 1.0).abs() < 1e-12);
+
+        let mut tranches = deposit.replicating_portfolio(&market_store, &tenors)?;
+        assert_eq!(tranches.len(), 3);
+
+        let indexer = IndexingVisitor::new();
+        for tranche in &mut tranches {
+            indexer.visit(tranche)?;
+        }
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+        let npv_visitor = NPVConstVisitor::new(&data, true);
+        for tranche in &tranches {
+            assert!(npv_visitor.visit(tranche)?.abs() < 1e-6);
+        }
+
+        let report = GapReport::new(
+            reference_date,
+            vec![
+                (
+                    Period::new(0, TimeUnit::Days),
+                    Period::new(1, TimeUnit::Months),
+                ),
+                (
+                    Period::new(1, TimeUnit::Months),
+                    Period::new(2, TimeUnit::Years),
+                ),
+                (
+                    Period::new(2, TimeUnit::Years),
+                    Period::new(5, TimeUnit::Years),
+                ),
+            ],
+        );
+        let table = report.repricing_gap(&[Portfolio::new().with_instruments(tranches)])?;
+        let gaps = table.total().gaps().to_vec();
+        let core_at =
+            |tenor: Period| deposit.core_balance_at(reference_date, reference_date + tenor);
+        let expected = [
+            deposit.volatile_balance(),
+            deposit.core_balance() - core_at(tenors[0]),
+            core_at(tenors[0]),
+        ];
+        for (gap, expected) in gaps.iter().zip(expected) {
+            assert!((gap + expected).abs() < 1e-9);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cashflows::{
+        cashflow::Cashflow, simplecashflow::SimpleCashflow, traits::InterestAccrual,
+        traits::Payable,
+    },
+    core::{
+        marketstore::MarketStore,
+        traits::{HasCurrency, HasDiscountCurveId, Registrable},
+    },
+    instruments::fixedrateinstrument::{BondAccrual, FixedRateInstrument},
+    rates::{indexstore::ReadIndex, traits::HasReferenceDate},
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+/// # `RateIncentiveCurve`
+/// An S-curve that gives the annual prepayment rate as a function of the refinancing
+/// incentive, the rate of the loan minus the current market rate:
+///
+/// `cpr = min_cpr + (max_cpr - min_cpr) / (1 + e^{-slope * (incentive - midpoint)})`
+///
+/// ## Parameters
+/// * `min_cpr` - The prepayment rate when refinancing is not attractive
+/// * `max_cpr` - The prepayment rate when refinancing is very attractive
+/// * `midpoint` - The incentive at which the rate is halfway between both
+/// * `slope` - How fast the rate moves with the incentive
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RateIncentiveCurve {
+    min_cpr: f64,
+    max_cpr: f64,
+    midpoint: f64,
+    slope: f64,
+}
+
+impl RateIncentiveCurve {
+    /// Creates a new `RateIncentiveCurve`.
+    #[must_use]
+    pub const fn new(min_cpr: f64, max_cpr: f64, midpoint: f64, slope: f64) -> Self {
+        Self {
+            min_cpr,
+            max_cpr,
+            midpoint,
+            slope,
+        }
+    }
+
+    /// Returns the annual prepayment rate for the given incentive.
+    #[must_use]
+    pub fn cpr(&self, incentive: f64) -> f64 {
+        let weight = 1.0 / (1.0 + (-self.slope * (incentive - self.midpoint)).exp());
+        weight.mul_add(self.max_cpr - self.min_cpr, self.min_cpr)
+    }
+}
+
+/// # `PrepaymentModel`
+/// The annual prepayment rate (CPR) of a loan.
+///
+/// ## Variants
+/// * `ConstantCpr` - The same rate during the whole life of the loan.
+/// * `Psa` - The PSA seasoning ramp: the rate grows linearly by 0.2% a month up to 6% in the
+///   30th month after the start of the loan and stays there, scaled by the given speed (1.0 is
+///   100% PSA).
+/// * `RateIncentive` - The rate given by an S-curve on the spread between the rate of the loan
+///   and the current market rate.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PrepaymentModel {
+    /// Constant annual prepayment rate.
+    ConstantCpr(f64),
+    /// PSA speed.
+    Psa(f64),
+    /// Rate-incentive S-curve.
+    RateIncentive(RateIncentiveCurve),
+}
+
+/// # `PrepaymentProjector`
+/// Re-projects the cashflows of a `FixedRateInstrument` under a `PrepaymentModel`.
+///
+/// At the end of every coupon period after the reference date of the market store, a part of
+/// the balance left after the scheduled redemption is prepaid. For a period of `t` years the
+/// part is the single monthly mortality `1 - (1 - cpr)^t`. The scheduled redemptions and the
+/// notionals of the coupons that follow are scaled down with the balance, so the loan keeps
+/// its amortization profile and maturity.
+///
+/// The cashflows of the instrument are updated in place and keep their ids. A prepayment paid
+/// on a date without a scheduled redemption is a new redemption that shares the id of the
+/// coupon paid that day, as both request the same market data. Instruments already indexed
+/// with an `IndexingVisitor` can therefore be projected and valued with the same market data.
+///
+/// ## Parameters
+/// * `model` - The prepayment model
+/// * `market_store` - The market store, with the reference date of the projection
+/// * `curve_id` - The index that gives the market rate of the `RateIncentive` model, defaults
+///   to the discount curve of the instrument
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 1);
+/// let market_store = MarketStore::new(reference_date, Currency::USD);
+/// let loan = MakeFixedRateInstrument::new()
+///     .with_start_date(reference_date)
+///     .with_end_date(Date::new(2029, 1, 1))
+///     .with_payment_frequency(Frequency::Monthly)
+///     .with_rate_value(0.05)
+///     .with_rate_definition(RateDefinition::default())
+///     .with_notional(100.0)
+///     .with_side(Side::Receive)
+///     .with_currency(Currency::USD)
+///     .equal_payments()
+///     .build()
+///     .unwrap();
+///
+/// let projector = PrepaymentProjector::new(PrepaymentModel::ConstantCpr(0.1), &market_store);
+/// let projected = projector.project(&loan).unwrap();
+/// let redemptions: f64 = projected
+///     .cashflows()
+///     .iter()
+///     .filter(|cf| matches!(cf, Cashflow::Redemption(_)))
+///     .map(|cf| cf.amount().unwrap())
+///     .sum();
+/// assert!((redemptions - 100.0).abs() < 1e-9);
+/// ```
+pub struct PrepaymentProjector<'a> {
+    model: PrepaymentModel,
+    market_store: &'a MarketStore,
+    curve_id: Option<usize>,
+}
+
+impl<'a> PrepaymentProjector<'a> {
+    /// Creates a new `PrepaymentProjector`.
+    #[must_use]
+    pub const fn new(model: PrepaymentModel, market_store: &'a MarketStore) -> Self {
+        Self {
+            model,
+            market_store,
+            curve_id: None,
+        }
+    }
+
+    /// Sets the index that gives the market rate of the `RateIncentive` model.
+    #[must_use]
+    pub const fn with_curve_id(mut self, curve_id: usize) -> Self {
+        self.curve_id = Some(curve_id);
+        self
+    }
+
+    /// Returns the prepayment model.
+    #[must_use]
+    pub const fn model(&self) -> PrepaymentModel {
+        self.model
+    }
+
+    /// Returns the current market rate for the remaining term of the instrument, quoted with
+    /// the compounding and frequency of its rate.
+    ///
+    /// # Errors
+    /// Returns an error if the curve is not set or not found in the market store.
+    pub fn market_rate(&self, instrument: &FixedRateInstrument) -> Result<f64> {
+        let curve_id = self
+            .curve_id
+            .or_else(|| instrument.discount_curve_id())
+            .ok_or(AtlasError::ValueNotSetErr(
+                "Market rate curve of the prepayment model".to_string(),
+            ))?;
+        let reference_date = self.market_store.reference_date();
+        let rate = instrument.rate();
+        self.market_store
+            .index_store()
+            .get_index(curve_id)?
+            .read_index()?
+            .forward_rate(
+                reference_date,
+                instrument.end_date().max(reference_date),
+                rate.compounding(),
+                rate.frequency(),
+            )
+    }
+
+    /// Returns the annual prepayment rate of the instrument in the period ending on `date`.
+    ///
+    /// # Errors
+    /// Returns an error if the market rate of the `RateIncentive` model cannot be computed.
+    pub fn cpr(&self, instrument: &FixedRateInstrument, date: Date) -> Result<f64> {
+        match self.model {
+            PrepaymentModel::ConstantCpr(cpr) => Ok(cpr),
+            PrepaymentModel::Psa(speed) => {
+                let age = 12.0 * DayCounter::Actual365.year_fraction(instrument.start_date(), date);
+                Ok(speed * 0.06 * (age / 30.0).clamp(0.0, 1.0))
+            }
+            PrepaymentModel::RateIncentive(curve) => {
+                let incentive = instrument.rate().rate() - self.market_rate(instrument)?;
+                Ok(curve.cpr(incentive))
+            }
+        }
+    }
+
+    /// Returns the instrument with its cashflows re-projected under the prepayment model.
+    ///
+    /// # Errors
+    /// Returns an error if the amounts or dates of the cashflows are not set, or if the
+    /// prepayment rate cannot be computed.
+    pub fn project(&self, instrument: &FixedRateInstrument) -> Result<FixedRateInstrument> {
+        let reference_date = self.market_store.reference_date();
+        let mut cashflows = instrument.cashflows().to_vec();
+        let mut coupons = Vec::new();
+        for (i, cf) in cashflows.iter().enumerate() {
+            if let Cashflow::FixedRateCoupon(coupon) = cf {
+                coupons.push((coupon.accrual_start_date()?, i));
+            }
+        }
+        coupons.sort_by_key(|(start_date, _)| *start_date);
+
+        // ratio between the projected and the scheduled balance
+        let mut factor = 1.0;
+        let mut prepayments = Vec::new();
+        for (k, (_, i)) in coupons.iter().enumerate() {
+            let Cashflow::FixedRateCoupon(coupon) = &mut cashflows[*i] else {
+                continue;
+            };
+            let payment_date = coupon.payment_date();
+            if payment_date <= reference_date {
+                continue;
+            }
+            let scheduled_notional = coupon.notional();
+            coupon.set_notional(scheduled_notional * factor);
+            let accrual_start_date = coupon.accrual_start_date()?;
+            let accrual_end_date = coupon.accrual_end_date()?;
+            let coupon = cashflows[*i];
+
+            let mut scheduled_redemption = 0.0;
+            let mut redemption_index = None;
+            for (j, cf) in cashflows.iter_mut().enumerate() {
+                if let Cashflow::Redemption(redemption) = cf {
+                    if redemption.payment_date() == payment_date {
+                        let amount = redemption.amount()?;
+                        scheduled_redemption += amount;
+                        redemption.set_amount(amount * factor);
+                        redemption_index.get_or_insert(j);
+                    }
+                }
+            }
+
+            let balance = (scheduled_notional - scheduled_redemption) * factor;
+            if k + 1 == coupons.len() || balance <= 0.0 {
+                continue;
+            }
+            let cpr = self.cpr(instrument, accrual_end_date)?;
+            let period = DayCounter::Actual365.year_fraction(accrual_start_date, accrual_end_date);
+            let smm = 1.0 - (1.0 - cpr).powf(period);
+            let prepayment = balance * smm;
+            factor *= 1.0 - smm;
+            if let Some(Cashflow::Redemption(redemption)) =
+                redemption_index.map(|j| &mut cashflows[j])
+            {
+                redemption.set_amount(redemption.amount()? + prepayment);
+            } else {
+                let mut redemption = Cashflow::Redemption(
+                    SimpleCashflow::new(payment_date, coupon.currency()?, coupon.side())
+                        .with_amount(prepayment),
+                );
+                if let Ok(discount_curve_id) = coupon.discount_curve_id() {
+                    redemption.set_discount_curve_id(discount_curve_id);
+                }
+                if let Ok(id) = coupon.id() {
+                    redemption.set_id(id);
+                }
+                prepayments.push(redemption);
+            }
+        }
+        cashflows.extend(prepayments);
+
+        Ok(FixedRateInstrument::new(
+            instrument.start_date(),
+            instrument.end_date(),
+            instrument.notional(),
+            instrument.rate(),
+            instrument.payment_frequency(),
+            cashflows,
+            instrument.structure(),
+            instrument.side(),
+            instrument.currency()?,
+            instrument.discount_curve_id(),
+            instrument.id(),
+            instrument.issue_date(),
+            instrument.yield_rate(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::interestrate::RateDefinition,
+        time::{
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        utils::testing::flat_market_store,
+        visitors::{
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    fn loan(rate: f64) -> Result<FixedRateInstrument> {
+        MakeFixedRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 1))
+            .with_end_date(Date::new(2034, 1, 1))
+            .with_payment_frequency(Frequency::Monthly)
+            .with_rate_value(rate)
+            .with_rate_definition(RateDefinition::default())
+            .with_notional(1_000.0)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .with_discount_curve_id(Some(0))
+            .bullet()
+            .build()
+    }
+
+    fn redemptions(instrument: &FixedRateInstrument) -> Result<Vec<(Date, f64)>> {
+        let mut redemptions = instrument
+            .cashflows()
+            .iter()
+            .filter(|cf| matches!(cf, Cashflow::Redemption(_)))
+            .map(|cf| Ok((cf.payment_date(), cf.amount()?)))
+            .collect::<Result<Vec<_>>>()?;
+        redemptions.sort_by_key(|(date, _)| *date);
+        Ok(redemptions)
+    }
+
+    #[test]
+    fn test_constant_cpr() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 1), 0.04)?;
+        let mut loan = loan(0.05)?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut loan)?;
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+
+        let projector = PrepaymentProjector::new(PrepaymentModel::ConstantCpr(0.1), &market_store);
+        let projected = projector.project(&loan)?;
+
+        let redemptions = redemptions(&projected)?;
+        let total: f64 = redemptions.iter().map(|(_, amount)| amount).sum();
+        assert!((total - 1_000.0).abs() < 1e-9);
+        assert_eq!(redemptions.len(), 120);
+
+        // after a year, 2024 being a leap year, the balance has run off by the CPR
+        let one_year = Date::new(2025, 1, 1);
+        let outstanding: f64 = redemptions
+            .iter()
+            .filter(|(date, _)| *date > one_year)
+            .map(|(_, amount)| amount)
+            .sum();
+        let expected = 1_000.0 * 0.9_f64.powf(366.0 / 365.0);
+        assert!((outstanding - expected).abs() < 1e-9);
+
+        // the coupons keep their ids and accrue on the projected balance
+        for (original, projected) in loan.cashflows().iter().zip(projected.cashflows()) {
+            assert_eq!(original.id()?, projected.id()?);
+        }
+        let npv_visitor = NPVConstVisitor::new(&data, true);
+        let npv = npv_visitor.visit(&projected)?;
+        assert!(npv > 0.0 && npv < npv_visitor.visit(&loan)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_psa_ramp() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 1), 0.04)?;
+        let loan = loan(0.05)?;
+        let projector = PrepaymentProjector::new(PrepaymentModel::Psa(2.0), &market_store);
+
+        let start_date = loan.start_date();
+        let ramp = projector.cpr(&loan, start_date + Period::new(365, TimeUnit::Days))?;
+        assert!((ramp - 0.048).abs() < 1e-12);
+        let seasoned = projector.cpr(&loan, start_date + Period::new(3, TimeUnit::Years))?;
+        assert!((seasoned - 0.12).abs() < 1e-12);
+
+        let projected = projector.project(&loan)?;
+        let first_prepayment = redemptions(&projected)?[0].1;
+        let last_prepayment = redemptions(&projected)?[40].1;
+        assert!(first_prepayment < last_prepayment);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_incentive() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 1), 0.04)?;
+        let curve = RateIncentiveCurve::new(0.02, 0.3, 0.01, 300.0);
+        assert!((curve.cpr(0.01) - 0.16).abs() < 1e-12);
+
+        let projector =
+            PrepaymentProjector::new(PrepaymentModel::RateIncentive(curve), &market_store);
+        let date = Date::new(2025, 1, 1);
+        let market_rate = projector.market_rate(&loan(0.05)?)?;
+        assert!((market_rate - 0.04).abs() < 1e-12);
+
+        let low_rate = projector.cpr(&loan(0.04)?, date)?;
+        let high_rate = projector.cpr(&loan(0.07)?, date)?;
+        assert!(low_rate < 0.16 && high_rate > 0.16);
+        Ok(())
+    }
+}
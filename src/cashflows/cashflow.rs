@@ -15,6 +15,7 @@ use crate::{
 use super::{
     fixedratecoupon::FixedRateCoupon,
     floatingratecoupon::FloatingRateCoupon,
+    overnightindexedcoupon::OvernightIndexedCoupon,
     simplecashflow::SimpleCashflow,
     traits::{InterestAccrual, Payable, RequiresFixingRate},
 };
@@ -82,6 +83,8 @@ pub enum Cashflow {
     FixedRateCoupon(FixedRateCoupon),
     /// A floating rate coupon cashflow.
     FloatingRateCoupon(FloatingRateCoupon),
+    /// A compounded in arrears overnight rate coupon cashflow.
+    OvernightIndexedCoupon(OvernightIndexedCoupon),
 }
 
 impl Cashflow {
@@ -93,13 +96,18 @@ impl Cashflow {
             }
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.set_discount_curve_id(id),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.set_discount_curve_id(id),
+            Self::OvernightIndexedCoupon(overnight_coupon) => {
+                overnight_coupon.set_discount_curve_id(id);
+            }
         }
     }
 
-    /// Sets the forecast curve ID for floating rate coupons.
+    /// Sets the forecast curve ID for floating rate and overnight indexed coupons.
     pub const fn set_forecast_curve_id(&mut self, id: usize) {
-        if let Self::FloatingRateCoupon(coupon) = self {
-            coupon.set_forecast_curve_id(id);
+        match self {
+            Self::FloatingRateCoupon(coupon) => coupon.set_forecast_curve_id(id),
+            Self::OvernightIndexedCoupon(coupon) => coupon.set_forecast_curve_id(id),
+            _ => (),
         }
     }
 }
@@ -110,6 +118,7 @@ impl Payable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.amount(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.amount(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.amount(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.amount(),
         }
     }
 
@@ -118,6 +127,7 @@ impl Payable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.side(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.side(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.side(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.side(),
         }
     }
 
@@ -126,6 +136,7 @@ impl Payable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.payment_date(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.payment_date(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.payment_date(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.payment_date(),
         }
     }
 }
@@ -136,6 +147,7 @@ impl HasCurrency for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.currency(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.currency(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.currency(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.currency(),
         }
     }
 }
@@ -148,6 +160,7 @@ impl HasDiscountCurveId for Cashflow {
             }
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.discount_curve_id(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.discount_curve_id(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.discount_curve_id(),
         }
     }
 }
@@ -160,6 +173,7 @@ impl HasForecastCurveId for Cashflow {
             }
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.forecast_curve_id(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.forecast_curve_id(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.forecast_curve_id(),
         }
     }
 }
@@ -170,6 +184,7 @@ impl Registrable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.set_id(id),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.set_id(id),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.set_id(id),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.set_id(id),
         }
     }
 
@@ -178,6 +193,7 @@ impl Registrable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.id(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.id(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.id(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.id(),
         }
     }
 
@@ -186,6 +202,7 @@ impl Registrable for Cashflow {
             Self::Redemption(cashflow) | Self::Disbursement(cashflow) => cashflow.market_request(),
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.market_request(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.market_request(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.market_request(),
         }
     }
 }
@@ -195,6 +212,7 @@ impl InterestAccrual for Cashflow {
         match self {
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.accrual_end_date(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.accrual_end_date(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.accrual_end_date(),
             Self::Disbursement(_) | Self::Redemption(_) => Err(AtlasError::InvalidValueErr(
                 "Disbursement and Redemption cashflows do not have an accrual end date".to_string(),
             )),
@@ -205,6 +223,7 @@ impl InterestAccrual for Cashflow {
         match self {
             Self::FixedRateCoupon(fixed_coupon) => fixed_coupon.accrual_start_date(),
             Self::FloatingRateCoupon(floating_coupon) => floating_coupon.accrual_start_date(),
+            Self::OvernightIndexedCoupon(overnight_coupon) => overnight_coupon.accrual_start_date(),
             Self::Disbursement(_) | Self::Redemption(_) => Err(AtlasError::InvalidValueErr(
                 "Disbursement and Redemption cashflows do not have an accrual start date"
                     .to_string(),
@@ -220,6 +239,9 @@ impl InterestAccrual for Cashflow {
             Self::FloatingRateCoupon(floating_coupon) => {
                 floating_coupon.accrued_amount(start_date, end_date)
             }
+            Self::OvernightIndexedCoupon(overnight_coupon) => {
+                overnight_coupon.accrued_amount(start_date, end_date)
+            }
             _ => Ok(0.0),
         }
     }
@@ -227,8 +249,10 @@ impl InterestAccrual for Cashflow {
 
 impl RequiresFixingRate for Cashflow {
     fn set_fixing_rate(&mut self, fixing_rate: f64) {
-        if let Self::FloatingRateCoupon(coupon) = self {
-            coupon.set_fixing_rate(fixing_rate);
+        match self {
+            Self::FloatingRateCoupon(coupon) => coupon.set_fixing_rate(fixing_rate),
+            Self::OvernightIndexedCoupon(coupon) => coupon.set_fixing_rate(fixing_rate),
+            _ => (),
         }
     }
 }
@@ -265,6 +289,13 @@ impl Display for Cashflow {
                 amount,
                 coupon.side()
             ),
+            Self::OvernightIndexedCoupon(coupon) => write!(
+                f,
+                "date: {}, amount: {}, side: {:?}, type: overnight indexed coupon",
+                coupon.payment_date(),
+                amount,
+                coupon.side()
+            ),
         }
     }
 }
@@ -281,6 +312,8 @@ pub enum CashflowType {
     FixedRateCoupon,
     /// A floating rate coupon type.
     FloatingRateCoupon,
+    /// An overnight indexed coupon type.
+    OvernightIndexedCoupon,
 }
 
 #[cfg(test)]
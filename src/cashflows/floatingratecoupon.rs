@@ -6,6 +6,7 @@ use crate::{
         traits::{HasCurrency, HasDiscountCurveId, HasForecastCurveId, Registrable},
     },
     currencies::enums::Currency,
+    math::ad::traits::Real,
     rates::interestrate::{InterestRate, RateDefinition},
     time::date::Date,
     utils::errors::{AtlasError, Result},
@@ -32,6 +33,11 @@ use super::{
 /// * `forecast_curve_id` - The ID of the forecast curve used to calculate the present value of the coupon
 /// * `currency` - The currency of the coupon
 /// * `side` - The side of the coupon (Pay or Receive)
+/// * `cap` - An optional maximum of the coupon rate (fixing plus spread)
+/// * `floor` - An optional minimum of the coupon rate (fixing plus spread)
+///
+/// A coupon with both a cap and a floor is collared. Its amount pays the capped and floored
+/// rate; the time value of the embedded options is given by `CapFloorNPVConstVisitor`.
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FloatingRateCoupon {
     notional: f64,
@@ -43,6 +49,8 @@ pub struct FloatingRateCoupon {
     cashflow: SimpleCashflow,
     fixing_rate: Option<f64>,
     forecast_curve_id: Option<usize>,
+    cap: Option<f64>,
+    floor: Option<f64>,
 }
 
 impl FloatingRateCoupon {
@@ -71,9 +79,25 @@ impl FloatingRateCoupon {
             rate_definition,
             forecast_curve_id: None,
             cashflow: SimpleCashflow::new(payment_date, currency, side),
+            cap: None,
+            floor: None,
         }
     }
 
+    /// Sets the cap of the coupon rate and returns the modified coupon.
+    #[must_use]
+    pub const fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Sets the floor of the coupon rate and returns the modified coupon.
+    #[must_use]
+    pub const fn with_floor(mut self, floor: f64) -> Self {
+        self.floor = Some(floor);
+        self
+    }
+
     /// Sets the discount curve ID and returns the modified coupon.
     #[must_use]
     pub const fn with_discount_curve_id(mut self, id: usize) -> Self {
@@ -107,6 +131,22 @@ impl FloatingRateCoupon {
         }
     }
 
+    /// Sets the cap and updates the cashflow if a fixing rate is set.
+    pub fn set_cap(&mut self, cap: Option<f64>) {
+        self.cap = cap;
+        if let Some(fixing_rate) = self.fixing_rate {
+            self.set_fixing_rate(fixing_rate);
+        }
+    }
+
+    /// Sets the floor and updates the cashflow if a fixing rate is set.
+    pub fn set_floor(&mut self, floor: Option<f64>) {
+        self.floor = floor;
+        if let Some(fixing_rate) = self.fixing_rate {
+            self.set_fixing_rate(fixing_rate);
+        }
+    }
+
     /// Sets the notional amount.
     pub const fn set_notional(&mut self, notional: f64) {
         self.notional = notional;
@@ -144,6 +184,61 @@ impl FloatingRateCoupon {
     pub const fn fixing_rate(&self) -> Option<f64> {
         self.fixing_rate
     }
+
+    /// Returns the cap of the coupon rate if set.
+    #[must_use]
+    pub const fn cap(&self) -> Option<f64> {
+        self.cap
+    }
+
+    /// Returns the floor of the coupon rate if set.
+    #[must_use]
+    pub const fn floor(&self) -> Option<f64> {
+        self.floor
+    }
+
+    /// Returns true if the coupon has a cap or a floor.
+    #[must_use]
+    pub const fn has_optionality(&self) -> bool {
+        self.cap.is_some() || self.floor.is_some()
+    }
+
+    /// Returns the coupon rate paid for the given fixing, the fixing plus the spread bounded by
+    /// the floor and the cap.
+    #[must_use]
+    pub fn coupon_rate(&self, fixing_rate: f64) -> f64 {
+        let rate = fixing_rate + self.spread;
+        let rate = self.floor.map_or(rate, |floor| rate.max(floor));
+        self.cap.map_or(rate, |cap| rate.min(cap))
+    }
+
+    /// Returns the amount paid over the whole accrual period for the given fixing, which can
+    /// be any `Real`, e.g. a `Dual` forward whose derivatives are carried to the amount. A
+    /// binding cap or floor pays a constant rate.
+    #[must_use]
+    pub fn amount_with<T: Real>(&self, fixing_rate: T) -> T {
+        let coupon_rate = self.coupon_rate(fixing_rate.value());
+        let rate = fixing_rate + self.spread;
+        let value = rate.value();
+        let rate = if self.floor.is_some_and(|floor| value < floor)
+            || self.cap.is_some_and(|cap| value > cap)
+        {
+            T::from_f64(coupon_rate)
+        } else {
+            rate
+        };
+        let year_fraction = self
+            .rate_definition
+            .day_counter()
+            .year_fraction(self.accrual_start_date, self.accrual_end_date);
+        let compound = InterestRate::compound_factor_from_rate(
+            rate,
+            self.rate_definition.compounding(),
+            self.rate_definition.frequency(),
+            year_fraction,
+        );
+        (compound - 1.0) * self.notional
+    }
 }
 
 impl InterestAccrual for FloatingRateCoupon {
@@ -157,7 +252,8 @@ impl InterestAccrual for FloatingRateCoupon {
         let fixing = self
             .fixing_rate
             .ok_or(AtlasError::ValueNotSetErr("Fixing rate".to_string()))?;
-        let rate = InterestRate::from_rate_definition(fixing + self.spread, self.rate_definition);
+        let rate =
+            InterestRate::from_rate_definition(self.coupon_rate(fixing), self.rate_definition);
 
         let (d1, d2) = self.relevant_accrual_dates(self.accrual_start_date, end_date)?;
         let acc_1 = self.notional * (rate.compound_factor(d1, d2) - 1.0);
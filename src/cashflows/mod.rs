@@ -4,6 +4,8 @@ pub mod cashflow;
 pub mod fixedratecoupon;
 /// Floating rate coupon module for variable rate instruments.
 pub mod floatingratecoupon;
+/// Overnight indexed coupon module for compounded in arrears overnight rates.
+pub mod overnightindexedcoupon;
 /// Simple cashflow module for basic cashflow representations.
 pub mod simplecashflow;
 /// Traits module defining common interfaces for cashflows.
@@ -0,0 +1,535 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        meta::{ForwardRateRequest, MarketRequest},
+        traits::{HasCurrency, HasDiscountCurveId, HasForecastCurveId, Registrable},
+    },
+    currencies::enums::Currency,
+    rates::{
+        enums::Compounding, interestrate::RateDefinition,
+        overnightobservation::OvernightObservation, traits::YieldProvider,
+    },
+    time::{
+        calendar::{Calendar, CalendarId},
+        date::Date,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    cashflow::Side,
+    simplecashflow::SimpleCashflow,
+    traits::{Expires, InterestAccrual, Payable, RequiresFixingRate},
+};
+
+/// # `OvernightIndexedCoupon`
+/// A coupon paying the daily compounded overnight rate (SOFR, ESTR, SONIA...) over its
+/// accrual period plus a spread, following the ISDA 2021 conventions:
+///
+/// * `lookback_days` - Each rate is observed this many business days before the day it applies to.
+/// * `observation_shift` - If true, the rates are also weighted with the days of the shifted
+///   (observation) period instead of those of the accrual period.
+/// * `lockout_days` - The rate of the last business days of the period is frozen to the one
+///   observed this many business days before the end.
+///
+/// The compounded rate is `(prod(1 + r_i * d_i) - 1) / D`, where `D` is the year fraction of the
+/// weighting period, and the coupon pays `notional * (rate + spread) * T` on the accrual year
+/// fraction `T`. `compounded_rate` mixes the published fixings of the index with forecasts of
+/// its curve day by day. Its market request asks for the forward rate over the observation
+/// period together with these conventions, so that models fix it with `compounded_rate` too.
+///
+/// ## Parameters
+/// * `notional` - The notional amount of the coupon
+/// * `spread` - The spread over the compounded rate
+/// * `accrual_start_date` - The date from which the coupon accrues interest
+/// * `accrual_end_date` - The date until which the coupon accrues interest
+/// * `payment_date` - The date on which the coupon is paid
+/// * `rate_definition` - The definition of the compounded rate (only its day counter is used)
+/// * `currency` - The currency of the coupon
+/// * `side` - The side of the coupon (Pay or Receive)
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let coupon = OvernightIndexedCoupon::new(
+///     1_000_000.0,
+///     0.0,
+///     Date::new(2024, 1, 2),
+///     Date::new(2024, 4, 2),
+///     Date::new(2024, 4, 4),
+///     RateDefinition::default(),
+///     Currency::USD,
+///     Side::Receive,
+/// )
+/// .with_calendar(&Calendar::WeekendsOnly(WeekendsOnly::new()))
+/// .with_lookback_days(2)
+/// .with_observation_shift(true);
+///
+/// let (start, end) = coupon.observation_period();
+/// assert_eq!(start, Date::new(2023, 12, 29));
+/// assert_eq!(end, Date::new(2024, 3, 29));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OvernightIndexedCoupon {
+    notional: f64,
+    spread: f64,
+    accrual_start_date: Date,
+    accrual_end_date: Date,
+    rate_definition: RateDefinition,
+    calendar: CalendarId,
+    lookback_days: i32,
+    lockout_days: usize,
+    observation_shift: bool,
+    cashflow: SimpleCashflow,
+    fixing_rate: Option<f64>,
+    forecast_curve_id: Option<usize>,
+}
+
+impl OvernightIndexedCoupon {
+    /// Creates a new overnight indexed coupon with no lookback, lockout or observation shift,
+    /// observed on every calendar day.
+    #[must_use]
+    // allowed: high-arity API; refactor deferred
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        notional: f64,
+        spread: f64,
+        accrual_start_date: Date,
+        accrual_end_date: Date,
+        payment_date: Date,
+        rate_definition: RateDefinition,
+        currency: Currency,
+        side: Side,
+    ) -> Self {
+        Self {
+            notional,
+            spread,
+            accrual_start_date,
+            accrual_end_date,
+            rate_definition,
+            calendar: CalendarId::NullCalendar,
+            lookback_days: 0,
+            lockout_days: 0,
+            observation_shift: false,
+            cashflow: SimpleCashflow::new(payment_date, currency, side),
+            fixing_rate: None,
+            forecast_curve_id: None,
+        }
+    }
+
+    /// Sets the calendar of the overnight index fixings. Only its id is kept, so holidays
+    /// added to or removed from it are not taken into account.
+    #[must_use]
+    pub fn with_calendar(mut self, calendar: &Calendar) -> Self {
+        self.calendar = CalendarId::from(calendar);
+        self
+    }
+
+    /// Sets the number of business days each rate is observed before the day it applies to.
+    #[must_use]
+    pub const fn with_lookback_days(mut self, lookback_days: i32) -> Self {
+        self.lookback_days = lookback_days;
+        self
+    }
+
+    /// Sets the number of business days at the end of the period using the same rate.
+    #[must_use]
+    pub const fn with_lockout_days(mut self, lockout_days: usize) -> Self {
+        self.lockout_days = lockout_days;
+        self
+    }
+
+    /// Sets whether the rates are weighted with the days of the observation period.
+    #[must_use]
+    pub const fn with_observation_shift(mut self, observation_shift: bool) -> Self {
+        self.observation_shift = observation_shift;
+        self
+    }
+
+    /// Sets the discount curve ID and returns the modified coupon.
+    #[must_use]
+    pub const fn with_discount_curve_id(mut self, id: usize) -> Self {
+        self.cashflow = self.cashflow.with_discount_curve_id(id);
+        self
+    }
+
+    /// Sets the forecast curve ID and returns the modified coupon.
+    #[must_use]
+    pub const fn with_forecast_curve_id(mut self, id: usize) -> Self {
+        self.forecast_curve_id = Some(id);
+        self
+    }
+
+    /// Sets the discount curve ID.
+    pub const fn set_discount_curve_id(&mut self, id: usize) {
+        self.cashflow.set_discount_curve_id(id);
+    }
+
+    /// Sets the forecast curve ID.
+    pub const fn set_forecast_curve_id(&mut self, id: usize) {
+        self.forecast_curve_id = Some(id);
+    }
+
+    /// Sets the spread and updates the cashflow if a fixing rate is set.
+    pub fn set_spread(&mut self, spread: f64) {
+        self.spread = spread;
+        if let Some(fixing_rate) = self.fixing_rate {
+            self.set_fixing_rate(fixing_rate);
+        }
+    }
+
+    /// Sets the notional amount.
+    pub const fn set_notional(&mut self, notional: f64) {
+        self.notional = notional;
+    }
+
+    /// Returns the notional amount.
+    #[must_use]
+    pub const fn notional(&self) -> f64 {
+        self.notional
+    }
+
+    /// Returns the spread.
+    #[must_use]
+    pub const fn spread(&self) -> f64 {
+        self.spread
+    }
+
+    /// Returns the rate definition.
+    #[must_use]
+    pub const fn rate_definition(&self) -> RateDefinition {
+        self.rate_definition
+    }
+
+    /// Returns the id of the calendar of the overnight index fixings.
+    #[must_use]
+    pub const fn calendar(&self) -> CalendarId {
+        self.calendar
+    }
+
+    /// Returns the lookback, in business days.
+    #[must_use]
+    pub const fn lookback_days(&self) -> i32 {
+        self.lookback_days
+    }
+
+    /// Returns the lockout, in business days.
+    #[must_use]
+    pub const fn lockout_days(&self) -> usize {
+        self.lockout_days
+    }
+
+    /// Returns whether the observation period is shifted.
+    #[must_use]
+    pub const fn observation_shift(&self) -> bool {
+        self.observation_shift
+    }
+
+    /// Returns the compounded rate (without spread) if set.
+    #[must_use]
+    pub const fn fixing_rate(&self) -> Option<f64> {
+        self.fixing_rate
+    }
+
+    /// Returns the conventions the overnight rates are observed and compounded with.
+    #[must_use]
+    pub const fn observation(&self) -> OvernightObservation {
+        OvernightObservation::new(
+            self.accrual_start_date,
+            self.accrual_end_date,
+            self.rate_definition.day_counter(),
+        )
+        .with_calendar(self.calendar)
+        .with_lookback_days(self.lookback_days)
+        .with_lockout_days(self.lockout_days)
+        .with_observation_shift(self.observation_shift)
+    }
+
+    /// Returns the period the overnight rates are observed over, i.e. the accrual period
+    /// moved back by the lookback.
+    #[must_use]
+    pub fn observation_period(&self) -> (Date, Date) {
+        self.observation().observation_period()
+    }
+
+    /// Returns the compounded overnight rate of the coupon, without spread. Rates fixed before
+    /// the reference date of `index` are taken from its fixings; the others are forecast from
+    /// its discount factors.
+    ///
+    /// # Errors
+    /// Returns an error if a fixing or a discount factor is not available, or if the lockout
+    /// covers the whole period.
+    pub fn compounded_rate<I: YieldProvider + ?Sized>(&self, index: &I) -> Result<f64> {
+        self.observation().compounded_rate(index)
+    }
+
+    /// Fixes the coupon with the compounded rate of `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the compounded rate cannot be calculated.
+    pub fn fix<I: YieldProvider + ?Sized>(&mut self, index: &I) -> Result<()> {
+        let rate = self.compounded_rate(index)?;
+        self.set_fixing_rate(rate);
+        Ok(())
+    }
+}
+
+impl InterestAccrual for OvernightIndexedCoupon {
+    fn accrual_start_date(&self) -> Result<Date> {
+        Ok(self.accrual_start_date)
+    }
+    fn accrual_end_date(&self) -> Result<Date> {
+        Ok(self.accrual_end_date)
+    }
+    fn accrued_amount(&self, start_date: Date, end_date: Date) -> Result<f64> {
+        let fixing = self
+            .fixing_rate
+            .ok_or(AtlasError::ValueNotSetErr("Fixing rate".to_string()))?;
+        let (d1, d2) = self.relevant_accrual_dates(start_date, end_date)?;
+        if d1 == Date::empty() {
+            return Ok(0.0);
+        }
+        let year_fraction = self.rate_definition.day_counter().year_fraction(d1, d2);
+        Ok(self.notional * (fixing + self.spread) * year_fraction)
+    }
+}
+
+impl RequiresFixingRate for OvernightIndexedCoupon {
+    fn set_fixing_rate(&mut self, fixing_rate: f64) {
+        self.fixing_rate = Some(fixing_rate);
+        if let Ok(accrual) = self.accrued_amount(self.accrual_start_date, self.accrual_end_date) {
+            self.cashflow = self.cashflow.with_amount(accrual);
+        }
+    }
+}
+
+impl Payable for OvernightIndexedCoupon {
+    fn amount(&self) -> Result<f64> {
+        self.cashflow.amount()
+    }
+    fn side(&self) -> Side {
+        self.cashflow.side()
+    }
+    fn payment_date(&self) -> Date {
+        self.cashflow.payment_date()
+    }
+}
+
+impl HasCurrency for OvernightIndexedCoupon {
+    fn currency(&self) -> Result<Currency> {
+        self.cashflow.currency()
+    }
+}
+
+impl HasDiscountCurveId for OvernightIndexedCoupon {
+    fn discount_curve_id(&self) -> Result<usize> {
+        self.cashflow.discount_curve_id()
+    }
+}
+
+impl HasForecastCurveId for OvernightIndexedCoupon {
+    fn forecast_curve_id(&self) -> Result<usize> {
+        self.forecast_curve_id
+            .ok_or(AtlasError::ValueNotSetErr("Forecast curve id".to_string()))
+    }
+}
+
+impl Registrable for OvernightIndexedCoupon {
+    fn id(&self) -> Result<usize> {
+        self.cashflow.id()
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.cashflow.set_id(id);
+    }
+
+    fn market_request(&self) -> Result<MarketRequest> {
+        let tmp = self.cashflow.market_request()?;
+        let (start_date, end_date) = self.observation_period();
+        let forecast = ForwardRateRequest::new(
+            self.forecast_curve_id()?,
+            start_date,
+            start_date,
+            end_date,
+            Compounding::Simple,
+            self.rate_definition.frequency(),
+        )
+        .with_overnight_observation(self.observation());
+        Ok(MarketRequest::new(
+            tmp.id(),
+            tmp.df(),
+            Some(forecast),
+            tmp.fx(),
+        ))
+    }
+}
+
+impl Expires for OvernightIndexedCoupon {
+    fn is_expired(&self, date: Date) -> bool {
+        self.cashflow.payment_date() < date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Cashflow,
+        core::marketstore::MarketStore,
+        instruments::swap::Swap,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            interestrateindex::{overnightindex::OvernightIndex, traits::FixingProvider},
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{
+            calendars::{traits::IsCalendar, weekendsonly::WeekendsOnly},
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    fn coupon() -> OvernightIndexedCoupon {
+        OvernightIndexedCoupon::new(
+            100.0,
+            0.0,
+            Date::new(2024, 1, 2),
+            Date::new(2024, 4, 2),
+            Date::new(2024, 4, 4),
+            RateDefinition::default(),
+            Currency::USD,
+            Side::Receive,
+        )
+        .with_calendar(&Calendar::WeekendsOnly(WeekendsOnly::new()))
+        .with_lookback_days(2)
+    }
+
+    /// An index with the published levels of an overnight rate rising one basis point each
+    /// business day.
+    fn index(reference_date: Date) -> OvernightIndex {
+        let calendar = WeekendsOnly::new();
+        let day_counter = RateDefinition::default().day_counter();
+        let mut fixings = HashMap::new();
+        let mut date = Date::new(2023, 12, 1);
+        let mut level = 100.0;
+        let mut rate: f64 = 0.03;
+        while date <= Date::new(2024, 4, 30) {
+            fixings.insert(date, level);
+            let next = calendar.advance(date, Period::new(1, TimeUnit::Days), None, false);
+            level *= rate.mul_add(day_counter.year_fraction(date, next), 1.0);
+            rate += 0.0001;
+            date = next;
+        }
+        OvernightIndex::new(reference_date)
+            .with_fixings(fixings)
+            .with_term_structure(Arc::new(FlatForwardTermStructure::new(
+                reference_date,
+                0.05,
+                RateDefinition::default(),
+            )))
+    }
+
+    #[test]
+    fn test_shifted_rate_matches_levels() -> Result<()> {
+        let index = index(Date::new(2024, 5, 1));
+        let mut coupon = coupon().with_observation_shift(true);
+        let (start, end) = coupon.observation_period();
+        let expected = (index.fixing(end)? / index.fixing(start)? - 1.0)
+            / RateDefinition::default()
+                .day_counter()
+                .year_fraction(start, end);
+
+        coupon.fix(&index)?;
+        assert!((coupon.fixing_rate().unwrap_or_default() - expected).abs() < 1e-12);
+        let year_fraction = RateDefinition::default()
+            .day_counter()
+            .year_fraction(Date::new(2024, 1, 2), Date::new(2024, 4, 2));
+        let amount = 100.0 * expected * year_fraction;
+        assert!((coupon.amount()? - amount).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_forecast_matches_forward_rate() -> Result<()> {
+        let index = index(Date::new(2023, 12, 15));
+        let coupon = coupon().with_observation_shift(true);
+        let (start, end) = coupon.observation_period();
+        let forward = index.forward_rate(start, end, Compounding::Simple, Frequency::Annual)?;
+        assert!((coupon.compounded_rate(&index)? - forward).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lockout() -> Result<()> {
+        let index = index(Date::new(2024, 5, 1));
+        let rate = coupon().compounded_rate(&index)?;
+        let locked = coupon().with_lockout_days(2).compounded_rate(&index)?;
+        // rates rise, so freezing the last ones lowers the compounded rate
+        assert!(locked < rate);
+        assert!(coupon()
+            .with_lockout_days(100)
+            .compounded_rate(&index)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pricing_with_lookback_lockout_and_payment_delay() -> Result<()> {
+        let coupon = coupon()
+            .with_lockout_days(2)
+            .with_forecast_curve_id(0)
+            .with_discount_curve_id(0);
+        let price = |reference_date: Date| -> Result<(OvernightIndexedCoupon, f64)> {
+            let mut market_store = MarketStore::new(reference_date, Currency::USD);
+            market_store
+                .mut_index_store()
+                .add_index(0, Arc::new(RwLock::new(index(reference_date))))?;
+            let mut swap = Swap::new(
+                vec![Cashflow::OvernightIndexedCoupon(coupon)],
+                Vec::new(),
+                None,
+            );
+            let indexer = IndexingVisitor::new();
+            indexer.visit(&mut swap)?;
+            let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+            FixingVisitor::new(&data).visit(&mut swap)?;
+            let npv = NPVConstVisitor::new(&data, false).visit(&swap)?;
+            match swap.cashflows()[0] {
+                Cashflow::OvernightIndexedCoupon(fixed) => Ok((fixed, npv)),
+                _ => Err(AtlasError::InvalidValueErr(
+                    "Unexpected cashflow".to_string(),
+                )),
+            }
+        };
+
+        // during the accrual period the past fixings are compounded with the forecasts
+        let reference_date = Date::new(2024, 2, 15);
+        let (fixed, npv) = price(reference_date)?;
+        let expected = coupon.compounded_rate(&index(reference_date))?;
+        assert!((fixed.fixing_rate().unwrap_or_default() - expected).abs() < 1e-12);
+        let df = index(reference_date).discount_factor(Date::new(2024, 4, 4))?;
+        assert!((npv / df - fixed.amount()?).abs() < 1e-10);
+
+        // the accrual period has ended but the payment is still due
+        let reference_date = Date::new(2024, 4, 3);
+        let (fixed, npv) = price(reference_date)?;
+        let expected = coupon.compounded_rate(&index(reference_date))?;
+        assert!(expected > 0.03);
+        assert!((fixed.fixing_rate().unwrap_or_default() - expected).abs() < 1e-12);
+        let df = index(reference_date).discount_factor(Date::new(2024, 4, 4))?;
+        assert!((npv / df - fixed.amount()?).abs() < 1e-10);
+        Ok(())
+    }
+}
@@ -11,6 +11,9 @@ use crate::{
         indexstore::{IndexStore, ReadIndex},
         interestrateindex::traits::InterestRateIndexTrait,
         traits::HasReferenceDate,
+        volatility::traits::{SwaptionVolatilityCubeTrait, VolatilitySurfaceTrait},
+        volatilitystore::VolatilityStore,
+        yieldtermstructure::bumpedtermstructure::{Bump, BumpedTermStructure},
     },
     time::{date::Date, enums::TimeUnit, period::Period},
     utils::{
@@ -27,12 +30,14 @@ use crate::{
 /// * `local_currency` - The local currency of the market store
 /// * `exchange_rate_store` - The exchange rate store
 /// * `index_store` - The index store
+/// * `volatility_store` - The volatility surfaces of the indices
 #[derive(Clone)]
 pub struct MarketStore {
     reference_date: Date,
     local_currency: Currency,
     exchange_rate_store: ExchangeRateStore,
     index_store: IndexStore,
+    volatility_store: VolatilityStore,
 }
 
 impl MarketStore {
@@ -44,6 +49,7 @@ impl MarketStore {
             local_currency,
             exchange_rate_store: ExchangeRateStore::new(reference_date),
             index_store: IndexStore::new(reference_date),
+            volatility_store: VolatilityStore::new(reference_date),
         }
     }
 
@@ -75,6 +81,17 @@ impl MarketStore {
         &mut self.index_store
     }
 
+    /// Returns a reference to the volatility store.
+    #[must_use]
+    pub const fn volatility_store(&self) -> &VolatilityStore {
+        &self.volatility_store
+    }
+
+    /// Returns a mutable reference to the volatility store.
+    pub const fn mut_volatility_store(&mut self) -> &mut VolatilityStore {
+        &mut self.volatility_store
+    }
+
     /// Gets the exchange rate between two currencies.
     ///
     /// If no second currency is provided, uses the local currency.
@@ -92,6 +109,28 @@ impl MarketStore {
             .get_exchange_rate(first_currency, second_currency)
     }
 
+    /// Gets the forward exchange rate between two currencies for the given date.
+    ///
+    /// If no second currency is provided, uses the local currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the forward exchange rate cannot be computed.
+    pub fn get_forward_exchange_rate(
+        &self,
+        first_currency: Currency,
+        second_currency: Option<Currency>,
+        date: Date,
+    ) -> Result<f64> {
+        let second_currency = second_currency.unwrap_or(self.local_currency);
+        self.exchange_rate_store.get_forward_exchange_rate(
+            first_currency,
+            second_currency,
+            date,
+            &self.index_store,
+        )
+    }
+
     /// Gets an interest rate index by its ID.
     ///
     /// # Errors
@@ -101,6 +140,47 @@ impl MarketStore {
         self.index_store.get_index(id)
     }
 
+    /// Gets the volatility surface of the index with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the surface cannot be found.
+    pub fn get_volatility_surface(&self, id: usize) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        self.volatility_store.get_surface(id)
+    }
+
+    /// Gets the swaption volatility cube of the index with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cube cannot be found.
+    pub fn get_swaption_volatility_cube(
+        &self,
+        id: usize,
+    ) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>> {
+        self.volatility_store.get_swaption_cube(id)
+    }
+
+    /// Returns a copy of the market store where the curve of the index with the given ID is
+    /// replaced by a `BumpedTermStructure`. The original store and its indices are left
+    /// untouched, so both can be used to compute sensitivities with a `SimpleModel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be found, has no term structure or the bump is
+    /// invalid.
+    pub fn with_bumped_index(&self, id: usize, bump: Bump) -> Result<Self> {
+        let index = self.get_index(id)?;
+        let bumped_index = {
+            let index = index.read_index()?;
+            let curve = BumpedTermStructure::new(index.term_structure()?, bump)?;
+            index.relinked_to(Arc::new(curve))
+        };
+        let mut market_store = self.clone();
+        market_store.index_store.replace_index(id, bumped_index)?;
+        Ok(market_store)
+    }
+
     /// Advances the market store to a new date by the given period.
     ///
     /// # Errors
@@ -117,12 +197,14 @@ impl MarketStore {
             .exchange_rate_store
             .advance_to_period(period, &self.index_store)?;
         let new_index_store = self.index_store.advance_to_period(period)?;
+        let new_volatility_store = self.volatility_store.advance_to_period(period)?;
 
         Ok(Self {
             reference_date: new_reference_date,
             local_currency: self.local_currency,
             exchange_rate_store: new_exchange_rate_store,
             index_store: new_index_store,
+            volatility_store: new_volatility_store,
         })
     }
 
@@ -226,6 +308,23 @@ impl fmt::Display for MarketStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            enums::Compounding,
+            interestrate::{InterestRate, RateDefinition},
+            interestrateindex::iborindex::IborIndex,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{daycounter::DayCounter, enums::Frequency},
+        visitors::{
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
 
     #[test]
     fn display_does_not_panic() {
@@ -233,9 +332,77 @@ mod tests {
         let market_store = MarketStore::new(reference_date, Currency::USD);
 
         let result = std::panic::catch_unwind(|| {
-            let _ = format!("{}", market_store);
+            let _ = format!("{market_store}");
         });
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_bumped_index_sensitivities() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        let curve = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.04,
+            RateDefinition::new(
+                DayCounter::Actual365,
+                Compounding::Continuous,
+                Frequency::Annual,
+            ),
+        ));
+        let index = IborIndex::new(reference_date).with_term_structure(curve);
+        market_store
+            .mut_index_store()
+            .add_index(0, Arc::new(RwLock::new(index)))?;
+
+        let mut instrument = MakeFixedRateInstrument::new()
+            .with_start_date(reference_date)
+            .with_end_date(reference_date + Period::new(7, TimeUnit::Years))
+            .with_rate(InterestRate::new(
+                0.04,
+                Compounding::Simple,
+                Frequency::Annual,
+                DayCounter::Actual365,
+            ))
+            .with_payment_frequency(Frequency::Annual)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .with_discount_curve_id(Some(0))
+            .with_notional(1_000_000.0)
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let npv = |store: &MarketStore| -> Result<f64> {
+            let data = SimpleModel::new(store).gen_market_data(&requests)?;
+            NPVConstVisitor::new(&data, false).visit(&instrument)
+        };
+        let base_npv = npv(&market_store)?;
+
+        let dv01 = npv(&market_store.with_bumped_index(0, Bump::Parallel(0.0001))?)? - base_npv;
+        assert!(dv01 < 0.0);
+
+        let tenors = [2, 5, 10]
+            .iter()
+            .map(|years| Period::new(*years, TimeUnit::Years))
+            .collect::<Vec<Period>>();
+        let key_rate_dv01s = Bump::key_rates(&tenors, 0.0001)
+            .into_iter()
+            .map(|bump| Ok(npv(&market_store.with_bumped_index(0, bump)?)? - base_npv))
+            .collect::<Result<Vec<f64>>>()?;
+        // a 7y bond has no exposure beyond the 10y pillar and most of it at 5y
+        assert!(key_rate_dv01s[1].abs() > key_rate_dv01s[0].abs());
+        let total: f64 = key_rate_dv01s.iter().sum();
+        assert!((total - dv01).abs() < 1e-2 * dv01.abs());
+
+        // the original store keeps its curve
+        assert!((npv(&market_store)? - base_npv).abs() < 1e-12);
+        assert!(market_store
+            .with_bumped_index(1, Bump::Parallel(0.0001))
+            .is_err());
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     currencies::enums::Currency,
-    rates::enums::Compounding,
+    rates::{enums::Compounding, overnightobservation::OvernightObservation},
     time::{date::Date, enums::Frequency},
     utils::errors::{AtlasError, Result},
 };
@@ -97,6 +97,7 @@ impl DiscountFactorRequest {
 /// * `end_date` - The end date of the forward rate.
 /// * `compounding` - The compounding of the forward rate.
 /// * `frequency` - The frequency of the forward rate.
+/// * `overnight_observation` - The conventions of a compounded overnight rate, if any.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ForwardRateRequest {
     provider_id: usize,
@@ -105,6 +106,7 @@ pub struct ForwardRateRequest {
     end_date: Date,
     compounding: Compounding,
     frequency: Frequency,
+    overnight_observation: Option<OvernightObservation>,
 }
 
 impl ForwardRateRequest {
@@ -125,9 +127,21 @@ impl ForwardRateRequest {
             end_date,
             compounding,
             frequency,
+            overnight_observation: None,
         }
     }
 
+    /// Requests the overnight rate compounded with the given conventions instead of the
+    /// forward rate between the start and end dates.
+    #[must_use]
+    pub const fn with_overnight_observation(
+        mut self,
+        overnight_observation: OvernightObservation,
+    ) -> Self {
+        self.overnight_observation = Some(overnight_observation);
+        self
+    }
+
     /// Returns the provider id.
     #[must_use]
     pub const fn provider_id(&self) -> usize {
@@ -157,6 +171,12 @@ impl ForwardRateRequest {
     pub const fn frequency(&self) -> Frequency {
         self.frequency
     }
+
+    /// Returns the conventions of a compounded overnight rate, if any.
+    #[must_use]
+    pub const fn overnight_observation(&self) -> Option<OvernightObservation> {
+        self.overnight_observation
+    }
 }
 
 /// # `MarketRequest`
@@ -220,26 +240,29 @@ impl MarketRequest {
 /// * `df` - The discount factor.
 /// * `fwd` - The forward rate.
 /// * `fx` - The exchange rate.
+///
+/// Values are `f64` by default; they can also be `Dual` numbers carrying derivatives with
+/// respect to the curve nodes (see `AdModel`).
 #[derive(Debug, Clone, Copy)]
-pub struct MarketData {
+pub struct MarketData<T = f64> {
     id: usize,
     reference_date: Date,
-    df: Option<f64>,
-    fwd: Option<f64>,
-    fx: Option<f64>,
-    numerarie: f64,
+    df: Option<T>,
+    fwd: Option<T>,
+    fx: Option<T>,
+    numerarie: T,
 }
 
-impl MarketData {
+impl<T: Clone> MarketData<T> {
     /// Creates a new `MarketData`.
     #[must_use]
     pub const fn new(
         id: usize,
         reference_date: Date,
-        df: Option<f64>,
-        fwd: Option<f64>,
-        fx: Option<f64>,
-        numerarie: f64,
+        df: Option<T>,
+        fwd: Option<T>,
+        fx: Option<T>,
+        numerarie: T,
     ) -> Self {
         Self {
             id,
@@ -267,29 +290,35 @@ impl MarketData {
     ///
     /// # Errors
     /// Returns an error if the discount factor was not set in this market data.
-    pub fn df(&self) -> Result<f64> {
-        self.df.ok_or(AtlasError::ValueNotSetErr("df".to_owned()))
+    pub fn df(&self) -> Result<T> {
+        self.df
+            .clone()
+            .ok_or(AtlasError::ValueNotSetErr("df".to_owned()))
     }
 
     /// Returns the forward rate.
     ///
     /// # Errors
     /// Returns an error if the forward rate was not set in this market data.
-    pub fn fwd(&self) -> Result<f64> {
-        self.fwd.ok_or(AtlasError::ValueNotSetErr("fwd".to_owned()))
+    pub fn fwd(&self) -> Result<T> {
+        self.fwd
+            .clone()
+            .ok_or(AtlasError::ValueNotSetErr("fwd".to_owned()))
     }
 
     /// Returns the exchange rate.
     ///
     /// # Errors
     /// Returns an error if the exchange rate was not set in this market data.
-    pub fn fx(&self) -> Result<f64> {
-        self.fx.ok_or(AtlasError::ValueNotSetErr("fx".to_owned()))
+    pub fn fx(&self) -> Result<T> {
+        self.fx
+            .clone()
+            .ok_or(AtlasError::ValueNotSetErr("fx".to_owned()))
     }
 
     /// Returns the numeraire.
     #[must_use]
-    pub const fn numerarie(&self) -> f64 {
-        self.numerarie
+    pub fn numerarie(&self) -> T {
+        self.numerarie.clone()
     }
 }
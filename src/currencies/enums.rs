@@ -27,6 +27,8 @@ pub enum Currency {
     BRL,
     /// Colombian Peso
     COP,
+    /// Colombian Unidad de Valor Real
+    COU,
     /// Mexican Peso
     MXN,
     /// Australian Dollar
@@ -76,6 +78,7 @@ impl Currency {
             Self::CHF => ("CHF", "Swiss Franc", "Fr", 2, 756),
             Self::BRL => ("BRL", "Brazilian Real", "R$", 2, 986),
             Self::COP => ("COP", "Colombian Peso", "$", 2, 170),
+            Self::COU => ("COU", "Colombian Unidad de Valor Real", "UVR", 4, 970),
             Self::MXN => ("MXN", "Mexican Peso", "Mex$", 2, 484),
             Self::AUD => ("AUD", "Australian Dollar", "A$", 2, 36),
             Self::CAD => ("CAD", "Canadian Dollar", "Can$", 2, 124),
@@ -162,6 +165,7 @@ impl TryFrom<&str> for Currency {
             "CHF" => Ok(Self::CHF),
             "BRL" => Ok(Self::BRL),
             "COP" => Ok(Self::COP),
+            "COU" => Ok(Self::COU),
             "MXN" => Ok(Self::MXN),
             "AUD" => Ok(Self::AUD),
             "CAD" => Ok(Self::CAD),
@@ -220,6 +224,7 @@ mod tests {
         Currency::CHF,
         Currency::BRL,
         Currency::COP,
+        Currency::COU,
         Currency::MXN,
         Currency::AUD,
         Currency::CAD,
@@ -3,10 +3,12 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use super::{enums::Currency, traits::AdvanceExchangeRateStoreInTime};
+use super::{
+    enums::Currency, fxforwardcurve::FxForwardCurve, traits::AdvanceExchangeRateStoreInTime,
+};
 
 use crate::{
-    rates::indexstore::IndexStore,
+    rates::{indexstore::IndexStore, inflation::unitofaccount::UnitOfAccount},
     time::{date::Date, period::Period},
     utils::errors::{AtlasError, Result},
 };
@@ -18,11 +20,16 @@ use crate::{
 /// ## Details
 /// - Exchange rates are stored as a map of pairs of currencies to rates.
 /// - The exchange rate between two currencies is calculated by traversing the graph of exchange rates.
+/// - Forward exchange rates use the `FxForwardCurve` of each pair in the path, if any, and the
+///   currency curves of the index store otherwise.
+/// - Advancing the store rolls the spot rates to their forwards and keeps the forward curves,
+///   rebased to the new date.
 #[derive(Clone)]
 pub struct ExchangeRateStore {
     reference_date: Date,
     exchange_rate_map: HashMap<(Currency, Currency), f64>,
     exchange_rate_cache: Arc<Mutex<HashMap<(Currency, Currency), f64>>>,
+    fx_forward_curves: HashMap<(Currency, Currency), FxForwardCurve>,
 }
 
 impl ExchangeRateStore {
@@ -33,6 +40,7 @@ impl ExchangeRateStore {
             reference_date: date,
             exchange_rate_map: HashMap::new(),
             exchange_rate_cache: Arc::new(Mutex::new(HashMap::new())),
+            fx_forward_curves: HashMap::new(),
         }
     }
 
@@ -50,6 +58,39 @@ impl ExchangeRateStore {
         self.exchange_rate_map.insert((currency1, currency2), rate);
     }
 
+    /// Adds the forward curve of a currency pair to this store. It replaces any curve of the
+    /// same pair, quoted in either direction.
+    pub fn add_fx_forward_curve(&mut self, curve: FxForwardCurve) {
+        let first_ccy = curve.first_currency();
+        let second_ccy = curve.second_currency();
+        self.fx_forward_curves.remove(&(second_ccy, first_ccy));
+        self.fx_forward_curves
+            .insert((first_ccy, second_ccy), curve);
+    }
+
+    /// Adds a unit of account indexed to inflation, such as the UF. Its value on the reference
+    /// date becomes the spot rate against the local currency and its projected values the
+    /// forward curve of the pair.
+    ///
+    /// # Errors
+    /// Returns an error if the unit of account has no value for the reference date.
+    pub fn add_unit_of_account(&mut self, unit: &UnitOfAccount) -> Result<()> {
+        let value = unit.value(self.reference_date)?;
+        self.add_exchange_rate(unit.local_currency(), unit.currency(), value);
+        self.add_fx_forward_curve(unit.fx_forward_curve(self.reference_date));
+        Ok(())
+    }
+
+    /// Returns the forward curve of a currency pair, if any.
+    #[must_use]
+    pub fn get_fx_forward_curve(
+        &self,
+        first_ccy: Currency,
+        second_ccy: Currency,
+    ) -> Option<&FxForwardCurve> {
+        self.fx_forward_curves.get(&(first_ccy, second_ccy))
+    }
+
     /// Returns the reference date of this exchange rate store.
     #[must_use]
     pub const fn reference_date(&self) -> Date {
@@ -122,6 +163,100 @@ impl ExchangeRateStore {
             "No exchange rate found between {first_ccy:?} and {second_ccy:?}"
         )))
     }
+
+    /// Returns the forward exchange rate between two currencies for the given date.
+    ///
+    /// Each pair in the conversion path is forecasted with its `FxForwardCurve`. Pairs without
+    /// one use the ratio of the discount factors of the currency curves of the index store. If
+    /// no pair in the path has a forward curve, only the curves of both ends are required.
+    ///
+    /// # Errors
+    /// Returns an error if no conversion path can be found or a forward rate cannot be computed.
+    pub fn get_forward_exchange_rate(
+        &self,
+        first_ccy: Currency,
+        second_ccy: Currency,
+        date: Date,
+        index_store: &IndexStore,
+    ) -> Result<f64> {
+        if first_ccy == second_ccy {
+            return Ok(1.0);
+        }
+        let path = self.conversion_path(first_ccy, second_ccy)?;
+        if path
+            .iter()
+            .all(|(from, to, _)| self.curve_forward_rate(*from, *to, 1.0, date).is_none())
+        {
+            let spot: f64 = path.iter().map(|(_, _, rate)| rate).product();
+            return Ok(spot * index_store.currency_forescast_factor(first_ccy, second_ccy, date)?);
+        }
+        path.iter().try_fold(1.0, |acc, (from, to, spot)| {
+            let forward = match self.curve_forward_rate(*from, *to, *spot, date) {
+                Some(forward) => forward?,
+                None => spot * index_store.currency_forescast_factor(*from, *to, date)?,
+            };
+            Ok(acc * forward)
+        })
+    }
+
+    /// Forward rate of a pair with a forward curve, quoted in either direction.
+    fn curve_forward_rate(
+        &self,
+        first_ccy: Currency,
+        second_ccy: Currency,
+        spot: f64,
+        date: Date,
+    ) -> Option<Result<f64>> {
+        if let Some(curve) = self.fx_forward_curves.get(&(first_ccy, second_ccy)) {
+            return Some(curve.forward_rate(spot, date));
+        }
+        self.fx_forward_curves
+            .get(&(second_ccy, first_ccy))
+            .map(|curve| {
+                curve
+                    .forward_rate(1.0 / spot, date)
+                    .map(|forward| 1.0 / forward)
+            })
+    }
+
+    /// Pairs traversed to convert between two currencies, with the spot rate of each pair.
+    fn conversion_path(
+        &self,
+        first_ccy: Currency,
+        second_ccy: Currency,
+    ) -> Result<Vec<(Currency, Currency, f64)>> {
+        let mut parents: HashMap<Currency, (Currency, f64)> = HashMap::new();
+        let mut q: VecDeque<Currency> = VecDeque::new();
+        q.push_back(first_ccy);
+        while let Some(current_ccy) = q.pop_front() {
+            if current_ccy == second_ccy {
+                let mut path = Vec::new();
+                let mut ccy = second_ccy;
+                while let Some(&(parent, rate)) = parents.get(&ccy) {
+                    path.push((parent, ccy, rate));
+                    ccy = parent;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            for (&(source, dest), &map_rate) in &self.exchange_rate_map {
+                let (next, rate) = if source == current_ccy {
+                    (dest, map_rate)
+                } else if dest == current_ccy {
+                    (source, 1.0 / map_rate)
+                } else {
+                    continue;
+                };
+                if next != first_ccy && !parents.contains_key(&next) {
+                    parents.insert(next, (current_ccy, rate));
+                    q.push_back(next);
+                }
+            }
+        }
+        Err(AtlasError::NotFoundErr(format!(
+            "No exchange rate found between {first_ccy:?} and {second_ccy:?}"
+        )))
+    }
 }
 
 impl AdvanceExchangeRateStoreInTime for ExchangeRateStore {
@@ -143,6 +278,10 @@ impl AdvanceExchangeRateStoreInTime for ExchangeRateStore {
 
         let mut new_store = Self::new(date);
         for ((ccy1, ccy2), fx) in &self.exchange_rate_map {
+            if let Some(forward) = self.curve_forward_rate(*ccy1, *ccy2, *fx, date) {
+                new_store.add_exchange_rate(*ccy1, *ccy2, forward?);
+                continue;
+            }
             let compound_factor = index_store.currency_forescast_factor(*ccy1, *ccy2, date);
             match compound_factor {
                 Ok(cf) => new_store.add_exchange_rate(*ccy1, *ccy2, fx * cf),
@@ -152,6 +291,9 @@ impl AdvanceExchangeRateStoreInTime for ExchangeRateStore {
                 }
             }
         }
+        for curve in self.fx_forward_curves.values() {
+            new_store.add_fx_forward_curve(curve.advance_to_date(date)?);
+        }
         Ok(new_store)
     }
 }
@@ -181,6 +323,7 @@ mod tests {
                 map
             },
             exchange_rate_cache: Arc::new(Mutex::new(HashMap::new())),
+            fx_forward_curves: HashMap::new(),
         };
 
         let rate = manager.get_exchange_rate(USD, EUR)?;
@@ -205,6 +348,7 @@ mod tests {
             reference_date: ref_date,
             exchange_rate_map: HashMap::new(),
             exchange_rate_cache: Arc::new(Mutex::new(HashMap::new())),
+            fx_forward_curves: HashMap::new(),
         };
 
         let result = manager.get_exchange_rate(USD, EUR);
@@ -223,6 +367,7 @@ mod tests {
                 map
             },
             exchange_rate_cache: Arc::new(Mutex::new(HashMap::new())),
+            fx_forward_curves: HashMap::new(),
         };
 
         let eur_usd = manager.get_exchange_rate(EUR, USD)?;
@@ -245,4 +390,80 @@ mod tests {
         assert!((eur_clp - (1.0 / (1.1 * 800.0))).abs() < 1e-12);
         Ok(())
     }
+
+    #[test]
+    fn test_forward_exchange_rate() -> Result<()> {
+        use crate::rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            interestrateindex::iborindex::IborIndex,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        };
+        use crate::time::{daycounter::DayCounter, enums::Frequency};
+        use std::sync::RwLock;
+
+        let ref_date = Date::new(2021, 1, 1);
+        let date = Date::new(2022, 1, 1);
+        let t = DayCounter::Actual365.year_fraction(ref_date, date);
+        let mut index_store = IndexStore::new(ref_date);
+        for (id, rate, ccy) in [(0, 0.06, CLP), (1, 0.05, USD), (2, 0.03, EUR)] {
+            let curve = Arc::new(FlatForwardTermStructure::new(
+                ref_date,
+                rate,
+                RateDefinition::new(
+                    DayCounter::Actual365,
+                    Compounding::Continuous,
+                    Frequency::Annual,
+                ),
+            ));
+            index_store.add_index(
+                id,
+                Arc::new(RwLock::new(
+                    IborIndex::new(ref_date).with_term_structure(curve),
+                )),
+            )?;
+            index_store.add_currency_curve(ccy, id);
+        }
+
+        let mut manager = ExchangeRateStore::new(ref_date);
+        manager.add_exchange_rate(CLP, USD, 800.0);
+        manager.add_exchange_rate(USD, EUR, 1.1);
+        let clp_eur = manager.get_forward_exchange_rate(CLP, EUR, date, &index_store)?;
+        assert!((clp_eur / 880.0 - (0.03 * t).exp()).abs() < 1e-12);
+
+        manager.add_fx_forward_curve(FxForwardCurve::from_forward_points(
+            CLP,
+            USD,
+            ref_date,
+            vec![Date::new(2021, 4, 1), date, Date::new(2022, 7, 1)],
+            vec![4.0, 10.0, 14.0],
+        )?);
+        let usd_clp = manager.get_forward_exchange_rate(USD, CLP, date, &index_store)?;
+        assert!((usd_clp - 1.0 / 810.0).abs() < 1e-12);
+        let clp_eur = manager.get_forward_exchange_rate(CLP, EUR, date, &index_store)?;
+        assert!((clp_eur / 891.0 - (0.02 * t).exp()).abs() < 1e-12);
+
+        let advanced = manager.advance_to_date(date, &index_store)?;
+        assert!((advanced.get_exchange_rate(CLP, USD)? - 810.0).abs() < 1e-12);
+        assert!((advanced.get_exchange_rate(USD, EUR)? / 1.1 - (0.02 * t).exp()).abs() < 1e-12);
+        assert!(advanced.get_fx_forward_curve(CLP, USD).is_some());
+
+        // advancing in two steps keeps the forward points and the basis of the curve
+        let mid_date = Date::new(2021, 7, 1);
+        let two_steps = manager
+            .advance_to_date(mid_date, &index_store)?
+            .advance_to_date(date, &index_store.advance_to_date(mid_date)?)?;
+        for (ccy1, ccy2) in [(CLP, USD), (USD, EUR)] {
+            let one_step_rate = advanced.get_exchange_rate(ccy1, ccy2)?;
+            assert!((two_steps.get_exchange_rate(ccy1, ccy2)? - one_step_rate).abs() < 1e-9);
+        }
+        let advanced_index_store = index_store.advance_to_date(date)?;
+        let later = Date::new(2022, 4, 1);
+        let forward = manager.get_forward_exchange_rate(CLP, USD, later, &index_store)?;
+        for store in [&advanced, &two_steps] {
+            let advanced_forward =
+                store.get_forward_exchange_rate(CLP, USD, later, &advanced_index_store)?;
+            assert!((advanced_forward - forward).abs() < 1e-9);
+        }
+        Ok(())
+    }
 }
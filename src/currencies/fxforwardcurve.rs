@@ -0,0 +1,370 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    math::interpolation::enums::Interpolator,
+    rates::yieldtermstructure::traits::YieldTermStructureTrait,
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::enums::Currency;
+
+/// # `FxForwardSource`
+/// Market data a `FxForwardCurve` is built from.
+#[derive(Clone)]
+pub enum FxForwardSource {
+    /// Forward points quoted for delivery dates after spot, in units of the first currency
+    /// per unit of the second currency. The points are zero on the spot date and flat
+    /// outside the quoted dates.
+    ForwardPoints {
+        /// Delivery dates of the quotes.
+        dates: Vec<Date>,
+        /// Forward points of each delivery date.
+        points: Vec<f64>,
+        /// Interpolation method of the points between delivery dates.
+        interpolator: Interpolator,
+    },
+    /// Discount curves of both currencies. The cross-currency basis spread is added to the
+    /// continuously compounded rate of the first currency curve.
+    CurvePair {
+        /// Discount curve of the first currency.
+        first_curve: Arc<dyn YieldTermStructureTrait>,
+        /// Discount curve of the second currency.
+        second_curve: Arc<dyn YieldTermStructureTrait>,
+        /// Cross-currency basis spread, Actual/365 continuously compounded.
+        basis_spread: f64,
+    },
+    /// Projected rates for each date, as for units of account indexed to inflation. Forwards
+    /// keep the ratio of the projected rates to the one of the spot date.
+    ProjectedRates {
+        /// Projected rate of each date.
+        rates: BTreeMap<Date, f64>,
+    },
+}
+
+/// # `FxForwardCurve`
+/// Forward exchange rates of a currency pair, following the convention of the
+/// `ExchangeRateStore`: units of the first currency per unit of the second currency. The spot
+/// rate of the store is taken to settle on the spot date of the curve, so forwards are
+/// computed from the spot date and not from the reference date.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// // USD/CLP at 900 with spot on 5 March and 12 points for June
+/// let curve = FxForwardCurve::from_forward_points(
+///     Currency::CLP,
+///     Currency::USD,
+///     Date::new(2024, 3, 5),
+///     vec![Date::new(2024, 6, 5)],
+///     vec![12.0],
+/// )
+/// .unwrap();
+/// let forward = curve.forward_rate(900.0, Date::new(2024, 6, 5)).unwrap();
+/// assert!((forward - 912.0).abs() < 1e-12);
+/// ```
+#[derive(Clone)]
+pub struct FxForwardCurve {
+    first_currency: Currency,
+    second_currency: Currency,
+    spot_date: Date,
+    source: FxForwardSource,
+}
+
+impl FxForwardCurve {
+    /// Creates a curve from forward points, interpolated linearly.
+    ///
+    /// # Errors
+    /// Returns an error if there are no quotes, if dates and points differ in length or if the
+    /// dates are not strictly increasing after the spot date.
+    pub fn from_forward_points(
+        first_currency: Currency,
+        second_currency: Currency,
+        spot_date: Date,
+        dates: Vec<Date>,
+        points: Vec<f64>,
+    ) -> Result<Self> {
+        if dates.is_empty() || dates.len() != points.len() {
+            return Err(AtlasError::InvalidValueErr(
+                "Forward points need one quote per delivery date".to_string(),
+            ));
+        }
+        let mut previous = spot_date;
+        for date in &dates {
+            if *date <= previous {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Forward point dates must be increasing and after the spot date ({date})"
+                )));
+            }
+            previous = *date;
+        }
+        Ok(Self {
+            first_currency,
+            second_currency,
+            spot_date,
+            source: FxForwardSource::ForwardPoints {
+                dates,
+                points,
+                interpolator: Interpolator::Linear,
+            },
+        })
+    }
+
+    /// Creates a curve from the discount curves of both currencies and a cross-currency basis
+    /// spread on the first currency.
+    #[must_use]
+    pub fn from_curves(
+        first_currency: Currency,
+        second_currency: Currency,
+        spot_date: Date,
+        first_curve: Arc<dyn YieldTermStructureTrait>,
+        second_curve: Arc<dyn YieldTermStructureTrait>,
+        basis_spread: f64,
+    ) -> Self {
+        Self {
+            first_currency,
+            second_currency,
+            spot_date,
+            source: FxForwardSource::CurvePair {
+                first_curve,
+                second_curve,
+                basis_spread,
+            },
+        }
+    }
+
+    /// Creates a curve from the projected rates of each date.
+    #[must_use]
+    pub const fn from_projected_rates(
+        first_currency: Currency,
+        second_currency: Currency,
+        spot_date: Date,
+        rates: BTreeMap<Date, f64>,
+    ) -> Self {
+        Self {
+            first_currency,
+            second_currency,
+            spot_date,
+            source: FxForwardSource::ProjectedRates { rates },
+        }
+    }
+
+    /// Sets the interpolation method of the forward points. Curves built from discount curves
+    /// are left unchanged.
+    #[must_use]
+    pub const fn with_interpolator(mut self, interpolator: Interpolator) -> Self {
+        if let FxForwardSource::ForwardPoints {
+            interpolator: current,
+            ..
+        } = &mut self.source
+        {
+            *current = interpolator;
+        }
+        self
+    }
+
+    /// Returns the first currency.
+    #[must_use]
+    pub const fn first_currency(&self) -> Currency {
+        self.first_currency
+    }
+
+    /// Returns the second currency.
+    #[must_use]
+    pub const fn second_currency(&self) -> Currency {
+        self.second_currency
+    }
+
+    /// Returns the spot date.
+    #[must_use]
+    pub const fn spot_date(&self) -> Date {
+        self.spot_date
+    }
+
+    /// Returns the market data of the curve.
+    #[must_use]
+    pub const fn source(&self) -> &FxForwardSource {
+        &self.source
+    }
+
+    /// Returns the forward exchange rate for delivery on `date`, given the spot rate.
+    ///
+    /// # Errors
+    /// Returns an error if the points cannot be interpolated, the discount factors cannot be
+    /// computed or the date has no projected rate.
+    pub fn forward_rate(&self, spot: f64, date: Date) -> Result<f64> {
+        match &self.source {
+            FxForwardSource::ForwardPoints {
+                dates,
+                points,
+                interpolator,
+            } => {
+                // every quote has been rolled into the spot rate
+                if dates.is_empty() {
+                    return Ok(spot);
+                }
+                let time = |d: Date| DayCounter::Actual365.year_fraction(self.spot_date, d);
+                let x_: Vec<f64> = std::iter::once(0.0)
+                    .chain(dates.iter().map(|d| time(*d)))
+                    .collect();
+                let y_: Vec<f64> = std::iter::once(0.0).chain(points.iter().copied()).collect();
+                let x = time(date).clamp(0.0, x_[x_.len() - 1]);
+                Ok(spot + interpolator.interpolate(x, &x_, &y_, false)?)
+            }
+            FxForwardSource::CurvePair {
+                first_curve,
+                second_curve,
+                basis_spread,
+            } => {
+                let first_growth = first_curve.discount_factor(self.spot_date)?
+                    / first_curve.discount_factor(date)?;
+                let second_growth = second_curve.discount_factor(self.spot_date)?
+                    / second_curve.discount_factor(date)?;
+                let t = DayCounter::Actual365.year_fraction(self.spot_date, date);
+                Ok(spot * first_growth * (basis_spread * t).exp() / second_growth)
+            }
+            FxForwardSource::ProjectedRates { rates } => {
+                let projected = |d: Date| {
+                    rates.get(&d).copied().ok_or_else(|| {
+                        AtlasError::NotFoundErr(format!("Projected exchange rate for {d}"))
+                    })
+                };
+                Ok(spot * projected(date)? / projected(self.spot_date)?)
+            }
+        }
+    }
+
+    /// Returns the curve with spot on `date`, for a spot rate rolled to its forward on that
+    /// date. Forward points are rebased to the new spot, so that forwards are unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if the forward points on `date` cannot be interpolated.
+    pub fn advance_to_date(&self, date: Date) -> Result<Self> {
+        let source = match &self.source {
+            FxForwardSource::ForwardPoints {
+                dates,
+                points,
+                interpolator,
+            } => {
+                if date <= self.spot_date {
+                    return Ok(self.clone());
+                }
+                let rolled = self.forward_rate(0.0, date)?;
+                let (dates, points) = dates
+                    .iter()
+                    .zip(points)
+                    .filter(|(d, _)| **d > date)
+                    .map(|(d, p)| (*d, p - rolled))
+                    .unzip();
+                FxForwardSource::ForwardPoints {
+                    dates,
+                    points,
+                    interpolator: *interpolator,
+                }
+            }
+            FxForwardSource::CurvePair { .. } | FxForwardSource::ProjectedRates { .. } => {
+                self.source.clone()
+            }
+        };
+        Ok(Self {
+            first_currency: self.first_currency,
+            second_currency: self.second_currency,
+            spot_date: date,
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::FxForwardCurve;
+    use crate::{
+        currencies::enums::Currency,
+        rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{date::Date, daycounter::DayCounter, enums::Frequency},
+        utils::errors::Result,
+    };
+
+    #[test]
+    fn test_forward_points() -> Result<()> {
+        let spot_date = Date::new(2024, 3, 5);
+        let curve = FxForwardCurve::from_forward_points(
+            Currency::CLP,
+            Currency::USD,
+            spot_date,
+            vec![spot_date + 30, spot_date + 90],
+            vec![3.0, 12.0],
+        )?;
+        assert!((curve.forward_rate(900.0, spot_date)? - 900.0).abs() < 1e-12);
+        assert!((curve.forward_rate(900.0, spot_date + 60)? - 907.5).abs() < 1e-12);
+        assert!((curve.forward_rate(900.0, spot_date + 365)? - 912.0).abs() < 1e-12);
+
+        // rolled to the spot rate of a later date
+        let advanced = curve.advance_to_date(spot_date + 60)?;
+        assert_eq!(advanced.spot_date(), spot_date + 60);
+        assert!((advanced.forward_rate(907.5, spot_date + 75)? - 909.75).abs() < 1e-12);
+        let advanced = curve.advance_to_date(spot_date + 120)?;
+        assert!((advanced.forward_rate(912.0, spot_date + 365)? - 912.0).abs() < 1e-12);
+
+        assert!(FxForwardCurve::from_forward_points(
+            Currency::CLP,
+            Currency::USD,
+            spot_date,
+            vec![spot_date],
+            vec![0.0],
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_curve_pair_with_basis() -> Result<()> {
+        let reference_date = Date::new(2024, 3, 1);
+        let spot_date = Date::new(2024, 3, 5);
+        let rate_definition = RateDefinition::new(
+            DayCounter::Actual365,
+            Compounding::Continuous,
+            Frequency::Annual,
+        );
+        let clp = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.06,
+            rate_definition,
+        ));
+        let usd = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.05,
+            rate_definition,
+        ));
+        let date = Date::new(2025, 3, 5);
+        let t = DayCounter::Actual365.year_fraction(spot_date, date);
+
+        let curve = FxForwardCurve::from_curves(
+            Currency::CLP,
+            Currency::USD,
+            spot_date,
+            clp.clone(),
+            usd.clone(),
+            0.0,
+        );
+        let forward = curve.forward_rate(900.0, date)?;
+        assert!((forward / 900.0 - (0.01 * t).exp()).abs() < 1e-12);
+
+        let curve =
+            FxForwardCurve::from_curves(Currency::CLP, Currency::USD, spot_date, clp, usd, 0.002);
+        let forward = curve.forward_rate(900.0, date)?;
+        assert!((forward / 900.0 - (0.012 * t).exp()).abs() < 1e-12);
+
+        let mid_date = Date::new(2024, 9, 5);
+        let advanced = curve.advance_to_date(mid_date)?;
+        let mid_spot = curve.forward_rate(900.0, mid_date)?;
+        assert!((advanced.forward_rate(mid_spot, date)? - forward).abs() < 1e-9);
+        Ok(())
+    }
+}
@@ -2,5 +2,9 @@
 pub mod enums;
 /// Exchange rate storage functionality.
 pub mod exchangeratestore;
+/// Forward exchange rate curves.
+pub mod fxforwardcurve;
+/// Amounts with their currency.
+pub mod money;
 /// Trait definitions for currency operations.
 pub mod traits;
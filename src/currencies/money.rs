@@ -0,0 +1,290 @@
+use std::{
+    fmt,
+    ops::{Div, Mul, Neg},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::traits::HasCurrency,
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{enums::Currency, exchangeratestore::ExchangeRateStore};
+
+/// # `RoundingMode`
+/// How ties and discarded decimals are treated when rounding an amount.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+    /// Round to nearest, ties to the even digit (banker's rounding).
+    HalfEven,
+    /// Discard the decimals, towards zero.
+    Down,
+    /// Round away from zero.
+    Up,
+}
+
+/// # `Rounding`
+/// Rounding rule for amounts. By default amounts are rounded to the precision of their
+/// currency; bank-specific rules can override the number of decimals.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let rounding = Rounding::new(RoundingMode::HalfEven);
+/// assert_eq!(rounding.round(2.125, Currency::USD), 2.12);
+/// assert_eq!(rounding.round(1_500.5, Currency::CLP), 1_500.0);
+///
+/// let rounding = Rounding::new(RoundingMode::HalfUp).with_decimals(4);
+/// assert_eq!(rounding.round(2.12345, Currency::USD), 2.1235);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rounding {
+    mode: RoundingMode,
+    decimals: Option<u8>,
+}
+
+impl Rounding {
+    /// Creates a new `Rounding` to the precision of the currency.
+    #[must_use]
+    pub const fn new(mode: RoundingMode) -> Self {
+        Self {
+            mode,
+            decimals: None,
+        }
+    }
+
+    /// Sets the number of decimals, overriding the precision of the currency.
+    #[must_use]
+    pub const fn with_decimals(mut self, decimals: u8) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Returns the rounding mode.
+    #[must_use]
+    pub const fn mode(&self) -> RoundingMode {
+        self.mode
+    }
+
+    /// Returns the number of decimals used for the given currency.
+    #[must_use]
+    pub const fn decimals(&self, currency: Currency) -> u8 {
+        match self.decimals {
+            Some(decimals) => decimals,
+            None => currency.precision(),
+        }
+    }
+
+    /// Rounds an amount expressed in the given currency.
+    #[must_use]
+    pub fn round(&self, amount: f64, currency: Currency) -> f64 {
+        let factor = 10f64.powi(i32::from(self.decimals(currency)));
+        // remove the representation error of the scaling before looking at the decimals
+        let scaled = (amount * factor * 1e9).round() / 1e9;
+        let rounded = match self.mode {
+            RoundingMode::HalfUp => scaled.round(),
+            RoundingMode::HalfEven => scaled.round_ties_even(),
+            RoundingMode::Down => scaled.trunc(),
+            RoundingMode::Up => scaled.abs().ceil().copysign(scaled),
+        };
+        rounded / factor
+    }
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Self::new(RoundingMode::HalfUp)
+    }
+}
+
+/// # `Money`
+/// An amount together with its currency. Arithmetic between amounts checks the currencies and
+/// fails on a mismatch instead of summing, for example, CLP and USD.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let a = Money::new(100.0, Currency::USD);
+/// let b = Money::new(50.0, Currency::USD);
+/// assert_eq!(a.checked_add(b).unwrap().amount(), 150.0);
+/// assert!(a.checked_add(Money::new(1.0, Currency::CLP)).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Money {
+    amount: f64,
+    currency: Currency,
+}
+
+impl Money {
+    /// Creates a new `Money`.
+    #[must_use]
+    pub const fn new(amount: f64, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    /// Creates a zero amount in the given currency.
+    #[must_use]
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(0.0, currency)
+    }
+
+    /// Returns the amount.
+    #[must_use]
+    pub const fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// Adds two amounts of the same currency.
+    ///
+    /// # Errors
+    /// Returns an error if the currencies differ.
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        self.check_currency(other.currency)?;
+        Ok(Self::new(self.amount + other.amount, self.currency))
+    }
+
+    /// Subtracts an amount of the same currency.
+    ///
+    /// # Errors
+    /// Returns an error if the currencies differ.
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        self.check_currency(other.currency)?;
+        Ok(Self::new(self.amount - other.amount, self.currency))
+    }
+
+    /// Sums amounts of the given currency.
+    ///
+    /// # Errors
+    /// Returns an error if any amount is in a different currency.
+    pub fn checked_sum<I: IntoIterator<Item = Self>>(currency: Currency, iter: I) -> Result<Self> {
+        iter.into_iter()
+            .try_fold(Self::zero(currency), Self::checked_add)
+    }
+
+    /// Converts the amount to another currency at the spot rate of the store.
+    ///
+    /// # Errors
+    /// Returns an error if the exchange rate is not available.
+    pub fn convert(self, currency: Currency, store: &ExchangeRateStore) -> Result<Self> {
+        let rate = store.get_exchange_rate(self.currency, currency)?;
+        Ok(Self::new(self.amount / rate, currency))
+    }
+
+    /// Returns the amount rounded with the given rule.
+    #[must_use]
+    pub fn round(self, rounding: Rounding) -> Self {
+        Self::new(rounding.round(self.amount, self.currency), self.currency)
+    }
+
+    fn check_currency(&self, currency: Currency) -> Result<()> {
+        if self.currency == currency {
+            Ok(())
+        } else {
+            Err(AtlasError::InvalidValueErr(format!(
+                "Currency mismatch: {} and {currency}",
+                self.currency
+            )))
+        }
+    }
+}
+
+impl HasCurrency for Money {
+    fn currency(&self) -> Result<Currency> {
+        Ok(self.currency)
+    }
+}
+
+impl Neg for Money {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.amount, self.currency)
+    }
+}
+
+impl Mul<f64> for Money {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.amount * rhs, self.currency)
+    }
+}
+
+impl Div<f64> for Money {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.amount / rhs, self.currency)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = usize::from(self.currency.precision());
+        write!(f, "{:.precision$} {}", self.amount, self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Money, Rounding, RoundingMode};
+    use crate::{
+        core::traits::HasCurrency,
+        currencies::{enums::Currency, exchangeratestore::ExchangeRateStore},
+        time::date::Date,
+        utils::errors::Result,
+    };
+
+    #[test]
+    fn test_checked_arithmetic() -> Result<()> {
+        let usd = Money::new(100.0, Currency::USD);
+        let clp = Money::new(90_000.0, Currency::CLP);
+        assert!(usd.checked_add(clp).is_err());
+        assert!(usd.checked_sub(clp).is_err());
+        assert!(Money::checked_sum(Currency::USD, [usd, clp]).is_err());
+
+        let total = Money::checked_sum(Currency::USD, [usd, usd * 0.5, -usd / 4.0])?;
+        assert!((total.amount() - 125.0).abs() < 1e-12);
+        assert_eq!(total.currency()?, Currency::USD);
+        assert_eq!(format!("{clp}"), "90000 CLP");
+        assert_eq!(format!("{usd}"), "100.00 USD");
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversion() -> Result<()> {
+        let mut store = ExchangeRateStore::new(Date::new(2024, 1, 1));
+        store.add_exchange_rate(Currency::CLP, Currency::USD, 900.0);
+        let usd = Money::new(90_000.0, Currency::CLP).convert(Currency::USD, &store)?;
+        assert!((usd.amount() - 100.0).abs() < 1e-12);
+        assert_eq!(usd.currency()?, Currency::USD);
+        let clp = usd.convert(Currency::CLP, &store)?;
+        assert!((clp.amount() - 90_000.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rounding() {
+        let half_up = Rounding::new(RoundingMode::HalfUp);
+        let half_even = Rounding::new(RoundingMode::HalfEven).with_decimals(2);
+        assert!((half_up.round(2.675, Currency::USD) - 2.68).abs() < 1e-12);
+        assert!((half_up.round(-2.675, Currency::USD) + 2.68).abs() < 1e-12);
+        assert!((half_even.round(2.675, Currency::CLP) - 2.68).abs() < 1e-12);
+        assert!((half_even.round(2.665, Currency::CLP) - 2.66).abs() < 1e-12);
+        assert!((half_up.round(1_234.5, Currency::CLP) - 1_235.0).abs() < 1e-12);
+
+        let down = Rounding::new(RoundingMode::Down);
+        let up = Rounding::new(RoundingMode::Up);
+        assert!((down.round(-1.019, Currency::EUR) + 1.01).abs() < 1e-12);
+        assert!((up.round(-1.011, Currency::EUR) + 1.02).abs() < 1e-12);
+        assert!((up.round(1.0, Currency::EUR) - 1.0).abs() < 1e-12);
+
+        let money = Money::new(1_000.126, Currency::USD).round(half_up);
+        assert!((money.amount() - 1_000.13).abs() < 1e-12);
+    }
+}
@@ -25,6 +25,7 @@ pub trait CurrencyDetails {
 /// Trait for advancing an exchange rate store in time using de index store
 /// It is necessary for any currency, have free risk curve tabulated in the index store
 /// If the currency does not have a free risk curve, method `advance_to_period` and `advance_to_date` will mantain the same fx
+/// Pairs with a `FxForwardCurve` are advanced to its forward rate instead, and the curves are not carried to the new store
 pub trait AdvanceExchangeRateStoreInTime {
     /// Advances the exchange rate store to a specific period using the index store
     ///
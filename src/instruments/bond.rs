@@ -0,0 +1,947 @@
+use argmin::{
+    core::{CostFunction, Error, Executor, State},
+    solver::brent::BrentRoot,
+};
+
+use super::fixedrateinstrument::FixedRateInstrument;
+use crate::{
+    cashflows::{
+        cashflow::Cashflow,
+        traits::{InterestAccrual, Payable},
+    },
+    rates::{enums::Compounding, interestrate::RateDefinition},
+    time::{
+        calendar::Calendar,
+        calendars::{
+            brazil::{self, Brazil},
+            chile::{self, Chile},
+            traits::{ImplCalendar, IsCalendar},
+            unitedstates::{self, UnitedStates},
+        },
+        date::Date,
+        daycounter::DayCounter,
+        enums::{Frequency, TimeUnit},
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+/// # `YieldTimeBasis`
+/// Measures the time between settlement and each cashflow when discounting at the yield.
+///
+/// ## Variants
+/// * `CouponPeriods` - Whole coupon periods plus the fraction of the current period left until
+///   the next coupon (Actual/Actual ICMA), as in the US Treasury street convention.
+/// * `YearFraction` - Year fraction from settlement to payment under the yield day counter.
+/// * `BusinessDays` - Business days of the convention calendar from settlement (included) to
+///   payment (excluded) over 252, as in the Brazilian market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YieldTimeBasis {
+    /// Coupon periods to each payment.
+    CouponPeriods,
+    /// Year fraction to each payment.
+    YearFraction,
+    /// Business days to each payment over 252.
+    BusinessDays,
+}
+
+/// # `PriceQuote`
+/// The price a market quotes for a bond, and the one `yield_to_maturity` expects.
+///
+/// ## Variants
+/// * `Clean` - Dirty price less accrued interest, per face value of outstanding notional.
+/// * `Dirty` - Present value of the remaining cashflows, per face value of outstanding notional.
+/// * `PercentOfPar` - Present value as a percentage of the par value (outstanding notional plus
+///   accrued interest), as quoted in the Chilean market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceQuote {
+    /// Clean price.
+    Clean,
+    /// Dirty price.
+    Dirty,
+    /// Percentage of par value.
+    PercentOfPar,
+}
+
+/// # `BondConvention`
+/// Market conventions used to settle, quote and discount a fixed rate bond.
+///
+/// ## Parameters
+/// * `calendar` - Calendar used for the settlement lag and the ex-coupon date.
+/// * `settlement_days` - Business days between trade and settlement.
+/// * `ex_coupon_days` - Business days before a coupon payment when the bond trades ex-coupon.
+/// * `yield_definition` - Compounding, frequency and day counter of the yield.
+/// * `time_basis` - How the time to each cashflow is measured.
+/// * `quote` - The quoted price.
+/// * `face_value` - Notional the prices and the accrued interest are expressed on.
+/// * `coupon_decimals` - Decimals the coupons per face value are rounded to, if any.
+/// * `price_decimals` - Decimals the quoted price is truncated to, if any.
+/// * `yield_bracket` - Lowest and highest yields searched for the yield to maturity.
+#[derive(Debug, Clone)]
+pub struct BondConvention {
+    calendar: Calendar,
+    settlement_days: i32,
+    ex_coupon_days: i32,
+    yield_definition: RateDefinition,
+    time_basis: YieldTimeBasis,
+    quote: PriceQuote,
+    face_value: f64,
+    coupon_decimals: Option<i32>,
+    price_decimals: Option<i32>,
+    yield_bracket: (f64, f64),
+}
+
+impl BondConvention {
+    /// Creates a new `BondConvention` settling on trade date, without ex-coupon period, measuring
+    /// time with the yield day counter, quoting unrounded clean prices per 100 and searching
+    /// yields between -50% and 100%.
+    #[must_use]
+    pub const fn new(calendar: Calendar, yield_definition: RateDefinition) -> Self {
+        Self {
+            calendar,
+            settlement_days: 0,
+            ex_coupon_days: 0,
+            yield_definition,
+            time_basis: YieldTimeBasis::YearFraction,
+            quote: PriceQuote::Clean,
+            face_value: 100.0,
+            coupon_decimals: None,
+            price_decimals: None,
+            yield_bracket: (-0.5, 1.0),
+        }
+    }
+
+    /// US Treasury notes and bonds: T+1 on the government bond calendar, semiannual yield over
+    /// Actual/Actual ICMA coupon periods and clean price quotes.
+    #[must_use]
+    pub fn us_treasury() -> Self {
+        Self::new(
+            Calendar::UnitedStates(UnitedStates::new(unitedstates::Market::GovernmentBond)),
+            RateDefinition::new(
+                DayCounter::ActualActual,
+                Compounding::Compounded,
+                Frequency::Semiannual,
+            ),
+        )
+        .with_settlement_days(1)
+        .with_time_basis(YieldTimeBasis::CouponPeriods)
+    }
+
+    /// Chilean central bank peso bonds (BCP): T+1 on the Santiago calendar, annual yield over
+    /// Actual/365 and prices quoted as a percentage of par value.
+    #[must_use]
+    pub fn chile_bcp() -> Self {
+        Self::new(
+            Calendar::Chile(Chile::new(chile::Market::SSE)),
+            RateDefinition::new(
+                DayCounter::Actual365,
+                Compounding::Compounded,
+                Frequency::Annual,
+            ),
+        )
+        .with_settlement_days(1)
+        .with_quote(PriceQuote::PercentOfPar)
+    }
+
+    /// Chilean central bank UF bonds (BTU). They follow the BCP conventions with cashflows
+    /// expressed in UF.
+    #[must_use]
+    pub fn chile_btu() -> Self {
+        Self::chile_bcp()
+    }
+
+    /// Brazilian NTN-F: T+1 on the settlement calendar and annual yield over business days/252.
+    /// The quote is the ANBIMA unit price (PU): the dirty price on a face value of 1000, with
+    /// coupons of 1000 * (1.1^0.5 - 1) rounded to 5 decimals and the price truncated to 6
+    /// decimals.
+    #[must_use]
+    pub fn brazil_ntnf() -> Self {
+        Self::new(
+            Calendar::Brazil(Brazil::new(brazil::Market::Settlement)),
+            RateDefinition::new(
+                DayCounter::Business252,
+                Compounding::Compounded,
+                Frequency::Annual,
+            ),
+        )
+        .with_settlement_days(1)
+        .with_time_basis(YieldTimeBasis::BusinessDays)
+        .with_quote(PriceQuote::Dirty)
+        .with_face_value(1000.0)
+        .with_coupon_decimals(5)
+        .with_price_decimals(6)
+    }
+
+    /// Sets the business days between trade and settlement.
+    #[must_use]
+    pub const fn with_settlement_days(mut self, settlement_days: i32) -> Self {
+        self.settlement_days = settlement_days;
+        self
+    }
+
+    /// Sets the business days before a coupon payment when the bond trades ex-coupon.
+    #[must_use]
+    pub const fn with_ex_coupon_days(mut self, ex_coupon_days: i32) -> Self {
+        self.ex_coupon_days = ex_coupon_days;
+        self
+    }
+
+    /// Sets how the time to each cashflow is measured.
+    #[must_use]
+    pub const fn with_time_basis(mut self, time_basis: YieldTimeBasis) -> Self {
+        self.time_basis = time_basis;
+        self
+    }
+
+    /// Sets the quoted price.
+    #[must_use]
+    pub const fn with_quote(mut self, quote: PriceQuote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the notional the prices and the accrued interest are expressed on.
+    #[must_use]
+    pub const fn with_face_value(mut self, face_value: f64) -> Self {
+        self.face_value = face_value;
+        self
+    }
+
+    /// Sets the decimals the coupons per face value are rounded to, half up.
+    #[must_use]
+    pub const fn with_coupon_decimals(mut self, decimals: i32) -> Self {
+        self.coupon_decimals = Some(decimals);
+        self
+    }
+
+    /// Sets the decimals the quoted price is truncated to.
+    #[must_use]
+    pub const fn with_price_decimals(mut self, decimals: i32) -> Self {
+        self.price_decimals = Some(decimals);
+        self
+    }
+
+    /// Sets the lowest and highest yields searched for the yield to maturity.
+    #[must_use]
+    pub const fn with_yield_bracket(mut self, min: f64, max: f64) -> Self {
+        self.yield_bracket = (min, max);
+        self
+    }
+
+    /// Returns the calendar.
+    #[must_use]
+    pub const fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// Returns the settlement lag in business days.
+    #[must_use]
+    pub const fn settlement_days(&self) -> i32 {
+        self.settlement_days
+    }
+
+    /// Returns the ex-coupon period in business days.
+    #[must_use]
+    pub const fn ex_coupon_days(&self) -> i32 {
+        self.ex_coupon_days
+    }
+
+    /// Returns the yield definition.
+    #[must_use]
+    pub const fn yield_definition(&self) -> RateDefinition {
+        self.yield_definition
+    }
+
+    /// Returns the time basis.
+    #[must_use]
+    pub const fn time_basis(&self) -> YieldTimeBasis {
+        self.time_basis
+    }
+
+    /// Returns the quoted price.
+    #[must_use]
+    pub const fn quote(&self) -> PriceQuote {
+        self.quote
+    }
+
+    /// Returns the face value.
+    #[must_use]
+    pub const fn face_value(&self) -> f64 {
+        self.face_value
+    }
+
+    /// Returns the decimals of the coupons, if any.
+    #[must_use]
+    pub const fn coupon_decimals(&self) -> Option<i32> {
+        self.coupon_decimals
+    }
+
+    /// Returns the decimals of the quoted price, if any.
+    #[must_use]
+    pub const fn price_decimals(&self) -> Option<i32> {
+        self.price_decimals
+    }
+
+    /// Returns the lowest and highest yields searched for the yield to maturity.
+    #[must_use]
+    pub const fn yield_bracket(&self) -> (f64, f64) {
+        self.yield_bracket
+    }
+}
+
+/// # `Bond`
+/// Price, yield and risk analytics for a fixed rate instrument under a `BondConvention`. Prices
+/// and accrued interest are expressed per face value of the notional outstanding at settlement
+/// and are computed from the holder's point of view.
+///
+/// ## Parameters
+/// * `instrument` - The fixed rate instrument.
+/// * `convention` - The market conventions.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let instrument = MakeFixedRateInstrument::new()
+///     .with_start_date(Date::new(2024, 5, 15))
+///     .with_end_date(Date::new(2034, 5, 15))
+///     .with_payment_frequency(Frequency::Semiannual)
+///     .with_rate(InterestRate::new(
+///         0.04,
+///         Compounding::Simple,
+///         Frequency::Annual,
+///         DayCounter::Thirty360,
+///     ))
+///     .with_notional(1_000_000.0)
+///     .with_side(Side::Receive)
+///     .with_currency(Currency::USD)
+///     .bullet()
+///     .build()
+///     .unwrap();
+///
+/// let bond = Bond::new(instrument, BondConvention::us_treasury());
+/// let settlement = bond.settlement_date(Date::new(2024, 11, 14));
+/// assert_eq!(settlement, Date::new(2024, 11, 15));
+/// let price = bond.clean_price(0.04, settlement).unwrap();
+/// assert!((price - 100.0).abs() < 1e-10);
+/// let ytm = bond.yield_to_maturity(price, settlement).unwrap();
+/// assert!((ytm - 0.04).abs() < 1e-8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bond {
+    instrument: FixedRateInstrument,
+    convention: BondConvention,
+}
+
+impl Bond {
+    /// Creates a new `Bond`.
+    #[must_use]
+    pub const fn new(instrument: FixedRateInstrument, convention: BondConvention) -> Self {
+        Self {
+            instrument,
+            convention,
+        }
+    }
+
+    /// Returns the underlying instrument.
+    #[must_use]
+    pub const fn instrument(&self) -> &FixedRateInstrument {
+        &self.instrument
+    }
+
+    /// Returns the market conventions.
+    #[must_use]
+    pub const fn convention(&self) -> &BondConvention {
+        &self.convention
+    }
+
+    /// Returns the settlement date for a trade done on `trade_date`.
+    #[must_use]
+    pub fn settlement_date(&self, trade_date: Date) -> Date {
+        let calendar = &self.convention.calendar;
+        let date = calendar.adjust(trade_date, None);
+        calendar.advance(
+            date,
+            Period::new(self.convention.settlement_days, TimeUnit::Days),
+            None,
+            false,
+        )
+    }
+
+    /// Returns the date from which the coupon paid on `payment_date` goes to the seller.
+    #[must_use]
+    pub fn ex_coupon_date(&self, payment_date: Date) -> Date {
+        self.convention.calendar.advance(
+            payment_date,
+            Period::new(-self.convention.ex_coupon_days, TimeUnit::Days),
+            None,
+            false,
+        )
+    }
+
+    /// Returns the accrued interest per face value at `settlement`, using the day counter of the
+    /// current coupon. It is negative when the bond trades ex-coupon.
+    ///
+    /// # Errors
+    /// Returns an error if no notional is outstanding at settlement.
+    pub fn accrued_interest(&self, settlement: Date) -> Result<f64> {
+        let outstanding = self.outstanding(settlement)?;
+        let accrued = match self.current_coupon(settlement) {
+            Some(coupon) => {
+                let accrued = coupon.accrued_amount(coupon.accrual_start_date()?, settlement)?;
+                if self.is_ex_coupon(coupon, settlement) {
+                    accrued - coupon.amount()?
+                } else {
+                    accrued
+                }
+            }
+            None => 0.0,
+        };
+        Ok(self.convention.face_value * accrued / outstanding)
+    }
+
+    /// Returns the dirty price per face value at the given yield.
+    ///
+    /// # Errors
+    /// Returns an error if no notional is outstanding at settlement or if the coupon period
+    /// containing the settlement date is required and cannot be found.
+    pub fn dirty_price(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        Ok(self.price_sensitivities(yield_rate, settlement)?.price)
+    }
+
+    /// Returns the clean price per face value at the given yield.
+    ///
+    /// # Errors
+    /// Returns an error if the dirty price or the accrued interest cannot be computed.
+    pub fn clean_price(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        Ok(self.dirty_price(yield_rate, settlement)? - self.accrued_interest(settlement)?)
+    }
+
+    /// Returns the price quoted under the convention at the given yield, truncated to the price
+    /// decimals if any.
+    ///
+    /// # Errors
+    /// Returns an error if the dirty price or the accrued interest cannot be computed.
+    pub fn quoted_price(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        let dirty = self.dirty_price(yield_rate, settlement)?;
+        let price = match self.convention.quote {
+            PriceQuote::Dirty => dirty,
+            PriceQuote::Clean => dirty - self.accrued_interest(settlement)?,
+            PriceQuote::PercentOfPar => {
+                100.0 * dirty / (self.convention.face_value + self.accrued_interest(settlement)?)
+            }
+        };
+        Ok(self.convention.price_decimals.map_or(price, |decimals| {
+            round_to_decimals(price, decimals, f64::trunc)
+        }))
+    }
+
+    /// Solves the yield that reproduces the quoted price under the convention, within its yield
+    /// bracket.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be evaluated, no yield in the bracket reproduces the
+    /// quoted price or the solver does not converge.
+    pub fn yield_to_maturity(&self, quoted_price: f64, settlement: Date) -> Result<f64> {
+        let (min, max) = self.convention.yield_bracket;
+        let low = self.quoted_price(min, settlement)? - quoted_price;
+        let high = self.quoted_price(max, settlement)? - quoted_price;
+        if low * high > 0.0 {
+            return Err(AtlasError::EvaluationErr(format!(
+                "No yield between {min} and {max} reproduces the quoted price {quoted_price}"
+            )));
+        }
+        let cost = YieldToMaturity {
+            bond: self,
+            settlement,
+            quoted_price,
+        };
+        let solver = BrentRoot::new(min, max, 1e-12);
+        let res = Executor::new(cost, solver)
+            .configure(|state| state.max_iters(100).target_cost(0.0))
+            .run()?;
+
+        res.state().get_best_param().copied().ok_or_else(|| {
+            AtlasError::EvaluationErr("No yield found for the quoted price".to_string())
+        })
+    }
+
+    /// Returns the Macaulay duration in years.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be evaluated.
+    pub fn macaulay_duration(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        let sensitivities = self.price_sensitivities(yield_rate, settlement)?;
+        Ok(sensitivities.time_weighted / sensitivities.price)
+    }
+
+    /// Returns the modified duration, the relative price change per unit of yield.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be evaluated.
+    pub fn modified_duration(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        let sensitivities = self.price_sensitivities(yield_rate, settlement)?;
+        Ok(-sensitivities.first_derivative / sensitivities.price)
+    }
+
+    /// Returns the convexity, the relative second derivative of the price to the yield.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be evaluated.
+    pub fn convexity(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        let sensitivities = self.price_sensitivities(yield_rate, settlement)?;
+        Ok(sensitivities.second_derivative / sensitivities.price)
+    }
+
+    /// Returns the basis point value, the dirty price gain per face value for a one basis point
+    /// fall in the yield.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be evaluated.
+    pub fn bpv(&self, yield_rate: f64, settlement: Date) -> Result<f64> {
+        let sensitivities = self.price_sensitivities(yield_rate, settlement)?;
+        Ok(-sensitivities.first_derivative * 1e-4)
+    }
+
+    fn outstanding(&self, settlement: Date) -> Result<f64> {
+        let outstanding = self
+            .instrument
+            .cashflows()
+            .iter()
+            .filter(|cf| matches!(cf, Cashflow::Redemption(_)) && cf.payment_date() > settlement)
+            .try_fold(0.0, |acc, cf| Ok::<f64, AtlasError>(acc + cf.amount()?))?;
+        if outstanding <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "No notional outstanding at settlement date {settlement}"
+            )));
+        }
+        Ok(outstanding)
+    }
+
+    fn current_coupon(&self, settlement: Date) -> Option<&Cashflow> {
+        self.instrument.cashflows().iter().find(|cf| {
+            matches!(cf, Cashflow::FixedRateCoupon(_))
+                && cf
+                    .accrual_start_date()
+                    .is_ok_and(|start| start <= settlement)
+                && cf.accrual_end_date().is_ok_and(|end| end > settlement)
+        })
+    }
+
+    fn is_ex_coupon(&self, cf: &Cashflow, settlement: Date) -> bool {
+        matches!(cf, Cashflow::FixedRateCoupon(_))
+            && settlement < cf.payment_date()
+            && settlement >= self.ex_coupon_date(cf.payment_date())
+    }
+
+    fn coupon_periods(&self, cf: &Cashflow, settlement: Date, next_coupon: f64) -> Result<f64> {
+        let previous_coupons = self
+            .instrument
+            .cashflows()
+            .iter()
+            .filter(|c| {
+                matches!(c, Cashflow::FixedRateCoupon(_))
+                    && c.payment_date() > settlement
+                    && c.payment_date() < cf.payment_date()
+            })
+            .map(Payable::payment_date)
+            .collect::<std::collections::BTreeSet<Date>>()
+            .len();
+        Ok(next_coupon + count_to_f64(previous_coupons)?)
+    }
+
+    fn price_sensitivities(&self, yield_rate: f64, settlement: Date) -> Result<PriceSensitivities> {
+        let outstanding = self.outstanding(settlement)?;
+        let definition = self.convention.yield_definition;
+        let frequency = f64::from(definition.frequency() as i32);
+        let coupon_frequency = f64::from(self.instrument.payment_frequency() as i32);
+
+        let next_coupon = match self.convention.time_basis {
+            YieldTimeBasis::YearFraction | YieldTimeBasis::BusinessDays => 0.0,
+            YieldTimeBasis::CouponPeriods => {
+                let coupon = self.current_coupon(settlement).ok_or_else(|| {
+                    AtlasError::NotFoundErr(format!(
+                        "Coupon period containing settlement date {settlement}"
+                    ))
+                })?;
+                let start = coupon.accrual_start_date()?;
+                let end = coupon.accrual_end_date()?;
+                count_to_f64(end - settlement)? / count_to_f64(end - start)?
+            }
+        };
+
+        let mut sensitivities = PriceSensitivities::default();
+        for cf in self.instrument.cashflows() {
+            if matches!(cf, Cashflow::Disbursement(_))
+                || cf.payment_date() <= settlement
+                || self.is_ex_coupon(cf, settlement)
+            {
+                continue;
+            }
+            let time = match self.convention.time_basis {
+                YieldTimeBasis::YearFraction => definition
+                    .day_counter()
+                    .year_fraction(settlement, cf.payment_date()),
+                YieldTimeBasis::CouponPeriods => {
+                    self.coupon_periods(cf, settlement, next_coupon)? / coupon_frequency
+                }
+                YieldTimeBasis::BusinessDays => {
+                    let business_days = self
+                        .convention
+                        .calendar
+                        .business_day_list(settlement, cf.payment_date() - 1)
+                        .len();
+                    count_to_f64(business_days)? / 252.0
+                }
+            };
+            let mut amount = self.convention.face_value * cf.amount()? / outstanding;
+            if let (Cashflow::FixedRateCoupon(_), Some(decimals)) =
+                (cf, self.convention.coupon_decimals)
+            {
+                amount = round_to_decimals(amount, decimals, f64::round);
+            }
+            let (df, d1, d2) =
+                discount_with_derivatives(yield_rate, time, definition.compounding(), frequency);
+            sensitivities.price += amount * df;
+            sensitivities.first_derivative += amount * d1;
+            sensitivities.second_derivative += amount * d2;
+            sensitivities.time_weighted += amount * df * time;
+        }
+        Ok(sensitivities)
+    }
+}
+
+/// Dirty price per face value with its first and second derivatives to the yield and the
+/// time-weighted present value.
+#[derive(Default)]
+struct PriceSensitivities {
+    price: f64,
+    first_derivative: f64,
+    second_derivative: f64,
+    time_weighted: f64,
+}
+
+/// Discount factor at `yield_rate` over `time` years, with its first and second derivatives to
+/// the yield.
+fn discount_with_derivatives(
+    yield_rate: f64,
+    time: f64,
+    compounding: Compounding,
+    frequency: f64,
+) -> (f64, f64, f64) {
+    let simple = match compounding {
+        Compounding::Simple => true,
+        Compounding::SimpleThenCompounded => time <= 1.0 / frequency,
+        Compounding::CompoundedThenSimple => time > 1.0 / frequency,
+        Compounding::Compounded | Compounding::Continuous => false,
+    };
+    if simple {
+        let df = 1.0 / yield_rate.mul_add(time, 1.0);
+        (df, -time * df * df, 2.0 * time * time * df * df * df)
+    } else if compounding == Compounding::Continuous {
+        let df = (-yield_rate * time).exp();
+        (df, -time * df, time * time * df)
+    } else {
+        let base = yield_rate / frequency + 1.0;
+        let df = base.powf(-frequency * time);
+        (
+            df,
+            -time * df / base,
+            time * (time + 1.0 / frequency) * df / (base * base),
+        )
+    }
+}
+
+/// Converts a number of days or coupon periods to `f64`.
+fn count_to_f64<T>(count: T) -> Result<f64>
+where
+    T: TryInto<u32> + Copy + std::fmt::Display,
+{
+    count.try_into().map(f64::from).map_err(|_| {
+        AtlasError::InvalidValueErr(format!("Count {count} cannot be used as a time measure"))
+    })
+}
+
+/// Rounds `value` to `decimals` with `rule`, once the representation error of the scaling has
+/// been removed.
+fn round_to_decimals(value: f64, decimals: i32, rule: fn(f64) -> f64) -> f64 {
+    let factor = 10f64.powi(decimals);
+    rule((value * factor * 1e9).round() / 1e9) / factor
+}
+
+/// # `YieldToMaturity`
+/// Cost function whose root is the yield reproducing a quoted price.
+struct YieldToMaturity<'a> {
+    bond: &'a Bond,
+    settlement: Date,
+    quoted_price: f64,
+}
+
+impl CostFunction for YieldToMaturity<'_> {
+    type Param = f64;
+    type Output = f64;
+    fn cost(&self, param: &Self::Param) -> std::result::Result<Self::Output, Error> {
+        let price = self
+            .bond
+            .quoted_price(*param, self.settlement)
+            .map_err(Error::from)?;
+        Ok(price - self.quoted_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bond, BondConvention, PriceQuote};
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::{
+            fixedrateinstrument::FixedRateInstrument,
+            makefixedrateinstrument::MakeFixedRateInstrument,
+        },
+        rates::{enums::Compounding, interestrate::InterestRate},
+        time::{date::Date, daycounter::DayCounter, enums::Frequency},
+        utils::errors::Result,
+    };
+
+    fn instrument(
+        start_date: Date,
+        end_date: Date,
+        rate: f64,
+        frequency: Frequency,
+        currency: Currency,
+    ) -> Result<FixedRateInstrument> {
+        MakeFixedRateInstrument::new()
+            .with_start_date(start_date)
+            .with_end_date(end_date)
+            .with_payment_frequency(frequency)
+            .with_rate(InterestRate::new(
+                rate,
+                Compounding::Compounded,
+                Frequency::Annual,
+                DayCounter::Thirty360,
+            ))
+            .with_notional(1_000_000.0)
+            .with_side(Side::Receive)
+            .with_currency(currency)
+            .bullet()
+            .build()
+    }
+
+    #[test]
+    fn test_us_treasury() -> Result<()> {
+        let bond = Bond::new(
+            instrument(
+                Date::new(2024, 2, 15),
+                Date::new(2034, 2, 15),
+                0.04,
+                Frequency::Annual,
+                Currency::USD,
+            )?,
+            BondConvention::us_treasury(),
+        );
+
+        let settlement = bond.settlement_date(Date::new(2025, 2, 13));
+        assert_eq!(settlement, Date::new(2025, 2, 14));
+        let accrued = bond.accrued_interest(settlement)?;
+        let expected = 100.0 * (1.04_f64.powf(359.0 / 360.0) - 1.0);
+        assert!((accrued - expected).abs() < 1e-10);
+
+        let settlement = Date::new(2025, 8, 20);
+        let clean = bond.clean_price(0.045, settlement)?;
+        let ytm = bond.yield_to_maturity(clean, settlement)?;
+        assert!((ytm - 0.045).abs() < 1e-8);
+        assert!(clean < 100.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_yield_bracket() -> Result<()> {
+        let instrument = instrument(
+            Date::new(2024, 2, 15),
+            Date::new(2029, 2, 15),
+            0.04,
+            Frequency::Semiannual,
+            Currency::USD,
+        )?;
+        let settlement = Date::new(2024, 8, 20);
+
+        // a distressed bond yielding 150% is outside the default bracket
+        let bond = Bond::new(instrument.clone(), BondConvention::us_treasury());
+        let clean = bond.clean_price(1.5, settlement)?;
+        assert_eq!(bond.convention().yield_bracket(), (-0.5, 1.0));
+        assert!(bond.yield_to_maturity(clean, settlement).is_err());
+
+        let bond = Bond::new(
+            instrument,
+            BondConvention::us_treasury().with_yield_bracket(0.0, 3.0),
+        );
+        let ytm = bond.yield_to_maturity(clean, settlement)?;
+        assert!((ytm - 1.5).abs() < 1e-8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_and_street_convention() -> Result<()> {
+        let bond = Bond::new(
+            instrument(
+                Date::new(2024, 5, 15),
+                Date::new(2029, 5, 15),
+                0.0404,
+                Frequency::Semiannual,
+                Currency::USD,
+            )?,
+            BondConvention::us_treasury(),
+        );
+
+        // 4.04% annually compounded on 30/360 pays 2.0 per semester
+        let coupon = 2.0;
+        let yield_rate = 0.04;
+        let clean = bond.clean_price(yield_rate, Date::new(2024, 11, 15))?;
+        assert!((clean - 100.0).abs() < 1e-10);
+
+        // between coupon dates the dirty price compounds over the fraction of the period
+        let settlement = Date::new(2025, 1, 15);
+        let dirty = bond.dirty_price(yield_rate, settlement)?;
+        let fraction = 120.0 / 181.0;
+        let base = 1.0 + yield_rate / 2.0;
+        let expected = (1..=9).fold(0.0, |acc, k| {
+            let periods = fraction + f64::from(k - 1);
+            acc + (if k == 9 { 100.0 + coupon } else { coupon }) / base.powf(periods)
+        });
+        assert!((dirty - expected).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_chile_and_brazil() -> Result<()> {
+        let bcp = Bond::new(
+            instrument(
+                Date::new(2023, 3, 1),
+                Date::new(2033, 3, 1),
+                0.06,
+                Frequency::Semiannual,
+                Currency::CLP,
+            )?,
+            BondConvention::chile_bcp(),
+        );
+        assert_eq!(bcp.convention().quote(), PriceQuote::PercentOfPar);
+
+        let settlement = bcp.settlement_date(Date::new(2024, 6, 10));
+        let dirty = bcp.dirty_price(0.055, settlement)?;
+        let accrued = bcp.accrued_interest(settlement)?;
+        let quote = bcp.quoted_price(0.055, settlement)?;
+        assert!((quote - 100.0 * dirty / (100.0 + accrued)).abs() < 1e-10);
+        assert!(quote > 100.0);
+        let ytm = bcp.yield_to_maturity(quote, settlement)?;
+        assert!((ytm - 0.055).abs() < 1e-8);
+
+        let ntnf = Bond::new(
+            instrument(
+                Date::new(2025, 1, 1),
+                Date::new(2035, 1, 1),
+                0.10,
+                Frequency::Semiannual,
+                Currency::BRL,
+            )?,
+            BondConvention::brazil_ntnf(),
+        );
+        let settlement = ntnf.settlement_date(Date::new(2025, 3, 14));
+        assert_eq!(settlement, Date::new(2025, 3, 17));
+        let price = ntnf.quoted_price(0.13, settlement)?;
+        assert!((price - ntnf.dirty_price(0.13, settlement)?).abs() < 1e-6);
+        let ytm = ntnf.yield_to_maturity(price, settlement)?;
+        assert!((ytm - 0.13).abs() < 1e-8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_brazil_ntnf_unit_price() -> Result<()> {
+        let ntnf = Bond::new(
+            instrument(
+                Date::new(2018, 1, 1),
+                Date::new(2023, 1, 1),
+                0.10,
+                Frequency::Semiannual,
+                Currency::BRL,
+            )?,
+            BondConvention::brazil_ntnf(),
+        );
+        let settlement = ntnf.settlement_date(Date::new(2022, 3, 14));
+        assert_eq!(settlement, Date::new(2022, 3, 15));
+
+        // ANBIMA unit price: coupons of 48.80885 discounted over 75 and 202 business days
+        let coupon = 48.80885;
+        let yield_rate = 0.12;
+        let expected =
+            coupon / 1.12_f64.powf(75.0 / 252.0) + (1000.0 + coupon) / 1.12_f64.powf(202.0 / 252.0);
+        let dirty = ntnf.dirty_price(yield_rate, settlement)?;
+        assert!((dirty - expected).abs() < 1e-9);
+
+        let price = ntnf.quoted_price(yield_rate, settlement)?;
+        assert!((price - 1_004.921_606).abs() < 1e-9);
+        let ytm = ntnf.yield_to_maturity(price, settlement)?;
+        assert!((ytm - yield_rate).abs() < 1e-8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ex_coupon() -> Result<()> {
+        let bond = Bond::new(
+            instrument(
+                Date::new(2024, 3, 1),
+                Date::new(2029, 3, 1),
+                0.05,
+                Frequency::Annual,
+                Currency::CLP,
+            )?,
+            BondConvention::chile_bcp().with_ex_coupon_days(5),
+        );
+        let payment = Date::new(2025, 3, 1);
+        let ex_date = bond.ex_coupon_date(payment);
+        assert_eq!(ex_date, Date::new(2025, 2, 24));
+
+        let cum = bond.dirty_price(0.05, ex_date - 1)?;
+        let ex = bond.dirty_price(0.05, ex_date)?;
+        assert!(cum - ex > 4.9);
+        assert!(bond.accrued_interest(ex_date - 1)? > 0.0);
+        assert!(bond.accrued_interest(ex_date)? < 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_risk_measures() -> Result<()> {
+        let bond = Bond::new(
+            instrument(
+                Date::new(2024, 5, 15),
+                Date::new(2034, 5, 15),
+                0.05,
+                Frequency::Semiannual,
+                Currency::USD,
+            )?,
+            BondConvention::us_treasury(),
+        );
+        let settlement = Date::new(2024, 9, 3);
+        let y = 0.045;
+        let h = 1e-5;
+
+        let price = bond.dirty_price(y, settlement)?;
+        let up = bond.dirty_price(y + h, settlement)?;
+        let down = bond.dirty_price(y - h, settlement)?;
+
+        let modified = bond.modified_duration(y, settlement)?;
+        assert!((modified - (down - up) / (2.0 * h * price)).abs() < 1e-6);
+
+        let convexity = bond.convexity(y, settlement)?;
+        let numerical = ((up - price) - (price - down)) / (h * h * price);
+        assert!((convexity - numerical).abs() < 1e-2);
+
+        let macaulay = bond.macaulay_duration(y, settlement)?;
+        assert!((macaulay / (1.0 + y / 2.0) - modified).abs() < 1e-12);
+
+        let bpv = bond.bpv(y, settlement)?;
+        assert!((bpv / 1e-4 - price * modified).abs() < 1e-8);
+        Ok(())
+    }
+}
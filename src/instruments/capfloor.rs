@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        floatingratecoupon::FloatingRateCoupon,
+    },
+    core::traits::HasCurrency,
+    currencies::enums::Currency,
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+use super::{instrument::RateType, leg::Leg};
+
+/// # `CapFloorType`
+/// The options bought with a `CapFloor`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CapFloorType {
+    /// Calls on the rate of each coupon, paying when it is above the cap strike.
+    Cap,
+    /// Puts on the rate of each coupon, paying when it is below the floor strike.
+    Floor,
+    /// A long cap and a short floor.
+    Collar,
+}
+
+/// # `CapFloor`
+/// A strip of options (caplets or floorlets) on the rates of the floating coupons of a leg.
+/// The instrument keeps the coupons, with its strikes as their cap and floor, so they can be
+/// indexed and fixed like any other floating coupon. Only the options are paid: a caplet pays
+/// `notional * max(rate - strike, 0) * accrual` on the coupon payment date, where `rate` is the
+/// fixing plus the spread of the coupon.
+///
+/// The side of the leg is the side of the options: `Receive` buys them and `Pay` sells them.
+/// Its value is given by `CapFloorNPVConstVisitor`.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let leg = MakeFloatingRateLeg::new()
+///     .with_start_date(Date::new(2024, 1, 2))
+///     .with_end_date(Date::new(2027, 1, 2))
+///     .with_payment_frequency(Frequency::Quarterly)
+///     .with_rate_definition(RateDefinition::default())
+///     .with_spread(0.0)
+///     .with_notional(1_000_000.0)
+///     .with_currency(Currency::USD)
+///     .with_side(Side::Receive)
+///     .bullet()
+///     .build()
+///     .unwrap();
+///
+/// let cap = CapFloor::cap(&leg, 0.05).unwrap();
+/// assert_eq!(cap.cashflows().len(), 12);
+/// assert_eq!(cap.cap_strike(), Some(0.05));
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapFloor {
+    kind: CapFloorType,
+    cap_strike: Option<f64>,
+    floor_strike: Option<f64>,
+    currency: Currency,
+    side: Side,
+    cashflows: Vec<Cashflow>,
+    discount_curve_id: Option<usize>,
+    forecast_curve_id: Option<usize>,
+    id: Option<String>,
+}
+
+impl CapFloor {
+    /// Creates a cap on the floating coupons of the leg.
+    ///
+    /// # Errors
+    /// Returns an error if the leg has no floating rate coupons.
+    pub fn cap(leg: &Leg, strike: f64) -> Result<Self> {
+        Self::from_leg(CapFloorType::Cap, leg, Some(strike), None)
+    }
+
+    /// Creates a floor on the floating coupons of the leg.
+    ///
+    /// # Errors
+    /// Returns an error if the leg has no floating rate coupons.
+    pub fn floor(leg: &Leg, strike: f64) -> Result<Self> {
+        Self::from_leg(CapFloorType::Floor, leg, None, Some(strike))
+    }
+
+    /// Creates a collar (long cap, short floor) on the floating coupons of the leg.
+    ///
+    /// # Errors
+    /// Returns an error if the leg has no floating rate coupons or the floor strike is above
+    /// the cap strike.
+    pub fn collar(leg: &Leg, cap_strike: f64, floor_strike: f64) -> Result<Self> {
+        if floor_strike > cap_strike {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Collar floor strike ({floor_strike}) is above its cap strike ({cap_strike})"
+            )));
+        }
+        Self::from_leg(
+            CapFloorType::Collar,
+            leg,
+            Some(cap_strike),
+            Some(floor_strike),
+        )
+    }
+
+    fn from_leg(
+        cap_floor_type: CapFloorType,
+        leg: &Leg,
+        cap_strike: Option<f64>,
+        floor_strike: Option<f64>,
+    ) -> Result<Self> {
+        if leg.rate_type() != RateType::Floating {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Caps and floors need a floating rate leg, got a {:?} leg",
+                leg.rate_type()
+            )));
+        }
+        let cashflows: Vec<Cashflow> = leg
+            .cashflows()
+            .iter()
+            .filter_map(|cf| match cf {
+                Cashflow::FloatingRateCoupon(coupon) => {
+                    let mut optionlet: FloatingRateCoupon = *coupon;
+                    optionlet.set_cap(cap_strike);
+                    optionlet.set_floor(floor_strike);
+                    Some(Cashflow::FloatingRateCoupon(optionlet))
+                }
+                _ => None,
+            })
+            .collect();
+        if cashflows.is_empty() {
+            return Err(AtlasError::InvalidValueErr(
+                "Leg has no floating rate coupons".to_string(),
+            ));
+        }
+        Ok(Self {
+            kind: cap_floor_type,
+            cap_strike,
+            floor_strike,
+            currency: leg.currency(),
+            side: leg.side(),
+            cashflows,
+            discount_curve_id: leg.discount_curve_id(),
+            forecast_curve_id: leg.forecast_curve_id(),
+            id: None,
+        })
+    }
+
+    /// Sets the ID of the instrument.
+    #[must_use]
+    pub fn with_id(mut self, id: Option<String>) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Returns the type of the options.
+    #[must_use]
+    pub const fn cap_floor_type(&self) -> CapFloorType {
+        self.kind
+    }
+
+    /// Returns the cap strike, if the instrument is a cap or a collar.
+    #[must_use]
+    pub const fn cap_strike(&self) -> Option<f64> {
+        self.cap_strike
+    }
+
+    /// Returns the floor strike, if the instrument is a floor or a collar.
+    #[must_use]
+    pub const fn floor_strike(&self) -> Option<f64> {
+        self.floor_strike
+    }
+
+    /// Returns the side.
+    #[must_use]
+    pub const fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Returns the discount curve ID.
+    #[must_use]
+    pub const fn discount_curve_id(&self) -> Option<usize> {
+        self.discount_curve_id
+    }
+
+    /// Returns the forecast curve ID.
+    #[must_use]
+    pub const fn forecast_curve_id(&self) -> Option<usize> {
+        self.forecast_curve_id
+    }
+
+    /// Returns the ID.
+    #[must_use]
+    pub fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+impl HasCurrency for CapFloor {
+    fn currency(&self) -> Result<Currency> {
+        Ok(self.currency)
+    }
+}
+
+impl HasCashflows for CapFloor {
+    fn cashflows(&self) -> &[Cashflow] {
+        &self.cashflows
+    }
+
+    fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+        &mut self.cashflows
+    }
+}
@@ -182,10 +182,10 @@ impl FloatingRateInstrument {
     #[must_use]
     pub fn set_spread(mut self, spread: f64) -> Self {
         self.spread = spread;
-        self.mut_cashflows().iter_mut().for_each(|cf| {
-            if let Cashflow::FloatingRateCoupon(coupon) = cf {
-                coupon.set_spread(spread);
-            }
+        self.mut_cashflows().iter_mut().for_each(|cf| match cf {
+            Cashflow::FloatingRateCoupon(coupon) => coupon.set_spread(spread),
+            Cashflow::OvernightIndexedCoupon(coupon) => coupon.set_spread(spread),
+            _ => (),
         });
         self
     }
@@ -0,0 +1,450 @@
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        simplecashflow::SimpleCashflow,
+    },
+    core::marketstore::MarketStore,
+    currencies::enums::Currency,
+    rates::traits::HasReferenceDate,
+    time::{
+        calendar::Calendar,
+        calendars::{
+            brazil::{self, Brazil},
+            chile::{self, Chile},
+            target::TARGET,
+            traits::IsCalendar,
+            unitedstates::{self, UnitedStates},
+            weekendsonly::WeekendsOnly,
+        },
+        date::Date,
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+/// # `CurrencyPair`
+/// A currency pair quoted as units of the quote currency per unit of the base currency, e.g.
+/// USD/CLP at 900 means 900 CLP per USD. Settlement dates are business days in every calendar
+/// of the pair (joint calendar).
+///
+/// ## Parameters
+/// * `base` - The base currency.
+/// * `quote` - The quote currency.
+/// * `spot_days` - Business days between trade date and spot date.
+/// * `calendars` - Settlement calendars of the pair.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let pair = CurrencyPair::new(Currency::USD, Currency::CLP);
+/// assert_eq!(pair.spot_days(), 2);
+/// // Chilean national holidays on 18-20 September 2024
+/// assert_eq!(pair.spot_date(Date::new(2024, 9, 16)), Date::new(2024, 9, 23));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CurrencyPair {
+    base: Currency,
+    quote: Currency,
+    spot_days: i64,
+    calendars: Vec<Calendar>,
+}
+
+impl CurrencyPair {
+    /// Creates a new `CurrencyPair` with the market spot lag of the pair and the settlement
+    /// calendars of both currencies.
+    #[must_use]
+    pub fn new(base: Currency, quote: Currency) -> Self {
+        let spot_days = match (base, quote) {
+            (Currency::USD, Currency::CAD) | (Currency::CAD, Currency::USD) => 1,
+            _ => 2,
+        };
+        Self {
+            base,
+            quote,
+            spot_days,
+            calendars: vec![settlement_calendar(base), settlement_calendar(quote)],
+        }
+    }
+
+    /// Sets the business days between trade date and spot date.
+    #[must_use]
+    pub const fn with_spot_days(mut self, spot_days: i64) -> Self {
+        self.spot_days = spot_days;
+        self
+    }
+
+    /// Sets the settlement calendars of the pair.
+    #[must_use]
+    pub fn with_calendars(mut self, calendars: Vec<Calendar>) -> Self {
+        self.calendars = calendars;
+        self
+    }
+
+    /// Returns the base currency.
+    #[must_use]
+    pub const fn base(&self) -> Currency {
+        self.base
+    }
+
+    /// Returns the quote currency.
+    #[must_use]
+    pub const fn quote(&self) -> Currency {
+        self.quote
+    }
+
+    /// Returns the spot lag in business days.
+    #[must_use]
+    pub const fn spot_days(&self) -> i64 {
+        self.spot_days
+    }
+
+    /// Returns the settlement calendars.
+    #[must_use]
+    pub fn calendars(&self) -> &[Calendar] {
+        &self.calendars
+    }
+
+    /// Checks if a date is a business day in every calendar of the pair.
+    #[must_use]
+    pub fn is_business_day(&self, date: Date) -> bool {
+        self.calendars.iter().all(|cal| cal.is_business_day(&date))
+    }
+
+    /// Adjusts a date to a business day of the joint calendar, using the following or the
+    /// modified following convention.
+    #[must_use]
+    pub fn adjust(&self, date: Date, modified: bool) -> Date {
+        let mut adjusted = date;
+        while !self.is_business_day(adjusted) {
+            adjusted += 1;
+        }
+        if modified && adjusted.month() != date.month() {
+            adjusted = date;
+            while !self.is_business_day(adjusted) {
+                adjusted -= 1;
+            }
+        }
+        adjusted
+    }
+
+    /// Returns the spot date for a trade done on `trade_date`.
+    #[must_use]
+    pub fn spot_date(&self, trade_date: Date) -> Date {
+        let mut date = self.adjust(trade_date, false);
+        for _ in 0..self.spot_days {
+            date = self.adjust(date + 1, false);
+        }
+        date
+    }
+
+    /// Returns the value date of an outright with the given tenor, counted from spot and
+    /// adjusted with the modified following convention.
+    #[must_use]
+    pub fn value_date(&self, trade_date: Date, tenor: Period) -> Date {
+        self.adjust(self.spot_date(trade_date) + tenor, true)
+    }
+
+    /// Returns the spot rate of the market, in units of quote currency per base currency.
+    ///
+    /// # Errors
+    /// Returns an error if the exchange rate of the pair is not available.
+    pub fn spot_rate(&self, market_store: &MarketStore) -> Result<f64> {
+        market_store.get_exchange_rate(self.quote, Some(self.base))
+    }
+
+    /// Returns the fair outright forward rate for delivery on `date`, from the forward curve of
+    /// the pair in the exchange rate store or, if missing, from covered interest parity between
+    /// the currency curves of the index store.
+    ///
+    /// # Errors
+    /// Returns an error if the exchange rate or the currency curves are not available.
+    pub fn fair_forward_rate(&self, market_store: &MarketStore, date: Date) -> Result<f64> {
+        if date <= market_store.reference_date() {
+            return self.spot_rate(market_store);
+        }
+        market_store.get_forward_exchange_rate(self.quote, Some(self.base), date)
+    }
+
+    /// Returns the fair forward points for delivery on `date`: the fair outright rate less the
+    /// fair rate for the spot date of the market.
+    ///
+    /// # Errors
+    /// Returns an error if the exchange rate or the currency curves are not available.
+    pub fn fair_forward_points(&self, market_store: &MarketStore, date: Date) -> Result<f64> {
+        let spot_date = self.spot_date(market_store.reference_date());
+        Ok(self.fair_forward_rate(market_store, date)?
+            - self.fair_forward_rate(market_store, spot_date)?)
+    }
+}
+
+/// Settlement calendar of a currency, defaulting to weekends only.
+fn settlement_calendar(currency: Currency) -> Calendar {
+    match currency {
+        Currency::USD => {
+            Calendar::UnitedStates(UnitedStates::new(unitedstates::Market::Settlement))
+        }
+        Currency::EUR => Calendar::TARGET(TARGET::new()),
+        Currency::BRL => Calendar::Brazil(Brazil::new(brazil::Market::Settlement)),
+        Currency::CLP | Currency::CLF => Calendar::Chile(Chile::new(chile::Market::SSE)),
+        _ => Calendar::WeekendsOnly(WeekendsOnly::new()),
+    }
+}
+
+/// # `FxForward`
+/// An outright FX forward: the exchange of a base currency notional against the quote currency
+/// notional at the agreed forward rate on the delivery date. The base currency amount is a
+/// `Cashflow::Redemption` and the quote currency amount a `Cashflow::Disbursement`.
+///
+/// ## Parameters
+/// * `pair` - The currency pair.
+/// * `base_notional` - The base currency amount.
+/// * `forward_rate` - The agreed rate, in units of quote currency per base currency.
+/// * `delivery_date` - The delivery date, adjusted to the joint calendar of the pair.
+/// * `side` - `Receive` buys the base currency, `Pay` sells it.
+#[derive(Debug, Clone)]
+pub struct FxForward {
+    pair: CurrencyPair,
+    base_notional: f64,
+    forward_rate: f64,
+    delivery_date: Date,
+    side: Side,
+    cashflows: Vec<Cashflow>,
+    id: Option<String>,
+}
+
+impl FxForward {
+    /// Creates a new `FxForward`.
+    ///
+    /// # Errors
+    /// Returns an error if the notional or the forward rate are not positive.
+    pub fn new(
+        pair: CurrencyPair,
+        base_notional: f64,
+        forward_rate: f64,
+        delivery_date: Date,
+        side: Side,
+    ) -> Result<Self> {
+        if base_notional <= 0.0 || forward_rate <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(
+                "FX forward notional and rate must be positive".to_string(),
+            ));
+        }
+        let delivery_date = pair.adjust(delivery_date, false);
+        let cashflows = vec![
+            Cashflow::Redemption(
+                SimpleCashflow::new(delivery_date, pair.base(), side).with_amount(base_notional),
+            ),
+            Cashflow::Disbursement(
+                SimpleCashflow::new(delivery_date, pair.quote(), side.inverse())
+                    .with_amount(base_notional * forward_rate),
+            ),
+        ];
+        Ok(Self {
+            pair,
+            base_notional,
+            forward_rate,
+            delivery_date,
+            side,
+            cashflows,
+            id: None,
+        })
+    }
+
+    /// Sets the discount curve ID of the base currency cashflow.
+    #[must_use]
+    pub fn with_base_discount_curve_id(mut self, id: usize) -> Self {
+        self.set_discount_curve_id(self.pair.base(), id);
+        self
+    }
+
+    /// Sets the discount curve ID of the quote currency cashflow.
+    #[must_use]
+    pub fn with_quote_discount_curve_id(mut self, id: usize) -> Self {
+        self.set_discount_curve_id(self.pair.quote(), id);
+        self
+    }
+
+    /// Sets the identifier.
+    #[must_use]
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Returns the currency pair.
+    #[must_use]
+    pub const fn pair(&self) -> &CurrencyPair {
+        &self.pair
+    }
+
+    /// Returns the base currency notional.
+    #[must_use]
+    pub const fn base_notional(&self) -> f64 {
+        self.base_notional
+    }
+
+    /// Returns the quote currency notional.
+    #[must_use]
+    pub fn quote_notional(&self) -> f64 {
+        self.base_notional * self.forward_rate
+    }
+
+    /// Returns the agreed forward rate.
+    #[must_use]
+    pub const fn forward_rate(&self) -> f64 {
+        self.forward_rate
+    }
+
+    /// Returns the delivery date.
+    #[must_use]
+    pub const fn delivery_date(&self) -> Date {
+        self.delivery_date
+    }
+
+    /// Returns the side of the base currency cashflow.
+    #[must_use]
+    pub const fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Returns the identifier.
+    #[must_use]
+    pub const fn id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    fn set_discount_curve_id(&mut self, currency: Currency, id: usize) {
+        let is_base = currency == self.pair.base();
+        for cf in &mut self.cashflows {
+            if matches!(cf, Cashflow::Redemption(_)) == is_base {
+                cf.set_discount_curve_id(id);
+            }
+        }
+    }
+}
+
+impl HasCashflows for FxForward {
+    fn cashflows(&self) -> &[Cashflow] {
+        &self.cashflows
+    }
+
+    fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+        &mut self.cashflows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurrencyPair, FxForward};
+    use crate::{
+        alm::cashaccount::CashAccount,
+        cashflows::cashflow::Side,
+        core::marketstore::MarketStore,
+        currencies::enums::Currency,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{enums::Compounding, interestrate::RateDefinition},
+        time::{
+            date::Date,
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        utils::{errors::Result, testing::add_flat_index},
+        visitors::{
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    fn market_store() -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 3, 1);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        for (id, rate, currency) in [(0, 0.05, Currency::USD), (1, 0.06, Currency::CLP)] {
+            add_flat_index(
+                &mut market_store,
+                id,
+                rate,
+                RateDefinition::new(
+                    DayCounter::Actual365,
+                    Compounding::Continuous,
+                    Frequency::Annual,
+                ),
+            )?;
+            market_store
+                .mut_index_store()
+                .add_currency_curve(currency, id);
+        }
+        market_store.mut_exchange_rate_store().add_exchange_rate(
+            Currency::CLP,
+            Currency::USD,
+            900.0,
+        );
+        Ok(market_store)
+    }
+
+    #[test]
+    fn test_settlement_dates() {
+        let usdcad = CurrencyPair::new(Currency::USD, Currency::CAD);
+        assert_eq!(
+            usdcad.spot_date(Date::new(2024, 3, 1)),
+            Date::new(2024, 3, 4)
+        );
+
+        // 4 July is a US holiday
+        let eurusd = CurrencyPair::new(Currency::EUR, Currency::USD);
+        assert_eq!(
+            eurusd.spot_date(Date::new(2024, 7, 2)),
+            Date::new(2024, 7, 5)
+        );
+
+        let usdclp = CurrencyPair::new(Currency::USD, Currency::CLP);
+        let value_date =
+            usdclp.value_date(Date::new(2024, 8, 28), Period::new(1, TimeUnit::Months));
+        assert_eq!(value_date, Date::new(2024, 9, 30));
+    }
+
+    #[test]
+    fn test_fair_forward() -> Result<()> {
+        let market_store = market_store()?;
+        let pair = CurrencyPair::new(Currency::USD, Currency::CLP);
+        let delivery_date =
+            pair.value_date(Date::new(2024, 3, 1), Period::new(3, TimeUnit::Months));
+        let fair = pair.fair_forward_rate(&market_store, delivery_date)?;
+        let t = DayCounter::Actual365.year_fraction(Date::new(2024, 3, 1), delivery_date);
+        assert!((fair / 900.0 - (0.01 * t).exp()).abs() < 1e-12);
+
+        let spot_date = pair.spot_date(Date::new(2024, 3, 1));
+        let points = pair.fair_forward_points(&market_store, delivery_date)?;
+        assert!(
+            (points - (fair - pair.fair_forward_rate(&market_store, spot_date)?)).abs() < 1e-12
+        );
+        assert!(points > 0.0);
+
+        let mut forward = FxForward::new(pair, 1_000_000.0, fair, delivery_date, Side::Receive)?
+            .with_base_discount_curve_id(0)
+            .with_quote_discount_curve_id(1);
+        let indexing_visitor = IndexingVisitor::new();
+        indexing_visitor.visit(&mut forward)?;
+        let data = SimpleModel::new(&market_store)
+            .with_transform_currencies(true)
+            .gen_market_data(&indexing_visitor.request())?;
+        let npv = NPVConstVisitor::new(&data, true).visit(&forward)?;
+        assert!(npv.abs() < 1e-6);
+
+        // the legs are in different currencies
+        let visitor = NPVConstVisitor::new(&data, true);
+        assert!(visitor.visit_money(&forward, None).is_err());
+        assert!(visitor.visit_money(&forward, Some(Currency::USD)).is_err());
+
+        let usd = CashAccount::new().with_currency(Currency::USD);
+        let clp = CashAccount::new().with_currency(Currency::CLP);
+        usd.add_flows_from_instrument(&forward)?;
+        clp.add_flows_from_instrument(&forward)?;
+        assert!((usd.amount.borrow()[&delivery_date] - 1_000_000.0).abs() < 1e-9);
+        assert!((clp.amount.borrow()[&delivery_date] + forward.quote_notional()).abs() < 1e-6);
+        Ok(())
+    }
+}
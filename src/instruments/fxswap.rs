@@ -0,0 +1,277 @@
+use crate::{
+    cashflows::cashflow::{Cashflow, Side},
+    core::marketstore::MarketStore,
+    time::date::Date,
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+use super::fxforward::{CurrencyPair, FxForward};
+
+/// # `FxSwap`
+/// An FX swap: a near leg exchange of the base currency notional at the near rate and the
+/// reverse exchange on the far date at the near rate plus the swap points. The side refers to
+/// the base currency on the near leg.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let pair = CurrencyPair::new(Currency::USD, Currency::CLP);
+/// let swap = FxSwap::new(
+///     pair,
+///     1_000_000.0,
+///     900.0,
+///     905.0,
+///     Date::new(2024, 3, 5),
+///     Date::new(2024, 6, 5),
+///     Side::Receive,
+/// )
+/// .unwrap();
+/// assert!((swap.forward_points() - 5.0).abs() < 1e-12);
+/// assert_eq!(swap.far().side(), Side::Pay);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FxSwap {
+    near: FxForward,
+    far: FxForward,
+    cashflows: Vec<Cashflow>,
+    id: Option<String>,
+}
+
+impl FxSwap {
+    /// Creates a new `FxSwap`.
+    ///
+    /// # Errors
+    /// Returns an error if the far delivery date is not after the near delivery date, or if the notional or the
+    /// rates are not positive.
+    pub fn new(
+        pair: CurrencyPair,
+        base_notional: f64,
+        near_rate: f64,
+        far_rate: f64,
+        near_delivery_date: Date,
+        far_delivery_date: Date,
+        side: Side,
+    ) -> Result<Self> {
+        if far_delivery_date <= near_delivery_date {
+            return Err(AtlasError::InvalidValueErr(
+                "FX swap far date must be after the near date".to_string(),
+            ));
+        }
+        let near = FxForward::new(
+            pair.clone(),
+            base_notional,
+            near_rate,
+            near_delivery_date,
+            side,
+        )?;
+        let far = FxForward::new(
+            pair,
+            base_notional,
+            far_rate,
+            far_delivery_date,
+            side.inverse(),
+        )?;
+        let cashflows = near
+            .cashflows()
+            .iter()
+            .chain(far.cashflows().iter())
+            .copied()
+            .collect();
+        Ok(Self {
+            near,
+            far,
+            cashflows,
+            id: None,
+        })
+    }
+
+    /// Sets the discount curve ID of the base currency cashflows.
+    #[must_use]
+    pub fn with_base_discount_curve_id(self, id: usize) -> Self {
+        let near = self.near.with_base_discount_curve_id(id);
+        let far = self.far.with_base_discount_curve_id(id);
+        Self::from_legs(near, far, self.id)
+    }
+
+    /// Sets the discount curve ID of the quote currency cashflows.
+    #[must_use]
+    pub fn with_quote_discount_curve_id(self, id: usize) -> Self {
+        let near = self.near.with_quote_discount_curve_id(id);
+        let far = self.far.with_quote_discount_curve_id(id);
+        Self::from_legs(near, far, self.id)
+    }
+
+    /// Sets the identifier.
+    #[must_use]
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Returns the near leg.
+    #[must_use]
+    pub const fn near(&self) -> &FxForward {
+        &self.near
+    }
+
+    /// Returns the far leg.
+    #[must_use]
+    pub const fn far(&self) -> &FxForward {
+        &self.far
+    }
+
+    /// Returns the swap points, far rate less near rate.
+    #[must_use]
+    pub fn forward_points(&self) -> f64 {
+        self.far.forward_rate() - self.near.forward_rate()
+    }
+
+    /// Returns the identifier.
+    #[must_use]
+    pub const fn id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    fn from_legs(near: FxForward, far: FxForward, id: Option<String>) -> Self {
+        let cashflows = near
+            .cashflows()
+            .iter()
+            .chain(far.cashflows().iter())
+            .copied()
+            .collect();
+        Self {
+            near,
+            far,
+            cashflows,
+            id,
+        }
+    }
+}
+
+impl HasCashflows for FxSwap {
+    fn cashflows(&self) -> &[Cashflow] {
+        &self.cashflows
+    }
+
+    fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+        &mut self.cashflows
+    }
+}
+
+/// Returns the fair swap points between `near_date` and `far_date`, implied by the currency
+/// curves of the index store.
+///
+/// # Errors
+/// Returns an error if the exchange rate or the currency curves are not available.
+pub fn fair_swap_points(
+    pair: &CurrencyPair,
+    market_store: &MarketStore,
+    near_date: Date,
+    far_date: Date,
+) -> Result<f64> {
+    Ok(pair.fair_forward_rate(market_store, far_date)?
+        - pair.fair_forward_rate(market_store, near_date)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fair_swap_points, FxSwap};
+    use crate::{
+        cashflows::{cashflow::Side, traits::Payable},
+        core::{marketstore::MarketStore, traits::HasCurrency},
+        currencies::enums::Currency,
+        instruments::fxforward::CurrencyPair,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{enums::Compounding, interestrate::RateDefinition},
+        time::{
+            date::Date,
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        utils::{errors::Result, testing::add_flat_index},
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, HasCashflows, Visit},
+        },
+    };
+
+    fn market_store() -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 3, 1);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        for (id, rate, currency) in [(0, 0.05, Currency::USD), (1, 0.03, Currency::EUR)] {
+            add_flat_index(
+                &mut market_store,
+                id,
+                rate,
+                RateDefinition::new(
+                    DayCounter::Actual360,
+                    Compounding::Simple,
+                    Frequency::Annual,
+                ),
+            )?;
+            market_store
+                .mut_index_store()
+                .add_currency_curve(currency, id);
+        }
+        market_store.mut_exchange_rate_store().add_exchange_rate(
+            Currency::USD,
+            Currency::EUR,
+            1.08,
+        );
+        Ok(market_store)
+    }
+
+    #[test]
+    fn test_fair_fx_swap() -> Result<()> {
+        let market_store = market_store()?;
+        let pair = CurrencyPair::new(Currency::EUR, Currency::USD);
+        let near_date = pair.spot_date(Date::new(2024, 3, 1));
+        let far_date = pair.value_date(Date::new(2024, 3, 1), Period::new(6, TimeUnit::Months));
+        let spot_rate = pair.fair_forward_rate(&market_store, near_date)?;
+        let points = fair_swap_points(&pair, &market_store, near_date, far_date)?;
+        assert!(points > 0.0);
+
+        let mut swap = FxSwap::new(
+            pair,
+            5_000_000.0,
+            spot_rate,
+            spot_rate + points,
+            near_date,
+            far_date,
+            Side::Pay,
+        )?
+        .with_base_discount_curve_id(1)
+        .with_quote_discount_curve_id(0);
+        assert!((swap.forward_points() - points).abs() < 1e-12);
+        assert_eq!(swap.cashflows().len(), 4);
+        for cf in swap.cashflows() {
+            let expected = if cf.payment_date() == near_date {
+                if cf.currency()? == Currency::EUR {
+                    Side::Pay
+                } else {
+                    Side::Receive
+                }
+            } else if cf.currency()? == Currency::EUR {
+                Side::Receive
+            } else {
+                Side::Pay
+            };
+            assert_eq!(cf.side(), expected);
+        }
+
+        let indexing_visitor = IndexingVisitor::new();
+        indexing_visitor.visit(&mut swap)?;
+        let data = SimpleModel::new(&market_store)
+            .with_transform_currencies(true)
+            .gen_market_data(&indexing_visitor.request())?;
+        FixingVisitor::new(&data).visit(&mut swap)?;
+        let npv = NPVConstVisitor::new(&data, true).visit(&swap)?;
+        assert!(npv.abs() < 1e-6);
+        Ok(())
+    }
+}
@@ -0,0 +1,691 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        traits::Payable,
+    },
+    core::marketstore::MarketStore,
+    currencies::enums::Currency,
+    rates::{interestrate::RateDefinition, traits::HasReferenceDate},
+    time::{
+        calendar::Calendar,
+        calendars::nullcalendar::NullCalendar,
+        date::Date,
+        enums::{BusinessDayConvention, DateGenerationRule, Frequency},
+        schedule::MakeSchedule,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{instrument::RateType, makeswap::MakeSwap, swap::Swap, traits::Structure};
+
+/// # `MakeCrossCurrencySwap`
+/// Builder for a cross-currency `Swap` whose legs pay in different currencies. Both legs share
+/// the same schedule. The first leg keeps a constant notional, while the second leg can reset
+/// its notional at the start of every period to the first leg notional converted at the FX
+/// forward of the reset date (mark-to-market resets). Each reset is settled with a notional
+/// exchange on the second leg.
+///
+/// ## Parameters
+/// * `start_date` - The start date.
+/// * `end_date` - The end date.
+/// * `payment_frequency` - The payment frequency of both legs.
+/// * `first_leg_*` - Rate type, rate value (fixed rate or spread), rate definition, currency,
+///   notional, side, curves and basis spread of the first leg.
+/// * `second_leg_*` - Rate type, rate value, rate definition, currency, notional, curves and
+///   basis spread of the second leg. Its side is the inverse of the first leg.
+/// * `initial_exchange` - Whether the notionals are exchanged at the start date. Defaults to true.
+/// * `final_exchange` - Whether the notionals are exchanged at the end date. Defaults to true.
+/// * `mtm_market_store` - Market used to project the notional resets of the second leg.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let swap = MakeCrossCurrencySwap::new()
+///     .with_start_date(Date::new(2024, 1, 1))
+///     .with_end_date(Date::new(2027, 1, 1))
+///     .with_payment_frequency(Frequency::Quarterly)
+///     .with_first_leg_rate_type(RateType::Floating)
+///     .with_first_leg_rate_definition(RateDefinition::default())
+///     .with_first_leg_currency(Currency::EUR)
+///     .with_first_leg_notional(1_000_000.0)
+///     .with_first_leg_side(Side::Receive)
+///     .with_first_leg_basis_spread(-0.001)
+///     .with_second_leg_rate_type(RateType::Floating)
+///     .with_second_leg_rate_definition(RateDefinition::default())
+///     .with_second_leg_currency(Currency::USD)
+///     .with_second_leg_notional(1_100_000.0)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(swap.legs()[0].currency(), Currency::EUR);
+/// assert_eq!(swap.legs()[1].side(), Side::Pay);
+/// ```
+pub struct MakeCrossCurrencySwap<'a> {
+    start_date: Option<Date>,
+    end_date: Option<Date>,
+    payment_frequency: Option<Frequency>,
+    calendar: Option<Calendar>,
+    business_day_convention: Option<BusinessDayConvention>,
+    date_generation_rule: Option<DateGenerationRule>,
+
+    first_leg_rate_type: Option<RateType>,
+    first_leg_rate_value: Option<f64>,
+    first_leg_rate_definition: Option<RateDefinition>,
+    first_leg_currency: Option<Currency>,
+    first_leg_notional: Option<f64>,
+    first_leg_side: Option<Side>,
+    first_leg_discount_curve_id: Option<usize>,
+    first_leg_forecast_curve_id: Option<usize>,
+    first_leg_basis_spread: f64,
+
+    second_leg_rate_type: Option<RateType>,
+    second_leg_rate_value: Option<f64>,
+    second_leg_rate_definition: Option<RateDefinition>,
+    second_leg_currency: Option<Currency>,
+    second_leg_notional: Option<f64>,
+    second_leg_discount_curve_id: Option<usize>,
+    second_leg_forecast_curve_id: Option<usize>,
+    second_leg_basis_spread: f64,
+
+    initial_exchange: bool,
+    final_exchange: bool,
+    mtm_market_store: Option<&'a MarketStore>,
+    id: Option<String>,
+}
+
+impl<'a> MakeCrossCurrencySwap<'a> {
+    /// Creates a new `MakeCrossCurrencySwap` builder with default values.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start_date: None,
+            end_date: None,
+            payment_frequency: None,
+            calendar: None,
+            business_day_convention: None,
+            date_generation_rule: None,
+
+            first_leg_rate_type: None,
+            first_leg_rate_value: None,
+            first_leg_rate_definition: None,
+            first_leg_currency: None,
+            first_leg_notional: None,
+            first_leg_side: None,
+            first_leg_discount_curve_id: None,
+            first_leg_forecast_curve_id: None,
+            first_leg_basis_spread: 0.0,
+
+            second_leg_rate_type: None,
+            second_leg_rate_value: None,
+            second_leg_rate_definition: None,
+            second_leg_currency: None,
+            second_leg_notional: None,
+            second_leg_discount_curve_id: None,
+            second_leg_forecast_curve_id: None,
+            second_leg_basis_spread: 0.0,
+
+            initial_exchange: true,
+            final_exchange: true,
+            mtm_market_store: None,
+            id: None,
+        }
+    }
+
+    /// Sets the start date.
+    #[must_use]
+    pub const fn with_start_date(mut self, start_date: Date) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets the end date.
+    #[must_use]
+    pub const fn with_end_date(mut self, end_date: Date) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Sets the payment frequency of both legs.
+    #[must_use]
+    pub const fn with_payment_frequency(mut self, frequency: Frequency) -> Self {
+        self.payment_frequency = Some(frequency);
+        self
+    }
+
+    /// Sets the calendar.
+    #[must_use]
+    pub fn with_calendar(mut self, calendar: Option<Calendar>) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Sets the business day convention.
+    #[must_use]
+    pub const fn with_business_day_convention(
+        mut self,
+        convention: Option<BusinessDayConvention>,
+    ) -> Self {
+        self.business_day_convention = convention;
+        self
+    }
+
+    /// Sets the date generation rule.
+    #[must_use]
+    pub const fn with_date_generation_rule(
+        mut self,
+        date_generation_rule: Option<DateGenerationRule>,
+    ) -> Self {
+        self.date_generation_rule = date_generation_rule;
+        self
+    }
+
+    /// Sets the rate type for the first leg.
+    #[must_use]
+    pub const fn with_first_leg_rate_type(mut self, rate_type: RateType) -> Self {
+        self.first_leg_rate_type = Some(rate_type);
+        self
+    }
+
+    /// Sets the rate value for the first leg: the fixed rate, or the spread of a floating leg.
+    #[must_use]
+    pub const fn with_first_leg_rate_value(mut self, rate_value: f64) -> Self {
+        self.first_leg_rate_value = Some(rate_value);
+        self
+    }
+
+    /// Sets the rate definition for the first leg.
+    #[must_use]
+    pub const fn with_first_leg_rate_definition(mut self, rate_definition: RateDefinition) -> Self {
+        self.first_leg_rate_definition = Some(rate_definition);
+        self
+    }
+
+    /// Sets the currency for the first leg.
+    #[must_use]
+    pub const fn with_first_leg_currency(mut self, currency: Currency) -> Self {
+        self.first_leg_currency = Some(currency);
+        self
+    }
+
+    /// Sets the constant notional of the first leg.
+    #[must_use]
+    pub const fn with_first_leg_notional(mut self, notional: f64) -> Self {
+        self.first_leg_notional = Some(notional);
+        self
+    }
+
+    /// Sets the side for the first leg. The second leg takes the inverse side.
+    #[must_use]
+    pub const fn with_first_leg_side(mut self, side: Side) -> Self {
+        self.first_leg_side = Some(side);
+        self
+    }
+
+    /// Sets the discount curve ID for the first leg.
+    #[must_use]
+    pub const fn with_first_leg_discount_curve_id(mut self, curve_id: Option<usize>) -> Self {
+        self.first_leg_discount_curve_id = curve_id;
+        self
+    }
+
+    /// Sets the forecast curve ID for the first leg.
+    #[must_use]
+    pub const fn with_first_leg_forecast_curve_id(mut self, curve_id: Option<usize>) -> Self {
+        self.first_leg_forecast_curve_id = curve_id;
+        self
+    }
+
+    /// Sets the cross-currency basis spread added to the rate of the first leg.
+    #[must_use]
+    pub const fn with_first_leg_basis_spread(mut self, basis_spread: f64) -> Self {
+        self.first_leg_basis_spread = basis_spread;
+        self
+    }
+
+    /// Sets the rate type for the second leg.
+    #[must_use]
+    pub const fn with_second_leg_rate_type(mut self, rate_type: RateType) -> Self {
+        self.second_leg_rate_type = Some(rate_type);
+        self
+    }
+
+    /// Sets the rate value for the second leg: the fixed rate, or the spread of a floating leg.
+    #[must_use]
+    pub const fn with_second_leg_rate_value(mut self, rate_value: f64) -> Self {
+        self.second_leg_rate_value = Some(rate_value);
+        self
+    }
+
+    /// Sets the rate definition for the second leg.
+    #[must_use]
+    pub const fn with_second_leg_rate_definition(
+        mut self,
+        rate_definition: RateDefinition,
+    ) -> Self {
+        self.second_leg_rate_definition = Some(rate_definition);
+        self
+    }
+
+    /// Sets the currency for the second leg.
+    #[must_use]
+    pub const fn with_second_leg_currency(mut self, currency: Currency) -> Self {
+        self.second_leg_currency = Some(currency);
+        self
+    }
+
+    /// Sets the notional of the second leg. With mark-to-market resets it is the notional of the
+    /// first period; otherwise it is kept until maturity.
+    #[must_use]
+    pub const fn with_second_leg_notional(mut self, notional: f64) -> Self {
+        self.second_leg_notional = Some(notional);
+        self
+    }
+
+    /// Sets the discount curve ID for the second leg.
+    #[must_use]
+    pub const fn with_second_leg_discount_curve_id(mut self, curve_id: Option<usize>) -> Self {
+        self.second_leg_discount_curve_id = curve_id;
+        self
+    }
+
+    /// Sets the forecast curve ID for the second leg.
+    #[must_use]
+    pub const fn with_second_leg_forecast_curve_id(mut self, curve_id: Option<usize>) -> Self {
+        self.second_leg_forecast_curve_id = curve_id;
+        self
+    }
+
+    /// Sets the cross-currency basis spread added to the rate of the second leg.
+    #[must_use]
+    pub const fn with_second_leg_basis_spread(mut self, basis_spread: f64) -> Self {
+        self.second_leg_basis_spread = basis_spread;
+        self
+    }
+
+    /// Sets whether the notionals are exchanged at the start date.
+    #[must_use]
+    pub const fn with_initial_exchange(mut self, flag: bool) -> Self {
+        self.initial_exchange = flag;
+        self
+    }
+
+    /// Sets whether the notionals are exchanged at the end date.
+    #[must_use]
+    pub const fn with_final_exchange(mut self, flag: bool) -> Self {
+        self.final_exchange = flag;
+        self
+    }
+
+    /// Enables mark-to-market notional resets on the second leg. The FX forwards are taken from
+    /// the exchange rate store of the market and the currency curves of its index store.
+    #[must_use]
+    pub const fn with_mtm_resets(mut self, market_store: &'a MarketStore) -> Self {
+        self.mtm_market_store = Some(market_store);
+        self
+    }
+
+    /// Sets the identifier.
+    #[must_use]
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Builds the cross-currency `Swap`.
+    ///
+    /// # Errors
+    /// Returns an error if required builder fields are missing or, with mark-to-market resets,
+    /// if the FX forwards cannot be projected.
+    #[allow(clippy::too_many_lines)]
+    pub fn build(self) -> Result<Swap> {
+        let start_date = self
+            .start_date
+            .ok_or(AtlasError::ValueNotSetErr("Start Date".to_string()))?;
+        let end_date = self
+            .end_date
+            .ok_or(AtlasError::ValueNotSetErr("End Date".to_string()))?;
+        let payment_frequency = self
+            .payment_frequency
+            .ok_or(AtlasError::ValueNotSetErr("Payment Frequency".to_string()))?;
+        let first_rate_type = self
+            .first_leg_rate_type
+            .ok_or(AtlasError::ValueNotSetErr("First Leg RateType".to_string()))?;
+        let first_rate_definition =
+            self.first_leg_rate_definition
+                .ok_or(AtlasError::ValueNotSetErr(
+                    "First Leg RateDefinition".to_string(),
+                ))?;
+        let first_currency = self
+            .first_leg_currency
+            .ok_or(AtlasError::ValueNotSetErr("First Leg Currency".to_string()))?;
+        let first_notional = self
+            .first_leg_notional
+            .ok_or(AtlasError::ValueNotSetErr("First Leg Notional".to_string()))?;
+        let first_side = self
+            .first_leg_side
+            .ok_or(AtlasError::ValueNotSetErr("First Leg Side".to_string()))?;
+        let second_rate_type = self.second_leg_rate_type.ok_or(AtlasError::ValueNotSetErr(
+            "Second Leg RateType".to_string(),
+        ))?;
+        let second_rate_definition =
+            self.second_leg_rate_definition
+                .ok_or(AtlasError::ValueNotSetErr(
+                    "Second Leg RateDefinition".to_string(),
+                ))?;
+        let second_currency = self.second_leg_currency.ok_or(AtlasError::ValueNotSetErr(
+            "Second Leg Currency".to_string(),
+        ))?;
+
+        let first_rate_value = leg_rate_value(
+            first_rate_type,
+            self.first_leg_rate_value,
+            self.first_leg_basis_spread,
+            "First Leg RateValue",
+        )?;
+        let second_rate_value = leg_rate_value(
+            second_rate_type,
+            self.second_leg_rate_value,
+            self.second_leg_basis_spread,
+            "Second Leg RateValue",
+        )?;
+
+        let calendar = self
+            .calendar
+            .unwrap_or(Calendar::NullCalendar(NullCalendar::new()));
+        let schedule = MakeSchedule::new(start_date, end_date)
+            .with_frequency(payment_frequency)
+            .with_calendar(calendar.clone())
+            .with_convention(
+                self.business_day_convention
+                    .unwrap_or(BusinessDayConvention::Unadjusted),
+            )
+            .with_rule(
+                self.date_generation_rule
+                    .unwrap_or(DateGenerationRule::Backward),
+            )
+            .build()?;
+        let dates = schedule.dates();
+        let first_date = *dates
+            .first()
+            .ok_or(AtlasError::ValueNotSetErr("Schedule dates".to_string()))?;
+        let last_date = *dates
+            .last()
+            .ok_or(AtlasError::ValueNotSetErr("Schedule dates".to_string()))?;
+
+        let builder = MakeSwap::new()
+            .with_first_leg_rate_type(first_rate_type)
+            .with_first_leg_rate_value(first_rate_value)
+            .with_first_leg_rate_definition(first_rate_definition)
+            .with_first_leg_currency(first_currency)
+            .with_first_leg_side(first_side)
+            .with_first_leg_notional(first_notional)
+            .with_first_leg_structure(Structure::Bullet)
+            .with_first_leg_payment_frequency(payment_frequency)
+            .with_first_leg_start_date(start_date)
+            .with_first_leg_end_date(end_date)
+            .with_first_leg_calendar(Some(calendar.clone()))
+            .with_first_leg_business_day_convention(self.business_day_convention)
+            .with_first_leg_date_generation_rule(self.date_generation_rule)
+            .with_first_leg_discount_curve_id(self.first_leg_discount_curve_id)
+            .with_first_leg_forecast_curve_id(self.first_leg_forecast_curve_id)
+            .with_second_leg_rate_type(second_rate_type)
+            .with_second_leg_rate_value(second_rate_value)
+            .with_second_leg_rate_definition(second_rate_definition)
+            .with_second_leg_currency(second_currency)
+            .with_second_leg_side(first_side.inverse())
+            .with_second_leg_payment_frequency(payment_frequency)
+            .with_second_leg_start_date(start_date)
+            .with_second_leg_end_date(end_date)
+            .with_second_leg_calendar(Some(calendar))
+            .with_second_leg_business_day_convention(self.business_day_convention)
+            .with_second_leg_date_generation_rule(self.date_generation_rule)
+            .with_second_leg_discount_curve_id(self.second_leg_discount_curve_id)
+            .with_second_leg_forecast_curve_id(self.second_leg_forecast_curve_id);
+
+        let builder = if let Some(market_store) = self.mtm_market_store {
+            let notionals = dates[..dates.len() - 1]
+                .iter()
+                .enumerate()
+                .map(|(i, date)| match (i, self.second_leg_notional) {
+                    (0, Some(notional)) => Ok(notional),
+                    _ => Ok(first_notional
+                        * fx_forward(market_store, second_currency, first_currency, *date)?),
+                })
+                .collect::<Result<Vec<f64>>>()?;
+
+            let mut disbursements = HashMap::from([(first_date, notionals[0])]);
+            let mut redemptions = HashMap::from([(last_date, notionals[notionals.len() - 1])]);
+            for (i, pair) in notionals.windows(2).enumerate() {
+                let change = pair[1] - pair[0];
+                if change > 0.0 {
+                    disbursements.insert(dates[i + 1], change);
+                } else if change < 0.0 {
+                    redemptions.insert(dates[i + 1], -change);
+                }
+            }
+
+            builder
+                .with_second_leg_notional(notionals[0])
+                .with_second_leg_structure(Structure::Other)
+                .with_second_leg_disbursements(disbursements)
+                .with_second_leg_redemptions(redemptions)
+                .with_second_leg_additional_coupon_dates(
+                    dates.iter().copied().collect::<HashSet<Date>>(),
+                )
+        } else {
+            let notional = self.second_leg_notional.ok_or(AtlasError::ValueNotSetErr(
+                "Second Leg Notional".to_string(),
+            ))?;
+            builder
+                .with_second_leg_notional(notional)
+                .with_second_leg_structure(Structure::Bullet)
+        };
+
+        let builder = match self.id {
+            Some(id) => builder.with_id(id),
+            None => builder,
+        };
+        let swap = builder.build()?;
+
+        let cashflows = swap
+            .cashflows()
+            .iter()
+            .filter(|cf| match cf {
+                Cashflow::Disbursement(_) => {
+                    self.initial_exchange || cf.payment_date() != first_date
+                }
+                Cashflow::Redemption(_) => self.final_exchange || cf.payment_date() != last_date,
+                _ => true,
+            })
+            .copied()
+            .collect();
+
+        Ok(Swap::new(cashflows, swap.legs().clone(), swap.id().clone()))
+    }
+}
+
+impl Default for MakeCrossCurrencySwap<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rate value of a leg including its basis spread. Floating legs default to a zero spread.
+fn leg_rate_value(
+    rate_type: RateType,
+    rate_value: Option<f64>,
+    basis_spread: f64,
+    name: &str,
+) -> Result<f64> {
+    let rate_value = match (rate_type, rate_value) {
+        (_, Some(value)) => value,
+        (RateType::Floating, None) => 0.0,
+        _ => Err(AtlasError::ValueNotSetErr(name.to_string()))?,
+    };
+    Ok(rate_value + basis_spread)
+}
+
+/// FX forward for `date`, in units of `first_currency` per unit of `second_currency`. Dates up to
+/// the reference date of the market use the spot rate.
+fn fx_forward(
+    market_store: &MarketStore,
+    first_currency: Currency,
+    second_currency: Currency,
+    date: Date,
+) -> Result<f64> {
+    if date <= market_store.reference_date() {
+        return market_store.get_exchange_rate(first_currency, Some(second_currency));
+    }
+    market_store.get_forward_exchange_rate(first_currency, Some(second_currency), date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MakeCrossCurrencySwap;
+    use crate::{
+        cashflows::{
+            cashflow::{Cashflow, Side},
+            traits::InterestAccrual,
+        },
+        core::{marketstore::MarketStore, traits::HasCurrency},
+        currencies::enums::Currency,
+        instruments::{instrument::RateType, swap::Swap},
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{enums::Compounding, interestrate::RateDefinition, traits::HasReferenceDate},
+        time::{date::Date, daycounter::DayCounter, enums::Frequency},
+        utils::{errors::Result, testing::add_flat_index},
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    fn rate_definition() -> RateDefinition {
+        RateDefinition::new(
+            DayCounter::Actual365,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )
+    }
+
+    fn market_store() -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 1, 1);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        for (id, rate, currency) in [(0, 0.05, Currency::USD), (1, 0.06, Currency::CLP)] {
+            add_flat_index(&mut market_store, id, rate, rate_definition())?;
+            market_store
+                .mut_index_store()
+                .add_currency_curve(currency, id);
+        }
+        market_store.mut_exchange_rate_store().add_exchange_rate(
+            Currency::CLP,
+            Currency::USD,
+            900.0,
+        );
+        Ok(market_store)
+    }
+
+    fn builder(market_store: &MarketStore) -> MakeCrossCurrencySwap<'_> {
+        MakeCrossCurrencySwap::new()
+            .with_start_date(market_store.reference_date())
+            .with_end_date(Date::new(2027, 1, 1))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_first_leg_rate_type(RateType::Floating)
+            .with_first_leg_rate_definition(rate_definition())
+            .with_first_leg_currency(Currency::USD)
+            .with_first_leg_notional(1_000_000.0)
+            .with_first_leg_side(Side::Receive)
+            .with_first_leg_discount_curve_id(Some(0))
+            .with_first_leg_forecast_curve_id(Some(0))
+            .with_second_leg_rate_type(RateType::Floating)
+            .with_second_leg_rate_definition(rate_definition())
+            .with_second_leg_currency(Currency::CLP)
+            .with_second_leg_notional(900_000_000.0)
+            .with_second_leg_discount_curve_id(Some(1))
+            .with_second_leg_forecast_curve_id(Some(1))
+    }
+
+    fn npv(market_store: &MarketStore, mut swap: Swap) -> Result<f64> {
+        let indexing_visitor = IndexingVisitor::new();
+        indexing_visitor.visit(&mut swap)?;
+        let data = SimpleModel::new(market_store)
+            .with_transform_currencies(true)
+            .gen_market_data(&indexing_visitor.request())?;
+        FixingVisitor::new(&data).visit(&mut swap)?;
+        NPVConstVisitor::new(&data, true).visit(&swap)
+    }
+
+    #[test]
+    fn test_notional_exchanges() -> Result<()> {
+        let market_store = market_store()?;
+        let swap = builder(&market_store).build()?;
+        let exchanges = |swap: &Swap| {
+            swap.cashflows()
+                .iter()
+                .filter(|cf| matches!(cf, Cashflow::Disbursement(_) | Cashflow::Redemption(_)))
+                .count()
+        };
+        assert_eq!(exchanges(&swap), 4);
+        assert!(npv(&market_store, swap)?.abs() < 1e-6);
+
+        let swap = builder(&market_store)
+            .with_initial_exchange(false)
+            .with_final_exchange(false)
+            .build()?;
+        assert_eq!(exchanges(&swap), 0);
+
+        // a basis spread on the first leg is worth its annuity
+        let swap = builder(&market_store)
+            .with_first_leg_basis_spread(0.001)
+            .build()?;
+        let value = npv(&market_store, swap)?;
+        assert!(value > 1_000_000.0 * 0.001 * 2.5 && value < 1_000_000.0 * 0.001 * 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mtm_resets() -> Result<()> {
+        let market_store = market_store()?;
+        let swap = builder(&market_store)
+            .with_mtm_resets(&market_store)
+            .build()?;
+
+        // CLP rates above USD rates make the FX forward, and so the CLP notional, grow
+        let resets = swap
+            .cashflows()
+            .iter()
+            .filter(|cf| {
+                matches!(cf, Cashflow::Disbursement(_))
+                    && cf.currency().is_ok_and(|ccy| ccy == Currency::CLP)
+            })
+            .count();
+        assert_eq!(resets, 6);
+
+        let mut notionals = swap
+            .cashflows()
+            .iter()
+            .filter_map(|cf| match cf {
+                Cashflow::FloatingRateCoupon(coupon)
+                    if coupon.currency().is_ok_and(|ccy| ccy == Currency::CLP) =>
+                {
+                    Some((coupon.accrual_start_date().ok()?, coupon.notional()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<(Date, f64)>>();
+        notionals.sort_by_key(|(date, _)| *date);
+        assert!((notionals[0].1 - 900_000_000.0).abs() < 1e-6);
+        let expected = 900_000_000.0 * (0.01_f64 * 182.0 / 365.0).exp();
+        assert!((notionals[1].1 - expected).abs() < 1e-3);
+        assert!(notionals.windows(2).all(|pair| pair[1].1 > pair[0].1));
+
+        assert!(npv(&market_store, swap)?.abs() < 1e-6);
+        Ok(())
+    }
+}
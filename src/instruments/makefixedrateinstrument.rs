@@ -1022,7 +1022,7 @@ impl From<FixedRateInstrument> for MakeFixedRateInstrument {
                         additional_coupon_dates.insert(end_date);
                     }
                 }
-                Cashflow::FloatingRateCoupon(_) => (),
+                Cashflow::FloatingRateCoupon(_) | Cashflow::OvernightIndexedCoupon(_) => (),
             }
         }
 
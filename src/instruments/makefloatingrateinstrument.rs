@@ -52,6 +52,8 @@ pub struct MakeFloatingRateInstrument {
     calendar: Option<Calendar>,
     business_day_convention: Option<BusinessDayConvention>,
     date_generation_rule: Option<DateGenerationRule>,
+    cap: Option<f64>,
+    floor: Option<f64>,
 }
 
 /// Constructor, setters and getters.
@@ -82,6 +84,8 @@ impl MakeFloatingRateInstrument {
             calendar: None,
             business_day_convention: None,
             date_generation_rule: None,
+            cap: None,
+            floor: None,
         }
     }
 
@@ -238,6 +242,20 @@ impl MakeFloatingRateInstrument {
         self
     }
 
+    /// Sets the cap of the coupon rates (fixing plus spread).
+    #[must_use]
+    pub const fn with_cap(mut self, cap: Option<f64>) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets the floor of the coupon rates (fixing plus spread).
+    #[must_use]
+    pub const fn with_floor(mut self, floor: Option<f64>) -> Self {
+        self.floor = floor;
+        self
+    }
+
     /// Sets the instrument structure to bullet.
     #[must_use]
     pub const fn bullet(mut self) -> Self {
@@ -415,6 +433,7 @@ impl MakeFloatingRateInstrument {
                         cf.set_forecast_curve_id(id);
                     }
                 }
+                set_cap_and_floor(&mut cashflows, self.cap, self.floor);
 
                 Ok(FloatingRateInstrument::new(
                     start_date,
@@ -514,6 +533,7 @@ impl MakeFloatingRateInstrument {
                         cf.set_forecast_curve_id(id);
                     }
                 }
+                set_cap_and_floor(&mut cashflows, self.cap, self.floor);
 
                 Ok(FloatingRateInstrument::new(
                     start_date,
@@ -628,6 +648,7 @@ impl MakeFloatingRateInstrument {
                         cf.set_forecast_curve_id(id);
                     }
                 }
+                set_cap_and_floor(&mut cashflows, self.cap, self.floor);
 
                 Ok(FloatingRateInstrument::new(
                     start_date,
@@ -718,6 +739,7 @@ impl MakeFloatingRateInstrument {
                         cf.set_forecast_curve_id(id);
                     }
                 }
+                set_cap_and_floor(&mut cashflows, self.cap, self.floor);
                 Ok(FloatingRateInstrument::new(
                     *start_date,
                     *end_date,
@@ -769,11 +791,21 @@ fn build_coupons_from_notionals(
     }
 }
 
+fn set_cap_and_floor(cashflows: &mut [Cashflow], cap: Option<f64>, floor: Option<f64>) {
+    for cf in cashflows {
+        if let Cashflow::FloatingRateCoupon(coupon) = cf {
+            coupon.set_cap(cap);
+            coupon.set_floor(floor);
+        }
+    }
+}
+
 impl From<FloatingRateInstrument> for MakeFloatingRateInstrument {
     fn from(val: FloatingRateInstrument) -> Self {
         let mut disbursements = HashMap::new();
         let mut redemptions = HashMap::new();
         let mut additional_coupon_dates = HashSet::new();
+        let (mut cap, mut floor) = (None, None);
 
         for cashflow in val.cashflows() {
             match cashflow {
@@ -788,6 +820,8 @@ impl From<FloatingRateInstrument> for MakeFloatingRateInstrument {
                     }
                 }
                 Cashflow::FloatingRateCoupon(c) => {
+                    cap = c.cap();
+                    floor = c.floor();
                     if let Ok(start_date) = c.accrual_start_date() {
                         additional_coupon_dates.insert(start_date);
                     }
@@ -795,7 +829,7 @@ impl From<FloatingRateInstrument> for MakeFloatingRateInstrument {
                         additional_coupon_dates.insert(end_date);
                     }
                 }
-                Cashflow::FixedRateCoupon(_) => (),
+                Cashflow::FixedRateCoupon(_) | Cashflow::OvernightIndexedCoupon(_) => (),
             }
         }
 
@@ -813,6 +847,8 @@ impl From<FloatingRateInstrument> for MakeFloatingRateInstrument {
             .with_disbursements(disbursements)
             .with_redemptions(redemptions)
             .with_additional_coupon_dates(additional_coupon_dates)
+            .with_cap(cap)
+            .with_floor(floor)
             .other()
     }
 }
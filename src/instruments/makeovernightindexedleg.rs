@@ -0,0 +1,473 @@
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, CashflowType, Side},
+        overnightindexedcoupon::OvernightIndexedCoupon,
+    },
+    currencies::enums::Currency,
+    rates::interestrate::RateDefinition,
+    time::{
+        calendar::Calendar,
+        calendars::{nullcalendar::NullCalendar, traits::IsCalendar},
+        date::Date,
+        enums::{BusinessDayConvention, DateGenerationRule, Frequency, TimeUnit},
+        period::Period,
+        schedule::MakeSchedule,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    instrument::RateType,
+    leg::Leg,
+    traits::{add_cashflows_to_vec, notionals_vector, Structure},
+};
+
+/// # `MakeOvernightIndexedLeg`
+/// Builder for a leg of compounded in arrears overnight rate coupons (SOFR, ESTR, SONIA...).
+/// The calendar is used both for the payment schedule and for the daily observation of the
+/// overnight rate, and payments are made `payment_delay` business days after each accrual
+/// period ends.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let leg = MakeOvernightIndexedLeg::new()
+///     .with_start_date(Date::new(2024, 1, 2))
+///     .with_end_date(Date::new(2025, 1, 2))
+///     .with_payment_frequency(Frequency::Quarterly)
+///     .with_calendar(Calendar::WeekendsOnly(WeekendsOnly::new()))
+///     .with_lookback_days(2)
+///     .with_observation_shift(true)
+///     .with_payment_delay(2)
+///     .with_notional(1_000_000.0)
+///     .with_currency(Currency::USD)
+///     .with_side(Side::Receive)
+///     .bullet()
+///     .build()
+///     .unwrap();
+///
+/// // a disbursement, four coupons and a redemption
+/// assert_eq!(leg.cashflows().len(), 6);
+/// assert_eq!(leg.cashflows()[1].payment_date(), Date::new(2024, 4, 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MakeOvernightIndexedLeg {
+    start_date: Option<Date>,
+    end_date: Option<Date>,
+    tenor: Option<Period>,
+    payment_frequency: Option<Frequency>,
+    rate_definition: Option<RateDefinition>,
+    notional: Option<f64>,
+    currency: Option<Currency>,
+    side: Option<Side>,
+    end_of_month: Option<bool>,
+    spread: Option<f64>,
+    structure: Option<Structure>,
+    forecast_curve_id: Option<usize>,
+    discount_curve_id: Option<usize>,
+    calendar: Option<Calendar>,
+    business_day_convention: Option<BusinessDayConvention>,
+    date_generation_rule: Option<DateGenerationRule>,
+    lookback_days: Option<i32>,
+    lockout_days: Option<usize>,
+    observation_shift: Option<bool>,
+    payment_delay: Option<i32>,
+}
+
+/// Constructor, setters and getters.
+impl MakeOvernightIndexedLeg {
+    /// Creates a new `MakeOvernightIndexedLeg` builder with default values.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start_date: None,
+            end_date: None,
+            tenor: None,
+            payment_frequency: None,
+            rate_definition: None,
+            notional: None,
+            currency: None,
+            side: None,
+            end_of_month: None,
+            spread: None,
+            structure: None,
+            forecast_curve_id: None,
+            discount_curve_id: None,
+            calendar: None,
+            business_day_convention: None,
+            date_generation_rule: None,
+            lookback_days: None,
+            lockout_days: None,
+            observation_shift: None,
+            payment_delay: None,
+        }
+    }
+
+    /// Sets the start date.
+    #[must_use]
+    pub const fn with_start_date(mut self, start_date: Date) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// Sets the end date.
+    #[must_use]
+    pub const fn with_end_date(mut self, end_date: Date) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Sets the tenor, used when no end date is given.
+    #[must_use]
+    pub const fn with_tenor(mut self, tenor: Period) -> Self {
+        self.tenor = Some(tenor);
+        self
+    }
+
+    /// Sets the payment frequency.
+    #[must_use]
+    pub const fn with_payment_frequency(mut self, frequency: Frequency) -> Self {
+        self.payment_frequency = Some(frequency);
+        self
+    }
+
+    /// Sets the rate definition of the compounded rate (defaults to Actual360 simple).
+    #[must_use]
+    pub const fn with_rate_definition(mut self, rate_definition: RateDefinition) -> Self {
+        self.rate_definition = Some(rate_definition);
+        self
+    }
+
+    /// Sets the notional amount.
+    #[must_use]
+    pub const fn with_notional(mut self, notional: f64) -> Self {
+        self.notional = Some(notional);
+        self
+    }
+
+    /// Sets the currency.
+    #[must_use]
+    pub const fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// Sets the side of the transaction.
+    #[must_use]
+    pub const fn with_side(mut self, side: Side) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    /// Sets the end of month flag.
+    #[must_use]
+    pub const fn with_end_of_month(mut self, end_of_month: Option<bool>) -> Self {
+        self.end_of_month = end_of_month;
+        self
+    }
+
+    /// Sets the spread over the compounded rate (defaults to zero).
+    #[must_use]
+    pub const fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = Some(spread);
+        self
+    }
+
+    /// Sets the forecast curve ID.
+    #[must_use]
+    pub const fn with_forecast_curve_id(mut self, forecast_curve_id: Option<usize>) -> Self {
+        self.forecast_curve_id = forecast_curve_id;
+        self
+    }
+
+    /// Sets the discount curve ID.
+    #[must_use]
+    pub const fn with_discount_curve_id(mut self, discount_curve_id: Option<usize>) -> Self {
+        self.discount_curve_id = discount_curve_id;
+        self
+    }
+
+    /// Sets the calendar of the payments and of the overnight fixings.
+    #[must_use]
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Sets the business day convention of the payment schedule.
+    #[must_use]
+    pub const fn with_business_day_convention(
+        mut self,
+        business_day_convention: BusinessDayConvention,
+    ) -> Self {
+        self.business_day_convention = Some(business_day_convention);
+        self
+    }
+
+    /// Sets the date generation rule of the payment schedule.
+    #[must_use]
+    pub const fn with_date_generation_rule(
+        mut self,
+        date_generation_rule: DateGenerationRule,
+    ) -> Self {
+        self.date_generation_rule = Some(date_generation_rule);
+        self
+    }
+
+    /// Sets the number of business days each rate is observed before the day it applies to.
+    #[must_use]
+    pub const fn with_lookback_days(mut self, lookback_days: i32) -> Self {
+        self.lookback_days = Some(lookback_days);
+        self
+    }
+
+    /// Sets the number of business days at the end of each period using the same rate.
+    #[must_use]
+    pub const fn with_lockout_days(mut self, lockout_days: usize) -> Self {
+        self.lockout_days = Some(lockout_days);
+        self
+    }
+
+    /// Sets whether the rates are weighted with the days of the observation period.
+    #[must_use]
+    pub const fn with_observation_shift(mut self, observation_shift: bool) -> Self {
+        self.observation_shift = Some(observation_shift);
+        self
+    }
+
+    /// Sets the number of business days between the end of each period and its payment.
+    #[must_use]
+    pub const fn with_payment_delay(mut self, payment_delay: i32) -> Self {
+        self.payment_delay = Some(payment_delay);
+        self
+    }
+
+    /// Sets the structure to bullet.
+    #[must_use]
+    pub const fn bullet(mut self) -> Self {
+        self.structure = Some(Structure::Bullet);
+        self
+    }
+
+    /// Sets the structure to equal redemptions.
+    #[must_use]
+    pub const fn equal_redemptions(mut self) -> Self {
+        self.structure = Some(Structure::EqualRedemptions);
+        self
+    }
+
+    /// Sets the structure to zero.
+    #[must_use]
+    pub const fn zero(mut self) -> Self {
+        self.structure = Some(Structure::Zero);
+        self.payment_frequency = Some(Frequency::Once);
+        self
+    }
+}
+
+impl Default for MakeOvernightIndexedLeg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build
+impl MakeOvernightIndexedLeg {
+    /// Builds the overnight indexed leg with the configured parameters.
+    ///
+    /// # Errors
+    /// Returns an error if required builder fields are missing, if the structure is not
+    /// `Bullet`, `EqualRedemptions` or `Zero`, or if the schedule cannot be built.
+    #[allow(clippy::too_many_lines)]
+    pub fn build(self) -> Result<Leg> {
+        let structure = self
+            .structure
+            .ok_or(AtlasError::ValueNotSetErr("Structure".into()))?;
+        let currency = self
+            .currency
+            .ok_or(AtlasError::ValueNotSetErr("Currency".into()))?;
+        let side = self.side.ok_or(AtlasError::ValueNotSetErr("Side".into()))?;
+        let notional = self
+            .notional
+            .ok_or(AtlasError::ValueNotSetErr("Notional".into()))?;
+        let payment_frequency = self
+            .payment_frequency
+            .ok_or(AtlasError::ValueNotSetErr("Payment frequency".into()))?;
+        let start_date = self
+            .start_date
+            .ok_or(AtlasError::ValueNotSetErr("Start date".into()))?;
+        let end_date = if let Some(date) = self.end_date {
+            date
+        } else {
+            let tenor = self
+                .tenor
+                .ok_or(AtlasError::ValueNotSetErr("Tenor".into()))?;
+            start_date + tenor
+        };
+        let rate_definition = self.rate_definition.unwrap_or_default();
+        let spread = self.spread.unwrap_or(0.0);
+        let calendar = self
+            .calendar
+            .unwrap_or(Calendar::NullCalendar(NullCalendar::new()));
+        let convention = self
+            .business_day_convention
+            .unwrap_or(BusinessDayConvention::ModifiedFollowing);
+
+        let schedule = MakeSchedule::new(start_date, end_date)
+            .end_of_month(self.end_of_month.unwrap_or(false))
+            .with_frequency(payment_frequency)
+            .with_calendar(calendar.clone())
+            .with_convention(convention)
+            .with_rule(
+                self.date_generation_rule
+                    .unwrap_or(DateGenerationRule::Backward),
+            )
+            .build()?;
+        let dates = schedule.dates();
+        let n = dates.len() - 1;
+        let (notionals, redemptions) = match structure {
+            Structure::Bullet => {
+                let mut redemptions = vec![0.0; n];
+                redemptions[n - 1] = notional;
+                (notionals_vector(n, notional, structure), redemptions)
+            }
+            Structure::Zero => (vec![notional], vec![notional]),
+            Structure::EqualRedemptions => {
+                let n_f64 = f64::from(u32::try_from(n).map_err(|_| {
+                    AtlasError::InvalidValueErr("Redemption count exceeds u32".into())
+                })?);
+                (
+                    notionals_vector(n, notional, structure),
+                    vec![notional / n_f64; n],
+                )
+            }
+            Structure::EqualPayments | Structure::Other => Err(AtlasError::InvalidValueErr(
+                "Invalid structure for overnight indexed leg".into(),
+            ))?,
+        };
+
+        let payment_delay = Period::new(self.payment_delay.unwrap_or(0), TimeUnit::Days);
+        let mut cashflows = Vec::new();
+        add_cashflows_to_vec(
+            &mut cashflows,
+            &[dates[0]],
+            &[notional],
+            side.inverse(),
+            currency,
+            CashflowType::Disbursement,
+        );
+        for ((date_pair, notional), redemption) in
+            dates.windows(2).zip(&notionals).zip(&redemptions)
+        {
+            let payment_date =
+                calendar.advance(date_pair[1], payment_delay, Some(convention), false);
+            let coupon = OvernightIndexedCoupon::new(
+                *notional,
+                spread,
+                date_pair[0],
+                date_pair[1],
+                payment_date,
+                rate_definition,
+                currency,
+                side,
+            )
+            .with_calendar(&calendar)
+            .with_lookback_days(self.lookback_days.unwrap_or(0))
+            .with_lockout_days(self.lockout_days.unwrap_or(0))
+            .with_observation_shift(self.observation_shift.unwrap_or(false));
+            cashflows.push(Cashflow::OvernightIndexedCoupon(coupon));
+            if *redemption != 0.0 {
+                add_cashflows_to_vec(
+                    &mut cashflows,
+                    &[payment_date],
+                    &[*redemption],
+                    side,
+                    currency,
+                    CashflowType::Redemption,
+                );
+            }
+        }
+
+        if let Some(id) = self.discount_curve_id {
+            for cf in &mut cashflows {
+                cf.set_discount_curve_id(id);
+            }
+        }
+        if let Some(id) = self.forecast_curve_id {
+            for cf in &mut cashflows {
+                cf.set_forecast_curve_id(id);
+            }
+        }
+
+        Ok(Leg::new(
+            structure,
+            RateType::Floating,
+            spread,
+            rate_definition,
+            currency,
+            side,
+            self.discount_curve_id,
+            self.forecast_curve_id,
+            cashflows,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cashflows::traits::Payable, time::calendars::weekendsonly::WeekendsOnly};
+
+    #[test]
+    fn test_equal_redemptions() -> Result<()> {
+        let leg = MakeOvernightIndexedLeg::new()
+            .with_start_date(Date::new(2024, 1, 2))
+            .with_tenor(Period::new(1, TimeUnit::Years))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_calendar(Calendar::WeekendsOnly(WeekendsOnly::new()))
+            .with_payment_delay(2)
+            .with_lockout_days(2)
+            .with_notional(100.0)
+            .with_currency(Currency::EUR)
+            .with_side(Side::Pay)
+            .with_forecast_curve_id(Some(1))
+            .equal_redemptions()
+            .build()?;
+
+        let coupons: Vec<&OvernightIndexedCoupon> = leg
+            .cashflows()
+            .iter()
+            .filter_map(|cf| match cf {
+                Cashflow::OvernightIndexedCoupon(coupon) => Some(coupon),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(coupons.len(), 2);
+        assert!((coupons[0].notional() - 100.0).abs() < 1e-12);
+        assert!((coupons[1].notional() - 50.0).abs() < 1e-12);
+        assert_eq!(coupons[1].lockout_days(), 2);
+        // 2025-01-02 is a Thursday, so payment is on the following Monday
+        assert_eq!(coupons[1].payment_date(), Date::new(2025, 1, 6));
+
+        let redemptions: f64 = leg
+            .cashflows()
+            .iter()
+            .filter(|cf| matches!(cf, Cashflow::Redemption(_)))
+            .map(Payable::amount)
+            .sum::<Result<f64>>()?;
+        assert!((redemptions - 100.0).abs() < 1e-12);
+
+        assert!(MakeOvernightIndexedLeg::new()
+            .with_start_date(Date::new(2024, 1, 2))
+            .with_end_date(Date::new(2025, 1, 2))
+            .with_payment_frequency(Frequency::Annual)
+            .with_notional(100.0)
+            .with_currency(Currency::EUR)
+            .with_side(Side::Pay)
+            .build()
+            .is_err());
+        Ok(())
+    }
+}
@@ -475,6 +475,7 @@ impl MakeSwap {
                             self.first_leg_additional_coupon_dates.unwrap_or_default();
 
                         builder
+                            .other()
                             .with_disbursements(disbursements)
                             .with_redemptions(redemptions)
                             .with_additional_coupon_dates(additional_coupon_dates)
@@ -556,6 +557,7 @@ impl MakeSwap {
                             self.first_leg_additional_coupon_dates.unwrap_or_default();
 
                         builder
+                            .other()
                             .with_disbursements(disbursements)
                             .with_redemptions(redemptions)
                             .with_additional_coupon_dates(additional_coupon_dates)
@@ -649,6 +651,7 @@ impl MakeSwap {
                             self.second_leg_additional_coupon_dates.unwrap_or_default();
 
                         builder
+                            .other()
                             .with_disbursements(disbursements)
                             .with_redemptions(redemptions)
                             .with_additional_coupon_dates(additional_coupon_dates)
@@ -713,6 +716,7 @@ impl MakeSwap {
                     .with_date_generation_rule(self.second_leg_date_generation_rule)
                     .with_business_day_convention(self.second_leg_business_day_convention)
                     .with_end_of_month(self.second_leg_end_of_month)
+                    .with_calendar(self.second_leg_calendar)
                     .with_discount_curve_id(self.second_leg_discount_curve_id)
                     .with_forecast_curve_id(self.second_leg_forecast_curve_id);
 
@@ -733,6 +737,7 @@ impl MakeSwap {
                             self.second_leg_additional_coupon_dates.unwrap_or_default();
 
                         builder
+                            .other()
                             .with_disbursements(disbursements)
                             .with_redemptions(redemptions)
                             .with_additional_coupon_dates(additional_coupon_dates)
@@ -752,8 +757,8 @@ impl MakeSwap {
         };
 
         let mut cashflows = Vec::new();
-        cashflows.extend(first_leg.cashflows());
-        cashflows.extend(second_leg.cashflows());
+        cashflows.extend(first_leg.cashflows().iter().copied());
+        cashflows.extend(second_leg.cashflows().iter().copied());
 
         // clear the leg cashflows to avoid unnecessary memory usage
         first_leg.clear();
@@ -976,12 +981,22 @@ impl Default for MakeFixFloatSwap {
 #[cfg(test)]
 mod tests {
 
+    use std::collections::HashMap;
+
     use crate::{
-        cashflows::cashflow::Side,
+        cashflows::{
+            cashflow::{Cashflow, Side},
+            traits::Payable,
+        },
         currencies::enums::Currency,
         instruments::{instrument::RateType, traits::Structure},
         rates::interestrate::RateDefinition,
-        time::{date::Date, enums::Frequency},
+        time::{
+            calendar::Calendar,
+            calendars::target::TARGET,
+            date::Date,
+            enums::{BusinessDayConvention, Frequency},
+        },
         utils::errors::Result,
     };
 
@@ -1022,4 +1037,114 @@ mod tests {
 
         Ok(())
     }
+
+    /// Test the notional flows of legs with an `Other` structure
+    #[test]
+    fn test_other_structure_legs() -> Result<()> {
+        let start_date = Date::new(2024, 1, 2);
+        let mid_date = Date::new(2025, 1, 2);
+        let end_date = Date::new(2026, 1, 2);
+        let disbursements = HashMap::from([(start_date, 1_000.0)]);
+        let redemptions = HashMap::from([(mid_date, 400.0), (end_date, 600.0)]);
+
+        let swap = MakeSwap::new()
+            .with_first_leg_start_date(start_date)
+            .with_first_leg_end_date(end_date)
+            .with_first_leg_rate_type(RateType::Fixed)
+            .with_first_leg_rate_value(0.05)
+            .with_first_leg_notional(1_000.0)
+            .with_first_leg_rate_definition(RateDefinition::default())
+            .with_first_leg_currency(Currency::USD)
+            .with_first_leg_side(Side::Pay)
+            .with_first_leg_structure(Structure::Other)
+            .with_first_leg_disbursements(disbursements.clone())
+            .with_first_leg_redemptions(redemptions.clone())
+            .with_second_leg_notional(1_000.0)
+            .with_second_leg_start_date(start_date)
+            .with_second_leg_end_date(end_date)
+            .with_second_leg_rate_type(RateType::Floating)
+            .with_second_leg_rate_value(0.01)
+            .with_second_leg_rate_definition(RateDefinition::default())
+            .with_second_leg_currency(Currency::USD)
+            .with_second_leg_side(Side::Receive)
+            .with_second_leg_structure(Structure::Other)
+            .with_second_leg_disbursements(disbursements)
+            .with_second_leg_redemptions(redemptions)
+            .with_second_leg_forecast_curve_id(Some(1))
+            .build()?;
+
+        for side in [Side::Pay, Side::Receive] {
+            let mut disbursed = Vec::new();
+            let mut redeemed = Vec::new();
+            for cf in swap.cashflows() {
+                match cf {
+                    Cashflow::Disbursement(flow) if flow.side() == side.inverse() => {
+                        disbursed.push((cf.payment_date(), cf.amount()?));
+                    }
+                    Cashflow::Redemption(flow) if flow.side() == side => {
+                        redeemed.push((cf.payment_date(), cf.amount()?));
+                    }
+                    _ => {}
+                }
+            }
+            redeemed.sort_by_key(|(date, _)| *date);
+            assert_eq!(disbursed, vec![(start_date, 1_000.0)]);
+            assert_eq!(redeemed, vec![(mid_date, 400.0), (end_date, 600.0)]);
+        }
+        Ok(())
+    }
+
+    /// Test that the second leg dates are adjusted with its own calendar
+    #[test]
+    fn test_second_leg_calendar() -> Result<()> {
+        // 1 May is a TARGET holiday
+        let start_date = Date::new(2024, 5, 1);
+        let end_date = Date::new(2026, 5, 1);
+
+        let swap = MakeSwap::new()
+            .with_first_leg_start_date(start_date)
+            .with_first_leg_end_date(end_date)
+            .with_first_leg_rate_type(RateType::Fixed)
+            .with_first_leg_rate_value(0.05)
+            .with_first_leg_notional(1_000.0)
+            .with_first_leg_rate_definition(RateDefinition::default())
+            .with_first_leg_currency(Currency::EUR)
+            .with_first_leg_side(Side::Pay)
+            .with_first_leg_structure(Structure::Bullet)
+            .with_first_leg_payment_frequency(Frequency::Annual)
+            .with_second_leg_notional(1_000.0)
+            .with_second_leg_start_date(start_date)
+            .with_second_leg_end_date(end_date)
+            .with_second_leg_rate_type(RateType::Floating)
+            .with_second_leg_rate_value(0.01)
+            .with_second_leg_rate_definition(RateDefinition::default())
+            .with_second_leg_currency(Currency::EUR)
+            .with_second_leg_side(Side::Receive)
+            .with_second_leg_structure(Structure::Bullet)
+            .with_second_leg_payment_frequency(Frequency::Annual)
+            .with_second_leg_calendar(Some(Calendar::TARGET(TARGET::new())))
+            .with_second_leg_business_day_convention(Some(BusinessDayConvention::Following))
+            .with_second_leg_forecast_curve_id(Some(1))
+            .build()?;
+
+        let coupon_dates = |side: Side| {
+            swap.cashflows()
+                .iter()
+                .filter(|cf| {
+                    matches!(cf, Cashflow::FloatingRateCoupon(c) if c.side() == side)
+                        || matches!(cf, Cashflow::FixedRateCoupon(c) if c.side() == side)
+                })
+                .map(Payable::payment_date)
+                .collect::<Vec<Date>>()
+        };
+        assert_eq!(
+            coupon_dates(Side::Pay),
+            vec![Date::new(2025, 5, 1), end_date]
+        );
+        assert_eq!(
+            coupon_dates(Side::Receive),
+            vec![Date::new(2025, 5, 2), end_date]
+        );
+        Ok(())
+    }
 }
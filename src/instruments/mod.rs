@@ -1,9 +1,17 @@
+/// Fixed rate bond analytics module.
+pub mod bond;
+/// Cap, floor and collar module.
+pub mod capfloor;
 /// Double rate instrument module.
 pub mod doublerateinstrument;
 /// Fixed rate instrument module.
 pub mod fixedrateinstrument;
 /// Floating rate instrument module.
 pub mod floatingrateinstrument;
+/// FX forward module.
+pub mod fxforward;
+/// FX swap module.
+pub mod fxswap;
 /// Hybrid rate instrument module.
 pub mod hybridrateinstrument;
 /// Instrument base module.
@@ -12,6 +20,8 @@ pub mod instrument;
 pub mod leg;
 /// Loan deposit module.
 pub mod loandepo;
+/// Factory for cross-currency swaps.
+pub mod makecrosscurrencyswap;
 /// Factory for double rate instruments.
 pub mod makedoublerateinstrument;
 /// Factory for fixed rate instruments.
@@ -22,9 +32,13 @@ pub mod makefixedrateleg;
 pub mod makefloatingrateinstrument;
 /// Factory for floating rate legs.
 pub mod makefloatingrateleg;
+/// Factory for overnight indexed legs.
+pub mod makeovernightindexedleg;
 /// Factory for swaps.
 pub mod makeswap;
 /// Swap module.
 pub mod swap;
+/// Swaption module.
+pub mod swaption;
 /// Common traits for instruments.
 pub mod traits;
@@ -1,8 +1,9 @@
 use super::leg::Leg;
-use crate::cashflows::cashflow::Cashflow;
+use crate::{cashflows::cashflow::Cashflow, visitors::traits::HasCashflows};
 
 /// # `Swap`
 /// A financial swap derivative.
+#[derive(Debug, Clone)]
 pub struct Swap {
     cashflows: Vec<Cashflow>,
     legs: Vec<Leg>,
@@ -50,3 +51,13 @@ impl Swap {
         self.id = Some(id);
     }
 }
+
+impl HasCashflows for Swap {
+    fn cashflows(&self) -> &[Cashflow] {
+        &self.cashflows
+    }
+
+    fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+        &mut self.cashflows
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cashflows::{
+        cashflow::{Cashflow, Side},
+        traits::InterestAccrual,
+    },
+    core::traits::HasCurrency,
+    currencies::enums::Currency,
+    time::date::Date,
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+use super::{instrument::RateType, leg::Leg, swap::Swap};
+
+/// # `SwaptionType`
+/// The swap that can be entered by exercising a `Swaption`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwaptionType {
+    /// The right to enter the swap paying the fixed rate.
+    Payer,
+    /// The right to enter the swap receiving the fixed rate.
+    Receiver,
+}
+
+/// # `SettlementType`
+/// How a `Swaption` is settled when exercised.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettlementType {
+    /// The underlying swap is entered.
+    Physical,
+    /// The value of the underlying swap is paid in cash at its start date, computed with the
+    /// cash annuity at the swap rate fixed on the expiry date.
+    Cash,
+}
+
+/// # `Swaption`
+/// A European option to enter a fixed-floating `Swap`. The strike is the rate of the fixed leg
+/// and the type follows its side: a swaption on a swap paying the fixed rate is a payer
+/// swaption.
+///
+/// The side is the side of the option: `Receive` buys it and `Pay` sells it. Its value is given
+/// by `SwaptionNPVConstVisitor`.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let swap = MakeSwap::new()
+///     .with_first_leg_rate_type(RateType::Fixed)
+///     .with_first_leg_rate_value(0.03)
+///     .with_first_leg_rate_definition(RateDefinition::default())
+///     .with_first_leg_currency(Currency::USD)
+///     .with_first_leg_side(Side::Pay)
+///     .with_first_leg_structure(Structure::Bullet)
+///     .with_first_leg_payment_frequency(Frequency::Semiannual)
+///     .with_first_leg_start_date(Date::new(2025, 1, 2))
+///     .with_first_leg_end_date(Date::new(2030, 1, 2))
+///     .with_first_leg_notional(1_000_000.0)
+///     .with_second_leg_rate_type(RateType::Floating)
+///     .with_second_leg_rate_value(0.0)
+///     .with_second_leg_rate_definition(RateDefinition::default())
+///     .with_second_leg_currency(Currency::USD)
+///     .with_second_leg_side(Side::Receive)
+///     .with_second_leg_structure(Structure::Bullet)
+///     .with_second_leg_payment_frequency(Frequency::Quarterly)
+///     .with_second_leg_start_date(Date::new(2025, 1, 2))
+///     .with_second_leg_end_date(Date::new(2030, 1, 2))
+///     .with_second_leg_notional(1_000_000.0)
+///     .build()
+///     .unwrap();
+///
+/// let swaption = Swaption::new(swap, Date::new(2024, 12, 31), SettlementType::Physical).unwrap();
+/// assert_eq!(swaption.swaption_type(), SwaptionType::Payer);
+/// assert_eq!(swaption.strike(), 0.03);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Swaption {
+    underlying: Swap,
+    expiry: Date,
+    strike: f64,
+    kind: SwaptionType,
+    settlement_type: SettlementType,
+    side: Side,
+    currency: Currency,
+    discount_curve_id: Option<usize>,
+    forecast_curve_id: Option<usize>,
+    id: Option<String>,
+}
+
+impl Swaption {
+    /// Creates a long swaption on the given swap, expiring on the given date.
+    ///
+    /// # Errors
+    /// Returns an error if the swap does not have one fixed and one floating leg in the same
+    /// currency, or if it starts before the expiry.
+    pub fn new(underlying: Swap, expiry: Date, settlement_type: SettlementType) -> Result<Self> {
+        let find_leg = |rate_type: RateType| -> Result<&Leg> {
+            let mut legs = underlying
+                .legs()
+                .iter()
+                .filter(|leg| leg.rate_type() == rate_type);
+            match (legs.next(), legs.next()) {
+                (Some(leg), None) => Ok(leg),
+                _ => Err(AtlasError::InvalidValueErr(format!(
+                    "Swaption underlying must have exactly one {rate_type:?} leg"
+                ))),
+            }
+        };
+        let fixed_leg = find_leg(RateType::Fixed)?;
+        let floating_leg = find_leg(RateType::Floating)?;
+        if fixed_leg.currency() != floating_leg.currency() {
+            return Err(AtlasError::InvalidValueErr(
+                "Swaption underlying legs must have the same currency".to_string(),
+            ));
+        }
+        let start_date = underlying
+            .cashflows()
+            .iter()
+            .filter_map(|cf| match cf {
+                Cashflow::FixedRateCoupon(coupon) => coupon.accrual_start_date().ok(),
+                _ => None,
+            })
+            .min()
+            .ok_or(AtlasError::InvalidValueErr(
+                "Swaption underlying has no fixed rate coupons".to_string(),
+            ))?;
+        if start_date < expiry {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Swaption underlying starts ({start_date}) before the expiry ({expiry})"
+            )));
+        }
+        let kind = match fixed_leg.side() {
+            Side::Pay => SwaptionType::Payer,
+            Side::Receive => SwaptionType::Receiver,
+        };
+        Ok(Self {
+            expiry,
+            strike: fixed_leg.rate_value(),
+            kind,
+            settlement_type,
+            side: Side::Receive,
+            currency: fixed_leg.currency(),
+            discount_curve_id: fixed_leg.discount_curve_id(),
+            forecast_curve_id: floating_leg.forecast_curve_id(),
+            underlying,
+            id: None,
+        })
+    }
+
+    /// Sets the side of the option, `Pay` for a sold swaption.
+    #[must_use]
+    pub const fn with_side(mut self, side: Side) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Sets the ID of the instrument.
+    #[must_use]
+    pub fn with_id(mut self, id: Option<String>) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Returns the underlying swap.
+    #[must_use]
+    pub const fn underlying(&self) -> &Swap {
+        &self.underlying
+    }
+
+    /// Returns the expiry date.
+    #[must_use]
+    pub const fn expiry(&self) -> Date {
+        self.expiry
+    }
+
+    /// Returns the strike, the rate of the fixed leg of the underlying swap.
+    #[must_use]
+    pub const fn strike(&self) -> f64 {
+        self.strike
+    }
+
+    /// Returns the type of the swaption.
+    #[must_use]
+    pub const fn swaption_type(&self) -> SwaptionType {
+        self.kind
+    }
+
+    /// Returns the settlement type.
+    #[must_use]
+    pub const fn settlement_type(&self) -> SettlementType {
+        self.settlement_type
+    }
+
+    /// Returns the side.
+    #[must_use]
+    pub const fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Returns the discount curve ID of the fixed leg.
+    #[must_use]
+    pub const fn discount_curve_id(&self) -> Option<usize> {
+        self.discount_curve_id
+    }
+
+    /// Returns the forecast curve ID of the floating leg.
+    #[must_use]
+    pub const fn forecast_curve_id(&self) -> Option<usize> {
+        self.forecast_curve_id
+    }
+
+    /// Returns the ID.
+    #[must_use]
+    pub fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+}
+
+impl HasCurrency for Swaption {
+    fn currency(&self) -> Result<Currency> {
+        Ok(self.currency)
+    }
+}
+
+impl HasCashflows for Swaption {
+    fn cashflows(&self) -> &[Cashflow] {
+        self.underlying.cashflows()
+    }
+
+    fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+        self.underlying.mut_cashflows()
+    }
+}
@@ -65,6 +65,7 @@ impl TryFrom<String> for CashflowType {
             "Disbursement" => Ok(Self::Disbursement),
             "FixedRateCoupon" => Ok(Self::FixedRateCoupon),
             "FloatingRateCoupon" => Ok(Self::FloatingRateCoupon),
+            "OvernightIndexedCoupon" => Ok(Self::OvernightIndexedCoupon),
             _ => Err(AtlasError::InvalidValueErr(format!(
                 "Invalid cashflow type: {s}"
             ))),
@@ -79,6 +80,7 @@ impl From<CashflowType> for String {
             CashflowType::Disbursement => "Disbursement".to_string(),
             CashflowType::FixedRateCoupon => "FixedRateCoupon".to_string(),
             CashflowType::FloatingRateCoupon => "FloatingRateCoupon".to_string(),
+            CashflowType::OvernightIndexedCoupon => "OvernightIndexedCoupon".to_string(),
         }
     }
 }
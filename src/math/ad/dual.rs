@@ -0,0 +1,254 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::traits::Real;
+
+/// # `Dual`
+/// Forward-mode dual number. Holds a value and its gradient with respect to a set of input
+/// variables, identified by their index. The gradient is sparse, so a price that depends on
+/// a few nodes of a curve with thousands of nodes only carries those few derivatives.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let x = Dual::variable(2.0, 0);
+/// let y = Dual::variable(3.0, 1);
+/// let z = x.clone() * y + x.exp();
+/// assert!((z.value() - (6.0 + 2.0f64.exp())).abs() < 1e-12);
+/// assert!((z.derivative(0) - (3.0 + 2.0f64.exp())).abs() < 1e-12);
+/// assert!((z.derivative(1) - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dual {
+    value: f64,
+    gradient: Vec<(usize, f64)>,
+}
+
+impl Dual {
+    /// Creates a constant, with no derivatives.
+    #[must_use]
+    pub const fn constant(value: f64) -> Self {
+        Self {
+            value,
+            gradient: Vec::new(),
+        }
+    }
+
+    /// Creates the input variable with the given index, whose derivative with respect to
+    /// itself is one.
+    #[must_use]
+    pub fn variable(value: f64, index: usize) -> Self {
+        Self {
+            value,
+            gradient: vec![(index, 1.0)],
+        }
+    }
+
+    /// Returns the value.
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the non-zero derivatives as `(variable, derivative)` pairs, sorted by variable.
+    #[must_use]
+    pub fn gradient(&self) -> &[(usize, f64)] {
+        &self.gradient
+    }
+
+    /// Returns the derivative with respect to the given variable.
+    #[must_use]
+    pub fn derivative(&self, index: usize) -> f64 {
+        self.gradient
+            .binary_search_by_key(&index, |(i, _)| *i)
+            .map_or(0.0, |position| self.gradient[position].1)
+    }
+
+    /// Returns `f(self)` given its value and its derivative at `self`.
+    fn chain(&self, value: f64, derivative: f64) -> Self {
+        Self {
+            value,
+            gradient: self
+                .gradient
+                .iter()
+                .map(|(i, d)| (*i, d * derivative))
+                .collect(),
+        }
+    }
+
+    /// Returns the gradient of `lhs_weight * lhs + rhs_weight * rhs`.
+    fn combine(lhs: &Self, lhs_weight: f64, rhs: &Self, rhs_weight: f64) -> Vec<(usize, f64)> {
+        let mut gradient = Vec::with_capacity(lhs.gradient.len() + rhs.gradient.len());
+        let mut lhs_iter = lhs.gradient.iter().peekable();
+        let mut rhs_iter = rhs.gradient.iter().peekable();
+        loop {
+            let next = match (lhs_iter.peek(), rhs_iter.peek()) {
+                (Some((i, dl)), Some((j, dr))) if i == j => {
+                    let value = lhs_weight.mul_add(*dl, rhs_weight * dr);
+                    lhs_iter.next();
+                    rhs_iter.next();
+                    (*i, value)
+                }
+                (Some((i, dl)), Some((j, _))) if i < j => {
+                    let value = lhs_weight * dl;
+                    lhs_iter.next();
+                    (*i, value)
+                }
+                (Some((i, dl)), None) => {
+                    let value = lhs_weight * dl;
+                    lhs_iter.next();
+                    (*i, value)
+                }
+                (_, Some((j, dr))) => {
+                    let value = rhs_weight * dr;
+                    rhs_iter.next();
+                    (*j, value)
+                }
+                (None, None) => break,
+            };
+            gradient.push(next);
+        }
+        gradient
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            value: self.value + rhs.value,
+            gradient: Self::combine(&self, 1.0, &rhs, 1.0),
+        }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            value: self.value - rhs.value,
+            gradient: Self::combine(&self, 1.0, &rhs, -1.0),
+        }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            value: self.value * rhs.value,
+            gradient: Self::combine(&self, rhs.value, &rhs, self.value),
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let value = self.value / rhs.value;
+        Self {
+            value,
+            gradient: Self::combine(&self, 1.0 / rhs.value, &rhs, -value / rhs.value),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.chain(-self.value, -1.0)
+    }
+}
+
+impl Add<f64> for Dual {
+    type Output = Self;
+    fn add(mut self, rhs: f64) -> Self {
+        self.value += rhs;
+        self
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Self;
+    fn sub(mut self, rhs: f64) -> Self {
+        self.value -= rhs;
+        self
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        self.chain(self.value * rhs, rhs)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        self.chain(self.value / rhs, 1.0 / rhs)
+    }
+}
+
+impl Real for Dual {
+    fn from_f64(value: f64) -> Self {
+        Self::constant(value)
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn is_constant(&self) -> bool {
+        self.gradient.is_empty()
+    }
+
+    fn exp(&self) -> Self {
+        let value = self.value.exp();
+        self.chain(value, value)
+    }
+
+    fn ln(&self) -> Self {
+        self.chain(self.value.ln(), 1.0 / self.value)
+    }
+
+    fn powf(&self, exponent: f64) -> Self {
+        self.chain(
+            self.value.powf(exponent),
+            exponent * self.value.powf(exponent - 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_finite_differences() {
+        fn f<T: Real>(x: T, y: T) -> T {
+            let power = x.powf(1.5);
+            ((x.clone() * y.clone() + 1.0).ln() - y.clone() / x).exp() * power - (-y) * 2.0
+        }
+        let (x, y) = (0.7, 1.3);
+        let value = f(Dual::variable(x, 0), Dual::variable(y, 3));
+        assert!((value.value() - f(x, y)).abs() < 1e-14);
+
+        let h = 1e-6;
+        let dx = (f(x + h, y) - f(x - h, y)) / (2.0 * h);
+        let dy = (f(x, y + h) - f(x, y - h)) / (2.0 * h);
+        assert!((value.derivative(0) - dx).abs() < 1e-8);
+        assert!((value.derivative(3) - dy).abs() < 1e-8);
+        assert!(value.derivative(1).abs() < f64::EPSILON);
+        assert_eq!(
+            value.gradient().iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![0, 3]
+        );
+    }
+}
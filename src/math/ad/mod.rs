@@ -0,0 +1,7 @@
+//! Forward-mode automatic differentiation, used to obtain the sensitivities of a price to
+//! every curve node in a single pricing pass.
+
+/// Dual number implementation.
+pub mod dual;
+/// Traits for numeric types.
+pub mod traits;
@@ -0,0 +1,59 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// # `Real`
+/// A scalar the pricing formulas can be evaluated with. It is implemented by `f64` and by
+/// `Dual`, which carries the derivatives of the value along the computation.
+pub trait Real:
+    Clone
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+{
+    /// Returns a constant with the given value.
+    fn from_f64(value: f64) -> Self;
+    /// Returns the value, discarding any derivative.
+    fn value(&self) -> f64;
+    /// Returns true if the value carries no derivative.
+    fn is_constant(&self) -> bool;
+    /// Returns `e^self`.
+    #[must_use]
+    fn exp(&self) -> Self;
+    /// Returns the natural logarithm of `self`.
+    #[must_use]
+    fn ln(&self) -> Self;
+    /// Returns `self` raised to a constant power.
+    #[must_use]
+    fn powf(&self, exponent: f64) -> Self;
+}
+
+impl Real for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn value(&self) -> f64 {
+        *self
+    }
+
+    fn is_constant(&self) -> bool {
+        true
+    }
+
+    fn exp(&self) -> Self {
+        Self::exp(*self)
+    }
+
+    fn ln(&self) -> Self {
+        Self::ln(*self)
+    }
+
+    fn powf(&self, exponent: f64) -> Self {
+        Self::powf(*self, exponent)
+    }
+}
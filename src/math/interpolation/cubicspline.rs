@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+
+use crate::utils::errors::Result;
+
+use super::traits::{check_interpolation_data, Interpolate};
+
+/// # `CubicSplineInterpolator`
+/// Cubic spline interpolator with continuous first and second derivatives. The trait
+/// implementation uses natural boundary conditions (zero second derivative at both ends);
+/// clamped boundary conditions are available through `interpolate_clamped`. Outside the
+/// data range the spline is extended linearly with the slope of the boundary node.
+#[derive(Clone)]
+pub struct CubicSplineInterpolator {}
+
+impl CubicSplineInterpolator {
+    /// Interpolates with clamped boundary conditions, i.e. with the first derivative fixed
+    /// to `left_derivative` and `right_derivative` at both ends.
+    ///
+    /// # Errors
+    /// Returns an error if there are not enough data points or if `x` is outside the data
+    /// range and extrapolation is disabled.
+    pub fn interpolate_clamped(
+        x: f64,
+        x_: &[f64],
+        y_: &[f64],
+        left_derivative: f64,
+        right_derivative: f64,
+        enable_extrapolation: bool,
+    ) -> Result<f64> {
+        check_interpolation_data(x, x_, y_, enable_extrapolation)?;
+        let derivatives = spline_derivatives(x_, y_, Some((left_derivative, right_derivative)));
+        Ok(hermite_interpolate(x, x_, y_, &derivatives))
+    }
+}
+
+impl Interpolate for CubicSplineInterpolator {
+    fn interpolate(x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> Result<f64> {
+        check_interpolation_data(x, x_, y_, enable_extrapolation)?;
+        let derivatives = spline_derivatives(x_, y_, None);
+        Ok(hermite_interpolate(x, x_, y_, &derivatives))
+    }
+}
+
+/// Returns the slopes of the secants between consecutive data points.
+pub(crate) fn secant_slopes(x_: &[f64], y_: &[f64]) -> Vec<f64> {
+    x_.windows(2)
+        .zip(y_.windows(2))
+        .map(|(x, y)| (y[1] - y[0]) / (x[1] - x[0]))
+        .collect()
+}
+
+/// Returns the first derivatives of the cubic spline at each node. Natural boundary
+/// conditions are used unless `clamped` holds the end derivatives. The data must have been
+/// validated with `check_interpolation_data`.
+pub(crate) fn spline_derivatives(x_: &[f64], y_: &[f64], clamped: Option<(f64, f64)>) -> Vec<f64> {
+    let n = x_.len();
+    let h: Vec<f64> = x_.windows(2).map(|x| x[1] - x[0]).collect();
+    let s = secant_slopes(x_, y_);
+
+    // tridiagonal system: lower[i] d[i-1] + diag[i] d[i] + upper[i] d[i+1] = rhs[i]
+    let mut lower = vec![0.0; n];
+    let mut diag = vec![0.0; n];
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+
+    for i in 1..n - 1 {
+        lower[i] = h[i];
+        diag[i] = 2.0 * (h[i - 1] + h[i]);
+        upper[i] = h[i - 1];
+        rhs[i] = 3.0 * h[i].mul_add(s[i - 1], h[i - 1] * s[i]);
+    }
+
+    if let Some((left, right)) = clamped {
+        diag[0] = 1.0;
+        rhs[0] = left;
+        diag[n - 1] = 1.0;
+        rhs[n - 1] = right;
+    } else {
+        diag[0] = 2.0;
+        upper[0] = 1.0;
+        rhs[0] = 3.0 * s[0];
+        lower[n - 1] = 1.0;
+        diag[n - 1] = 2.0;
+        rhs[n - 1] = 3.0 * s[n - 2];
+    }
+
+    // Thomas algorithm
+    for i in 1..n {
+        let w = lower[i] / diag[i - 1];
+        diag[i] -= w * upper[i - 1];
+        rhs[i] -= w * rhs[i - 1];
+    }
+    let mut derivatives = vec![0.0; n];
+    derivatives[n - 1] = rhs[n - 1] / diag[n - 1];
+    for i in (0..n - 1).rev() {
+        derivatives[i] = upper[i].mul_add(-derivatives[i + 1], rhs[i]) / diag[i];
+    }
+    derivatives
+}
+
+/// Evaluates the piecewise cubic Hermite polynomial defined by the node values and first
+/// derivatives. Outside the data range the boundary tangent is used. The data must have been
+/// validated with `check_interpolation_data`.
+#[allow(clippy::suboptimal_flops)]
+pub(crate) fn hermite_interpolate(x: f64, x_: &[f64], y_: &[f64], derivatives: &[f64]) -> f64 {
+    let n = x_.len();
+    if x <= x_[0] {
+        return derivatives[0].mul_add(x - x_[0], y_[0]);
+    }
+    if x >= x_[n - 1] {
+        return derivatives[n - 1].mul_add(x - x_[n - 1], y_[n - 1]);
+    }
+
+    let index = match x_.binary_search_by(|&probe| probe.partial_cmp(&x).unwrap_or(Ordering::Less))
+    {
+        Ok(index) => return y_[index],
+        Err(index) => index,
+    };
+
+    let h = x_[index] - x_[index - 1];
+    let t = (x - x_[index - 1]) / h;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+
+    h00 * y_[index - 1]
+        + h10 * h * derivatives[index - 1]
+        + h01 * y_[index]
+        + h11 * h * derivatives[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_spline_reproduces_nodes_and_lines() -> Result<()> {
+        let x_ = vec![0.0, 1.0, 2.5, 4.0];
+        let y_ = vec![1.0, 3.0, 6.0, 9.0];
+        for (x, y) in x_.iter().zip(&y_) {
+            let value = CubicSplineInterpolator::interpolate(*x, &x_, &y_, false)?;
+            assert!((value - y).abs() < 1e-12);
+        }
+
+        // a straight line is reproduced exactly
+        let y_line: Vec<f64> = x_.iter().map(|x| 2.0 * x + 1.0).collect();
+        let value = CubicSplineInterpolator::interpolate(1.7, &x_, &y_line, false)?;
+        assert!((value - 4.4).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamped_spline_reproduces_cubic() -> Result<()> {
+        // a cubic with the exact end derivatives is reproduced by the clamped spline
+        let f = |x: f64| x * x.mul_add(x, -2.0);
+        let df = |x: f64| (3.0 * x).mul_add(x, -2.0);
+        let x_ = vec![0.0, 0.5, 1.5, 2.0, 3.0];
+        let y_: Vec<f64> = x_.iter().map(|x| f(*x)).collect();
+
+        let value =
+            CubicSplineInterpolator::interpolate_clamped(1.2, &x_, &y_, df(0.0), df(3.0), false)?;
+        assert!((value - f(1.2)).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -1,10 +1,29 @@
+use std::cmp::Ordering;
+
 use serde::{Deserialize, Serialize};
 
-use super::{linear::LinearInterpolator, loglinear::LogLinearInterpolator, traits::Interpolate};
+use crate::{
+    math::ad::traits::Real,
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    cubicspline::CubicSplineInterpolator,
+    linear::LinearInterpolator,
+    loglinear::LogLinearInterpolator,
+    monotoneconvex::MonotoneConvexInterpolator,
+    monotonecubic::MonotoneCubicInterpolator,
+    traits::{check_interpolation_data, check_positive_data, Interpolate},
+};
 
 /// # `Interpolator`
 /// Enum that represents the type of interpolation.
 ///
+/// `FlatForward` and `MonotoneConvex` read the data as a cumulative quantity (zero rate
+/// times time for a yield curve): `FlatForward` is linear on it, i.e. its derivative is
+/// piecewise constant, and `MonotoneConvex` keeps the derivative continuous. Term
+/// structures apply them on `-ln(df)` through `interpolate_discount_factor`.
+///
 /// ## Example
 /// ```
 /// use rustatlas::prelude::*;
@@ -12,8 +31,15 @@ use super::{linear::LinearInterpolator, loglinear::LogLinearInterpolator, traits
 /// let x_ = vec![0.0, 1.0, 2.0];
 /// let y_ = vec![0.0, 1.0, 4.0];
 /// let interpolator = Interpolator::Linear;
-/// let y = interpolator.interpolate(x, &x_, &y_, true);
+/// let y = interpolator.interpolate(x, &x_, &y_, true).unwrap();
 /// assert_eq!(y, 1.0);
+///
+/// let spline = Interpolator::NaturalCubicSpline;
+/// let y = spline.interpolate(1.5, &x_, &y_, true).unwrap();
+/// assert!(y > 2.0 && y < 2.5);
+///
+/// // points outside the data range are rejected unless extrapolation is enabled
+/// assert!(interpolator.interpolate(3.0, &x_, &y_, false).is_err());
 /// ```
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 pub enum Interpolator {
@@ -21,19 +47,215 @@ pub enum Interpolator {
     Linear,
     /// Logarithmic linear interpolation method.
     LogLinear,
+    /// Cubic spline with zero second derivative at both ends.
+    NaturalCubicSpline,
+    /// Cubic spline with the first derivative fixed at both ends.
+    ClampedCubicSpline {
+        /// First derivative at the first node.
+        left_derivative: f64,
+        /// First derivative at the last node.
+        right_derivative: f64,
+    },
+    /// Natural cubic spline with Hyman-filtered derivatives, monotone on monotone data.
+    MonotoneCubic,
+    /// Hagan-West monotone convex interpolation of a cumulative quantity.
+    MonotoneConvex,
+    /// Piecewise constant derivative of a cumulative quantity (flat instantaneous forwards).
+    FlatForward,
 }
 
 impl Interpolator {
     /// Performs interpolation for a given x value using the specified interpolation method.
-    #[must_use]
-    pub fn interpolate(&self, x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> f64 {
+    ///
+    /// # Errors
+    /// Returns an error if there are not enough data points, if `x` is outside the data range
+    /// and extrapolation is disabled, or if `LogLinear` is given non-positive values.
+    pub fn interpolate(
+        &self,
+        x: f64,
+        x_: &[f64],
+        y_: &[f64],
+        enable_extrapolation: bool,
+    ) -> Result<f64> {
         match self {
-            Self::Linear => {
+            Self::Linear | Self::FlatForward => {
                 LinearInterpolator::interpolate(x, x_, y_, enable_extrapolation)
             }
-            Self::LogLinear => {
-                LogLinearInterpolator::interpolate(x, x_, y_, enable_extrapolation)
+            Self::LogLinear => LogLinearInterpolator::interpolate(x, x_, y_, enable_extrapolation),
+            Self::NaturalCubicSpline => {
+                CubicSplineInterpolator::interpolate(x, x_, y_, enable_extrapolation)
+            }
+            Self::ClampedCubicSpline {
+                left_derivative,
+                right_derivative,
+            } => CubicSplineInterpolator::interpolate_clamped(
+                x,
+                x_,
+                y_,
+                *left_derivative,
+                *right_derivative,
+                enable_extrapolation,
+            ),
+            Self::MonotoneCubic => {
+                MonotoneCubicInterpolator::interpolate(x, x_, y_, enable_extrapolation)
+            }
+            Self::MonotoneConvex => {
+                MonotoneConvexInterpolator::interpolate(x, x_, y_, enable_extrapolation)
+            }
+        }
+    }
+
+    /// Checks once, when the data is set, that `y_` can be interpolated by the method:
+    /// `LogLinear` requires strictly positive values. Interpolating does not scan the data
+    /// again, so term structures call it on construction.
+    ///
+    /// # Errors
+    /// Returns a `NonPositiveValueErr` if a value cannot be interpolated.
+    pub fn check_data(&self, y_: &[f64]) -> Result<()> {
+        match self {
+            Self::LogLinear => check_positive_data(y_),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks once, when the curve is built, that discount factors can be interpolated by the
+    /// method: logarithmic and forward-based methods require strictly positive values.
+    ///
+    /// # Errors
+    /// Returns a `NonPositiveValueErr` if a discount factor is not positive.
+    pub fn check_discount_factors(&self, dfs: &[f64]) -> Result<()> {
+        if self.is_forward_based() {
+            check_positive_data(dfs)
+        } else {
+            self.check_data(dfs)
+        }
+    }
+
+    /// Returns true if the method interpolates a cumulative quantity, in which case curves
+    /// apply it on `-ln(df)` rather than on the discount factors or zero rates.
+    #[must_use]
+    pub const fn is_forward_based(&self) -> bool {
+        matches!(self, Self::FlatForward | Self::MonotoneConvex)
+    }
+
+    /// Interpolates a discount factor at time `t` given the discount factors `dfs` at times `t_`.
+    /// Forward-based methods interpolate `-ln(df)`; the others interpolate the discount
+    /// factors directly. Discount factors are expected to have been validated with
+    /// `check_discount_factors`.
+    ///
+    /// # Errors
+    /// Returns an error if the interpolation fails.
+    pub fn interpolate_discount_factor(
+        &self,
+        t: f64,
+        t_: &[f64],
+        dfs: &[f64],
+        enable_extrapolation: bool,
+    ) -> Result<f64> {
+        match self {
+            // linear on -ln(df) is log-linear on the discount factors
+            Self::FlatForward => {
+                LogLinearInterpolator::interpolate(t, t_, dfs, enable_extrapolation)
+            }
+            Self::MonotoneConvex => {
+                let y: Vec<f64> = dfs.iter().map(|df| -df.ln()).collect();
+                Ok((-self.interpolate(t, t_, &y, enable_extrapolation)?).exp())
+            }
+            _ => self.interpolate(t, t_, dfs, enable_extrapolation),
+        }
+    }
+
+    /// Interpolates a discount factor like `interpolate_discount_factor`, with discount
+    /// factors given as any `Real`, e.g. `Dual` numbers carrying their derivatives with respect
+    /// to the curve nodes. Only `Linear`, `LogLinear` and `FlatForward` are supported, since
+    /// the other methods solve for their coefficients in `f64`.
+    ///
+    /// # Errors
+    /// Returns an error if the method is not supported, if the interpolation data is invalid,
+    /// or if a discount factor is not positive when a logarithmic method is used.
+    pub fn interpolate_discount_factor_with<T: Real>(
+        &self,
+        t: f64,
+        t_: &[f64],
+        dfs: &[T],
+        enable_extrapolation: bool,
+    ) -> Result<T> {
+        let values: Vec<f64> = dfs.iter().map(Real::value).collect();
+        check_interpolation_data(t, t_, &values, enable_extrapolation)?;
+        let index =
+            match t_.binary_search_by(|&probe| probe.partial_cmp(&t).unwrap_or(Ordering::Equal)) {
+                Ok(index) | Err(index) => index.clamp(1, t_.len() - 1),
+            };
+        let weight = (t - t_[index - 1]) / (t_[index] - t_[index - 1]);
+        let (df0, df1) = (dfs[index - 1].clone(), dfs[index].clone());
+        match self {
+            Self::Linear => Ok(df0 * (1.0 - weight) + df1 * weight),
+            Self::LogLinear | Self::FlatForward => {
+                if let Some(df) = [&df0, &df1]
+                    .into_iter()
+                    .map(Real::value)
+                    .find(|df| *df <= 0.0 || df.is_nan())
+                {
+                    return Err(AtlasError::NonPositiveValueErr(format!(
+                        "Discount factors must be positive, got {df}"
+                    )));
+                }
+                Ok((df0.ln() * (1.0 - weight) + df1.ln() * weight).exp())
+            }
+            _ => Err(AtlasError::InvalidValueErr(format!(
+                "{self:?} interpolation does not support automatic differentiation"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_forward_discount_factor() -> Result<()> {
+        // flat forward on discount factors matches log-linear interpolation
+        let t_ = vec![0.0, 1.0, 2.0];
+        let dfs = vec![1.0, 0.97, 0.93];
+        let flat = Interpolator::FlatForward.interpolate_discount_factor(1.4, &t_, &dfs, false)?;
+        let loglinear = Interpolator::LogLinear.interpolate(1.4, &t_, &dfs, false)?;
+        assert!((flat - loglinear).abs() < 1e-14);
+        Ok(())
+    }
+
+    #[test]
+    fn test_monotone_convex_discount_factor() -> Result<()> {
+        let t_ = vec![0.0, 1.0, 2.0, 5.0];
+        let dfs = vec![1.0, 0.97, 0.93, 0.85];
+        let interpolator = Interpolator::MonotoneConvex;
+        for (t, df) in t_.iter().zip(&dfs) {
+            let value = interpolator.interpolate_discount_factor(*t, &t_, &dfs, false)?;
+            assert!((value - df).abs() < 1e-14);
+        }
+        let value = interpolator.interpolate_discount_factor(3.0, &t_, &dfs, false)?;
+        assert!(value < 0.93 && value > 0.85);
+        Ok(())
+    }
+
+    #[test]
+    fn test_discount_factor_with_matches_f64() -> Result<()> {
+        let t_ = vec![0.0, 0.5, 1.0, 2.0];
+        let dfs = vec![1.0, 0.985, 0.97, 0.93];
+        for interpolator in [
+            Interpolator::Linear,
+            Interpolator::LogLinear,
+            Interpolator::FlatForward,
+        ] {
+            for t in [0.0, 0.3, 1.0, 1.7, 2.5] {
+                let expected = interpolator.interpolate_discount_factor(t, &t_, &dfs, true)?;
+                let df = interpolator.interpolate_discount_factor_with(t, &t_, &dfs, true)?;
+                assert!((df - expected).abs() < 1e-14);
             }
         }
+        assert!(Interpolator::MonotoneConvex
+            .interpolate_discount_factor_with(0.3, &t_, &dfs, true)
+            .is_err());
+        Ok(())
     }
 }
@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
 
-use super::traits::Interpolate;
+use crate::utils::errors::Result;
+
+use super::traits::{check_interpolation_data, Interpolate};
 
 /// # `Linear Interpolator`
 /// Basic linear interpolator.
@@ -8,22 +10,15 @@ use super::traits::Interpolate;
 pub struct LinearInterpolator {}
 
 impl Interpolate for LinearInterpolator {
-    fn interpolate(x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> f64 {
+    fn interpolate(x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> Result<f64> {
+        check_interpolation_data(x, x_, y_, enable_extrapolation)?;
+
         let index =
             match x_.binary_search_by(|&probe| probe.partial_cmp(&x).unwrap_or(Ordering::Equal)) {
                 Ok(index) | Err(index) => index,
             };
 
-        let (Some(first_x), Some(last_x)) = (x_.first(), x_.last()) else {
-            panic!("Interpolation data must contain at least one x value.");
-        };
-
-        assert!(
-            enable_extrapolation || (x >= *first_x && x <= *last_x),
-            "Extrapolation is not enabled, and the provided value is outside the range."
-        );
-
-        match index {
+        let value = match index {
             0 => y_[0] + (x - x_[0]) * (y_[1] - y_[0]) / (x_[1] - x_[0]),
             index if index == x_.len() => {
                 y_[index - 1]
@@ -35,20 +30,41 @@ impl Interpolate for LinearInterpolator {
                     + (x - x_[index - 1]) * (y_[index] - y_[index - 1])
                         / (x_[index] - x_[index - 1])
             }
-        }
+        };
+        Ok(value)
     }
 }
 #[cfg(test)]
 mod tests {
     use super::Interpolate;
     use super::LinearInterpolator;
+    use crate::utils::errors::{AtlasError, Result};
 
     #[test]
-    fn test_linear_interpolation() {
+    fn test_linear_interpolation() -> Result<()> {
         let x = 0.5;
         let x_ = vec![0.0, 1.0];
         let y_ = vec![0.0, 1.0];
-        let y = LinearInterpolator::interpolate(x, &x_, &y_, true);
+        let y = LinearInterpolator::interpolate(x, &x_, &y_, true)?;
         assert!((y - 0.5).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_interpolation_errors() {
+        let x_ = vec![0.0, 1.0];
+        let y_ = vec![0.0, 1.0];
+        assert!(matches!(
+            LinearInterpolator::interpolate(1.5, &x_, &y_, false),
+            Err(AtlasError::OutOfRangeErr(_))
+        ));
+        assert!(matches!(
+            LinearInterpolator::interpolate(0.5, &[], &[], true),
+            Err(AtlasError::InsufficientPointsErr(_))
+        ));
+        assert!(matches!(
+            LinearInterpolator::interpolate(0.5, &[0.0], &[1.0], true),
+            Err(AtlasError::InsufficientPointsErr(_))
+        ));
     }
 }
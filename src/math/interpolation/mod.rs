@@ -1,10 +1,16 @@
 //! Interpolation module containing various interpolation methods and traits.
 
+/// Cubic spline interpolation implementation.
+pub mod cubicspline;
 /// Enumeration types for interpolation.
 pub mod enums;
 /// Linear interpolation implementation.
 pub mod linear;
 /// Log-linear interpolation implementation.
 pub mod loglinear;
+/// Hagan-West monotone convex interpolation implementation.
+pub mod monotoneconvex;
+/// Hyman-filtered monotone cubic interpolation implementation.
+pub mod monotonecubic;
 /// Traits for interpolation.
 pub mod traits;
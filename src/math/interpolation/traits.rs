@@ -1,3 +1,5 @@
+use crate::utils::errors::{AtlasError, Result};
+
 /// # `Interpolation` trait
 /// A trait that defines the interpolation of a function.
 pub trait Interpolate {
@@ -11,5 +13,58 @@ pub trait Interpolate {
     ///
     /// # Returns
     /// The interpolated value at point `x`
-    fn interpolate(x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> f64;
+    ///
+    /// # Errors
+    /// Returns an error if there are not enough data points, if `x_` and `y_` have
+    /// different lengths or if `x` is outside the data range and extrapolation is disabled.
+    fn interpolate(x: f64, x_: &[f64], y_: &[f64], enable_extrapolation: bool) -> Result<f64>;
+}
+
+/// Checks that the data holds at least two points with as many x as y values, and that `x`
+/// lies within the data range unless extrapolation is enabled.
+///
+/// # Errors
+/// Returns an `InsufficientPointsErr`, an `InvalidValueErr` or an `OutOfRangeErr` accordingly.
+pub(crate) fn check_interpolation_data(
+    x: f64,
+    x_: &[f64],
+    y_: &[f64],
+    enable_extrapolation: bool,
+) -> Result<()> {
+    if x_.len() != y_.len() {
+        return Err(AtlasError::InvalidValueErr(format!(
+            "Interpolation data has {} x values but {} y values",
+            x_.len(),
+            y_.len()
+        )));
+    }
+
+    if x_.len() < 2 {
+        return Err(AtlasError::InsufficientPointsErr(format!(
+            "Interpolation needs at least two points, got {}",
+            x_.len()
+        )));
+    }
+
+    let (first_x, last_x) = (x_[0], x_[x_.len() - 1]);
+    if !enable_extrapolation && !(first_x..=last_x).contains(&x) {
+        return Err(AtlasError::OutOfRangeErr(format!(
+            "Extrapolation is not enabled, and {x} is outside the range [{first_x}, {last_x}]"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that values interpolated on their logarithm are strictly positive. It scans every
+/// value, so it is meant to run once when the data is set rather than on each interpolation.
+///
+/// # Errors
+/// Returns a `NonPositiveValueErr` on the first value that is not strictly positive.
+pub(crate) fn check_positive_data(y_: &[f64]) -> Result<()> {
+    if let Some(value) = y_.iter().find(|y| **y <= 0.0 || y.is_nan()) {
+        return Err(AtlasError::NonPositiveValueErr(format!(
+            "Logarithmic interpolation requires positive values, got {value}"
+        )));
+    }
+    Ok(())
 }
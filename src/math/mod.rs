@@ -1 +1,3 @@
+pub mod ad;
 pub mod interpolation;
+pub mod optionpricing;
@@ -0,0 +1,188 @@
+//! Closed form prices of European options on a forward, under the shifted lognormal (Black-76)
+//! and normal (Bachelier) models.
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::{AtlasError, Result};
+
+/// # `OptionType`
+/// The right bought with a European option.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionType {
+    /// The right to receive the underlying, paying the strike.
+    Call,
+    /// The right to deliver the underlying, receiving the strike.
+    Put,
+}
+
+impl OptionType {
+    /// Returns 1 for calls and -1 for puts.
+    #[must_use]
+    pub const fn sign(&self) -> f64 {
+        match self {
+            Self::Call => 1.0,
+            Self::Put => -1.0,
+        }
+    }
+
+    /// Returns the payoff of the option exercised at the given underlying value.
+    #[must_use]
+    pub fn intrinsic_value(&self, forward: f64, strike: f64) -> f64 {
+        (self.sign() * (forward - strike)).max(0.0)
+    }
+}
+
+/// Returns the standard normal density at `x`.
+#[must_use]
+pub fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Returns the standard normal cumulative distribution at `x`, using the double precision
+/// rational approximation of Hart (1968) as given by West (2005).
+#[must_use]
+pub fn normal_cdf(x: f64) -> f64 {
+    let x_abs = x.abs();
+    let tail = if x_abs > 37.0 {
+        0.0
+    } else if x_abs < 7.071_067_811_865_47 {
+        let numerator = [
+            0.035_262_496_599_891_1,
+            0.700_383_064_443_688,
+            6.373_962_203_531_65,
+            33.912_866_078_383,
+            112.079_291_497_871,
+            221.213_596_169_931,
+            220.206_867_912_376,
+        ]
+        .iter()
+        .fold(0.0_f64, |acc, c| acc.mul_add(x_abs, *c));
+        let denominator = [
+            0.088_388_347_648_318_4,
+            1.755_667_163_182_64,
+            16.064_177_579_207,
+            86.780_732_202_946_1,
+            296.564_248_779_674,
+            637.333_633_378_831,
+            793.826_512_519_948,
+            440.413_735_824_752,
+        ]
+        .iter()
+        .fold(0.0_f64, |acc, c| acc.mul_add(x_abs, *c));
+        (-0.5 * x_abs * x_abs).exp() * numerator / denominator
+    } else {
+        let fraction = [4.0, 3.0, 2.0, 1.0]
+            .iter()
+            .fold(x_abs + 0.65, |acc, c| x_abs + c / acc);
+        (-0.5 * x_abs * x_abs).exp() / fraction / (2.0 * std::f64::consts::PI).sqrt()
+    };
+    if x > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Returns the undiscounted Black-76 price of an option on `forward`.
+///
+/// The shifted forward `forward + shift` is lognormal with total standard deviation `std_dev`
+/// (volatility times the square root of the time to expiry). A zero shift gives the classic
+/// Black formula.
+///
+/// # Errors
+/// Returns an error if the standard deviation is negative or the shifted forward is not
+/// positive.
+pub fn black_formula(
+    option_type: OptionType,
+    forward: f64,
+    strike: f64,
+    std_dev: f64,
+    shift: f64,
+) -> Result<f64> {
+    if std_dev < 0.0 {
+        return Err(AtlasError::InvalidValueErr(format!(
+            "Negative standard deviation ({std_dev}) in Black formula"
+        )));
+    }
+    let (forward, strike) = (forward + shift, strike + shift);
+    if forward <= 0.0 {
+        return Err(AtlasError::NonPositiveValueErr(format!(
+            "Shifted forward ({forward}) must be positive in Black formula"
+        )));
+    }
+    // a non positive shifted strike is always exercised
+    if strike <= 0.0 || std_dev == 0.0 {
+        return Ok(option_type.intrinsic_value(forward, strike));
+    }
+    let sign = option_type.sign();
+    let d1 = 0.5f64.mul_add(std_dev, (forward / strike).ln() / std_dev);
+    let d2 = d1 - std_dev;
+    Ok(sign * forward.mul_add(normal_cdf(sign * d1), -strike * normal_cdf(sign * d2)))
+}
+
+/// Returns the undiscounted Bachelier price of an option on a normally distributed `forward`.
+///
+/// The total standard deviation `std_dev` is the normal volatility times the square root of the
+/// time to expiry.
+///
+/// # Errors
+/// Returns an error if the standard deviation is negative.
+pub fn bachelier_formula(
+    option_type: OptionType,
+    forward: f64,
+    strike: f64,
+    std_dev: f64,
+) -> Result<f64> {
+    if std_dev < 0.0 {
+        return Err(AtlasError::InvalidValueErr(format!(
+            "Negative standard deviation ({std_dev}) in Bachelier formula"
+        )));
+    }
+    if std_dev == 0.0 {
+        return Ok(option_type.intrinsic_value(forward, strike));
+    }
+    let moneyness = option_type.sign() * (forward - strike);
+    let d = moneyness / std_dev;
+    Ok(moneyness.mul_add(normal_cdf(d), std_dev * normal_pdf(d)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-15);
+        assert!((normal_cdf(1.959_963_984_540_054) - 0.975).abs() < 1e-14);
+        assert!((normal_cdf(-1.0) - 0.158_655_253_931_457_05).abs() < 1e-14);
+        assert!((normal_cdf(8.0) + normal_cdf(-8.0) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_put_call_parity() -> Result<()> {
+        let (forward, strike) = (0.03, 0.035);
+        let call = black_formula(OptionType::Call, forward, strike, 0.2, 0.01)?;
+        let put = black_formula(OptionType::Put, forward, strike, 0.2, 0.01)?;
+        assert!((call - put - (forward - strike)).abs() < 1e-15);
+
+        let call = bachelier_formula(OptionType::Call, forward, strike, 0.01)?;
+        let put = bachelier_formula(OptionType::Put, forward, strike, 0.01)?;
+        assert!((call - put - (forward - strike)).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_at_the_money() -> Result<()> {
+        // at the money, Black gives F (2N(s/2) - 1) and Bachelier s / sqrt(2 pi)
+        let black = black_formula(OptionType::Call, 0.05, 0.05, 0.1, 0.0)?;
+        let expected = 0.05 * 2.0f64.mul_add(normal_cdf(0.05), -1.0);
+        assert!((black - expected).abs() < 1e-15);
+        let bachelier = bachelier_formula(OptionType::Put, 0.05, 0.05, 0.01)?;
+        let expected = 0.01 * normal_pdf(0.0);
+        assert!((bachelier - expected).abs() < 1e-15);
+
+        assert!(black_formula(OptionType::Call, -0.02, 0.01, 0.1, 0.01).is_err());
+        assert!(bachelier_formula(OptionType::Call, 0.01, 0.01, -0.1).is_err());
+        Ok(())
+    }
+}
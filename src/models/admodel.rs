@@ -0,0 +1,431 @@
+use std::ops::Range;
+
+use crate::{
+    core::{
+        marketstore::MarketStore,
+        meta::{ForwardRateRequest, MarketData, MarketRequest},
+    },
+    math::ad::dual::Dual,
+    rates::{
+        interestrate::InterestRate, traits::HasReferenceDate,
+        yieldtermstructure::discounttermstructure::DiscountTermStructure,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{simplemodel::SimpleModel, traits::Model};
+
+/// Curve whose nodes are differentiated, with the index of its first node variable.
+#[derive(Clone)]
+struct DifferentiatedCurve<'a> {
+    id: usize,
+    curve: &'a DiscountTermStructure,
+    offset: usize,
+}
+
+impl DifferentiatedCurve<'_> {
+    /// Returns the node discount factors as `Dual` variables.
+    fn nodes(&self) -> Vec<Dual> {
+        self.curve
+            .discount_factors()
+            .iter()
+            .enumerate()
+            .map(|(i, df)| Dual::variable(*df, self.offset + i))
+            .collect()
+    }
+
+    const fn variables(&self) -> Range<usize> {
+        self.offset..self.offset + self.curve.discount_factors().len()
+    }
+}
+
+/// # `AdModel`
+/// A model that generates market data as `Dual` numbers, so that a single pricing pass with
+/// `NPVConstVisitor` returns the NPV together with its derivatives with respect to the
+/// discount factor of every node of the registered curves.
+///
+/// Curves are registered with the id of the index they are linked to in the market store.
+/// Discount factors and forward rates on those indices are differentiated; every other value
+/// (other indices, exchange rates, past fixings, compounded overnight rates) is taken from a
+/// `SimpleModel` as a constant. `NPVConstVisitor` pays floating rate coupons on the
+/// differentiated forwards, so their NPV carries the sensitivity of both their projection and
+/// their discounting; coupons fixed on past fixings keep a constant amount.
+///
+/// ## Parameters
+/// * `market_store` - The market store.
+///
+/// ## Example
+/// ```
+/// use std::sync::{Arc, RwLock};
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let curve = DiscountTermStructure::new(
+///     vec![reference_date, Date::new(2025, 1, 2), Date::new(2026, 1, 2)],
+///     vec![1.0, 0.96, 0.92],
+///     DayCounter::Actual365,
+///     Interpolator::LogLinear,
+///     true,
+/// )
+/// .unwrap();
+///
+/// let mut market_store = MarketStore::new(reference_date, Currency::USD);
+/// let index = IborIndex::new(reference_date).with_term_structure(Arc::new(curve.clone()));
+/// market_store
+///     .mut_index_store()
+///     .add_index(0, Arc::new(RwLock::new(index)))
+///     .unwrap();
+///
+/// let model = AdModel::new(&market_store).with_curve(0, &curve);
+/// let request = MarketRequest::new(
+///     0,
+///     Some(DiscountFactorRequest::new(0, Date::new(2025, 7, 2))),
+///     None,
+///     Some(ExchangeRateRequest::new(Currency::USD, None, None)),
+/// );
+/// let data = model.gen_market_data(&[request]).unwrap();
+/// let df = data[0].df().unwrap();
+/// // the discount factor only depends on the two surrounding nodes
+/// assert_eq!(model.gradient(0, &df).unwrap()[0], 0.0);
+/// assert!(df.derivative(1) > 0.0 && df.derivative(2) > 0.0);
+/// ```
+#[derive(Clone)]
+pub struct AdModel<'a> {
+    market_store: &'a MarketStore,
+    curves: Vec<DifferentiatedCurve<'a>>,
+}
+
+#[allow(clippy::elidable_lifetime_names)]
+impl<'a> AdModel<'a> {
+    /// Creates a new `AdModel` with no differentiated curves.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(market_store: &'a MarketStore) -> Self {
+        Self {
+            market_store,
+            curves: Vec::new(),
+        }
+    }
+
+    /// Differentiates the curve linked to the index `id`. The curve must be the one the index
+    /// prices with; its nodes are numbered after those of the curves registered before it.
+    #[must_use]
+    pub fn with_curve(mut self, id: usize, curve: &'a DiscountTermStructure) -> Self {
+        let offset = self.curves.last().map_or(0, |last| last.variables().end);
+        self.curves.push(DifferentiatedCurve { id, curve, offset });
+        self
+    }
+
+    /// Returns the variables holding the nodes of the curve registered for the index `id`.
+    ///
+    /// # Errors
+    /// Returns an error if no curve is registered for the index.
+    pub fn variables(&self, id: usize) -> Result<Range<usize>> {
+        Ok(self.curve(id)?.variables())
+    }
+
+    /// Returns the derivatives of `value` with respect to each node of the curve registered
+    /// for the index `id`, including the zero ones.
+    ///
+    /// # Errors
+    /// Returns an error if no curve is registered for the index.
+    pub fn gradient(&self, id: usize, value: &Dual) -> Result<Vec<f64>> {
+        Ok(self.variables(id)?.map(|i| value.derivative(i)).collect())
+    }
+
+    /// Generates the market data for the given requests.
+    ///
+    /// # Errors
+    /// Returns an error if any requested value cannot be generated.
+    pub fn gen_market_data(
+        &self,
+        market_request: &[MarketRequest],
+    ) -> Result<Vec<MarketData<Dual>>> {
+        let model = SimpleModel::new(self.market_store);
+        market_request
+            .iter()
+            .map(|request| self.gen_node(&model, request))
+            .collect()
+    }
+
+    fn curve(&self, id: usize) -> Result<&DifferentiatedCurve<'a>> {
+        self.curves
+            .iter()
+            .find(|curve| curve.id == id)
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "No differentiated curve for index {id}"
+            )))
+    }
+
+    fn gen_node(&self, model: &SimpleModel, request: &MarketRequest) -> Result<MarketData<Dual>> {
+        let reference_date = self.market_store.reference_date();
+        let df = match request.df() {
+            Some(df) => match self.curve(df.provider_id()) {
+                Ok(curve) if df.date() > reference_date => Some(
+                    curve
+                        .curve
+                        .discount_factor_with(df.date(), &curve.nodes())?,
+                ),
+                _ => Some(Dual::constant(model.gen_df_data(df)?)),
+            },
+            None => None,
+        };
+        let fwd = match request.fwd() {
+            Some(fwd) => Some(self.gen_fwd_data(model, fwd)?),
+            None => None,
+        };
+        let fx = match request.fx() {
+            Some(fx) => Some(Dual::constant(model.gen_fx_data(fx)?)),
+            None => None,
+        };
+        let numerarie = Dual::constant(model.gen_numerarie(request)?);
+        Ok(MarketData::new(
+            request.id(),
+            reference_date,
+            df,
+            fwd,
+            fx,
+            numerarie,
+        ))
+    }
+
+    fn gen_fwd_data(&self, model: &SimpleModel, fwd: ForwardRateRequest) -> Result<Dual> {
+        let reference_date = self.market_store.reference_date();
+        let Ok(curve) = self.curve(fwd.provider_id()) else {
+            return Ok(Dual::constant(model.gen_fwd_data(fwd)?));
+        };
+        // past fixings, expired forwards and compounded overnight rates are not differentiated
+        if fwd.start_date() < reference_date
+            || fwd.end_date() <= reference_date
+            || fwd.overnight_observation().is_some()
+        {
+            return Ok(Dual::constant(model.gen_fwd_data(fwd)?));
+        }
+        let nodes = curve.nodes();
+        let compound = curve.curve.discount_factor_with(fwd.start_date(), &nodes)?
+            / curve.curve.discount_factor_with(fwd.end_date(), &nodes)?;
+        let t = curve
+            .curve
+            .day_counter()
+            .year_fraction(fwd.start_date(), fwd.end_date());
+        InterestRate::implied_rate_from_compound_factor(
+            compound,
+            fwd.compounding(),
+            fwd.frequency(),
+            t,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::{
+            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument,
+        },
+        math::interpolation::enums::Interpolator,
+        rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            interestrateindex::iborindex::IborIndex, traits::YieldProvider,
+        },
+        time::{
+            date::Date,
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    fn create_curve(discount_factors: Vec<f64>) -> Result<DiscountTermStructure> {
+        let reference_date = Date::new(2024, 1, 2);
+        let dates = [0, 1, 2, 3, 5, 7]
+            .iter()
+            .map(|years| reference_date + Period::new(*years, TimeUnit::Years))
+            .collect();
+        DiscountTermStructure::new(
+            dates,
+            discount_factors,
+            DayCounter::Actual365,
+            Interpolator::LogLinear,
+            true,
+        )
+    }
+
+    fn create_store(curve: &DiscountTermStructure) -> Result<MarketStore> {
+        let reference_date = curve.reference_date();
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        let index = IborIndex::new(reference_date).with_term_structure(Arc::new(curve.clone()));
+        market_store
+            .mut_index_store()
+            .add_index(0, Arc::new(RwLock::new(index)))?;
+        Ok(market_store)
+    }
+
+    #[test]
+    fn test_gradient_against_bump_and_reprice() -> Result<()> {
+        let discount_factors = vec![1.0, 0.965, 0.93, 0.895, 0.83, 0.77];
+        let curve = create_curve(discount_factors.clone())?;
+        let market_store = create_store(&curve)?;
+
+        let mut instrument = MakeFixedRateInstrument::new()
+            .with_start_date(curve.reference_date())
+            .with_end_date(curve.reference_date() + Period::new(6, TimeUnit::Years))
+            .with_rate(InterestRate::new(
+                0.04,
+                Compounding::Simple,
+                Frequency::Annual,
+                DayCounter::Actual365,
+            ))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .with_discount_curve_id(Some(0))
+            .with_notional(1_000_000.0)
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let model = AdModel::new(&market_store).with_curve(0, &curve);
+        let market_data = model.gen_market_data(&requests)?;
+        let npv = NPVConstVisitor::new(&market_data, true).visit(&instrument)?;
+        let gradient = model.gradient(0, &npv)?;
+
+        let reprice = |discount_factors: Vec<f64>| -> Result<f64> {
+            let store = create_store(&create_curve(discount_factors)?)?;
+            let market_data = SimpleModel::new(&store).gen_market_data(&requests)?;
+            NPVConstVisitor::new(&market_data, true).visit(&instrument)
+        };
+        assert!((npv.value() - reprice(discount_factors.clone())?).abs() < 1e-8);
+
+        // the first node is pinned to one by the curve, so it cannot be bumped and repriced
+        let h = 1e-6;
+        for i in 1..discount_factors.len() {
+            let (mut up, mut down) = (discount_factors.clone(), discount_factors.clone());
+            up[i] += h;
+            down[i] -= h;
+            let bumped = (reprice(up)? - reprice(down)?) / (2.0 * h);
+            assert!(
+                (gradient[i] - bumped).abs() < 1e-4 * bumped.abs().max(1.0),
+                "node {i}: {} vs {bumped}",
+                gradient[i]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_floating_rate_gradient_against_bump_and_reprice() -> Result<()> {
+        let discount_factors = vec![1.0, 0.965, 0.93, 0.895, 0.83, 0.77];
+        let curve = create_curve(discount_factors.clone())?;
+        let market_store = create_store(&curve)?;
+
+        let mut instrument = MakeFloatingRateInstrument::new()
+            .with_start_date(curve.reference_date())
+            .with_end_date(curve.reference_date() + Period::new(6, TimeUnit::Years))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_definition(RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Semiannual,
+            ))
+            .with_spread(0.002)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .with_notional(1_000_000.0)
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let model = AdModel::new(&market_store).with_curve(0, &curve);
+        let market_data = model.gen_market_data(&requests)?;
+        let npv = NPVConstVisitor::new(&market_data, true).visit(&instrument)?;
+        let gradient = model.gradient(0, &npv)?;
+
+        // the coupons are fixed again on every bumped curve
+        let reprice = |discount_factors: Vec<f64>| -> Result<f64> {
+            let store = create_store(&create_curve(discount_factors)?)?;
+            let market_data = SimpleModel::new(&store).gen_market_data(&requests)?;
+            let mut instrument = instrument.clone();
+            FixingVisitor::new(&market_data).visit(&mut instrument)?;
+            NPVConstVisitor::new(&market_data, true).visit(&instrument)
+        };
+        assert!((npv.value() - reprice(discount_factors.clone())?).abs() < 1e-6);
+
+        let h = 1e-6;
+        for i in 1..discount_factors.len() {
+            let (mut up, mut down) = (discount_factors.clone(), discount_factors.clone());
+            up[i] += h;
+            down[i] -= h;
+            let bumped = (reprice(up)? - reprice(down)?) / (2.0 * h);
+            assert!(
+                (gradient[i] - bumped).abs() < 1e-4 * bumped.abs().max(1.0),
+                "node {i}: {} vs {bumped}",
+                gradient[i]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_forward_rate_gradient() -> Result<()> {
+        let discount_factors = vec![1.0, 0.965, 0.93, 0.895, 0.83, 0.77];
+        let curve = create_curve(discount_factors.clone())?;
+        let market_store = create_store(&curve)?;
+        let (start, end) = (Date::new(2025, 7, 2), Date::new(2026, 7, 2));
+        let request = MarketRequest::new(
+            0,
+            None,
+            Some(ForwardRateRequest::new(
+                0,
+                start,
+                start,
+                end,
+                Compounding::Simple,
+                Frequency::Annual,
+            )),
+            None,
+        );
+
+        let model = AdModel::new(&market_store).with_curve(0, &curve);
+        let fwd = model.gen_market_data(&[request])?[0].fwd()?;
+        let gradient = model.gradient(0, &fwd)?;
+        let forward_rate = |discount_factors: Vec<f64>| -> Result<f64> {
+            create_curve(discount_factors)?.forward_rate(
+                start,
+                end,
+                Compounding::Simple,
+                Frequency::Annual,
+            )
+        };
+        assert!((fwd.value() - forward_rate(discount_factors.clone())?).abs() < 1e-12);
+
+        let h = 1e-7;
+        for i in 1..discount_factors.len() {
+            let (mut up, mut down) = (discount_factors.clone(), discount_factors.clone());
+            up[i] += h;
+            down[i] -= h;
+            let bumped = (forward_rate(up)? - forward_rate(down)?) / (2.0 * h);
+            assert!((gradient[i] - bumped).abs() < 1e-6, "node {i}");
+        }
+        assert!(model.variables(1).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,989 @@
+use argmin::{
+    core::{CostFunction, Error, Executor, State},
+    solver::brent::BrentRoot,
+};
+
+use crate::{
+    core::{
+        marketstore::MarketStore,
+        meta::{
+            DiscountFactorRequest, ExchangeRateRequest, ForwardRateRequest, MarketData,
+            MarketRequest,
+        },
+    },
+    math::optionpricing::{bachelier_formula, black_formula, OptionType},
+    rates::{
+        enums::VolatilityType, indexstore::ReadIndex, interestrate::InterestRate,
+        traits::HasReferenceDate,
+    },
+    time::{
+        date::Date,
+        daycounter::DayCounter,
+        enums::{Frequency, TimeUnit},
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    simplemodel::SimpleModel,
+    traits::{Model, StochasticModel},
+};
+
+/// Mean reversions below this value are treated as zero in the closed form integrals.
+const MIN_MEAN_REVERSION: f64 = 1e-8;
+/// Lower bound of the volatility searched for each calibration helper.
+const MIN_VOLATILITY: f64 = 1e-8;
+/// Upper bound of the volatility searched for each calibration helper.
+const MAX_VOLATILITY: f64 = 0.1;
+/// Lower bound of the factor searched in the Jamshidian decomposition.
+const MIN_FACTOR: f64 = -1.0;
+/// Upper bound of the factor searched in the Jamshidian decomposition.
+const MAX_FACTOR: f64 = 1.0;
+/// Accuracy of the root finders.
+const ACCURACY: f64 = 1e-12;
+/// Maximum number of iterations of the root finders.
+const MAX_ITERATIONS: u64 = 200;
+
+/// # `HullWhiteState`
+/// The state of a `HullWhiteModel` on a date: the factor `x`, the short rate minus the
+/// instantaneous forward rate of the initial curve, and its integral from the reference date of
+/// the market store, which gives the value of the bank account.
+///
+/// ## Parameters
+/// * `date` - The date of the state
+/// * `factor` - The value of the factor on the date
+/// * `integrated_factor` - The integral of the factor up to the date
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HullWhiteState {
+    date: Date,
+    factor: f64,
+    integrated_factor: f64,
+}
+
+impl HullWhiteState {
+    /// Creates a new `HullWhiteState`.
+    #[must_use]
+    pub const fn new(date: Date, factor: f64, integrated_factor: f64) -> Self {
+        Self {
+            date,
+            factor,
+            integrated_factor,
+        }
+    }
+
+    /// Returns the date of the state.
+    #[must_use]
+    pub const fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Returns the factor.
+    #[must_use]
+    pub const fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns the integral of the factor.
+    #[must_use]
+    pub const fn integrated_factor(&self) -> f64 {
+        self.integrated_factor
+    }
+}
+
+/// The end date and accrual of each period of the underlying rate of a calibration helper.
+type Periods = Vec<(Date, f64)>;
+
+/// Variance of the factor, of its integral and their covariance, accumulated over a period.
+#[derive(Default)]
+struct FactorMoments {
+    variance: f64,
+    covariance: f64,
+    integral_variance: f64,
+}
+
+/// # `HullWhiteCalibrationHelper`
+/// A European option on a fixed rate, quoted by its volatility, to calibrate the volatility of
+/// a `HullWhiteModel`. A caplet is an option on a single period, paid at its end; a swaption is
+/// an option on the swap rate of the periods of its underlying swap, which starts at expiry.
+/// Accruals are measured with the day counter of the model and strikes default to the forward
+/// rate.
+///
+/// ## Parameters
+/// * `expiry` - The expiry of the option
+/// * `payment_dates` - The end dates of the periods of the underlying rate
+/// * `strike` - The strike, or `None` for an at the money option
+/// * `volatility` - The quoted volatility
+/// * `volatility_type` - The quoting convention of the volatility
+#[derive(Clone, Debug, PartialEq)]
+pub struct HullWhiteCalibrationHelper {
+    expiry: Date,
+    payment_dates: Vec<Date>,
+    strike: Option<f64>,
+    volatility: f64,
+    volatility_type: VolatilityType,
+}
+
+impl HullWhiteCalibrationHelper {
+    /// Creates a helper for a caplet on the rate between the expiry and the end date.
+    ///
+    /// # Errors
+    /// Returns an error if the end date is not after the expiry or the volatility is negative.
+    pub fn caplet(
+        expiry: Date,
+        end_date: Date,
+        strike: Option<f64>,
+        volatility: f64,
+        volatility_type: VolatilityType,
+    ) -> Result<Self> {
+        Self::new(expiry, vec![end_date], strike, volatility, volatility_type)
+    }
+
+    /// Creates a helper for a swaption on a swap starting at the expiry, with the given tenor
+    /// and frequency of its fixed leg.
+    ///
+    /// # Errors
+    /// Returns an error if the frequency has no period, the tenor is shorter than a period or
+    /// the volatility is negative.
+    pub fn swaption(
+        expiry: Date,
+        tenor: Period,
+        frequency: Frequency,
+        strike: Option<f64>,
+        volatility: f64,
+        volatility_type: VolatilityType,
+    ) -> Result<Self> {
+        let period = Period::from_frequency(frequency).ok_or(AtlasError::InvalidValueErr(
+            format!("Frequency {frequency:?} has no period"),
+        ))?;
+        let end_date = expiry + tenor;
+        let mut payment_dates = Vec::new();
+        let mut date = expiry + period;
+        while date <= end_date {
+            payment_dates.push(date);
+            date = date + period;
+        }
+        Self::new(expiry, payment_dates, strike, volatility, volatility_type)
+    }
+
+    fn new(
+        expiry: Date,
+        payment_dates: Vec<Date>,
+        strike: Option<f64>,
+        volatility: f64,
+        volatility_type: VolatilityType,
+    ) -> Result<Self> {
+        if payment_dates.first().is_none_or(|first| *first <= expiry) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Calibration helper with expiry {expiry} needs payment dates after its expiry"
+            )));
+        }
+        if volatility < 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Negative volatility ({volatility})"
+            )));
+        }
+        Ok(Self {
+            expiry,
+            payment_dates,
+            strike,
+            volatility,
+            volatility_type,
+        })
+    }
+
+    /// Returns the expiry.
+    #[must_use]
+    pub const fn expiry(&self) -> Date {
+        self.expiry
+    }
+
+    /// Returns the end dates of the periods of the underlying rate.
+    #[must_use]
+    pub fn payment_dates(&self) -> &[Date] {
+        &self.payment_dates
+    }
+
+    /// Returns the strike, if set.
+    #[must_use]
+    pub const fn strike(&self) -> Option<f64> {
+        self.strike
+    }
+
+    /// Returns the quoted volatility.
+    #[must_use]
+    pub const fn volatility(&self) -> f64 {
+        self.volatility
+    }
+
+    /// Returns the end date and accrual of each period, the annuity and the forward rate.
+    fn periods(&self, model: &HullWhiteModel) -> Result<(Periods, f64, f64)> {
+        let mut start_date = self.expiry;
+        let mut annuity = 0.0;
+        let mut periods = Vec::new();
+        let mut last_discount = 1.0;
+        for date in &self.payment_dates {
+            let accrual = model.day_counter.year_fraction(start_date, *date);
+            last_discount = model.initial_discount(model.curve_id, *date)?;
+            annuity = accrual.mul_add(last_discount, annuity);
+            periods.push((*date, accrual));
+            start_date = *date;
+        }
+        let forward =
+            (model.initial_discount(model.curve_id, self.expiry)? - last_discount) / annuity;
+        Ok((periods, annuity, forward))
+    }
+
+    /// Returns the price of the option with its quoted volatility.
+    fn market_price(&self, model: &HullWhiteModel) -> Result<f64> {
+        let (_, annuity, forward) = self.periods(model)?;
+        let strike = self.strike.unwrap_or(forward);
+        let std_dev = self.volatility * model.time(self.expiry).sqrt();
+        let price = match self.volatility_type {
+            VolatilityType::ShiftedLognormal(shift) => {
+                black_formula(OptionType::Call, forward, strike, std_dev, shift)?
+            }
+            VolatilityType::Normal => {
+                bachelier_formula(OptionType::Call, forward, strike, std_dev)?
+            }
+        };
+        Ok(annuity * price)
+    }
+
+    /// Returns the price of the option in the model, with the decomposition of Jamshidian into
+    /// options on the zero coupon bonds paying the fixed coupons.
+    fn model_price(&self, model: &HullWhiteModel) -> Result<f64> {
+        let (periods, _, forward) = self.periods(model)?;
+        let strike = self.strike.unwrap_or(forward);
+        let coupons: Vec<(Date, f64)> = periods
+            .iter()
+            .enumerate()
+            .map(|(i, (date, accrual))| {
+                let principal = if i + 1 == periods.len() { 1.0 } else { 0.0 };
+                (*date, strike.mul_add(*accrual, principal))
+            })
+            .collect();
+        let cost = CouponBondValue {
+            model,
+            expiry: self.expiry,
+            coupons: &coupons,
+        };
+        let solver = BrentRoot::new(MIN_FACTOR, MAX_FACTOR, ACCURACY);
+        let res = Executor::new(cost, solver)
+            .configure(|state| state.max_iters(MAX_ITERATIONS).target_cost(0.0))
+            .run()?;
+        let factor = res.state().get_best_param().copied().ok_or_else(|| {
+            AtlasError::EvaluationErr(format!(
+                "No exercise boundary found for calibration helper with expiry {}",
+                self.expiry
+            ))
+        })?;
+        // a payer option is a put on the coupon bond, struck at par
+        coupons.iter().try_fold(0.0, |acc, (date, coupon)| {
+            let strike = model.discount_bond(self.expiry, *date, factor)?;
+            let put = model.zero_bond_option(OptionType::Put, self.expiry, *date, strike)?;
+            Ok(coupon.mul_add(put, acc))
+        })
+    }
+}
+
+/// # `CouponBondValue`
+/// Value at expiry of a bond paying the given coupons, minus par, as a function of the factor.
+struct CouponBondValue<'a> {
+    model: &'a HullWhiteModel<'a>,
+    expiry: Date,
+    coupons: &'a [(Date, f64)],
+}
+
+impl CostFunction for CouponBondValue<'_> {
+    type Param = f64;
+    type Output = f64;
+    fn cost(&self, param: &Self::Param) -> std::result::Result<Self::Output, Error> {
+        let value = self.coupons.iter().try_fold(0.0, |acc, (date, coupon)| {
+            Ok::<f64, AtlasError>(
+                coupon.mul_add(self.model.discount_bond(self.expiry, *date, *param)?, acc),
+            )
+        })?;
+        Ok(value - 1.0)
+    }
+}
+
+/// # `CalibrationError`
+/// Model minus market price of a calibration helper when the volatility of its last period is
+/// the trial value.
+struct CalibrationError<'a> {
+    model: &'a HullWhiteModel<'a>,
+    helper: &'a HullWhiteCalibrationHelper,
+    market_price: f64,
+}
+
+impl CostFunction for CalibrationError<'_> {
+    type Param = f64;
+    type Output = f64;
+    fn cost(&self, param: &Self::Param) -> std::result::Result<Self::Output, Error> {
+        let mut model = self.model.clone();
+        if let Some(volatility) = model.volatilities.last_mut() {
+            *volatility = *param;
+        }
+        Ok(self.helper.model_price(&model)? - self.market_price)
+    }
+}
+
+/// # `HullWhiteModel`
+/// The one factor Hull-White model `dr = (theta(t) - a r) dt + sigma(t) dW`, with constant mean
+/// reversion `a` and piecewise constant volatility, fitted to the initial curves of the index
+/// store. The model is written in terms of the factor `x = r - f(0, t)`, the short rate minus
+/// the instantaneous forward rate of the initial curve, so zero coupon bonds have the closed
+/// form
+///
+/// `P(t, T | x) = P(0, T) / P(0, t) * exp(-B(t, T) x - B(t, T)^2 y(t) / 2)`
+///
+/// where `B(t, T) = (1 - exp(-a (T - t))) / a` and `y(t)` is the variance of the factor. The
+/// numeraire is the bank account, `exp(integral of x) / P(0, t)`.
+///
+/// The model generates the market data in a given state, so instruments valued with
+/// `NPVConstVisitor` get their value in that state divided by the numeraire. Every index is
+/// driven by the same factor, keeping its initial spread to the curve of the model. Forward
+/// rates are the initial forward rates of the indices, moved by the change the factor implies
+/// on them. In the initial state, the model gives the same market data as `SimpleModel`.
+///
+/// ## Parameters
+/// * `market_store` - The market store with the initial curves
+/// * `curve_id` - The ID of the index whose curve defines the short rate and the numeraire
+/// * `mean_reversion` - The mean reversion `a`
+/// * `volatility` - The volatility `sigma`, until other volatilities are set or calibrated
+///
+/// ## Example
+/// ```
+/// use std::sync::{Arc, RwLock};
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let curve = Arc::new(FlatForwardTermStructure::new(
+///     reference_date,
+///     0.03,
+///     RateDefinition::default(),
+/// ));
+/// let index = IborIndex::new(reference_date).with_term_structure(curve);
+/// let mut market_store = MarketStore::new(reference_date, Currency::USD);
+/// market_store
+///     .mut_index_store()
+///     .add_index(0, Arc::new(RwLock::new(index)))
+///     .unwrap();
+///
+/// let model = HullWhiteModel::new(&market_store, 0, 0.03, 0.01);
+/// let (expiry, maturity) = (Date::new(2025, 1, 2), Date::new(2030, 1, 2));
+/// // bonds are cheaper when the short rate is higher
+/// let low = model.discount_bond(expiry, maturity, -0.01).unwrap();
+/// let high = model.discount_bond(expiry, maturity, 0.01).unwrap();
+/// assert!(high < low);
+///
+/// // market data is generated in the state of the model
+/// let model = model
+///     .with_state(HullWhiteState::new(expiry, 0.01, 0.0))
+///     .unwrap();
+/// let df = model
+///     .gen_df_data(DiscountFactorRequest::new(0, maturity))
+///     .unwrap();
+/// assert!((df - high).abs() < 1e-15);
+/// ```
+#[derive(Clone)]
+pub struct HullWhiteModel<'a> {
+    market_store: &'a MarketStore,
+    curve_id: usize,
+    mean_reversion: f64,
+    volatility_dates: Vec<Date>,
+    volatilities: Vec<f64>,
+    day_counter: DayCounter,
+    state: HullWhiteState,
+}
+
+#[allow(clippy::elidable_lifetime_names)]
+impl<'a> HullWhiteModel<'a> {
+    /// Creates a new `HullWhiteModel` with constant volatility, in its initial state on the
+    /// reference date of the market store.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        market_store: &'a MarketStore,
+        curve_id: usize,
+        mean_reversion: f64,
+        volatility: f64,
+    ) -> Self {
+        Self {
+            market_store,
+            curve_id,
+            mean_reversion,
+            volatility_dates: Vec::new(),
+            volatilities: vec![volatility],
+            day_counter: DayCounter::Actual365,
+            state: HullWhiteState::new(market_store.reference_date(), 0.0, 0.0),
+        }
+    }
+
+    /// Sets piecewise constant volatilities: the first one applies until the first date, each
+    /// of the next ones until the following date, and the last one after the last date.
+    ///
+    /// # Errors
+    /// Returns an error if there is not one more volatility than dates or if the dates are not
+    /// increasing and after the reference date.
+    pub fn with_volatilities(mut self, dates: Vec<Date>, volatilities: Vec<f64>) -> Result<Self> {
+        if volatilities.len() != dates.len() + 1 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Hull-White model needs {} volatilities for {} dates",
+                dates.len() + 1,
+                dates.len()
+            )));
+        }
+        if dates
+            .first()
+            .is_some_and(|first| *first <= self.market_store.reference_date())
+            || dates.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Hull-White volatility dates must be increasing and after the reference date"
+                    .to_string(),
+            ));
+        }
+        self.volatility_dates = dates;
+        self.volatilities = volatilities;
+        Ok(self)
+    }
+
+    /// Sets the day counter used to measure times in the model. Defaults to `Actual365`.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the state in which market data is generated.
+    ///
+    /// # Errors
+    /// Returns an error if the state date is before the reference date of the market store.
+    pub fn with_state(mut self, state: HullWhiteState) -> Result<Self> {
+        if state.date() < self.market_store.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Hull-White state date ({}) is before the reference date ({})",
+                state.date(),
+                self.market_store.reference_date()
+            )));
+        }
+        self.state = state;
+        Ok(self)
+    }
+
+    /// Calibrates one volatility by helper, from the earliest expiry to the latest, so that
+    /// the model reprices each helper. The volatility dates become the helper expiries.
+    ///
+    /// # Errors
+    /// Returns an error if there are no helpers, two helpers share an expiry, an expiry is not
+    /// after the reference date or no volatility reprices a helper.
+    pub fn calibrate(mut self, helpers: &[HullWhiteCalibrationHelper]) -> Result<Self> {
+        if helpers.is_empty() {
+            return Err(AtlasError::InvalidValueErr(
+                "At least one helper is needed to calibrate a Hull-White model".to_string(),
+            ));
+        }
+        let mut helpers: Vec<&HullWhiteCalibrationHelper> = helpers.iter().collect();
+        helpers.sort_by_key(|helper| helper.expiry());
+
+        self.volatility_dates = Vec::new();
+        self.volatilities = Vec::new();
+        for helper in helpers {
+            let last_date = self
+                .volatility_dates
+                .last()
+                .copied()
+                .unwrap_or_else(|| self.market_store.reference_date());
+            if helper.expiry() <= last_date {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Calibration helper expiry {} needs to be after the reference date and unique among helpers",
+                    helper.expiry()
+                )));
+            }
+            self.volatilities.push(MIN_VOLATILITY);
+            let cost = CalibrationError {
+                model: &self,
+                helper,
+                market_price: helper.market_price(&self)?,
+            };
+            let solver = BrentRoot::new(MIN_VOLATILITY, MAX_VOLATILITY, ACCURACY);
+            let res = Executor::new(cost, solver)
+                .configure(|state| state.max_iters(MAX_ITERATIONS).target_cost(0.0))
+                .run()?;
+            let volatility = res.state().get_best_param().copied().ok_or_else(|| {
+                AtlasError::EvaluationErr(format!(
+                    "No volatility found for calibration helper with expiry {}",
+                    helper.expiry()
+                ))
+            })?;
+            if let Some(last) = self.volatilities.last_mut() {
+                *last = volatility;
+            }
+            self.volatility_dates.push(helper.expiry());
+        }
+        self.volatility_dates.pop();
+        Ok(self)
+    }
+
+    /// Returns the ID of the index that defines the short rate.
+    #[must_use]
+    pub const fn curve_id(&self) -> usize {
+        self.curve_id
+    }
+
+    /// Returns the mean reversion.
+    #[must_use]
+    pub const fn mean_reversion(&self) -> f64 {
+        self.mean_reversion
+    }
+
+    /// Returns the dates where the volatility changes.
+    #[must_use]
+    pub fn volatility_dates(&self) -> &[Date] {
+        &self.volatility_dates
+    }
+
+    /// Returns the piecewise constant volatilities.
+    #[must_use]
+    pub fn volatilities(&self) -> &[f64] {
+        &self.volatilities
+    }
+
+    /// Returns the state in which market data is generated.
+    #[must_use]
+    pub const fn state(&self) -> HullWhiteState {
+        self.state
+    }
+
+    /// Returns the time from the reference date of the market store to the date.
+    #[must_use]
+    pub fn time(&self, date: Date) -> f64 {
+        self.day_counter
+            .year_fraction(self.market_store.reference_date(), date)
+    }
+
+    /// Returns `B(t, T)`, the sensitivity of the log price of the bond maturing at `maturity`
+    /// to the factor on `date`.
+    #[must_use]
+    pub fn bond_sensitivity(&self, date: Date, maturity: Date) -> f64 {
+        let tau = self.time(maturity) - self.time(date);
+        if self.mean_reversion.abs() < MIN_MEAN_REVERSION {
+            tau
+        } else {
+            -(-self.mean_reversion * tau).exp_m1() / self.mean_reversion
+        }
+    }
+
+    /// Returns `y(t)`, the variance of the factor on `date`.
+    #[must_use]
+    pub fn factor_variance(&self, date: Date) -> f64 {
+        self.moments(0.0, self.time(date)).variance
+    }
+
+    /// Returns the price on `date` of the zero coupon bond maturing at `maturity`, given the
+    /// factor on that date.
+    ///
+    /// # Errors
+    /// Returns an error if the initial curve cannot be evaluated on the dates.
+    pub fn discount_bond(&self, date: Date, maturity: Date, factor: f64) -> Result<f64> {
+        self.discount_bond_of(self.curve_id, date, maturity, factor)
+    }
+
+    /// Returns the short rate on `date`, given the factor on that date.
+    ///
+    /// # Errors
+    /// Returns an error if the initial curve cannot be evaluated on the date.
+    pub fn short_rate(&self, date: Date, factor: f64) -> Result<f64> {
+        let next = date + Period::new(1, TimeUnit::Days);
+        let forward = (self.initial_discount(self.curve_id, date)?
+            / self.initial_discount(self.curve_id, next)?)
+        .ln()
+            / (self.time(next) - self.time(date));
+        Ok(forward + factor)
+    }
+
+    /// Returns the value of the bank account in the given state.
+    ///
+    /// # Errors
+    /// Returns an error if the initial curve cannot be evaluated on the state date.
+    pub fn numeraire(&self, state: HullWhiteState) -> Result<f64> {
+        Ok(state.integrated_factor().exp() / self.initial_discount(self.curve_id, state.date())?)
+    }
+
+    /// Returns the price on the reference date of a European option expiring at `expiry` on
+    /// the zero coupon bond maturing at `maturity`.
+    ///
+    /// # Errors
+    /// Returns an error if the initial curve cannot be evaluated on the dates.
+    pub fn zero_bond_option(
+        &self,
+        option_type: OptionType,
+        expiry: Date,
+        maturity: Date,
+        strike: f64,
+    ) -> Result<f64> {
+        let expiry_discount = self.initial_discount(self.curve_id, expiry)?;
+        let forward = self.initial_discount(self.curve_id, maturity)? / expiry_discount;
+        let std_dev = self.bond_sensitivity(expiry, maturity) * self.factor_variance(expiry).sqrt();
+        Ok(expiry_discount * black_formula(option_type, forward, strike, std_dev, 0.0)?)
+    }
+
+    /// Returns the moments of the factor and of its integral accumulated between two times.
+    fn moments(&self, start_time: f64, end_time: f64) -> FactorMoments {
+        let a = self.mean_reversion;
+        let bounds: Vec<f64> = self
+            .volatility_dates
+            .iter()
+            .map(|date| self.time(*date))
+            .collect();
+        let mut moments = FactorMoments::default();
+        for (i, volatility) in self.volatilities.iter().enumerate() {
+            let start = if i == 0 { 0.0 } else { bounds[i - 1] }.max(start_time);
+            let end = bounds
+                .get(i)
+                .copied()
+                .unwrap_or(f64::INFINITY)
+                .min(end_time);
+            if start >= end {
+                continue;
+            }
+            let variance = volatility * volatility;
+            if a.abs() < MIN_MEAN_REVERSION {
+                let (from_start, from_end) = (end_time - start, end_time - end);
+                moments.variance += variance * (end - start);
+                moments.covariance +=
+                    variance * from_start.mul_add(from_start, -from_end * from_end) / 2.0;
+                moments.integral_variance +=
+                    variance * (from_start.powi(3) - from_end.powi(3)) / 3.0;
+            } else {
+                // integrals of exp(-k a (end_time - u)) over the period
+                let decay = |k: f64| {
+                    ((-k * a * (end_time - end)).exp() - (-k * a * (end_time - start)).exp())
+                        / (k * a)
+                };
+                let (single, double) = (decay(1.0), decay(2.0));
+                moments.variance += variance * double;
+                moments.covariance += variance * (single - double) / a;
+                moments.integral_variance +=
+                    variance * (2.0f64.mul_add(-single, end - start) + double) / (a * a);
+            }
+        }
+        moments
+    }
+
+    /// Returns the mean of the factor and of its integral on `date`.
+    fn factor_means(&self, date: Date) -> (f64, f64) {
+        let moments = self.moments(0.0, self.time(date));
+        (moments.covariance, 0.5 * moments.integral_variance)
+    }
+
+    /// Returns the discount factor of the initial curve of the index to the date.
+    fn initial_discount(&self, id: usize, date: Date) -> Result<f64> {
+        if date == self.market_store.reference_date() {
+            return Ok(1.0);
+        }
+        let index = self.market_store.get_index(id)?;
+        let curve = index.read_index()?.term_structure()?;
+        curve.discount_factor(date)
+    }
+
+    fn discount_bond_of(&self, id: usize, date: Date, maturity: Date, factor: f64) -> Result<f64> {
+        let sensitivity = self.bond_sensitivity(date, maturity);
+        let convexity = 0.5 * sensitivity * sensitivity * self.factor_variance(date);
+        Ok(
+            self.initial_discount(id, maturity)? / self.initial_discount(id, date)?
+                * (-sensitivity).mul_add(factor, -convexity).exp(),
+        )
+    }
+}
+
+#[allow(clippy::elidable_lifetime_names)]
+impl<'a> Model for HullWhiteModel<'a> {
+    fn reference_date(&self) -> Date {
+        self.state.date()
+    }
+
+    fn gen_df_data(&self, df: DiscountFactorRequest) -> Result<f64> {
+        let date = df.date();
+        let state_date = self.state.date();
+        if state_date > date {
+            return Ok(0.0);
+        } else if state_date == date {
+            return Ok(1.0);
+        }
+        self.discount_bond_of(df.provider_id(), state_date, date, self.state.factor())
+    }
+
+    fn gen_fwd_data(&self, fwd: ForwardRateRequest) -> Result<f64> {
+        let state_date = self.state.date();
+        let end_date = fwd.end_date();
+        let initial_forward = SimpleModel::new(self.market_store).gen_fwd_data(fwd)?;
+        if end_date <= state_date {
+            return Ok(initial_forward);
+        }
+        // the part of the period before the state date is not moved by the factor
+        let start_date = fwd.start_date().max(state_date);
+        let id = fwd.provider_id();
+        let initial_compound =
+            self.initial_discount(id, start_date)? / self.initial_discount(id, end_date)?;
+        let compound = self.discount_bond_of(id, state_date, start_date, self.state.factor())?
+            / self.discount_bond_of(id, state_date, end_date, self.state.factor())?;
+        let time = self.day_counter.year_fraction(start_date, end_date);
+        let rate = |compound: f64| {
+            InterestRate::implied_rate_from_compound_factor(
+                compound,
+                fwd.compounding(),
+                fwd.frequency(),
+                time,
+            )
+        };
+        Ok(initial_forward + rate(compound)? - rate(initial_compound)?)
+    }
+
+    fn gen_numerarie(&self, _: &MarketRequest) -> Result<f64> {
+        self.numeraire(self.state)
+    }
+
+    fn gen_fx_data(&self, fx: ExchangeRateRequest) -> Result<f64> {
+        SimpleModel::new(self.market_store).gen_fx_data(fx)
+    }
+}
+
+#[allow(clippy::elidable_lifetime_names)]
+impl<'a> StochasticModel for HullWhiteModel<'a> {
+    type State = HullWhiteState;
+
+    fn initial_state(&self) -> HullWhiteState {
+        self.state
+    }
+
+    fn state_date(&self, state: &HullWhiteState) -> Date {
+        state.date()
+    }
+
+    fn factors(&self) -> usize {
+        2
+    }
+
+    /// Samples the factor and its integral exactly: both are gaussian and, once their means are
+    /// removed, they evolve as an Ornstein-Uhlenbeck process and its integral.
+    fn evolve(
+        &self,
+        state: &HullWhiteState,
+        date: Date,
+        normals: &[f64],
+    ) -> Result<HullWhiteState> {
+        if date < state.date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Hull-White state on {} cannot be evolved back to {date}",
+                state.date()
+            )));
+        }
+        let [first, second] = normals else {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Hull-White model needs 2 normal draws, got {}",
+                normals.len()
+            )));
+        };
+        let (start, end) = (self.time(state.date()), self.time(date));
+        let (start_factor_mean, start_integral_mean) = self.factor_means(state.date());
+        let (end_factor_mean, end_integral_mean) = self.factor_means(date);
+        let factor = state.factor() - start_factor_mean;
+        let integrated_factor = state.integrated_factor() - start_integral_mean;
+
+        let a = self.mean_reversion;
+        let (decay, sensitivity) = if a.abs() < MIN_MEAN_REVERSION {
+            (1.0, end - start)
+        } else {
+            let decay = (-a * (end - start)).exp();
+            (decay, (1.0 - decay) / a)
+        };
+        let moments = self.moments(start, end);
+        let factor_std_dev = moments.variance.max(0.0).sqrt();
+        let correlated = if factor_std_dev > 0.0 {
+            moments.covariance / factor_std_dev
+        } else {
+            0.0
+        };
+        let independent = correlated
+            .mul_add(-correlated, moments.integral_variance)
+            .max(0.0)
+            .sqrt();
+
+        let factor_shock = factor_std_dev * first;
+        let integral_shock = correlated.mul_add(*first, independent * second);
+        Ok(HullWhiteState::new(
+            date,
+            factor.mul_add(decay, factor_shock) + end_factor_mean,
+            factor.mul_add(sensitivity, integrated_factor) + integral_shock + end_integral_mean,
+        ))
+    }
+
+    fn gen_state_numerarie(&self, state: &HullWhiteState) -> Result<f64> {
+        self.numeraire(*state)
+    }
+
+    fn gen_state_market_data(
+        &self,
+        state: &HullWhiteState,
+        market_request: &[MarketRequest],
+    ) -> Result<Vec<MarketData>> {
+        self.clone()
+            .with_state(*state)?
+            .gen_market_data(market_request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alm::montecarloengine::MonteCarloEngine,
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefloatingrateinstrument::MakeFloatingRateInstrument,
+        rates::interestrate::RateDefinition,
+        utils::testing::flat_market_store,
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, Visit},
+        },
+    };
+
+    #[test]
+    fn test_initial_state_matches_simple_model() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let mut instrument = MakeFloatingRateInstrument::new()
+            .with_start_date(Date::new(2024, 1, 2))
+            .with_end_date(Date::new(2029, 1, 2))
+            .with_payment_frequency(Frequency::Quarterly)
+            .with_rate_definition(RateDefinition::default())
+            .with_spread(0.01)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::USD)
+            .with_side(Side::Receive)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let simple_data = SimpleModel::new(&market_store).gen_market_data(&requests)?;
+        let model = HullWhiteModel::new(&market_store, 0, 0.05, 0.01);
+        let model_data = model.gen_market_data(&requests)?;
+        FixingVisitor::new(&model_data).visit(&mut instrument)?;
+        let simple_npv = NPVConstVisitor::new(&simple_data, true).visit(&instrument)?;
+        let model_npv = NPVConstVisitor::new(&model_data, true).visit(&instrument)?;
+        assert!((simple_npv - model_npv).abs() < 1e-8);
+        assert!((model.numeraire(model.state())? - 1.0).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_piecewise_volatility() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let (a, sigma) = (0.05, 0.01);
+        let constant = HullWhiteModel::new(&market_store, 0, a, sigma);
+        let piecewise = HullWhiteModel::new(&market_store, 0, a, 0.0).with_volatilities(
+            vec![Date::new(2025, 1, 2), Date::new(2027, 1, 2)],
+            vec![sigma; 3],
+        )?;
+        let date = Date::new(2029, 1, 2);
+        let time = constant.time(date);
+        let expected = sigma * sigma * -(-2.0 * a * time).exp_m1() / (2.0 * a);
+        assert!((constant.factor_variance(date) - expected).abs() < 1e-15);
+        assert!((piecewise.factor_variance(date) - expected).abs() < 1e-15);
+
+        // bond options satisfy put-call parity
+        let (expiry, maturity) = (Date::new(2026, 1, 2), Date::new(2031, 1, 2));
+        let strike = 0.85;
+        let call = piecewise.zero_bond_option(OptionType::Call, expiry, maturity, strike)?;
+        let put = piecewise.zero_bond_option(OptionType::Put, expiry, maturity, strike)?;
+        let forward = strike.mul_add(
+            -piecewise.initial_discount(0, expiry)?,
+            piecewise.initial_discount(0, maturity)?,
+        );
+        assert!((call - put - forward).abs() < 1e-14);
+
+        // the short rate of the initial state is the instantaneous forward rate
+        let short_rate = piecewise.short_rate(market_store.reference_date(), 0.0)?;
+        assert!((short_rate - 0.03 * 365.0 / 360.0).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calibration() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let helpers = vec![
+            HullWhiteCalibrationHelper::swaption(
+                Date::new(2025, 1, 2),
+                Period::new(5, TimeUnit::Years),
+                Frequency::Semiannual,
+                None,
+                0.009,
+                VolatilityType::Normal,
+            )?,
+            HullWhiteCalibrationHelper::caplet(
+                Date::new(2026, 1, 2),
+                Date::new(2026, 4, 2),
+                Some(0.035),
+                0.2,
+                VolatilityType::ShiftedLognormal(0.01),
+            )?,
+            HullWhiteCalibrationHelper::swaption(
+                Date::new(2027, 1, 2),
+                Period::new(2, TimeUnit::Years),
+                Frequency::Annual,
+                Some(0.03),
+                0.008,
+                VolatilityType::Normal,
+            )?,
+        ];
+        let model = HullWhiteModel::new(&market_store, 0, 0.03, 0.01).calibrate(&helpers)?;
+        assert_eq!(model.volatilities().len(), 3);
+        assert_eq!(model.volatility_dates().len(), 2);
+        for helper in &helpers {
+            let market_price = helper.market_price(&model)?;
+            assert!((helper.model_price(&model)? - market_price).abs() < 1e-10);
+        }
+        assert!(model
+            .volatilities()
+            .iter()
+            .all(|volatility| *volatility > 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulation_is_arbitrage_free() -> Result<()> {
+        let market_store = flat_market_store(Date::new(2024, 1, 2), 0.03)?;
+        let model = HullWhiteModel::new(&market_store, 0, 0.05, 0.0)
+            .with_volatilities(vec![Date::new(2026, 1, 2)], vec![0.008, 0.012])?;
+        let dates = vec![Date::new(2025, 1, 2), Date::new(2029, 1, 2)];
+        let paths = MonteCarloEngine::new(&model, dates.clone(), 10_000).simulate()?;
+        let maturity = Date::new(2034, 1, 2);
+        for (i, date) in dates.iter().enumerate() {
+            let mut deflated_bond = 0.0;
+            let mut factor_variance = 0.0;
+            for path in &paths {
+                let state = path[i];
+                assert_eq!(state.date(), *date);
+                deflated_bond += model.discount_bond(*date, maturity, state.factor())?
+                    / model.numeraire(state)?;
+                factor_variance += state.factor().powi(2);
+            }
+            deflated_bond /= 10_000.0;
+            factor_variance /= 10_000.0;
+            let expected = model.initial_discount(0, maturity)?;
+            assert!((deflated_bond / expected - 1.0).abs() < 5e-3);
+            let expected = model.factor_variance(*date);
+            assert!((factor_variance / expected - 1.0).abs() < 0.1);
+        }
+        Ok(())
+    }
+}
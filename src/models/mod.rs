@@ -1,3 +1,7 @@
+/// Forward-mode automatic differentiation model.
+pub mod admodel;
+/// Hull-White one factor model.
+pub mod hullwhitemodel;
 /// Simple model implementation.
 pub mod simplemodel;
 /// Trait definitions for models.
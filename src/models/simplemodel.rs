@@ -81,6 +81,13 @@ impl<'a> Model for SimpleModel<'a> {
 
     fn gen_fwd_data(&self, fwd: ForwardRateRequest) -> Result<f64> {
         let id = fwd.provider_id();
+        // compounded overnight rates mix past fixings and forecasts day by day, also once
+        // the period has ended and the payment is still due
+        if let Some(observation) = fwd.overnight_observation() {
+            let index = self.market_store.get_index(id)?;
+            let provider = index.read_index()?;
+            return observation.compounded_rate(&*provider);
+        }
         let end_date = fwd.end_date();
         let ref_date = self.market_store.reference_date();
         if end_date <= ref_date {
@@ -108,19 +115,11 @@ impl<'a> Model for SimpleModel<'a> {
         });
 
         match fx.reference_date() {
-            Some(date) => {
-                let currency_forescast_factor = self
-                    .market_store
-                    .index_store()
-                    .currency_forescast_factor(first_currency, second_currency, date)?;
-
-                let spot = self
-                    .market_store
-                    .exchange_rate_store()
-                    .get_exchange_rate(first_currency, second_currency)?;
-
-                Ok(spot * currency_forescast_factor)
-            }
+            Some(date) => self.market_store.get_forward_exchange_rate(
+                first_currency,
+                Some(second_currency),
+                date,
+            ),
             None => Ok(self
                 .market_store
                 .exchange_rate_store()
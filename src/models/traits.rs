@@ -73,3 +73,38 @@ pub trait Model {
         market_request.iter().map(|x| self.gen_node(x)).collect()
     }
 }
+
+/// # `StochasticModel`
+/// A model whose state variables evolve randomly in time. Each state is a scenario of the
+/// market, in which market data is generated like a `Model` does on its reference date.
+pub trait StochasticModel: Sync {
+    /// The state variables of the model on a date.
+    type State: Clone + Send + Sync;
+    /// Returns the state of the model on its reference date.
+    fn initial_state(&self) -> Self::State;
+    /// Returns the date of a state.
+    fn state_date(&self, state: &Self::State) -> Date;
+    /// Returns the number of independent normal draws needed to evolve a state.
+    fn factors(&self) -> usize;
+    /// Evolves a state to a later date given independent standard normal draws.
+    ///
+    /// # Errors
+    /// Returns an error if the date is before the date of the state or if the number of draws
+    /// does not match the number of factors.
+    fn evolve(&self, state: &Self::State, date: Date, normals: &[f64]) -> Result<Self::State>;
+    /// Generates the value of the numeraire in a state.
+    ///
+    /// # Errors
+    /// Returns an error if the numeraire cannot be evaluated in the state.
+    fn gen_state_numerarie(&self, state: &Self::State) -> Result<f64>;
+    /// Generates market data for a slice of market requests in a state, as seen on the date of
+    /// the state.
+    ///
+    /// # Errors
+    /// Returns an error if any request cannot be satisfied in the state.
+    fn gen_state_market_data(
+        &self,
+        state: &Self::State,
+        market_request: &[MarketRequest],
+    ) -> Result<Vec<MarketData>>;
+}
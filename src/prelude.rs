@@ -1,28 +1,56 @@
 pub use crate::{
-    alm::{cashaccount::*, enums::*, positiongenerator::*, rolloversimulationengine::*},
+    alm::{
+        cashaccount::*, enums::*, ftp::*, gap::*, irrbb::*, montecarloengine::*, niiengine::*,
+        nonmaturitydeposit::*, positiongenerator::*, prepayment::*, rolloversimulationengine::*,
+    },
     cashflows::cashflow::Side,
     cashflows::{
-        cashflow::*, fixedratecoupon::*, floatingratecoupon::*, simplecashflow::*, traits::*,
+        cashflow::*, fixedratecoupon::*, floatingratecoupon::*, overnightindexedcoupon::*,
+        simplecashflow::*, traits::*,
     },
     core::meta::*,
     core::{marketstore::MarketStore, traits::*},
-    currencies::{enums::*, traits::*},
+    currencies::{enums::*, fxforwardcurve::*, money::*, traits::*},
     instruments::{
-        fixedrateinstrument::*, floatingrateinstrument::*, instrument::*, leg::*, loandepo::*,
+        bond::*, capfloor::*, fixedrateinstrument::*, floatingrateinstrument::*, fxforward::*,
+        fxswap::*, instrument::*, leg::*, loandepo::*, makecrosscurrencyswap::*,
         makefixedrateinstrument::*, makefixedrateleg::*, makefloatingrateinstrument::*,
-        makefloatingrateleg::*, traits::*,
+        makefloatingrateleg::*, makeovernightindexedleg::*, makeswap::*, swap::*, swaption::*,
+        traits::*,
+    },
+    math::{
+        ad::{dual::*, traits::*},
+        interpolation::{
+            cubicspline::*, enums::*, linear::*, loglinear::*, monotoneconvex::*, monotonecubic::*,
+            traits::*,
+        },
+        optionpricing::*,
     },
-    math::interpolation::{enums::*, linear::*, loglinear::*, traits::*},
-    models::{simplemodel::*, traits::*},
+    models::{admodel::*, hullwhitemodel::*, simplemodel::*, traits::*},
     rates::{
+        bootstrap::{
+            basisswapratehelper::*, depositratehelper::*, fraratehelper::*, futuresratehelper::*,
+            multicurvebootstrapper::*, oisratehelper::*, piecewisebootstrapper::*, ratehelper::*,
+            swapratehelper::*, traits::*,
+        },
         enums::*,
+        fitting::{fittingbond::*, nelsonsiegelfitter::*, smithwilsonfitter::*, svenssonfitter::*},
         indexstore::*,
+        inflation::{inflationindex::*, traits::*, unitofaccount::*, zerocouponinflationcurve::*},
         interestrate::*,
         interestrateindex::{iborindex::*, overnightindex::*, traits::*},
+        overnightobservation::*,
         traits::*,
+        volatility::{
+            constantvolatility::*, interpolatedvolatilitysurface::*, swaptionvolatilitycube::*,
+            traits::*,
+        },
+        volatilitystore::*,
         yieldtermstructure::{
-            compositetermstructure::*, discounttermstructure::*, flatforwardtermstructure::*,
-            tenorbasedzeroratetermstructure::*, traits::*, zeroratetermstructure::*,
+            bumpedtermstructure::*, compositetermstructure::*, discounttermstructure::*,
+            flatforwardtermstructure::*, nelsonsiegeltermstructure::*, smithwilsontermstructure::*,
+            svenssontermstructure::*, tenorbasedzeroratetermstructure::*, traits::*,
+            zeroratetermstructure::*,
         },
     },
     time::{
@@ -39,8 +67,9 @@ pub use crate::{
     },
     utils::errors::*,
     visitors::{
-        accruedamountconstvisitor::*, cashflowaggregationvisitor::*,
+        accruedamountconstvisitor::*, capfloornpvconstvisitor::*, cashflowaggregationvisitor::*,
         cashflowcompressorconstvisitor::*, fixingvisitor::*, indexingvisitor::*,
-        npvbydateconstvisitor::*, npvconstvisitor::*, parvaluevisitor::*, traits::*,
+        keyratedurationconstvisitor::*, npvbydateconstvisitor::*, npvconstvisitor::*,
+        parvaluevisitor::*, swaptionnpvconstvisitor::*, traits::*,
     },
 };
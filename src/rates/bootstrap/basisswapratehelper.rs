@@ -0,0 +1,232 @@
+use std::sync::{Arc, RwLock};
+
+use crate::{
+    rates::{
+        indexstore::ReadIndex, interestrateindex::traits::InterestRateIndexTrait,
+        traits::YieldProvider,
+    },
+    time::{date::Date, enums::Frequency, period::Period},
+    utils::errors::Result,
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `TenorBasisSwapRateHelper`
+/// Ibor-vs-Ibor swap of two different tenors (e.g. 3M vs 6M) quoted as a spread. One leg is
+/// projected on the curve being bootstrapped and the other on the term structure of an
+/// already built `known_index`. The spread is paid on the known leg unless configured
+/// otherwise. Both legs start at spot and end at spot plus `tenor`; its pillar is the maturity.
+///
+/// The known index is read when the quote is implied, so an index stored in an `IndexStore`
+/// can be used before its term structure has been linked.
+///
+/// ## Parameters
+/// * `quote` - The quoted basis spread
+/// * `tenor` - The tenor of the swap
+/// * `frequency` - The payment frequency of the leg on the bootstrapped curve
+/// * `known_index` - The index projecting the other leg
+/// * `known_frequency` - The payment frequency of the leg on the known index
+/// * `conventions` - The market conventions of the swap
+#[derive(Clone)]
+pub struct TenorBasisSwapRateHelper {
+    quote: f64,
+    tenor: Period,
+    frequency: Frequency,
+    known_index: Arc<RwLock<dyn InterestRateIndexTrait>>,
+    known_frequency: Frequency,
+    spread_on_known_leg: bool,
+    conventions: RateHelperConventions,
+}
+
+impl TenorBasisSwapRateHelper {
+    /// Creates a new `TenorBasisSwapRateHelper` with the spread paid on the known leg.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        quote: f64,
+        tenor: Period,
+        frequency: Frequency,
+        known_index: Arc<RwLock<dyn InterestRateIndexTrait>>,
+        known_frequency: Frequency,
+        conventions: RateHelperConventions,
+    ) -> Self {
+        Self {
+            quote,
+            tenor,
+            frequency,
+            known_index,
+            known_frequency,
+            spread_on_known_leg: true,
+            conventions,
+        }
+    }
+
+    /// Sets whether the spread is paid on the known leg or on the bootstrapped leg.
+    #[must_use]
+    pub const fn with_spread_on_known_leg(mut self, spread_on_known_leg: bool) -> Self {
+        self.spread_on_known_leg = spread_on_known_leg;
+        self
+    }
+
+    /// Returns the tenor of the swap.
+    #[must_use]
+    pub const fn tenor(&self) -> Period {
+        self.tenor
+    }
+
+    /// Returns the payment frequency of the leg on the bootstrapped curve.
+    #[must_use]
+    pub const fn frequency(&self) -> Frequency {
+        self.frequency
+    }
+
+    /// Returns the payment frequency of the leg on the known index.
+    #[must_use]
+    pub const fn known_frequency(&self) -> Frequency {
+        self.known_frequency
+    }
+
+    /// Returns whether the spread is paid on the known leg.
+    #[must_use]
+    pub const fn spread_on_known_leg(&self) -> bool {
+        self.spread_on_known_leg
+    }
+
+    /// Returns the market conventions of the swap.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+
+    /// Returns the start and maturity dates of the swap.
+    #[must_use]
+    pub fn dates(&self, reference_date: Date) -> (Date, Date) {
+        let start_date = self.conventions.spot_date(reference_date);
+        let end_date = self.conventions.advance(start_date, self.tenor);
+        (start_date, end_date)
+    }
+
+    /// Returns the discounted floating payments and the annuity of a leg.
+    fn leg_values(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+        frequency: Frequency,
+    ) -> Result<(f64, f64)> {
+        let (start_date, end_date) = self.dates(discount_curve.reference_date());
+        let schedule = self.conventions.schedule(start_date, end_date, frequency)?;
+
+        let mut floating_npv = 0.0;
+        let mut annuity = 0.0;
+        for period in schedule.dates().windows(2) {
+            let yf = self
+                .conventions
+                .day_counter()
+                .year_fraction(period[0], period[1]);
+            let df = discount_curve.discount_factor(period[1])?;
+            let compound = forecast_curve.discount_factor(period[0])?
+                / forecast_curve.discount_factor(period[1])?;
+            floating_npv += (compound - 1.0) * df;
+            annuity += yf * df;
+        }
+        Ok((floating_npv, annuity))
+    }
+
+    /// Returns the fair basis spread, projecting one leg on `forecast_curve` and the other
+    /// on the known index, and discounting both legs on `discount_curve`.
+    ///
+    /// # Errors
+    /// Returns an error if the known index has no term structure, if the schedules cannot
+    /// be built or if a discount factor cannot be obtained from the curves.
+    pub fn fair_spread(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        let known_curve = self.known_index.read_index()?.term_structure()?;
+        let (npv, annuity) = self.leg_values(forecast_curve, discount_curve, self.frequency)?;
+        let (known_npv, known_annuity) =
+            self.leg_values(known_curve.as_ref(), discount_curve, self.known_frequency)?;
+
+        if self.spread_on_known_leg {
+            Ok((npv - known_npv) / known_annuity)
+        } else {
+            Ok((known_npv - npv) / annuity)
+        }
+    }
+}
+
+impl RateHelperTrait for TenorBasisSwapRateHelper {
+    fn quote(&self) -> f64 {
+        self.quote
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        self.dates(reference_date).1
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        self.fair_spread(curve, curve)
+    }
+
+    fn dual_curve_implied_quote(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        self.fair_spread(forecast_curve, discount_curve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            interestrateindex::iborindex::IborIndex,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{daycounter::DayCounter, enums::TimeUnit},
+    };
+
+    #[test]
+    fn test_fair_spread_sign() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let rate_definition = RateDefinition::new(
+            DayCounter::Actual360,
+            Compounding::Continuous,
+            Frequency::Annual,
+        );
+        let known_curve = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.030,
+            rate_definition,
+        ));
+        let forecast_curve = FlatForwardTermStructure::new(reference_date, 0.032, rate_definition);
+        let discount_curve = FlatForwardTermStructure::new(reference_date, 0.028, rate_definition);
+
+        let known_index: Arc<RwLock<dyn InterestRateIndexTrait>> = Arc::new(RwLock::new(
+            IborIndex::new(reference_date).with_term_structure(known_curve),
+        ));
+        let helper = TenorBasisSwapRateHelper::new(
+            0.0,
+            Period::new(5, TimeUnit::Years),
+            Frequency::Semiannual,
+            known_index,
+            Frequency::Quarterly,
+            RateHelperConventions::new(),
+        );
+
+        // the known leg needs a positive spread to match a higher projected curve
+        let spread = helper.dual_curve_implied_quote(&forecast_curve, &discount_curve)?;
+        assert!(spread > 0.0);
+
+        // paying it on the other leg flips the sign
+        let other_leg = helper
+            .with_spread_on_known_leg(false)
+            .dual_curve_implied_quote(&forecast_curve, &discount_curve)?;
+        assert!(other_leg < 0.0);
+        Ok(())
+    }
+}
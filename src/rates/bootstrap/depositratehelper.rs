@@ -0,0 +1,131 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{date::Date, period::Period},
+    utils::errors::Result,
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `DepositRateHelper`
+/// Deposit quoted as a simple rate, accruing from the spot date to spot plus `tenor`.
+/// Its pillar is the maturity of the deposit.
+///
+/// ## Parameters
+/// * `quote` - The quoted simple rate
+/// * `tenor` - The tenor of the deposit
+/// * `conventions` - The market conventions of the deposit
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let helper = DepositRateHelper::new(
+///     0.05,
+///     Period::new(3, TimeUnit::Months),
+///     RateHelperConventions::new(),
+/// );
+/// assert_eq!(helper.pillar_date(Date::new(2024, 1, 2)), Date::new(2024, 4, 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DepositRateHelper {
+    quote: f64,
+    tenor: Period,
+    conventions: RateHelperConventions,
+}
+
+impl DepositRateHelper {
+    /// Creates a new `DepositRateHelper`.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(quote: f64, tenor: Period, conventions: RateHelperConventions) -> Self {
+        Self {
+            quote,
+            tenor,
+            conventions,
+        }
+    }
+
+    /// Returns the tenor of the deposit.
+    #[must_use]
+    pub const fn tenor(&self) -> Period {
+        self.tenor
+    }
+
+    /// Returns the market conventions of the deposit.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+
+    /// Returns the start and maturity dates of the deposit.
+    #[must_use]
+    pub fn dates(&self, reference_date: Date) -> (Date, Date) {
+        let start_date = self.conventions.spot_date(reference_date);
+        let end_date = self.conventions.advance(start_date, self.tenor);
+        (start_date, end_date)
+    }
+}
+
+impl RateHelperTrait for DepositRateHelper {
+    fn quote(&self) -> f64 {
+        self.quote
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        self.dates(reference_date).1
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        let (start_date, end_date) = self.dates(curve.reference_date());
+        let yf = self
+            .conventions
+            .day_counter()
+            .year_fraction(start_date, end_date);
+        let compound = curve.discount_factor(start_date)? / curve.discount_factor(end_date)?;
+        Ok((compound - 1.0) / yf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{
+            calendar::Calendar,
+            calendars::weekendsonly::WeekendsOnly,
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+        },
+    };
+
+    #[test]
+    fn test_deposit_implied_quote() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 5);
+        let conventions = RateHelperConventions::new()
+            .with_calendar(Calendar::WeekendsOnly(WeekendsOnly::new()))
+            .with_settlement_days(2);
+        let helper = DepositRateHelper::new(0.05, Period::new(6, TimeUnit::Months), conventions);
+
+        let (start_date, end_date) = helper.dates(reference_date);
+        assert_eq!(start_date, Date::new(2024, 1, 9));
+        assert_eq!(end_date, Date::new(2024, 7, 9));
+
+        let curve = FlatForwardTermStructure::new(
+            reference_date,
+            0.05,
+            RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Annual,
+            ),
+        );
+        let expected =
+            curve.forward_rate(start_date, end_date, Compounding::Simple, Frequency::Annual)?;
+        assert!((helper.implied_quote(&curve)? - expected).abs() < 1e-12);
+        Ok(())
+    }
+}
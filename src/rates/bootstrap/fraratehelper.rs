@@ -0,0 +1,105 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{date::Date, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `FraRateHelper`
+/// Forward rate agreement quoted as a simple rate. The accrual period starts at spot plus
+/// `start` and ends at spot plus `end` (e.g. a 3x6 FRA). Its pillar is the end of the
+/// accrual period.
+///
+/// ## Parameters
+/// * `quote` - The quoted simple forward rate
+/// * `start` - The period from spot to the start of the accrual period
+/// * `end` - The period from spot to the end of the accrual period
+/// * `conventions` - The market conventions of the FRA
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let helper = FraRateHelper::new(
+///     0.05,
+///     Period::new(3, TimeUnit::Months),
+///     Period::new(6, TimeUnit::Months),
+///     RateHelperConventions::new(),
+/// );
+/// assert_eq!(helper.pillar_date(Date::new(2024, 1, 2)), Date::new(2024, 7, 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FraRateHelper {
+    quote: f64,
+    start: Period,
+    end: Period,
+    conventions: RateHelperConventions,
+}
+
+impl FraRateHelper {
+    /// Creates a new `FraRateHelper`.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(quote: f64, start: Period, end: Period, conventions: RateHelperConventions) -> Self {
+        Self {
+            quote,
+            start,
+            end,
+            conventions,
+        }
+    }
+
+    /// Returns the period from spot to the start of the accrual period.
+    #[must_use]
+    pub const fn start(&self) -> Period {
+        self.start
+    }
+
+    /// Returns the period from spot to the end of the accrual period.
+    #[must_use]
+    pub const fn end(&self) -> Period {
+        self.end
+    }
+
+    /// Returns the market conventions of the FRA.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+
+    /// Returns the start and end dates of the accrual period.
+    #[must_use]
+    pub fn dates(&self, reference_date: Date) -> (Date, Date) {
+        let spot_date = self.conventions.spot_date(reference_date);
+        (
+            self.conventions.advance(spot_date, self.start),
+            self.conventions.advance(spot_date, self.end),
+        )
+    }
+}
+
+impl RateHelperTrait for FraRateHelper {
+    fn quote(&self) -> f64 {
+        self.quote
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        self.dates(reference_date).1
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        let (start_date, end_date) = self.dates(curve.reference_date());
+        if end_date <= start_date {
+            return Err(AtlasError::InvalidValueErr(
+                "FRA end date needs to be greater than start date".to_string(),
+            ));
+        }
+        let yf = self
+            .conventions
+            .day_counter()
+            .year_fraction(start_date, end_date);
+        let compound = curve.discount_factor(start_date)? / curve.discount_factor(end_date)?;
+        Ok((compound - 1.0) / yf)
+    }
+}
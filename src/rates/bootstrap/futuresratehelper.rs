@@ -0,0 +1,116 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{date::Date, period::Period},
+    utils::errors::Result,
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `FuturesRateHelper`
+/// Interest rate future quoted as a price (100 minus the futures rate in percent). The
+/// underlying rate accrues from `start_date` (usually an IMM date) to `start_date` plus
+/// `length`. The futures rate is the forward rate plus the convexity adjustment. The
+/// settlement lag of the conventions is ignored since the start date is explicit.
+///
+/// ## Parameters
+/// * `price` - The quoted futures price
+/// * `start_date` - The start of the underlying accrual period
+/// * `length` - The length of the underlying accrual period
+/// * `conventions` - The market conventions of the underlying rate
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+/// use rustatlas::time::imm::IMM;
+///
+/// let start_date = IMM::next_date(Date::new(2024, 1, 2), true);
+/// let helper = FuturesRateHelper::new(
+///     94.75,
+///     start_date,
+///     Period::new(3, TimeUnit::Months),
+///     RateHelperConventions::new(),
+/// )
+/// .with_convexity_adjustment(0.0001);
+/// assert_eq!(start_date, Date::new(2024, 3, 20));
+/// assert_eq!(helper.pillar_date(Date::new(2024, 1, 2)), Date::new(2024, 6, 20));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FuturesRateHelper {
+    price: f64,
+    start_date: Date,
+    length: Period,
+    convexity_adjustment: f64,
+    conventions: RateHelperConventions,
+}
+
+impl FuturesRateHelper {
+    /// Creates a new `FuturesRateHelper` with no convexity adjustment.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        price: f64,
+        start_date: Date,
+        length: Period,
+        conventions: RateHelperConventions,
+    ) -> Self {
+        Self {
+            price,
+            start_date,
+            length,
+            convexity_adjustment: 0.0,
+            conventions,
+        }
+    }
+
+    /// Sets the convexity adjustment, expressed as a rate.
+    #[must_use]
+    pub const fn with_convexity_adjustment(mut self, convexity_adjustment: f64) -> Self {
+        self.convexity_adjustment = convexity_adjustment;
+        self
+    }
+
+    /// Returns the start of the underlying accrual period.
+    #[must_use]
+    pub const fn start_date(&self) -> Date {
+        self.start_date
+    }
+
+    /// Returns the end of the underlying accrual period.
+    #[must_use]
+    pub fn end_date(&self) -> Date {
+        self.conventions.advance(self.start_date, self.length)
+    }
+
+    /// Returns the convexity adjustment.
+    #[must_use]
+    pub const fn convexity_adjustment(&self) -> f64 {
+        self.convexity_adjustment
+    }
+
+    /// Returns the market conventions of the underlying rate.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+}
+
+impl RateHelperTrait for FuturesRateHelper {
+    fn quote(&self) -> f64 {
+        self.price
+    }
+
+    fn pillar_date(&self, _reference_date: Date) -> Date {
+        self.end_date()
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        let end_date = self.end_date();
+        let yf = self
+            .conventions
+            .day_counter()
+            .year_fraction(self.start_date, end_date);
+        let compound = curve.discount_factor(self.start_date)? / curve.discount_factor(end_date)?;
+        let forward = (compound - 1.0) / yf;
+        Ok(100.0 * (1.0 - forward - self.convexity_adjustment))
+    }
+}
@@ -0,0 +1,28 @@
+//! Curve bootstrapping from market quotes.
+//!
+//! Rate helpers wrap a quoted instrument together with its market conventions and
+//! know how to imply their quote from a candidate curve. The piecewise bootstrapper
+//! sorts the helpers by pillar and solves one discount factor per pillar so that
+//! every helper is repriced to par. Forecast curves can be stripped against an
+//! exogenous discount curve, in which case swaps are valued under dual-curve pricing.
+
+/// Tenor basis swap helper.
+pub mod basisswapratehelper;
+/// Deposit rate helper.
+pub mod depositratehelper;
+/// Forward rate agreement helper.
+pub mod fraratehelper;
+/// Interest rate futures helper.
+pub mod futuresratehelper;
+/// Multi-curve bootstrapper linking forecast curves into an index store.
+pub mod multicurvebootstrapper;
+/// Overnight indexed swap helper.
+pub mod oisratehelper;
+/// Piecewise bootstrapper.
+pub mod piecewisebootstrapper;
+/// Rate helper enum and shared conventions.
+pub mod ratehelper;
+/// Fixed-vs-Ibor swap helper.
+pub mod swapratehelper;
+/// Rate helper traits.
+pub mod traits;
@@ -0,0 +1,419 @@
+use std::sync::Arc;
+
+use crate::{
+    math::interpolation::enums::Interpolator,
+    rates::{
+        indexstore::{IndexStore, ReadIndex},
+        yieldtermstructure::traits::YieldTermStructureTrait,
+    },
+    time::daycounter::DayCounter,
+    utils::errors::Result,
+};
+
+use super::{piecewisebootstrapper::PiecewiseBootstrapper, ratehelper::RateHelper};
+
+/// # `MultiCurveBootstrapper`
+/// Strips a set of forecast curves against the term structure of a discount index already
+/// present in an `IndexStore` (e.g. SOFR, ESTR or ICP) and links each resulting curve to
+/// its index via `link_term_structure`.
+///
+/// Forecast curves are bootstrapped in the order they were added, so tenor basis helpers
+/// may reference an index whose curve is stripped earlier in the same run.
+///
+/// ## Parameters
+/// * `discount_curve_id` - The id of the index holding the discount curve
+/// * `forecast_curves` - The forecast index ids with their rate helpers
+/// * `day_counter` - The day counter of the forecast curves (defaults to `Actual365`)
+/// * `interpolator` - The interpolator of the forecast curves (defaults to `LogLinear`)
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+/// use std::sync::{Arc, RwLock};
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let mut index_store = IndexStore::new(reference_date);
+///
+/// let ois_curve = Arc::new(FlatForwardTermStructure::new(
+///     reference_date,
+///     0.03,
+///     RateDefinition::new(DayCounter::Actual360, Compounding::Continuous, Frequency::Annual),
+/// ));
+/// let ois_index = OvernightIndex::new(reference_date).with_term_structure(ois_curve);
+/// index_store.add_index(0, Arc::new(RwLock::new(ois_index))).unwrap();
+///
+/// let ibor_index = IborIndex::new(reference_date).with_frequency(Frequency::Quarterly);
+/// index_store.add_index(1, Arc::new(RwLock::new(ibor_index))).unwrap();
+///
+/// let helpers = vec![
+///     RateHelper::Deposit(DepositRateHelper::new(
+///         0.032,
+///         Period::new(3, TimeUnit::Months),
+///         RateHelperConventions::new(),
+///     )),
+///     RateHelper::Swap(SwapRateHelper::new(
+///         0.033,
+///         Period::new(2, TimeUnit::Years),
+///         Frequency::Annual,
+///         Frequency::Quarterly,
+///         RateHelperConventions::new(),
+///     )),
+/// ];
+///
+/// MultiCurveBootstrapper::new(0)
+///     .with_forecast_curve(1, helpers)
+///     .bootstrap(&index_store)
+///     .unwrap();
+///
+/// let ibor_index = index_store.get_index(1).unwrap();
+/// assert!(ibor_index.read_index().unwrap().term_structure().is_ok());
+/// ```
+#[derive(Clone)]
+pub struct MultiCurveBootstrapper {
+    discount_curve_id: usize,
+    forecast_curves: Vec<(usize, Vec<RateHelper>)>,
+    day_counter: DayCounter,
+    interpolator: Interpolator,
+}
+
+impl MultiCurveBootstrapper {
+    /// Creates a new `MultiCurveBootstrapper` discounting on the curve of the given index.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(discount_curve_id: usize) -> Self {
+        Self {
+            discount_curve_id,
+            forecast_curves: Vec::new(),
+            day_counter: DayCounter::Actual365,
+            interpolator: Interpolator::LogLinear,
+        }
+    }
+
+    /// Adds a forecast curve to be stripped from `helpers` and linked to the index `index_id`.
+    #[must_use]
+    pub fn with_forecast_curve(mut self, index_id: usize, helpers: Vec<RateHelper>) -> Self {
+        self.forecast_curves.push((index_id, helpers));
+        self
+    }
+
+    /// Sets the day counter of the forecast curves.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the interpolator of the forecast curves.
+    #[must_use]
+    pub const fn with_interpolator(mut self, interpolator: Interpolator) -> Self {
+        self.interpolator = interpolator;
+        self
+    }
+
+    /// Returns the id of the index holding the discount curve.
+    #[must_use]
+    pub const fn discount_curve_id(&self) -> usize {
+        self.discount_curve_id
+    }
+
+    /// Returns the forecast index ids with their rate helpers.
+    #[must_use]
+    pub const fn forecast_curves(&self) -> &Vec<(usize, Vec<RateHelper>)> {
+        &self.forecast_curves
+    }
+
+    /// Bootstraps every forecast curve against the discount curve and links it into the store.
+    ///
+    /// # Errors
+    /// Returns an error if the discount index or a forecast index is not found in the store,
+    /// if the discount index has no term structure or if a curve fails to bootstrap.
+    pub fn bootstrap(&self, index_store: &IndexStore) -> Result<()> {
+        let discount_curve = index_store
+            .get_index(self.discount_curve_id)?
+            .read_index()?
+            .term_structure()?;
+
+        for (index_id, helpers) in &self.forecast_curves {
+            // fail before bootstrapping if the index is missing
+            index_store.get_index(*index_id)?;
+
+            let curve = PiecewiseBootstrapper::new(index_store.reference_date(), helpers.clone())
+                .with_day_counter(self.day_counter)
+                .with_interpolator(self.interpolator)
+                .with_discount_curve(discount_curve.clone())
+                .bootstrap()?;
+            let curve: Arc<dyn YieldTermStructureTrait> = Arc::new(curve);
+            index_store.link_term_structure(*index_id, curve)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::RwLock;
+
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        core::marketstore::MarketStore,
+        currencies::enums::Currency,
+        instruments::{
+            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument,
+        },
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            bootstrap::{
+                basisswapratehelper::TenorBasisSwapRateHelper,
+                depositratehelper::DepositRateHelper, fraratehelper::FraRateHelper,
+                oisratehelper::OisRateHelper, ratehelper::RateHelperConventions,
+                swapratehelper::SwapRateHelper, traits::RateHelperTrait,
+            },
+            enums::Compounding,
+            interestrate::{InterestRate, RateDefinition},
+            interestrateindex::{iborindex::IborIndex, overnightindex::OvernightIndex},
+        },
+        time::{
+            calendar::Calendar,
+            calendars::target::TARGET,
+            date::Date,
+            enums::{BusinessDayConvention, Frequency, TimeUnit},
+            period::Period,
+        },
+        visitors::{
+            fixingvisitor::FixingVisitor,
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, HasCashflows, Visit},
+        },
+    };
+
+    fn conventions() -> RateHelperConventions {
+        RateHelperConventions::new()
+            .with_calendar(Calendar::TARGET(TARGET::new()))
+            .with_settlement_days(2)
+    }
+
+    fn create_store(reference_date: Date) -> Result<IndexStore> {
+        let mut index_store = IndexStore::new(reference_date);
+
+        let ois_helpers: Vec<RateHelper> = [
+            (Period::new(3, TimeUnit::Months), 0.0380),
+            (Period::new(1, TimeUnit::Years), 0.0350),
+            (Period::new(2, TimeUnit::Years), 0.0320),
+            (Period::new(5, TimeUnit::Years), 0.0300),
+            (Period::new(10, TimeUnit::Years), 0.0300),
+        ]
+        .iter()
+        .map(|(tenor, quote)| {
+            RateHelper::Ois(OisRateHelper::new(
+                *quote,
+                *tenor,
+                Frequency::Annual,
+                conventions(),
+            ))
+        })
+        .collect();
+        let ois_curve = PiecewiseBootstrapper::new(reference_date, ois_helpers).bootstrap()?;
+
+        let ois_index =
+            OvernightIndex::new(reference_date).with_term_structure(Arc::new(ois_curve));
+        index_store.add_index(0, Arc::new(RwLock::new(ois_index)))?;
+
+        let ibor_3m = IborIndex::new(reference_date).with_frequency(Frequency::Quarterly);
+        index_store.add_index(1, Arc::new(RwLock::new(ibor_3m)))?;
+
+        let ibor_6m = IborIndex::new(reference_date).with_frequency(Frequency::Semiannual);
+        index_store.add_index(2, Arc::new(RwLock::new(ibor_6m)))?;
+        Ok(index_store)
+    }
+
+    /// Fixes and discounts an instrument through the pricing visitors.
+    fn npv<T: HasCashflows>(market_store: &MarketStore, instrument: &mut T) -> Result<f64> {
+        let indexer = IndexingVisitor::new();
+        indexer.visit(instrument)?;
+        let data = SimpleModel::new(market_store).gen_market_data(&indexer.request())?;
+        FixingVisitor::new(&data).visit(instrument)?;
+        NPVConstVisitor::new(&data, true).visit(instrument)
+    }
+
+    /// Fixed leg of a market swap starting at spot, paying annual Actual/360 coupons.
+    fn fixed_leg(dates: (Date, Date), rate: f64) -> MakeFixedRateInstrument {
+        MakeFixedRateInstrument::new()
+            .with_start_date(dates.0)
+            .with_end_date(dates.1)
+            .with_payment_frequency(Frequency::Annual)
+            .with_calendar(Some(Calendar::TARGET(TARGET::new())))
+            .with_business_day_convention(Some(BusinessDayConvention::ModifiedFollowing))
+            .with_rate(InterestRate::new(
+                rate,
+                Compounding::Simple,
+                Frequency::Annual,
+                DayCounter::Actual360,
+            ))
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::EUR)
+            .with_side(Side::Pay)
+            .with_discount_curve_id(Some(0))
+            .bullet()
+    }
+
+    /// Floating leg of a market swap starting at spot on the given forecast index.
+    fn floating_leg(
+        dates: (Date, Date),
+        frequency: Frequency,
+        forecast_curve_id: usize,
+        spread: f64,
+        side: Side,
+    ) -> MakeFloatingRateInstrument {
+        MakeFloatingRateInstrument::new()
+            .with_start_date(dates.0)
+            .with_end_date(dates.1)
+            .with_payment_frequency(frequency)
+            .with_calendar(Some(Calendar::TARGET(TARGET::new())))
+            .with_business_day_convention(Some(BusinessDayConvention::ModifiedFollowing))
+            .with_rate_definition(RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Annual,
+            ))
+            .with_spread(spread)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::EUR)
+            .with_side(side)
+            .with_forecast_curve_id(Some(forecast_curve_id))
+            .with_discount_curve_id(Some(0))
+            .bullet()
+    }
+
+    #[test]
+    fn test_dual_curve_bootstrap() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 3);
+        let index_store = create_store(reference_date)?;
+
+        let helpers_3m = vec![
+            RateHelper::Deposit(DepositRateHelper::new(
+                0.0395,
+                Period::new(3, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Fra(FraRateHelper::new(
+                0.0380,
+                Period::new(3, TimeUnit::Months),
+                Period::new(6, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0345,
+                Period::new(2, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Quarterly,
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0325,
+                Period::new(5, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Quarterly,
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0330,
+                Period::new(10, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Quarterly,
+                conventions(),
+            )),
+        ];
+
+        let index_3m = index_store.get_index(1)?;
+        let helpers_6m: Vec<RateHelper> = [
+            (Period::new(2, TimeUnit::Years), 0.0010),
+            (Period::new(5, TimeUnit::Years), 0.0012),
+            (Period::new(10, TimeUnit::Years), 0.0011),
+        ]
+        .iter()
+        .map(|(tenor, quote)| {
+            RateHelper::TenorBasisSwap(TenorBasisSwapRateHelper::new(
+                *quote,
+                *tenor,
+                Frequency::Semiannual,
+                index_3m.clone(),
+                Frequency::Quarterly,
+                conventions(),
+            ))
+        })
+        .chain(std::iter::once(RateHelper::Deposit(
+            DepositRateHelper::new(0.0405, Period::new(6, TimeUnit::Months), conventions()),
+        )))
+        .collect();
+
+        let mut market_store = MarketStore::new(reference_date, Currency::EUR);
+        *market_store.mut_index_store() = index_store;
+        MultiCurveBootstrapper::new(0)
+            .with_forecast_curve(1, helpers_3m.clone())
+            .with_forecast_curve(2, helpers_6m.clone())
+            .with_day_counter(DayCounter::Actual360)
+            .bootstrap(market_store.index_store())?;
+
+        // market swaps built from the quotes are worth zero on the stripped curves
+        for helper in helpers_3m.iter().skip(2) {
+            let RateHelper::Swap(swap) = helper else {
+                continue;
+            };
+            let dates = swap.dates(reference_date);
+            let mut fixed_leg = fixed_leg(dates, swap.quote()).build()?;
+            let mut float_leg =
+                floating_leg(dates, Frequency::Quarterly, 1, 0.0, Side::Receive).build()?;
+            let npv = npv(&market_store, &mut fixed_leg)? + npv(&market_store, &mut float_leg)?;
+            assert!(npv.abs() < 1e-4, "{:?} swap is worth {npv}", swap.tenor());
+        }
+
+        for helper in helpers_6m.iter().take(3) {
+            let RateHelper::TenorBasisSwap(basis) = helper else {
+                continue;
+            };
+            let dates = basis.dates(reference_date);
+            let mut leg_6m =
+                floating_leg(dates, Frequency::Semiannual, 2, 0.0, Side::Receive).build()?;
+            let mut leg_3m =
+                floating_leg(dates, Frequency::Quarterly, 1, basis.quote(), Side::Pay).build()?;
+            let npv = npv(&market_store, &mut leg_6m)? + npv(&market_store, &mut leg_3m)?;
+            assert!(
+                npv.abs() < 1e-4,
+                "{:?} basis swap is worth {npv}",
+                basis.tenor()
+            );
+        }
+
+        // the forecast curve carries a spread over the discount curve
+        let date = Date::new(2029, 1, 3);
+        let discount_curve = market_store.get_index(0)?.read_index()?.term_structure()?;
+        let forecast_curve = market_store.get_index(1)?.read_index()?.term_structure()?;
+        assert!(forecast_curve.discount_factor(date)? < discount_curve.discount_factor(date)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_discount_curve() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 3);
+        let index_store = create_store(reference_date)?;
+        let helpers = vec![RateHelper::Deposit(DepositRateHelper::new(
+            0.04,
+            Period::new(3, TimeUnit::Months),
+            conventions(),
+        ))];
+
+        // index 1 has no term structure yet
+        assert!(MultiCurveBootstrapper::new(1)
+            .with_forecast_curve(2, helpers.clone())
+            .bootstrap(&index_store)
+            .is_err());
+        assert!(MultiCurveBootstrapper::new(0)
+            .with_forecast_curve(5, helpers)
+            .bootstrap(&index_store)
+            .is_err());
+        Ok(())
+    }
+}
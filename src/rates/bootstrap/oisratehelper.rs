@@ -0,0 +1,155 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{date::Date, enums::Frequency, period::Period},
+    utils::errors::Result,
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `OisRateHelper`
+/// Overnight indexed swap quoted as a par fixed rate. Both legs start at spot, end at spot
+/// plus `tenor` and pay with the same frequency. The overnight leg pays the overnight rate
+/// compounded over each period, which is read directly from the curve discount factors.
+/// Its pillar is the maturity of the swap.
+///
+/// ## Parameters
+/// * `quote` - The quoted par fixed rate
+/// * `tenor` - The tenor of the swap
+/// * `payment_frequency` - The payment frequency of both legs
+/// * `conventions` - The market conventions of the swap
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let helper = OisRateHelper::new(
+///     0.05,
+///     Period::new(18, TimeUnit::Months),
+///     Frequency::Annual,
+///     RateHelperConventions::new(),
+/// );
+/// assert_eq!(helper.pillar_date(Date::new(2024, 1, 2)), Date::new(2025, 7, 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct OisRateHelper {
+    quote: f64,
+    tenor: Period,
+    payment_frequency: Frequency,
+    spread: f64,
+    conventions: RateHelperConventions,
+}
+
+impl OisRateHelper {
+    /// Creates a new `OisRateHelper` with no spread.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        quote: f64,
+        tenor: Period,
+        payment_frequency: Frequency,
+        conventions: RateHelperConventions,
+    ) -> Self {
+        Self {
+            quote,
+            tenor,
+            payment_frequency,
+            spread: 0.0,
+            conventions,
+        }
+    }
+
+    /// Sets the spread paid over the overnight leg.
+    #[must_use]
+    pub const fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Returns the tenor of the swap.
+    #[must_use]
+    pub const fn tenor(&self) -> Period {
+        self.tenor
+    }
+
+    /// Returns the payment frequency of both legs.
+    #[must_use]
+    pub const fn payment_frequency(&self) -> Frequency {
+        self.payment_frequency
+    }
+
+    /// Returns the spread paid over the overnight leg.
+    #[must_use]
+    pub const fn spread(&self) -> f64 {
+        self.spread
+    }
+
+    /// Returns the market conventions of the swap.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+
+    /// Returns the start and maturity dates of the swap.
+    #[must_use]
+    pub fn dates(&self, reference_date: Date) -> (Date, Date) {
+        let start_date = self.conventions.spot_date(reference_date);
+        let end_date = self.conventions.advance(start_date, self.tenor);
+        (start_date, end_date)
+    }
+
+    /// Returns the par fixed rate of the swap, compounding the overnight rate on
+    /// `forecast_curve` and discounting both legs on `discount_curve`.
+    ///
+    /// # Errors
+    /// Returns an error if the schedule cannot be built or if a discount factor
+    /// cannot be obtained from the curves.
+    pub fn par_rate(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        let (start_date, end_date) = self.dates(discount_curve.reference_date());
+        let schedule = self
+            .conventions
+            .schedule(start_date, end_date, self.payment_frequency)?;
+
+        let mut annuity = 0.0;
+        let mut overnight_npv = 0.0;
+        for period in schedule.dates().windows(2) {
+            let yf = self
+                .conventions
+                .day_counter()
+                .year_fraction(period[0], period[1]);
+            let df = discount_curve.discount_factor(period[1])?;
+            let compound = forecast_curve.discount_factor(period[0])?
+                / forecast_curve.discount_factor(period[1])?;
+            let accrual = self.spread.mul_add(yf, compound - 1.0);
+            annuity += yf * df;
+            overnight_npv += accrual * df;
+        }
+
+        Ok(overnight_npv / annuity)
+    }
+}
+
+impl RateHelperTrait for OisRateHelper {
+    fn quote(&self) -> f64 {
+        self.quote
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        self.dates(reference_date).1
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        self.par_rate(curve, curve)
+    }
+
+    fn dual_curve_implied_quote(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        self.par_rate(forecast_curve, discount_curve)
+    }
+}
@@ -0,0 +1,458 @@
+use std::sync::Arc;
+
+use argmin::{
+    core::{CostFunction, Error, Executor, State},
+    solver::brent::BrentRoot,
+};
+
+use crate::{
+    math::interpolation::enums::Interpolator,
+    rates::{
+        traits::YieldProvider,
+        yieldtermstructure::{
+            discounttermstructure::DiscountTermStructure, traits::YieldTermStructureTrait,
+        },
+    },
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{ratehelper::RateHelper, traits::RateHelperTrait};
+
+/// Lower bound of the discount factor searched at each pillar.
+const MIN_DISCOUNT_FACTOR: f64 = 1e-6;
+/// Upper bound of the discount factor searched at each pillar.
+const MAX_DISCOUNT_FACTOR: f64 = 2.0;
+
+/// # `QuoteError`
+/// `QuoteError` is a cost function that returns the quote error of a helper when the
+/// curve solved so far is extended with a trial discount factor at the helper pillar.
+///
+/// ## Parameters
+/// * `helper` - The helper being solved
+/// * `dates` - The curve dates, the last one being the helper pillar
+/// * `discount_factors` - The discount factors solved so far
+/// * `day_counter` - The day counter of the curve
+/// * `interpolator` - The interpolator of the curve
+/// * `discount_curve` - The exogenous discount curve, if any
+struct QuoteError<'a> {
+    helper: &'a RateHelper,
+    discount_curve: Option<&'a dyn YieldProvider>,
+    dates: &'a [Date],
+    discount_factors: &'a [f64],
+    day_counter: DayCounter,
+    interpolator: Interpolator,
+}
+
+impl CostFunction for QuoteError<'_> {
+    type Param = f64;
+    type Output = f64;
+    fn cost(&self, param: &Self::Param) -> std::result::Result<Self::Output, Error> {
+        let mut discount_factors = self.discount_factors.to_vec();
+        discount_factors.push(*param);
+
+        let curve = DiscountTermStructure::new(
+            self.dates.to_vec(),
+            discount_factors,
+            self.day_counter,
+            self.interpolator,
+            true,
+        )?;
+        let implied = self.discount_curve.map_or_else(
+            || self.helper.implied_quote(&curve),
+            |discount_curve| self.helper.dual_curve_implied_quote(&curve, discount_curve),
+        )?;
+        Ok(implied - self.helper.quote())
+    }
+}
+
+/// # `PiecewiseBootstrapper`
+/// Bootstraps a `DiscountTermStructure` from a set of rate helpers. Helpers are sorted by
+/// pillar and the discount factor of each pillar is solved with a Brent root finder so
+/// that the helper is repriced to its quote, keeping the previously solved nodes fixed.
+///
+/// By default the curve both projects and discounts the helper cashflows. When a discount
+/// curve is set, the bootstrapped curve is a forecast curve and swap helpers are discounted
+/// on the given curve instead.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the curve
+/// * `helpers` - The rate helpers, one per pillar
+/// * `day_counter` - The day counter of the curve (defaults to `Actual365`)
+/// * `interpolator` - The interpolator of the curve (defaults to `LogLinear`)
+/// * `enable_extrapolation` - Enable extrapolation on the resulting curve (defaults to `true`)
+/// * `discount_curve` - An optional exogenous discount curve
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let conventions = RateHelperConventions::new();
+/// let helpers = vec![
+///     RateHelper::Deposit(DepositRateHelper::new(
+///         0.050,
+///         Period::new(6, TimeUnit::Months),
+///         conventions.clone(),
+///     )),
+///     RateHelper::Swap(SwapRateHelper::new(
+///         0.045,
+///         Period::new(2, TimeUnit::Years),
+///         Frequency::Semiannual,
+///         Frequency::Semiannual,
+///         conventions,
+///     )),
+/// ];
+///
+/// let curve = PiecewiseBootstrapper::new(reference_date, helpers.clone())
+///     .bootstrap()
+///     .unwrap();
+///
+/// for helper in &helpers {
+///     let implied = helper.implied_quote(&curve).unwrap();
+///     assert!((implied - helper.quote()).abs() < 1e-10);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PiecewiseBootstrapper {
+    reference_date: Date,
+    helpers: Vec<RateHelper>,
+    day_counter: DayCounter,
+    interpolator: Interpolator,
+    enable_extrapolation: bool,
+    accuracy: f64,
+    max_iterations: u64,
+    discount_curve: Option<Arc<dyn YieldTermStructureTrait>>,
+}
+
+impl PiecewiseBootstrapper {
+    /// Creates a new `PiecewiseBootstrapper` for the given helpers.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(reference_date: Date, helpers: Vec<RateHelper>) -> Self {
+        Self {
+            reference_date,
+            helpers,
+            day_counter: DayCounter::Actual365,
+            interpolator: Interpolator::LogLinear,
+            enable_extrapolation: true,
+            accuracy: 1e-12,
+            max_iterations: 100,
+            discount_curve: None,
+        }
+    }
+
+    /// Sets the day counter of the curve.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the interpolator of the curve.
+    #[must_use]
+    pub const fn with_interpolator(mut self, interpolator: Interpolator) -> Self {
+        self.interpolator = interpolator;
+        self
+    }
+
+    /// Enables or disables extrapolation on the resulting curve.
+    #[must_use]
+    pub const fn with_enable_extrapolation(mut self, enable_extrapolation: bool) -> Self {
+        self.enable_extrapolation = enable_extrapolation;
+        self
+    }
+
+    /// Sets the tolerance of the root finder.
+    #[must_use]
+    pub const fn with_accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Sets the maximum number of root finder iterations per pillar.
+    #[must_use]
+    pub const fn with_max_iterations(mut self, max_iterations: u64) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets an exogenous discount curve, turning the bootstrapped curve into a forecast curve.
+    #[must_use]
+    pub fn with_discount_curve(mut self, discount_curve: Arc<dyn YieldTermStructureTrait>) -> Self {
+        self.discount_curve = Some(discount_curve);
+        self
+    }
+
+    /// Returns the reference date of the curve.
+    #[must_use]
+    pub const fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+
+    /// Returns the rate helpers.
+    #[must_use]
+    pub const fn helpers(&self) -> &Vec<RateHelper> {
+        &self.helpers
+    }
+
+    /// Bootstraps the curve node by node.
+    ///
+    /// # Errors
+    /// Returns an error if there are no helpers, if a pillar is not after the reference date,
+    /// if two helpers share the same pillar, if the discount curve reference date differs from
+    /// the curve reference date or if the root finder fails on a pillar.
+    pub fn bootstrap(&self) -> Result<DiscountTermStructure> {
+        if self.helpers.is_empty() {
+            return Err(AtlasError::InvalidValueErr(
+                "At least one rate helper is needed to bootstrap a curve".to_string(),
+            ));
+        }
+
+        if let Some(discount_curve) = &self.discount_curve {
+            if discount_curve.reference_date() != self.reference_date {
+                return Err(AtlasError::InvalidValueErr(
+                    "Discount curve reference date needs to match the curve reference date"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let mut helpers: Vec<(Date, &RateHelper)> = self
+            .helpers
+            .iter()
+            .map(|helper| (helper.pillar_date(self.reference_date), helper))
+            .collect();
+        helpers.sort_by_key(|(pillar, _)| *pillar);
+
+        let mut dates = vec![self.reference_date];
+        let mut discount_factors = vec![1.0];
+        for (pillar, helper) in helpers {
+            if pillar <= dates[dates.len() - 1] {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Pillar {pillar} needs to be after the reference date and unique among helpers"
+                )));
+            }
+            dates.push(pillar);
+
+            let cost = QuoteError {
+                helper,
+                discount_curve: self
+                    .discount_curve
+                    .as_deref()
+                    .map(|curve| curve as &dyn YieldProvider),
+                dates: &dates,
+                discount_factors: &discount_factors,
+                day_counter: self.day_counter,
+                interpolator: self.interpolator,
+            };
+            let solver = BrentRoot::new(MIN_DISCOUNT_FACTOR, MAX_DISCOUNT_FACTOR, self.accuracy);
+            let res = Executor::new(cost, solver)
+                .configure(|state| state.max_iters(self.max_iterations).target_cost(0.0))
+                .run()?;
+
+            let discount_factor = res.state().get_best_param().copied().ok_or_else(|| {
+                AtlasError::EvaluationErr(format!(
+                    "No discount factor found in PiecewiseBootstrapper for pillar {pillar}"
+                ))
+            })?;
+            discount_factors.push(discount_factor);
+        }
+
+        DiscountTermStructure::new(
+            dates,
+            discount_factors,
+            self.day_counter,
+            self.interpolator,
+            self.enable_extrapolation,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rates::bootstrap::{
+            depositratehelper::DepositRateHelper, fraratehelper::FraRateHelper,
+            futuresratehelper::FuturesRateHelper, oisratehelper::OisRateHelper,
+            ratehelper::RateHelperConventions, swapratehelper::SwapRateHelper,
+        },
+        time::{
+            calendar::Calendar,
+            calendars::target::TARGET,
+            enums::{Frequency, TimeUnit},
+            imm::IMM,
+            period::Period,
+        },
+    };
+
+    fn conventions() -> RateHelperConventions {
+        RateHelperConventions::new()
+            .with_calendar(Calendar::TARGET(TARGET::new()))
+            .with_settlement_days(2)
+    }
+
+    fn assert_reprices(curve: &DiscountTermStructure, helpers: &[RateHelper]) -> Result<()> {
+        for helper in helpers {
+            let implied = helper.implied_quote(curve)?;
+            assert!(
+                (implied - helper.quote()).abs() < 1e-9,
+                "helper implied {implied} but quoted {}",
+                helper.quote()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bootstrap_ibor_curve() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 3);
+        let first_imm = IMM::next_date(reference_date + Period::new(6, TimeUnit::Months), true);
+        let second_imm = IMM::next_date(first_imm, true);
+
+        let helpers = vec![
+            RateHelper::Deposit(DepositRateHelper::new(
+                0.0390,
+                Period::new(1, TimeUnit::Weeks),
+                conventions(),
+            )),
+            RateHelper::Deposit(DepositRateHelper::new(
+                0.0395,
+                Period::new(3, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Fra(FraRateHelper::new(
+                0.0385,
+                Period::new(3, TimeUnit::Months),
+                Period::new(6, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Futures(
+                FuturesRateHelper::new(
+                    96.30,
+                    first_imm,
+                    Period::new(3, TimeUnit::Months),
+                    conventions(),
+                )
+                .with_convexity_adjustment(0.0001),
+            ),
+            RateHelper::Futures(FuturesRateHelper::new(
+                96.45,
+                second_imm,
+                Period::new(3, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0340,
+                Period::new(2, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Semiannual,
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0320,
+                Period::new(5, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Semiannual,
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                0.0330,
+                Period::new(10, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Semiannual,
+                conventions(),
+            )),
+        ];
+
+        let curve = PiecewiseBootstrapper::new(reference_date, helpers.clone()).bootstrap()?;
+        assert_eq!(curve.dates().len(), helpers.len() + 1);
+        assert_reprices(&curve, &helpers)?;
+
+        // discount factors must be decreasing for positive rates
+        for window in curve.discount_factors().windows(2) {
+            assert!(window[1] < window[0]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bootstrap_ois_curve() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 3);
+        let quotes = [
+            (Period::new(1, TimeUnit::Months), 0.0390),
+            (Period::new(6, TimeUnit::Months), 0.0385),
+            (Period::new(1, TimeUnit::Years), 0.0360),
+            (Period::new(3, TimeUnit::Years), 0.0300),
+            (Period::new(10, TimeUnit::Years), 0.0280),
+            (Period::new(30, TimeUnit::Years), 0.0260),
+        ];
+        let helpers: Vec<RateHelper> = quotes
+            .iter()
+            .map(|(tenor, quote)| {
+                RateHelper::Ois(OisRateHelper::new(
+                    *quote,
+                    *tenor,
+                    Frequency::Annual,
+                    conventions(),
+                ))
+            })
+            .collect();
+
+        let curve = PiecewiseBootstrapper::new(reference_date, helpers.clone())
+            .with_interpolator(Interpolator::Linear)
+            .bootstrap()?;
+        assert_reprices(&curve, &helpers)?;
+        assert!((curve.discount_factor(reference_date)? - 1.0).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bootstrap_negative_rates() -> Result<()> {
+        let reference_date = Date::new(2020, 6, 1);
+        let helpers = vec![
+            RateHelper::Deposit(DepositRateHelper::new(
+                -0.0075,
+                Period::new(6, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Swap(SwapRateHelper::new(
+                -0.0050,
+                Period::new(5, TimeUnit::Years),
+                Frequency::Annual,
+                Frequency::Semiannual,
+                conventions(),
+            )),
+        ];
+
+        let curve = PiecewiseBootstrapper::new(reference_date, helpers.clone()).bootstrap()?;
+        assert_reprices(&curve, &helpers)?;
+        assert!(curve.discount_factors().iter().skip(1).all(|df| *df > 1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bootstrap_duplicated_pillars() {
+        let reference_date = Date::new(2024, 1, 3);
+        let helpers = vec![
+            RateHelper::Deposit(DepositRateHelper::new(
+                0.04,
+                Period::new(6, TimeUnit::Months),
+                conventions(),
+            )),
+            RateHelper::Fra(FraRateHelper::new(
+                0.04,
+                Period::new(3, TimeUnit::Months),
+                Period::new(6, TimeUnit::Months),
+                conventions(),
+            )),
+        ];
+        assert!(PiecewiseBootstrapper::new(reference_date, helpers)
+            .bootstrap()
+            .is_err());
+        assert!(PiecewiseBootstrapper::new(reference_date, Vec::new())
+            .bootstrap()
+            .is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{
+        calendar::Calendar,
+        calendars::{nullcalendar::NullCalendar, traits::IsCalendar},
+        date::Date,
+        daycounter::DayCounter,
+        enums::{BusinessDayConvention, Frequency, TimeUnit},
+        period::Period,
+        schedule::{MakeSchedule, Schedule},
+    },
+    utils::errors::Result,
+};
+
+use super::{
+    basisswapratehelper::TenorBasisSwapRateHelper, depositratehelper::DepositRateHelper,
+    fraratehelper::FraRateHelper, futuresratehelper::FuturesRateHelper,
+    oisratehelper::OisRateHelper, swapratehelper::SwapRateHelper, traits::RateHelperTrait,
+};
+
+/// # `RateHelperConventions`
+/// Market conventions shared by the rate helpers.
+///
+/// ## Parameters
+/// * `calendar` - The calendar used to roll dates
+/// * `day_counter` - The day counter of the quoted rate
+/// * `business_day_convention` - The convention used to adjust dates
+/// * `settlement_days` - The number of business days between trade and spot date
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let conventions = RateHelperConventions::new()
+///     .with_calendar(Calendar::WeekendsOnly(WeekendsOnly::new()))
+///     .with_settlement_days(2);
+///
+/// // Friday trade settles on Tuesday
+/// let spot = conventions.spot_date(Date::new(2024, 1, 5));
+/// assert_eq!(spot, Date::new(2024, 1, 9));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateHelperConventions {
+    calendar: Calendar,
+    day_counter: DayCounter,
+    business_day_convention: BusinessDayConvention,
+    settlement_days: i32,
+}
+
+impl RateHelperConventions {
+    /// Creates conventions with a null calendar, `Actual360`, modified following and no settlement lag.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            calendar: Calendar::NullCalendar(NullCalendar::new()),
+            day_counter: DayCounter::Actual360,
+            business_day_convention: BusinessDayConvention::ModifiedFollowing,
+            settlement_days: 0,
+        }
+    }
+
+    /// Sets the calendar.
+    #[must_use]
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Sets the day counter.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the business day convention.
+    #[must_use]
+    pub const fn with_business_day_convention(
+        mut self,
+        business_day_convention: BusinessDayConvention,
+    ) -> Self {
+        self.business_day_convention = business_day_convention;
+        self
+    }
+
+    /// Sets the settlement lag in business days.
+    #[must_use]
+    pub const fn with_settlement_days(mut self, settlement_days: i32) -> Self {
+        self.settlement_days = settlement_days;
+        self
+    }
+
+    /// Returns the calendar.
+    #[must_use]
+    pub const fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// Returns the day counter.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+
+    /// Returns the business day convention.
+    #[must_use]
+    pub const fn business_day_convention(&self) -> BusinessDayConvention {
+        self.business_day_convention
+    }
+
+    /// Returns the settlement lag in business days.
+    #[must_use]
+    pub const fn settlement_days(&self) -> i32 {
+        self.settlement_days
+    }
+
+    /// Returns the spot date for a trade done on `reference_date`.
+    #[must_use]
+    pub fn spot_date(&self, reference_date: Date) -> Date {
+        self.calendar.advance(
+            reference_date,
+            Period::new(self.settlement_days, TimeUnit::Days),
+            Some(self.business_day_convention),
+            false,
+        )
+    }
+
+    /// Advances `date` by `period` and adjusts the result with the business day convention.
+    #[must_use]
+    pub fn advance(&self, date: Date, period: Period) -> Date {
+        self.calendar
+            .advance(date, period, Some(self.business_day_convention), false)
+    }
+
+    /// Builds a backward schedule between `start_date` and `end_date` with the given frequency.
+    ///
+    /// # Errors
+    /// Returns an error if the schedule cannot be built.
+    pub fn schedule(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        frequency: Frequency,
+    ) -> Result<Schedule> {
+        MakeSchedule::new(start_date, end_date)
+            .with_frequency(frequency)
+            .with_calendar(self.calendar.clone())
+            .with_convention(self.business_day_convention)
+            .build()
+    }
+}
+
+impl Default for RateHelperConventions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # `RateHelper`
+/// Enum grouping the quoted instruments that can be used to bootstrap a curve.
+#[derive(Clone)]
+pub enum RateHelper {
+    /// Deposit quoted as a simple rate.
+    Deposit(DepositRateHelper),
+    /// Forward rate agreement quoted as a simple rate.
+    Fra(FraRateHelper),
+    /// Interest rate future quoted as a price.
+    Futures(FuturesRateHelper),
+    /// Fixed-vs-Ibor swap quoted as a par rate.
+    Swap(SwapRateHelper),
+    /// Overnight indexed swap quoted as a par rate.
+    Ois(OisRateHelper),
+    /// Ibor-vs-Ibor tenor basis swap quoted as a spread.
+    TenorBasisSwap(TenorBasisSwapRateHelper),
+}
+
+impl RateHelperTrait for RateHelper {
+    fn quote(&self) -> f64 {
+        match self {
+            Self::Deposit(helper) => helper.quote(),
+            Self::Fra(helper) => helper.quote(),
+            Self::Futures(helper) => helper.quote(),
+            Self::Swap(helper) => helper.quote(),
+            Self::Ois(helper) => helper.quote(),
+            Self::TenorBasisSwap(helper) => helper.quote(),
+        }
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        match self {
+            Self::Deposit(helper) => helper.pillar_date(reference_date),
+            Self::Fra(helper) => helper.pillar_date(reference_date),
+            Self::Futures(helper) => helper.pillar_date(reference_date),
+            Self::Swap(helper) => helper.pillar_date(reference_date),
+            Self::Ois(helper) => helper.pillar_date(reference_date),
+            Self::TenorBasisSwap(helper) => helper.pillar_date(reference_date),
+        }
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        match self {
+            Self::Deposit(helper) => helper.implied_quote(curve),
+            Self::Fra(helper) => helper.implied_quote(curve),
+            Self::Futures(helper) => helper.implied_quote(curve),
+            Self::Swap(helper) => helper.implied_quote(curve),
+            Self::Ois(helper) => helper.implied_quote(curve),
+            Self::TenorBasisSwap(helper) => helper.implied_quote(curve),
+        }
+    }
+
+    fn dual_curve_implied_quote(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        match self {
+            Self::Deposit(helper) => {
+                helper.dual_curve_implied_quote(forecast_curve, discount_curve)
+            }
+            Self::Fra(helper) => helper.dual_curve_implied_quote(forecast_curve, discount_curve),
+            Self::Futures(helper) => {
+                helper.dual_curve_implied_quote(forecast_curve, discount_curve)
+            }
+            Self::Swap(helper) => helper.dual_curve_implied_quote(forecast_curve, discount_curve),
+            Self::Ois(helper) => helper.dual_curve_implied_quote(forecast_curve, discount_curve),
+            Self::TenorBasisSwap(helper) => {
+                helper.dual_curve_implied_quote(forecast_curve, discount_curve)
+            }
+        }
+    }
+}
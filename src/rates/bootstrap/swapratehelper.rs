@@ -0,0 +1,238 @@
+use crate::{
+    rates::traits::YieldProvider,
+    time::{date::Date, daycounter::DayCounter, enums::Frequency, period::Period},
+    utils::errors::Result,
+};
+
+use super::{ratehelper::RateHelperConventions, traits::RateHelperTrait};
+
+/// # `SwapRateHelper`
+/// Fixed-vs-Ibor swap quoted as a par fixed rate. Both legs start at spot and end at spot
+/// plus `tenor`. The fixed leg accrues with the day counter of the conventions and the
+/// floating leg pays simple forwards projected from the curve plus an optional spread.
+/// Its pillar is the maturity of the swap.
+///
+/// ## Parameters
+/// * `quote` - The quoted par fixed rate
+/// * `tenor` - The tenor of the swap
+/// * `fixed_frequency` - The payment frequency of the fixed leg
+/// * `floating_frequency` - The payment frequency of the floating leg (the Ibor tenor)
+/// * `conventions` - The market conventions of the swap
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let helper = SwapRateHelper::new(
+///     0.05,
+///     Period::new(2, TimeUnit::Years),
+///     Frequency::Semiannual,
+///     Frequency::Quarterly,
+///     RateHelperConventions::new().with_day_counter(DayCounter::Thirty360),
+/// );
+/// assert_eq!(helper.pillar_date(Date::new(2024, 1, 2)), Date::new(2026, 1, 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SwapRateHelper {
+    quote: f64,
+    tenor: Period,
+    fixed_frequency: Frequency,
+    floating_frequency: Frequency,
+    floating_day_counter: DayCounter,
+    spread: f64,
+    conventions: RateHelperConventions,
+}
+
+impl SwapRateHelper {
+    /// Creates a new `SwapRateHelper` with an `Actual360` floating leg and no spread.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        quote: f64,
+        tenor: Period,
+        fixed_frequency: Frequency,
+        floating_frequency: Frequency,
+        conventions: RateHelperConventions,
+    ) -> Self {
+        Self {
+            quote,
+            tenor,
+            fixed_frequency,
+            floating_frequency,
+            floating_day_counter: DayCounter::Actual360,
+            spread: 0.0,
+            conventions,
+        }
+    }
+
+    /// Sets the day counter of the floating leg.
+    #[must_use]
+    pub const fn with_floating_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.floating_day_counter = day_counter;
+        self
+    }
+
+    /// Sets the spread paid over the floating leg.
+    #[must_use]
+    pub const fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Returns the tenor of the swap.
+    #[must_use]
+    pub const fn tenor(&self) -> Period {
+        self.tenor
+    }
+
+    /// Returns the payment frequency of the fixed leg.
+    #[must_use]
+    pub const fn fixed_frequency(&self) -> Frequency {
+        self.fixed_frequency
+    }
+
+    /// Returns the payment frequency of the floating leg.
+    #[must_use]
+    pub const fn floating_frequency(&self) -> Frequency {
+        self.floating_frequency
+    }
+
+    /// Returns the day counter of the floating leg.
+    #[must_use]
+    pub const fn floating_day_counter(&self) -> DayCounter {
+        self.floating_day_counter
+    }
+
+    /// Returns the spread paid over the floating leg.
+    #[must_use]
+    pub const fn spread(&self) -> f64 {
+        self.spread
+    }
+
+    /// Returns the market conventions of the swap.
+    #[must_use]
+    pub const fn conventions(&self) -> &RateHelperConventions {
+        &self.conventions
+    }
+
+    /// Returns the start and maturity dates of the swap.
+    #[must_use]
+    pub fn dates(&self, reference_date: Date) -> (Date, Date) {
+        let start_date = self.conventions.spot_date(reference_date);
+        let end_date = self.conventions.advance(start_date, self.tenor);
+        (start_date, end_date)
+    }
+
+    /// Returns the par fixed rate of the swap, projecting the floating leg on
+    /// `forecast_curve` and discounting both legs on `discount_curve`.
+    ///
+    /// # Errors
+    /// Returns an error if the schedules cannot be built or if a discount factor
+    /// cannot be obtained from the curves.
+    pub fn par_rate(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        let (start_date, end_date) = self.dates(discount_curve.reference_date());
+
+        let fixed_schedule =
+            self.conventions
+                .schedule(start_date, end_date, self.fixed_frequency)?;
+        let mut annuity = 0.0;
+        for period in fixed_schedule.dates().windows(2) {
+            let yf = self
+                .conventions
+                .day_counter()
+                .year_fraction(period[0], period[1]);
+            annuity += yf * discount_curve.discount_factor(period[1])?;
+        }
+
+        let floating_schedule =
+            self.conventions
+                .schedule(start_date, end_date, self.floating_frequency)?;
+        let mut floating_npv = 0.0;
+        for period in floating_schedule.dates().windows(2) {
+            let yf = self
+                .floating_day_counter
+                .year_fraction(period[0], period[1]);
+            let compound = forecast_curve.discount_factor(period[0])?
+                / forecast_curve.discount_factor(period[1])?;
+            let accrual = self.spread.mul_add(yf, compound - 1.0);
+            floating_npv += accrual * discount_curve.discount_factor(period[1])?;
+        }
+
+        Ok(floating_npv / annuity)
+    }
+}
+
+impl RateHelperTrait for SwapRateHelper {
+    fn quote(&self) -> f64 {
+        self.quote
+    }
+
+    fn pillar_date(&self, reference_date: Date) -> Date {
+        self.dates(reference_date).1
+    }
+
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        self.par_rate(curve, curve)
+    }
+
+    fn dual_curve_implied_quote(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        self.par_rate(forecast_curve, discount_curve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rates::{
+            enums::Compounding, interestrate::RateDefinition,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::enums::TimeUnit,
+    };
+
+    #[test]
+    fn test_single_curve_par_rate() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let helper = SwapRateHelper::new(
+            0.05,
+            Period::new(5, TimeUnit::Years),
+            Frequency::Annual,
+            Frequency::Quarterly,
+            RateHelperConventions::new(),
+        );
+        let curve = FlatForwardTermStructure::new(
+            reference_date,
+            0.04,
+            RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Compounded,
+                Frequency::Annual,
+            ),
+        );
+
+        // single curve: the floating leg telescopes to df(start) - df(end)
+        let (start_date, end_date) = helper.dates(reference_date);
+        let schedule = helper
+            .conventions()
+            .schedule(start_date, end_date, Frequency::Annual)?;
+        let mut annuity = 0.0;
+        for period in schedule.dates().windows(2) {
+            annuity += DayCounter::Actual360.year_fraction(period[0], period[1])
+                * curve.discount_factor(period[1])?;
+        }
+        let expected =
+            (curve.discount_factor(start_date)? - curve.discount_factor(end_date)?) / annuity;
+
+        assert!((helper.implied_quote(&curve)? - expected).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{rates::traits::YieldProvider, time::date::Date, utils::errors::Result};
+
+/// # `RateHelperTrait`
+/// Implement this trait for a quoted instrument that can be used to bootstrap a curve.
+/// A helper exposes its market quote, the curve node it determines and the quote
+/// implied by a given curve.
+pub trait RateHelperTrait {
+    /// Returns the market quote of the helper.
+    fn quote(&self) -> f64;
+
+    /// Returns the date of the curve node determined by this helper, given the curve reference date.
+    fn pillar_date(&self, reference_date: Date) -> Date;
+
+    /// Returns the quote implied by the given curve. The curve reference date is used
+    /// as the trade date of the helper.
+    ///
+    /// # Errors
+    /// Returns an error if the required discount factors cannot be obtained from the curve
+    /// or if the helper schedule cannot be built.
+    fn implied_quote(&self, curve: &dyn YieldProvider) -> Result<f64>;
+
+    /// Returns the quote implied when cashflows are projected on `forecast_curve` and
+    /// discounted on `discount_curve`. Helpers whose quote does not depend on discounting
+    /// (deposits, FRAs, futures) return the single-curve implied quote.
+    ///
+    /// # Errors
+    /// Returns an error if the required discount factors cannot be obtained from the curves
+    /// or if the helper schedule cannot be built.
+    fn dual_curve_implied_quote(
+        &self,
+        forecast_curve: &dyn YieldProvider,
+        _discount_curve: &dyn YieldProvider,
+    ) -> Result<f64> {
+        self.implied_quote(forecast_curve)
+    }
+
+    /// Returns the difference between the implied quote and the market quote.
+    ///
+    /// # Errors
+    /// Returns an error if the implied quote cannot be computed.
+    fn quote_error(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        Ok(self.implied_quote(curve)? - self.quote())
+    }
+}
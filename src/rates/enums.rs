@@ -46,3 +46,13 @@ impl From<Compounding> for String {
         }
     }
 }
+
+/// # `VolatilityType`
+/// Enumerate the quoting conventions of interest rate volatilities.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum VolatilityType {
+    /// Lognormal (Black) volatilities of the rate plus the given shift.
+    ShiftedLognormal(f64),
+    /// Normal (Bachelier) volatilities, in absolute rate terms.
+    Normal,
+}
@@ -0,0 +1,187 @@
+use crate::{
+    cashflows::{cashflow::Cashflow, traits::Payable},
+    instruments::fixedrateinstrument::FixedRateInstrument,
+    rates::{interestrate::InterestRate, traits::YieldProvider},
+    time::date::Date,
+    utils::errors::{AtlasError, Result},
+    visitors::traits::HasCashflows,
+};
+
+/// # `BondQuote`
+/// Market quote of a bond used in a curve fit.
+#[derive(Clone, Copy, Debug)]
+pub enum BondQuote {
+    /// Dirty price per unit of notional (e.g. `1.02` for 102%).
+    DirtyPrice(f64),
+    /// Yield to maturity, expressed with the rate definition of the bond coupon.
+    Yield(f64),
+}
+
+/// # `FittingBond`
+/// A `FixedRateInstrument` with its market quote, as used by the parametric curve fitters.
+/// Prices are measured per unit of notional on the cashflows paid after the fitting
+/// reference date, from the point of view of the holder; yield quotes are turned into
+/// dirty prices before fitting.
+///
+/// ## Parameters
+/// * `instrument` - The bond
+/// * `quote` - Its dirty price or yield
+/// * `weight` - Its weight in the fitting objective (defaults to 1.0)
+#[derive(Clone)]
+pub struct FittingBond {
+    instrument: FixedRateInstrument,
+    quote: BondQuote,
+    weight: f64,
+}
+
+impl FittingBond {
+    /// Creates a new `FittingBond` with unit weight.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(instrument: FixedRateInstrument, quote: BondQuote) -> Self {
+        Self {
+            instrument,
+            quote,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the weight of the bond in the fitting objective.
+    #[must_use]
+    pub const fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Returns the bond.
+    #[must_use]
+    pub const fn instrument(&self) -> &FixedRateInstrument {
+        &self.instrument
+    }
+
+    /// Returns the market quote.
+    #[must_use]
+    pub const fn quote(&self) -> BondQuote {
+        self.quote
+    }
+
+    /// Returns the weight of the bond in the fitting objective.
+    #[must_use]
+    pub const fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Returns the payment dates and amounts per unit of notional of the cashflows paid
+    /// after `reference_date`.
+    ///
+    /// # Errors
+    /// Returns an error if the bond has a zero notional, no cashflows after the reference
+    /// date, or if a cashflow amount is not available.
+    pub fn cashflows(&self, reference_date: Date) -> Result<Vec<(Date, f64)>> {
+        let notional = self.instrument.notional();
+        if notional == 0.0 {
+            return Err(AtlasError::InvalidValueErr(
+                "Bond notional must be different from zero".to_string(),
+            ));
+        }
+        let cashflows = self
+            .instrument
+            .cashflows()
+            .iter()
+            .filter(|cf| {
+                !matches!(cf, Cashflow::Disbursement(_)) && cf.payment_date() > reference_date
+            })
+            .map(|cf| Ok((cf.payment_date(), cf.amount()? / notional.abs())))
+            .collect::<Result<Vec<(Date, f64)>>>()?;
+
+        if cashflows.is_empty() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Bond maturing on {} has no cashflows after {reference_date}",
+                self.instrument.end_date()
+            )));
+        }
+        Ok(cashflows)
+    }
+
+    /// Returns the quoted dirty price per unit of notional.
+    ///
+    /// # Errors
+    /// Returns an error if the cashflows of the bond cannot be obtained.
+    pub fn target_price(&self, reference_date: Date) -> Result<f64> {
+        match self.quote {
+            BondQuote::DirtyPrice(price) => Ok(price),
+            BondQuote::Yield(yield_rate) => {
+                let rate = InterestRate::from_rate_definition(
+                    yield_rate,
+                    self.instrument.rate().rate_definition(),
+                );
+                Ok(self
+                    .cashflows(reference_date)?
+                    .iter()
+                    .map(|(date, amount)| amount * rate.discount_factor(reference_date, *date))
+                    .sum())
+            }
+        }
+    }
+
+    /// Returns the dirty price per unit of notional implied by `curve`.
+    ///
+    /// # Errors
+    /// Returns an error if the cashflows of the bond cannot be obtained or the curve cannot
+    /// provide a discount factor.
+    pub fn model_price(&self, curve: &dyn YieldProvider) -> Result<f64> {
+        self.cashflows(curve.reference_date())?
+            .iter()
+            .try_fold(0.0, |acc, (date, amount)| {
+                Ok(amount.mul_add(curve.discount_factor(*date)?, acc))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        rates::{
+            enums::Compounding,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+    };
+
+    #[test]
+    fn test_yield_quote_matches_flat_curve() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let rate = InterestRate::new(
+            0.05,
+            Compounding::Compounded,
+            Frequency::Annual,
+            DayCounter::Actual365,
+        );
+        let instrument = MakeFixedRateInstrument::new()
+            .with_start_date(reference_date)
+            .with_end_date(reference_date + Period::new(5, TimeUnit::Years))
+            .with_payment_frequency(Frequency::Annual)
+            .with_rate(rate)
+            .with_notional(1_000_000.0)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .build()?;
+
+        // at a yield equal to the coupon the bond prices at par
+        let bond = FittingBond::new(instrument, BondQuote::Yield(0.05));
+        assert!((bond.target_price(reference_date)? - 1.0).abs() < 1e-12);
+
+        let curve = FlatForwardTermStructure::new(reference_date, 0.05, rate.rate_definition());
+        assert!((bond.model_price(&curve)? - 1.0).abs() < 1e-12);
+        Ok(())
+    }
+}
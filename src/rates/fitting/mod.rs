@@ -0,0 +1,12 @@
+//! Parametric yield curve fitting to bond prices or yields.
+
+/// Bonds with their market quotes used as fitting inputs.
+pub mod fittingbond;
+/// Nelson-Siegel curve fitter.
+pub mod nelsonsiegelfitter;
+/// Pricing error objective shared by the parametric fitters.
+pub(crate) mod pricingerror;
+/// Smith-Wilson curve fitter.
+pub mod smithwilsonfitter;
+/// Nelson-Siegel-Svensson curve fitter.
+pub mod svenssonfitter;
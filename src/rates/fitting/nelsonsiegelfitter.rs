@@ -0,0 +1,225 @@
+use crate::{
+    rates::yieldtermstructure::nelsonsiegeltermstructure::NelsonSiegelTermStructure,
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    fittingbond::FittingBond,
+    pricingerror::{minimize_pricing_error, MAX_TAU, MIN_TAU, TAU_GRID},
+};
+
+/// # `NelsonSiegelFitter`
+/// Calibrates a `NelsonSiegelTermStructure` to a set of bond prices or yields by minimising
+/// the weighted sum of squared dirty price errors with a Nelder-Mead simplex. Besides the
+/// initial parameters, the solver is also started with `tau` set to a few typical values
+/// between six months and ten years, and the best fit is kept.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the fitted curve
+/// * `bonds` - The bonds with their quotes
+/// * `day_counter` - The day counter of the curve (defaults to `Actual365`)
+/// * `initial_parameters` - The starting point `[b0, b1, b2, tau]` (defaults to `[0.03, 0.0, 0.0, 2.0]`)
+/// * `accuracy` - The tolerance on the simplex costs (defaults to 1e-16)
+/// * `max_iterations` - The maximum number of solver iterations (defaults to 5000)
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let bonds: Vec<FittingBond> = [(2, 0.031), (5, 0.034), (10, 0.037), (20, 0.039)]
+///     .iter()
+///     .map(|(years, yield_rate)| {
+///         let rate = InterestRate::new(
+///             *yield_rate,
+///             Compounding::Compounded,
+///             Frequency::Annual,
+///             DayCounter::Actual365,
+///         );
+///         let instrument = MakeFixedRateInstrument::new()
+///             .with_start_date(reference_date)
+///             .with_end_date(reference_date + Period::new(*years, TimeUnit::Years))
+///             .with_payment_frequency(Frequency::Annual)
+///             .with_rate(rate)
+///             .with_notional(100.0)
+///             .with_side(Side::Receive)
+///             .with_currency(Currency::USD)
+///             .bullet()
+///             .build()
+///             .unwrap();
+///         FittingBond::new(instrument, BondQuote::Yield(*yield_rate))
+///     })
+///     .collect();
+///
+/// let curve = NelsonSiegelFitter::new(reference_date, bonds.clone()).fit().unwrap();
+/// for bond in &bonds {
+///     let error = bond.model_price(&curve).unwrap() - bond.target_price(reference_date).unwrap();
+///     assert!(error.abs() < 1e-3);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct NelsonSiegelFitter {
+    reference_date: Date,
+    bonds: Vec<FittingBond>,
+    day_counter: DayCounter,
+    initial_parameters: [f64; 4],
+    accuracy: f64,
+    max_iterations: u64,
+}
+
+impl NelsonSiegelFitter {
+    /// Creates a new `NelsonSiegelFitter`.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(reference_date: Date, bonds: Vec<FittingBond>) -> Self {
+        Self {
+            reference_date,
+            bonds,
+            day_counter: DayCounter::Actual365,
+            initial_parameters: [0.03, 0.0, 0.0, 2.0],
+            accuracy: 1e-16,
+            max_iterations: 5000,
+        }
+    }
+
+    /// Sets the day counter of the fitted curve.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the starting point `[b0, b1, b2, tau]` of the solver.
+    #[must_use]
+    pub const fn with_initial_parameters(mut self, initial_parameters: [f64; 4]) -> Self {
+        self.initial_parameters = initial_parameters;
+        self
+    }
+
+    /// Sets the tolerance on the simplex costs.
+    #[must_use]
+    pub const fn with_accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Sets the maximum number of solver iterations.
+    #[must_use]
+    pub const fn with_max_iterations(mut self, max_iterations: u64) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns the bonds with their quotes.
+    #[must_use]
+    pub const fn bonds(&self) -> &Vec<FittingBond> {
+        &self.bonds
+    }
+
+    /// Fits the curve.
+    ///
+    /// # Errors
+    /// Returns an error if there are no bonds, if a bond quote cannot be turned into a price
+    /// or if the solver fails.
+    pub fn fit(&self) -> Result<NelsonSiegelTermStructure> {
+        let [b0, b1, b2, tau] = self.initial_parameters;
+        if tau <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Initial Nelson-Siegel tau must be positive, got {tau}"
+            )));
+        }
+
+        // the solver works on ln(tau), which keeps tau positive and better scaled
+        let build_curve = |parameters: &[f64]| {
+            let [b0, b1, b2, log_tau]: [f64; 4] = parameters.try_into().map_err(|_| {
+                AtlasError::InvalidValueErr("Nelson-Siegel needs four parameters".to_string())
+            })?;
+            let tau = log_tau.exp();
+            if !(MIN_TAU..=MAX_TAU).contains(&tau) {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Nelson-Siegel tau {tau} is outside [{MIN_TAU}, {MAX_TAU}]"
+                )));
+            }
+            NelsonSiegelTermStructure::new(self.reference_date, [b0, b1, b2, tau], self.day_counter)
+        };
+        let starting_points: Vec<Vec<f64>> = std::iter::once(tau)
+            .chain(TAU_GRID)
+            .map(|tau| vec![b0, b1, b2, tau.ln()])
+            .collect();
+        let parameters = minimize_pricing_error(
+            self.reference_date,
+            &self.bonds,
+            &starting_points,
+            &[0.01, 0.01, 0.01, 0.5],
+            build_curve,
+            self.accuracy,
+            self.max_iterations,
+        )?;
+        build_curve(&parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        rates::{
+            enums::Compounding, fitting::fittingbond::BondQuote, interestrate::InterestRate,
+            traits::YieldProvider,
+        },
+        time::{
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+    };
+
+    #[test]
+    fn test_recovers_parameters() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let true_curve = NelsonSiegelTermStructure::new(
+            reference_date,
+            [0.045, -0.02, 0.015, 1.8],
+            DayCounter::Actual365,
+        )?;
+
+        let bonds = [1, 2, 3, 5, 7, 10, 15, 20, 30]
+            .iter()
+            .map(|years| {
+                let instrument = MakeFixedRateInstrument::new()
+                    .with_start_date(reference_date)
+                    .with_end_date(reference_date + Period::new(*years, TimeUnit::Years))
+                    .with_payment_frequency(Frequency::Semiannual)
+                    .with_rate(InterestRate::new(
+                        0.04,
+                        Compounding::Simple,
+                        Frequency::Annual,
+                        DayCounter::Actual365,
+                    ))
+                    .with_notional(100.0)
+                    .with_side(Side::Receive)
+                    .with_currency(Currency::USD)
+                    .bullet()
+                    .build()?;
+                let bond = FittingBond::new(instrument, BondQuote::DirtyPrice(0.0));
+                let price = bond.model_price(&true_curve)?;
+                Ok(FittingBond::new(
+                    bond.instrument().clone(),
+                    BondQuote::DirtyPrice(price),
+                ))
+            })
+            .collect::<Result<Vec<FittingBond>>>()?;
+
+        let curve = NelsonSiegelFitter::new(reference_date, bonds.clone()).fit()?;
+        for bond in &bonds {
+            let error = bond.model_price(&curve)? - bond.target_price(reference_date)?;
+            assert!(error.abs() < 1e-6, "pricing error {error}");
+        }
+        let date = Date::new(2040, 1, 2);
+        assert!((curve.discount_factor(date)? - true_curve.discount_factor(date)?).abs() < 1e-5);
+        Ok(())
+    }
+}
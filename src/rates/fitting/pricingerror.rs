@@ -0,0 +1,182 @@
+use argmin::{
+    core::{CostFunction, Error, Executor, State},
+    solver::neldermead::NelderMead,
+};
+
+use crate::{
+    rates::traits::YieldProvider,
+    time::date::Date,
+    utils::errors::{AtlasError, Result},
+};
+
+use super::fittingbond::FittingBond;
+
+/// Cost returned for parameters the curve cannot be built with, so that the simplex moves
+/// away from them.
+const INVALID_PARAMETERS_COST: f64 = 1e10;
+
+/// Smallest decay time, in years, the parametric fitters accept. Below it the hump
+/// collapses into the short end and the fit degenerates into a flat curve.
+pub const MIN_TAU: f64 = 0.05;
+
+/// Largest decay time, in years, the parametric fitters accept.
+pub const MAX_TAU: f64 = 100.0;
+
+/// Decay times, in years, the parametric fitters also start the solver from. The pricing
+/// error has several local minima in `tau`, so a single start is not reliable.
+pub const TAU_GRID: [f64; 5] = [0.5, 1.0, 3.0, 5.0, 10.0];
+
+/// Maximum number of times the simplex is rebuilt around the best point found so far.
+const MAX_RESTARTS: usize = 20;
+
+/// Remaining cashflows, target price and weight of a bond at the fitting reference date.
+struct PreparedBond {
+    cashflows: Vec<(Date, f64)>,
+    target_price: f64,
+    weight: f64,
+}
+
+/// Weighted sum of squared price errors of the curve built from a parameter vector.
+struct PricingError<'a, F> {
+    bonds: &'a [PreparedBond],
+    build_curve: &'a F,
+}
+
+impl<F, C> PricingError<'_, F>
+where
+    F: Fn(&[f64]) -> Result<C>,
+    C: YieldProvider,
+{
+    fn evaluate(&self, parameters: &[f64]) -> Result<f64> {
+        let curve = (self.build_curve)(parameters)?;
+        self.bonds.iter().try_fold(0.0, |acc, bond| {
+            let price = bond.cashflows.iter().try_fold(0.0, |acc, (date, amount)| {
+                Ok::<f64, AtlasError>(amount.mul_add(curve.discount_factor(*date)?, acc))
+            })?;
+            let error = price - bond.target_price;
+            Ok((bond.weight * error).mul_add(error, acc))
+        })
+    }
+}
+
+impl<F, C> CostFunction for PricingError<'_, F>
+where
+    F: Fn(&[f64]) -> Result<C>,
+    C: YieldProvider,
+{
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, parameters: &Self::Param) -> std::result::Result<Self::Output, Error> {
+        Ok(self
+            .evaluate(parameters)
+            .ok()
+            .filter(|cost| cost.is_finite())
+            .unwrap_or(INVALID_PARAMETERS_COST))
+    }
+}
+
+/// Minimises the weighted squared pricing errors of `bonds` over the parameters of the
+/// curve returned by `build_curve`, using a Nelder-Mead simplex around each of the
+/// `starting_points` with the given initial `steps`, and returns the best parameters found.
+/// Since the simplex tends to collapse before reaching the minimum on these problems, it is
+/// rebuilt around the best point until the cost stops improving by more than `accuracy`.
+///
+/// # Errors
+/// Returns an error if there are no bonds or starting points, if a bond cannot be priced
+/// from its quote or if the solver fails.
+pub fn minimize_pricing_error<F, C>(
+    reference_date: Date,
+    bonds: &[FittingBond],
+    starting_points: &[Vec<f64>],
+    steps: &[f64],
+    build_curve: F,
+    accuracy: f64,
+    max_iterations: u64,
+) -> Result<Vec<f64>>
+where
+    F: Fn(&[f64]) -> Result<C>,
+    C: YieldProvider,
+{
+    if bonds.is_empty() {
+        return Err(AtlasError::InvalidValueErr(
+            "At least one bond is needed to fit a curve".to_string(),
+        ));
+    }
+    let bonds = bonds
+        .iter()
+        .map(|bond| {
+            Ok(PreparedBond {
+                cashflows: bond.cashflows(reference_date)?,
+                target_price: bond.target_price(reference_date)?,
+                weight: bond.weight(),
+            })
+        })
+        .collect::<Result<Vec<PreparedBond>>>()?;
+
+    let mut best: Option<(Vec<f64>, f64)> = None;
+    for initial_parameters in starting_points {
+        let (parameters, cost) = minimize_from(
+            &bonds,
+            initial_parameters,
+            steps,
+            &build_curve,
+            accuracy,
+            max_iterations,
+        )?;
+        if best.as_ref().is_none_or(|(_, best_cost)| cost < *best_cost) {
+            best = Some((parameters, cost));
+        }
+    }
+    best.map(|(parameters, _)| parameters).ok_or_else(|| {
+        AtlasError::InvalidValueErr("At least one starting point is needed".to_string())
+    })
+}
+
+/// Runs the restarted simplex from a single starting point and returns the best parameters
+/// with their cost.
+fn minimize_from<F, C>(
+    bonds: &[PreparedBond],
+    initial_parameters: &[f64],
+    steps: &[f64],
+    build_curve: &F,
+    accuracy: f64,
+    max_iterations: u64,
+) -> Result<(Vec<f64>, f64)>
+where
+    F: Fn(&[f64]) -> Result<C>,
+    C: YieldProvider,
+{
+    let mut best_parameters = initial_parameters.to_vec();
+    let mut best_cost = f64::INFINITY;
+    for _ in 0..MAX_RESTARTS {
+        let simplex = std::iter::once(best_parameters.clone())
+            .chain(steps.iter().enumerate().map(|(i, step)| {
+                let mut vertex = best_parameters.clone();
+                vertex[i] += step;
+                vertex
+            }))
+            .collect();
+        let solver = NelderMead::new(simplex).with_sd_tolerance(accuracy)?;
+        let cost = PricingError { bonds, build_curve };
+
+        let res = Executor::new(cost, solver)
+            .configure(|state| state.max_iters(max_iterations))
+            .run()?;
+
+        let cost = res.state().get_best_cost();
+        let parameters = res.state().get_best_param().cloned().ok_or_else(|| {
+            AtlasError::EvaluationErr("No parameters found when fitting the curve".to_string())
+        })?;
+        if cost >= best_cost {
+            break;
+        }
+        let improvement = best_cost - cost;
+        best_parameters = parameters;
+        best_cost = cost;
+        if improvement <= accuracy {
+            break;
+        }
+    }
+    Ok((best_parameters, best_cost))
+}
@@ -0,0 +1,292 @@
+use crate::{
+    rates::yieldtermstructure::smithwilsontermstructure::{
+        wilson_function, SmithWilsonTermStructure,
+    },
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::fittingbond::FittingBond;
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting.
+fn solve_linear_system(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Result<Vec<f64>> {
+    let n = b.len();
+    let scale = a.iter().flatten().fold(0.0_f64, |acc, x| acc.max(x.abs()));
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))
+            .unwrap_or(col);
+        if a[pivot][col].abs() <= scale * 1e-12 {
+            return Err(AtlasError::EvaluationErr(
+                "Smith-Wilson system is singular, check for duplicated bonds".to_string(),
+            ));
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, pivot_value) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Ok(x)
+}
+
+/// # `SmithWilsonFitter`
+/// Calibrates a `SmithWilsonTermStructure` to a set of bond prices or yields. Every cashflow
+/// date becomes a node of the curve and the node weights solve the linear system that makes
+/// the curve reprice every bond, so for a given ultimate forward rate and convergence speed
+/// the pricing errors are zero up to rounding.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the fitted curve
+/// * `bonds` - The bonds with their quotes
+/// * `ultimate_forward_rate` - The annually compounded ultimate forward rate (defaults to 3.6%)
+/// * `convergence_speed` - The convergence speed `alpha` (defaults to 0.1)
+/// * `day_counter` - The day counter of the curve (defaults to `Actual365`)
+#[derive(Clone)]
+pub struct SmithWilsonFitter {
+    reference_date: Date,
+    bonds: Vec<FittingBond>,
+    ultimate_forward_rate: f64,
+    convergence_speed: f64,
+    day_counter: DayCounter,
+}
+
+impl SmithWilsonFitter {
+    /// Creates a new `SmithWilsonFitter`.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(reference_date: Date, bonds: Vec<FittingBond>) -> Self {
+        Self {
+            reference_date,
+            bonds,
+            ultimate_forward_rate: 0.036,
+            convergence_speed: 0.1,
+            day_counter: DayCounter::Actual365,
+        }
+    }
+
+    /// Sets the annually compounded ultimate forward rate.
+    #[must_use]
+    pub const fn with_ultimate_forward_rate(mut self, ultimate_forward_rate: f64) -> Self {
+        self.ultimate_forward_rate = ultimate_forward_rate;
+        self
+    }
+
+    /// Sets the convergence speed `alpha`.
+    #[must_use]
+    pub const fn with_convergence_speed(mut self, convergence_speed: f64) -> Self {
+        self.convergence_speed = convergence_speed;
+        self
+    }
+
+    /// Sets the day counter of the fitted curve.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Returns the bonds with their quotes.
+    #[must_use]
+    pub const fn bonds(&self) -> &Vec<FittingBond> {
+        &self.bonds
+    }
+
+    /// Fits the curve.
+    ///
+    /// # Errors
+    /// Returns an error if there are no bonds, if a bond quote cannot be turned into a price,
+    /// if the curve parameters are invalid or if the bonds do not define a solvable system.
+    pub fn fit(&self) -> Result<SmithWilsonTermStructure> {
+        if self.bonds.is_empty() {
+            return Err(AtlasError::InvalidValueErr(
+                "At least one bond is needed to fit a curve".to_string(),
+            ));
+        }
+
+        let cashflows = self
+            .bonds
+            .iter()
+            .map(|bond| bond.cashflows(self.reference_date))
+            .collect::<Result<Vec<Vec<(Date, f64)>>>>()?;
+        let prices = self
+            .bonds
+            .iter()
+            .map(|bond| bond.target_price(self.reference_date))
+            .collect::<Result<Vec<f64>>>()?;
+
+        let mut dates: Vec<Date> = cashflows.iter().flatten().map(|(date, _)| *date).collect();
+        dates.sort();
+        dates.dedup();
+        let nodes: Vec<f64> = dates
+            .iter()
+            .map(|date| self.day_counter.year_fraction(self.reference_date, *date))
+            .collect();
+
+        // cashflow matrix: one row per bond, one column per node
+        let matrix: Vec<Vec<f64>> = cashflows
+            .iter()
+            .map(|bond_cashflows| {
+                let mut row = vec![0.0; dates.len()];
+                for (date, amount) in bond_cashflows {
+                    if let Ok(j) = dates.binary_search(date) {
+                        row[j] += amount;
+                    }
+                }
+                row
+            })
+            .collect();
+
+        let omega = self.ultimate_forward_rate.ln_1p();
+        let alpha = self.convergence_speed;
+        let kernel: Vec<Vec<f64>> = nodes
+            .iter()
+            .map(|t| {
+                nodes
+                    .iter()
+                    .map(|u| wilson_function(*t, *u, omega, alpha))
+                    .collect()
+            })
+            .collect();
+
+        // (C W C') zeta = p - C mu
+        let weighted: Vec<Vec<f64>> = matrix
+            .iter()
+            .map(|row| {
+                (0..nodes.len())
+                    .map(|k| row.iter().zip(&kernel).map(|(c, w)| c * w[k]).sum())
+                    .collect()
+            })
+            .collect();
+        let system: Vec<Vec<f64>> = weighted
+            .iter()
+            .map(|wrow| {
+                matrix
+                    .iter()
+                    .map(|row| wrow.iter().zip(row).map(|(a, b)| a * b).sum())
+                    .collect()
+            })
+            .collect();
+        let rhs: Vec<f64> = matrix
+            .iter()
+            .zip(&prices)
+            .map(|(row, price)| {
+                price
+                    - row
+                        .iter()
+                        .zip(&nodes)
+                        .map(|(c, u)| c * (-omega * u).exp())
+                        .sum::<f64>()
+            })
+            .collect();
+        let zeta = solve_linear_system(system, rhs)?;
+
+        let weights: Vec<f64> = (0..nodes.len())
+            .map(|j| matrix.iter().zip(&zeta).map(|(row, z)| row[j] * z).sum())
+            .collect();
+
+        SmithWilsonTermStructure::new(
+            self.reference_date,
+            self.ultimate_forward_rate,
+            self.convergence_speed,
+            nodes,
+            weights,
+            self.day_counter,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        rates::{
+            enums::Compounding, fitting::fittingbond::BondQuote, interestrate::InterestRate,
+            traits::YieldProvider, yieldtermstructure::traits::AdvanceTermStructureInTime,
+        },
+        time::{
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+    };
+
+    fn par_bonds(reference_date: Date) -> Result<Vec<FittingBond>> {
+        [(1, 0.030), (2, 0.032), (5, 0.034), (10, 0.035), (20, 0.036)]
+            .iter()
+            .map(|(years, coupon)| {
+                let instrument = MakeFixedRateInstrument::new()
+                    .with_start_date(reference_date)
+                    .with_end_date(reference_date + Period::new(*years, TimeUnit::Years))
+                    .with_payment_frequency(Frequency::Annual)
+                    .with_rate(InterestRate::new(
+                        *coupon,
+                        Compounding::Compounded,
+                        Frequency::Annual,
+                        DayCounter::Actual365,
+                    ))
+                    .with_notional(100.0)
+                    .with_side(Side::Receive)
+                    .with_currency(Currency::EUR)
+                    .bullet()
+                    .build()?;
+                Ok(FittingBond::new(instrument, BondQuote::Yield(*coupon)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reprices_bonds_and_converges() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 1);
+        let bonds = par_bonds(reference_date)?;
+        let curve = SmithWilsonFitter::new(reference_date, bonds.clone())
+            .with_ultimate_forward_rate(0.036)
+            .with_convergence_speed(0.15)
+            .fit()?;
+
+        for bond in &bonds {
+            let error = bond.model_price(&curve)? - bond.target_price(reference_date)?;
+            assert!(error.abs() < 1e-10, "pricing error {error}");
+        }
+
+        let start = reference_date + Period::new(100, TimeUnit::Years);
+        let end = reference_date + Period::new(101, TimeUnit::Years);
+        let forward = curve.forward_rate(start, end, Compounding::Compounded, Frequency::Annual)?;
+        assert!((forward - 0.036).abs() < 1e-4);
+
+        // the advanced curve keeps the implied forwards
+        let advanced = curve.advance_to_period(Period::new(1, TimeUnit::Years))?;
+        let (start, end) = (Date::new(2030, 1, 1), Date::new(2035, 1, 1));
+        let expected =
+            curve.forward_rate(start, end, Compounding::Compounded, Frequency::Annual)?;
+        let forward =
+            advanced.forward_rate(start, end, Compounding::Compounded, Frequency::Annual)?;
+        assert!((forward - expected).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicated_bonds_fail() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 1);
+        let mut bonds = par_bonds(reference_date)?;
+        bonds.push(bonds[0].clone());
+        assert!(SmithWilsonFitter::new(reference_date, bonds).fit().is_err());
+        assert!(SmithWilsonFitter::new(reference_date, Vec::new())
+            .fit()
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    rates::yieldtermstructure::svenssontermstructure::SvenssonTermStructure,
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    fittingbond::FittingBond,
+    nelsonsiegelfitter::NelsonSiegelFitter,
+    pricingerror::{minimize_pricing_error, MAX_TAU, MIN_TAU, TAU_GRID},
+};
+
+/// # `SvenssonFitter`
+/// Calibrates a `SvenssonTermStructure` to a set of bond prices or yields by minimising the
+/// weighted sum of squared dirty price errors with a Nelder-Mead simplex.
+///
+/// Unless a starting point is given, the bonds are first fitted with a `NelsonSiegelFitter`
+/// and the solver starts from that curve with no second hump, which is usually much more
+/// stable than starting the six parameters from scratch. The solver is also started with
+/// `tau2` set to a few typical values, since `b2` and `b3` cannot be told apart when both
+/// humps sit at the same place, and the best fit is kept.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the fitted curve
+/// * `bonds` - The bonds with their quotes
+/// * `day_counter` - The day counter of the curve (defaults to `Actual365`)
+/// * `initial_parameters` - The starting point `[b0, b1, b2, b3, tau1, tau2]`, if any
+/// * `accuracy` - The tolerance on the simplex costs (defaults to 1e-16)
+/// * `max_iterations` - The maximum number of solver iterations (defaults to 10000)
+#[derive(Clone)]
+pub struct SvenssonFitter {
+    reference_date: Date,
+    bonds: Vec<FittingBond>,
+    day_counter: DayCounter,
+    initial_parameters: Option<[f64; 6]>,
+    accuracy: f64,
+    max_iterations: u64,
+}
+
+impl SvenssonFitter {
+    /// Creates a new `SvenssonFitter`.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(reference_date: Date, bonds: Vec<FittingBond>) -> Self {
+        Self {
+            reference_date,
+            bonds,
+            day_counter: DayCounter::Actual365,
+            initial_parameters: None,
+            accuracy: 1e-16,
+            max_iterations: 10000,
+        }
+    }
+
+    /// Sets the day counter of the fitted curve.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
+    }
+
+    /// Sets the starting point `[b0, b1, b2, b3, tau1, tau2]` of the solver.
+    #[must_use]
+    pub const fn with_initial_parameters(mut self, initial_parameters: [f64; 6]) -> Self {
+        self.initial_parameters = Some(initial_parameters);
+        self
+    }
+
+    /// Sets the tolerance on the simplex costs.
+    #[must_use]
+    pub const fn with_accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Sets the maximum number of solver iterations.
+    #[must_use]
+    pub const fn with_max_iterations(mut self, max_iterations: u64) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns the bonds with their quotes.
+    #[must_use]
+    pub const fn bonds(&self) -> &Vec<FittingBond> {
+        &self.bonds
+    }
+
+    /// Fits the curve.
+    ///
+    /// # Errors
+    /// Returns an error if there are no bonds, if a bond quote cannot be turned into a price
+    /// or if the solver fails.
+    pub fn fit(&self) -> Result<SvenssonTermStructure> {
+        let initial_parameters = if let Some(parameters) = self.initial_parameters {
+            parameters
+        } else {
+            let [b0, b1, b2, tau] =
+                NelsonSiegelFitter::new(self.reference_date, self.bonds.clone())
+                    .with_day_counter(self.day_counter)
+                    .with_accuracy(self.accuracy)
+                    .with_max_iterations(self.max_iterations)
+                    .fit()?
+                    .parameters();
+            [b0, b1, b2, 0.0, tau, 3.0 * tau]
+        };
+
+        let [b0, b1, b2, b3, tau1, tau2] = initial_parameters;
+        if tau1 <= 0.0 || tau2 <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Initial Svensson tau1 and tau2 must be positive, got {tau1} and {tau2}"
+            )));
+        }
+
+        // the solver works on ln(tau1) and ln(tau2) to keep them positive
+        let build_curve = |parameters: &[f64]| {
+            let [b0, b1, b2, b3, log_tau1, log_tau2]: [f64; 6] =
+                parameters.try_into().map_err(|_| {
+                    AtlasError::InvalidValueErr("Svensson needs six parameters".to_string())
+                })?;
+            let (tau1, tau2) = (log_tau1.exp(), log_tau2.exp());
+            if !(MIN_TAU..=MAX_TAU).contains(&tau1) || !(MIN_TAU..=MAX_TAU).contains(&tau2) {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Svensson tau1 {tau1} and tau2 {tau2} must be within [{MIN_TAU}, {MAX_TAU}]"
+                )));
+            }
+            SvenssonTermStructure::new(
+                self.reference_date,
+                [b0, b1, b2, b3, tau1, tau2],
+                self.day_counter,
+            )
+        };
+        let starting_points: Vec<Vec<f64>> = std::iter::once(tau2)
+            .chain(TAU_GRID)
+            .map(|tau2| vec![b0, b1, b2, b3, tau1.ln(), tau2.ln()])
+            .collect();
+        let parameters = minimize_pricing_error(
+            self.reference_date,
+            &self.bonds,
+            &starting_points,
+            &[0.01, 0.01, 0.01, 0.01, 0.5, 0.5],
+            build_curve,
+            self.accuracy,
+            self.max_iterations,
+        )?;
+        build_curve(&parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::makefixedrateinstrument::MakeFixedRateInstrument,
+        rates::{
+            enums::Compounding, fitting::fittingbond::BondQuote, interestrate::InterestRate,
+            traits::YieldProvider, yieldtermstructure::traits::AdvanceTermStructureInTime,
+        },
+        time::{
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+    };
+
+    #[test]
+    fn test_fit_and_advance() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let true_curve = SvenssonTermStructure::new(
+            reference_date,
+            [0.04, -0.02, 0.02, -0.015, 1.5, 9.0],
+            DayCounter::Actual365,
+        )?;
+
+        let bonds = [1, 2, 3, 4, 5, 7, 10, 12, 15, 20, 25, 30]
+            .iter()
+            .map(|years| {
+                let instrument = MakeFixedRateInstrument::new()
+                    .with_start_date(reference_date)
+                    .with_end_date(reference_date + Period::new(*years, TimeUnit::Years))
+                    .with_payment_frequency(Frequency::Annual)
+                    .with_rate(InterestRate::new(
+                        0.035,
+                        Compounding::Simple,
+                        Frequency::Annual,
+                        DayCounter::Actual365,
+                    ))
+                    .with_notional(100.0)
+                    .with_side(Side::Receive)
+                    .with_currency(Currency::USD)
+                    .bullet()
+                    .build()?;
+                let bond = FittingBond::new(instrument, BondQuote::DirtyPrice(0.0));
+                let price = bond.model_price(&true_curve)?;
+                Ok(FittingBond::new(
+                    bond.instrument().clone(),
+                    BondQuote::DirtyPrice(price),
+                ))
+            })
+            .collect::<Result<Vec<FittingBond>>>()?;
+
+        let curve = SvenssonFitter::new(reference_date, bonds.clone()).fit()?;
+        for bond in &bonds {
+            let error = bond.model_price(&curve)? - bond.target_price(reference_date)?;
+            assert!(error.abs() < 1e-5, "pricing error {error}");
+        }
+
+        let advanced = curve.advance_to_period(Period::new(6, TimeUnit::Months))?;
+        let (start, end) = (Date::new(2030, 1, 2), Date::new(2031, 1, 2));
+        let expected =
+            curve.forward_rate(start, end, Compounding::Continuous, Frequency::Annual)?;
+        let forward =
+            advanced.forward_rate(start, end, Compounding::Continuous, Frequency::Annual)?;
+        assert!((forward - expected).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    time::{date::Date, enums::TimeUnit, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::InflationTermStructure;
+
+/// # `InflationInterpolation`
+/// How the index value referenced by a date is read from the monthly values.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InflationInterpolation {
+    /// The value of the reference month for every day of the month.
+    Flat,
+    /// Linear between the reference month and the next one, on the day of the month.
+    Linear,
+}
+
+/// # `Seasonality`
+/// Multiplicative month-on-month seasonal factors, from January to December. Factors are
+/// normalized so that their product is one and a full year is left unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Seasonality {
+    factors: [f64; 12],
+}
+
+impl Seasonality {
+    /// Creates a new `Seasonality` from the factors of each calendar month.
+    ///
+    /// # Errors
+    /// Returns an error if any factor is not positive.
+    pub fn new(factors: [f64; 12]) -> Result<Self> {
+        if factors.iter().any(|f| *f <= 0.0) {
+            return Err(AtlasError::NonPositiveValueErr(
+                "Seasonality factors must be positive".to_string(),
+            ));
+        }
+        let mean = factors.iter().map(|f| f.ln()).sum::<f64>() / 12.0;
+        Ok(Self {
+            factors: factors.map(|f| f / mean.exp()),
+        })
+    }
+
+    /// Returns the normalized factors.
+    #[must_use]
+    pub const fn factors(&self) -> &[f64; 12] {
+        &self.factors
+    }
+
+    /// Returns the seasonal adjustment between the months of `from` and `to`: the product of
+    /// the factors of the months after `from` up to `to`.
+    #[must_use]
+    pub fn adjustment(&self, from: Date, to: Date) -> f64 {
+        if to < from {
+            return 1.0 / self.adjustment(to, from);
+        }
+        let last = month_start(to);
+        let mut month = month_start(from);
+        let mut adjustment = 1.0;
+        while month < last {
+            month = month.advance(1, TimeUnit::Months);
+            adjustment *= self.factors[month.month() as usize - 1];
+        }
+        adjustment
+    }
+}
+
+/// # `InflationIndex`
+/// A consumer price index with monthly fixings. Months after the last fixing are projected with
+/// an `InflationTermStructure` from the fixing of its base month, and adjusted by the
+/// seasonality if any.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let mut index = InflationIndex::new()
+///     .with_publication_lag(Period::new(3, TimeUnit::Months))
+///     .with_interpolation(InflationInterpolation::Linear);
+/// index.add_fixing(Date::new(2024, 1, 1), 100.0);
+/// index.add_fixing(Date::new(2024, 2, 1), 101.0);
+///
+/// // 16 April references January and February
+/// let fixing = index.fixing(Date::new(2024, 4, 16)).unwrap();
+/// assert!((fixing - 100.5).abs() < 1e-12);
+/// ```
+#[derive(Clone)]
+pub struct InflationIndex {
+    name: Option<String>,
+    fixings: BTreeMap<Date, f64>,
+    publication_lag: Period,
+    interpolation: InflationInterpolation,
+    seasonality: Option<Seasonality>,
+    term_structure: Option<Arc<dyn InflationTermStructure>>,
+}
+
+impl InflationIndex {
+    /// Creates a new `InflationIndex` without publication lag and with flat interpolation.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            fixings: BTreeMap::new(),
+            publication_lag: Period::new(0, TimeUnit::Months),
+            interpolation: InflationInterpolation::Flat,
+            seasonality: None,
+            term_structure: None,
+        }
+    }
+
+    /// Sets the name of the index.
+    #[must_use]
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the fixings of the index. Dates are moved to the first day of their month.
+    #[must_use]
+    pub fn with_fixings(mut self, fixings: BTreeMap<Date, f64>) -> Self {
+        self.fixings = fixings
+            .into_iter()
+            .map(|(date, value)| (month_start(date), value))
+            .collect();
+        self
+    }
+
+    /// Sets the lag between a date and the month of the index it references.
+    #[must_use]
+    pub const fn with_publication_lag(mut self, publication_lag: Period) -> Self {
+        self.publication_lag = publication_lag;
+        self
+    }
+
+    /// Sets the interpolation of the referenced value.
+    #[must_use]
+    pub const fn with_interpolation(mut self, interpolation: InflationInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the seasonality of the projected values.
+    #[must_use]
+    pub const fn with_seasonality(mut self, seasonality: Seasonality) -> Self {
+        self.seasonality = Some(seasonality);
+        self
+    }
+
+    /// Sets the term structure used to project the index.
+    #[must_use]
+    pub fn with_term_structure(mut self, term_structure: Arc<dyn InflationTermStructure>) -> Self {
+        self.term_structure = Some(term_structure);
+        self
+    }
+
+    /// Adds the fixing of the month of `date`.
+    pub fn add_fixing(&mut self, date: Date, value: f64) {
+        self.fixings.insert(month_start(date), value);
+    }
+
+    /// Returns the name of the index.
+    #[must_use]
+    pub const fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Returns the fixings, keyed by the first day of each month.
+    #[must_use]
+    pub const fn fixings(&self) -> &BTreeMap<Date, f64> {
+        &self.fixings
+    }
+
+    /// Returns the publication lag.
+    #[must_use]
+    pub const fn publication_lag(&self) -> Period {
+        self.publication_lag
+    }
+
+    /// Returns the interpolation of the referenced value.
+    #[must_use]
+    pub const fn interpolation(&self) -> InflationInterpolation {
+        self.interpolation
+    }
+
+    /// Returns the value of the index for the month of `date`: its fixing or, if not published,
+    /// its projection.
+    ///
+    /// # Errors
+    /// Returns an error if the month has no fixing and cannot be projected.
+    pub fn monthly_value(&self, date: Date) -> Result<f64> {
+        let month = month_start(date);
+        if let Some(value) = self.fixings.get(&month) {
+            return Ok(*value);
+        }
+        let missing = || AtlasError::NotFoundErr(format!("Inflation index fixing for {month}"));
+        let term_structure = self.term_structure.as_ref().ok_or_else(missing)?;
+        let base_month = month_start(term_structure.base_date());
+        if month < base_month {
+            return Err(missing());
+        }
+        let base_value = self.fixings.get(&base_month).ok_or_else(|| {
+            AtlasError::NotFoundErr(format!(
+                "Inflation index fixing for the base month {base_month}"
+            ))
+        })?;
+        let growth =
+            term_structure.growth_factor(month)? / term_structure.growth_factor(base_month)?;
+        let seasonal = self
+            .seasonality
+            .map_or(1.0, |seasonality| seasonality.adjustment(base_month, month));
+        Ok(base_value * growth * seasonal)
+    }
+
+    /// Returns the index value referenced by `date`, after the publication lag and the
+    /// interpolation.
+    ///
+    /// # Errors
+    /// Returns an error if the referenced months have no fixing and cannot be projected.
+    pub fn fixing(&self, date: Date) -> Result<f64> {
+        let reference_month = month_start(date) - self.publication_lag;
+        let value = self.monthly_value(reference_month)?;
+        match self.interpolation {
+            InflationInterpolation::Flat => Ok(value),
+            InflationInterpolation::Linear => {
+                if date.day() == 1 {
+                    return Ok(value);
+                }
+                let next_value =
+                    self.monthly_value(reference_month + Period::new(1, TimeUnit::Months))?;
+                let weight = f64::from(date.day() - 1) / f64::from(date.days_in_month());
+                Ok((next_value - value).mul_add(weight, value))
+            }
+        }
+    }
+}
+
+impl Default for InflationIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// First day of the month of `date`.
+pub(crate) fn month_start(date: Date) -> Date {
+    Date::new(date.year(), date.month(), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use super::{InflationIndex, InflationInterpolation, Seasonality};
+    use crate::{
+        rates::inflation::zerocouponinflationcurve::ZeroCouponInflationCurve,
+        time::{date::Date, daycounter::DayCounter, enums::TimeUnit, period::Period},
+        utils::errors::Result,
+    };
+
+    #[test]
+    fn test_fixings_and_interpolation() -> Result<()> {
+        let fixings = BTreeMap::from([
+            (Date::new(2024, 1, 15), 100.0),
+            (Date::new(2024, 2, 1), 103.0),
+        ]);
+        let index = InflationIndex::new()
+            .with_fixings(fixings)
+            .with_publication_lag(Period::new(2, TimeUnit::Months));
+        assert!((index.fixing(Date::new(2024, 3, 31))? - 100.0).abs() < 1e-12);
+        assert!(index.fixing(Date::new(2024, 5, 1)).is_err());
+
+        let index = index.with_interpolation(InflationInterpolation::Linear);
+        assert!((index.fixing(Date::new(2024, 3, 1))? - 100.0).abs() < 1e-12);
+        assert!(
+            (index.fixing(Date::new(2024, 3, 11))? - (100.0 + 3.0 * 10.0 / 31.0)).abs() < 1e-12
+        );
+        assert!(index.fixing(Date::new(2024, 4, 2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_projection_with_seasonality() -> Result<()> {
+        let base_month = Date::new(2024, 2, 1);
+        let curve = Arc::new(ZeroCouponInflationCurve::new(
+            Date::new(2024, 3, 15),
+            base_month,
+            vec![Date::new(2025, 2, 1)],
+            vec![0.04],
+            DayCounter::Actual365,
+        )?);
+        let mut index = InflationIndex::new().with_term_structure(curve);
+        index.add_fixing(base_month, 110.0);
+        let t = DayCounter::Actual365.year_fraction(base_month, Date::new(2024, 5, 1));
+        let projected = index.monthly_value(Date::new(2024, 5, 20))?;
+        assert!((projected / 110.0 - 1.04f64.powf(t)).abs() < 1e-12);
+        assert!(index.monthly_value(Date::new(2024, 1, 1)).is_err());
+
+        let mut factors = [1.0; 12];
+        factors[2] = 1.01;
+        factors[3] = 1.02;
+        let seasonality = Seasonality::new(factors)?;
+        let product: f64 = seasonality.factors().iter().product();
+        assert!((product - 1.0).abs() < 1e-12);
+        assert!((seasonality.adjustment(base_month, Date::new(2025, 2, 1)) - 1.0).abs() < 1e-12);
+
+        let index = index.with_seasonality(seasonality);
+        let adjusted = index.monthly_value(Date::new(2024, 5, 1))?;
+        let expected = projected * seasonality.factors()[2..5].iter().product::<f64>();
+        assert!((adjusted - expected).abs() < 1e-9);
+        assert!((index.monthly_value(base_month)? - 110.0).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+/// Inflation index module.
+pub mod inflationindex;
+/// Traits module.
+pub mod traits;
+/// Daily units of account indexed to inflation.
+pub mod unitofaccount;
+/// Zero-coupon inflation curve module.
+pub mod zerocouponinflationcurve;
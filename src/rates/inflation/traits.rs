@@ -0,0 +1,24 @@
+use std::panic::RefUnwindSafe;
+
+use crate::{rates::traits::HasReferenceDate, time::date::Date, utils::errors::Result};
+
+/// # `InflationTermStructure`
+/// Trait that defines a term structure of expected inflation. Growth is measured from the base
+/// date of the curve, usually the month of the last published index fixing.
+pub trait InflationTermStructure: HasReferenceDate + Send + Sync + RefUnwindSafe {
+    /// Returns the base date of the curve.
+    fn base_date(&self) -> Date;
+
+    /// Returns the annually compounded zero-coupon inflation rate between the base date and
+    /// `date`.
+    ///
+    /// # Errors
+    /// Returns an error if the rate cannot be interpolated.
+    fn zero_rate(&self, date: Date) -> Result<f64>;
+
+    /// Returns the expected growth of the index between the base date and `date`.
+    ///
+    /// # Errors
+    /// Returns an error if the rate cannot be interpolated.
+    fn growth_factor(&self, date: Date) -> Result<f64>;
+}
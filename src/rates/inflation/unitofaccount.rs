@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    currencies::{enums::Currency, fxforwardcurve::FxForwardCurve},
+    time::{date::Date, daycounter::DayCounter, enums::TimeUnit, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::inflationindex::InflationIndex;
+
+/// # `UnitOfAccount`
+/// Daily values of an inflation-indexed unit of account, such as the Chilean UF (`CLF`) or the
+/// Colombian UVR (`COU`), in units of its local currency.
+#[derive(Debug, Clone)]
+pub struct UnitOfAccount {
+    currency: Currency,
+    local_currency: Currency,
+    values: BTreeMap<Date, f64>,
+}
+
+impl UnitOfAccount {
+    /// Creates a new `UnitOfAccount` from its daily values.
+    #[must_use]
+    pub const fn new(
+        currency: Currency,
+        local_currency: Currency,
+        values: BTreeMap<Date, f64>,
+    ) -> Self {
+        Self {
+            currency,
+            local_currency,
+            values,
+        }
+    }
+
+    /// Returns the currency of the unit of account.
+    #[must_use]
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the currency the unit of account is valued in.
+    #[must_use]
+    pub const fn local_currency(&self) -> Currency {
+        self.local_currency
+    }
+
+    /// Returns the daily values.
+    #[must_use]
+    pub const fn values(&self) -> &BTreeMap<Date, f64> {
+        &self.values
+    }
+
+    /// Returns the value of the unit of account on `date`.
+    ///
+    /// # Errors
+    /// Returns an error if `date` is outside the built values.
+    pub fn value(&self, date: Date) -> Result<f64> {
+        self.values
+            .get(&date)
+            .copied()
+            .ok_or_else(|| AtlasError::NotFoundErr(format!("{} value for {date}", self.currency)))
+    }
+
+    /// Returns the forward curve of the local currency per unit of account, with spot on
+    /// `spot_date`. Forwards follow the projected daily values.
+    #[must_use]
+    pub fn fx_forward_curve(&self, spot_date: Date) -> FxForwardCurve {
+        FxForwardCurve::from_projected_rates(
+            self.local_currency,
+            self.currency,
+            spot_date,
+            self.values.clone(),
+        )
+    }
+}
+
+/// # `MakeUnitOfAccount`
+/// Builder of the daily values of a unit of account indexed to a CPI. The value grows daily
+/// over periods running from the day after an anchor day of a month to the anchor day of the
+/// next one, at the index variation of the month before the period starts.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let mut cpi = InflationIndex::new();
+/// cpi.add_fixing(Date::new(2024, 1, 1), 100.0);
+/// cpi.add_fixing(Date::new(2024, 2, 1), 101.0);
+///
+/// // UF from 10 March to 9 April grows with the February CPI
+/// let uf = MakeUnitOfAccount::uf(&cpi)
+///     .with_start(Date::new(2024, 3, 9), 37_000.0)
+///     .with_end_date(Date::new(2024, 4, 9))
+///     .build()
+///     .unwrap();
+/// assert!((uf.value(Date::new(2024, 4, 9)).unwrap() - 37_370.0).abs() < 1e-8);
+/// ```
+pub struct MakeUnitOfAccount<'a> {
+    currency: Currency,
+    local_currency: Currency,
+    index: &'a InflationIndex,
+    anchor_day: u32,
+    start: Option<(Date, f64)>,
+    end_date: Option<Date>,
+}
+
+impl<'a> MakeUnitOfAccount<'a> {
+    /// Creates a new `MakeUnitOfAccount` with the given anchor day of the month.
+    #[must_use]
+    pub const fn new(
+        currency: Currency,
+        local_currency: Currency,
+        index: &'a InflationIndex,
+        anchor_day: u32,
+    ) -> Self {
+        Self {
+            currency,
+            local_currency,
+            index,
+            anchor_day,
+            start: None,
+            end_date: None,
+        }
+    }
+
+    /// Chilean Unidad de Fomento in CLP: periods from the 10th to the 9th of the next month.
+    #[must_use]
+    pub const fn uf(index: &'a InflationIndex) -> Self {
+        Self::new(Currency::CLF, Currency::CLP, index, 9)
+    }
+
+    /// Colombian Unidad de Valor Real in COP: periods from the 16th to the 15th of the next
+    /// month.
+    #[must_use]
+    pub const fn uvr(index: &'a InflationIndex) -> Self {
+        Self::new(Currency::COU, Currency::COP, index, 15)
+    }
+
+    /// Sets a known value of the unit of account, the first date of the build.
+    #[must_use]
+    pub const fn with_start(mut self, date: Date, value: f64) -> Self {
+        self.start = Some((date, value));
+        self
+    }
+
+    /// Sets the last date of the build.
+    #[must_use]
+    pub const fn with_end_date(mut self, end_date: Date) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Builds the daily values from the start date to the end date.
+    ///
+    /// # Errors
+    /// Returns an error if the start or end are not set, if the anchor day is not between 1 and
+    /// 28, or if a needed index value is neither published nor projected.
+    pub fn build(self) -> Result<UnitOfAccount> {
+        let (start_date, start_value) = self
+            .start
+            .ok_or(AtlasError::ValueNotSetErr("Start".to_string()))?;
+        let end_date = self
+            .end_date
+            .ok_or(AtlasError::ValueNotSetErr("End date".to_string()))?;
+        if !(1..=28).contains(&self.anchor_day) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Anchor day must be between 1 and 28 ({})",
+                self.anchor_day
+            )));
+        }
+
+        let mut anchor = if start_date.day() >= self.anchor_day {
+            Date::new(start_date.year(), start_date.month(), self.anchor_day)
+        } else {
+            let previous = start_date - Period::new(1, TimeUnit::Months);
+            Date::new(previous.year(), previous.month(), self.anchor_day)
+        };
+        let mut growth = self.period_growth(anchor)?;
+        let mut next_anchor = anchor + Period::new(1, TimeUnit::Months);
+        let mut anchor_value = start_value / growth.powf(accrual(anchor, next_anchor, start_date));
+
+        let mut values = BTreeMap::new();
+        let mut date = start_date;
+        while date <= end_date {
+            if date > next_anchor {
+                anchor_value *= growth;
+                anchor = next_anchor;
+                next_anchor = anchor + Period::new(1, TimeUnit::Months);
+                growth = self.period_growth(anchor)?;
+            }
+            values.insert(
+                date,
+                anchor_value * growth.powf(accrual(anchor, next_anchor, date)),
+            );
+            date += 1;
+        }
+        Ok(UnitOfAccount::new(
+            self.currency,
+            self.local_currency,
+            values,
+        ))
+    }
+
+    /// Index variation of the month before the month of `anchor`.
+    fn period_growth(&self, anchor: Date) -> Result<f64> {
+        let month = Period::new(1, TimeUnit::Months);
+        let previous = self.index.monthly_value(anchor - month)?;
+        let before_previous = self.index.monthly_value(anchor - month - month)?;
+        Ok(previous / before_previous)
+    }
+}
+
+/// Fraction of the period from `anchor` to `next_anchor` elapsed on `date`.
+fn accrual(anchor: Date, next_anchor: Date, date: Date) -> f64 {
+    DayCounter::Actual365.year_fraction(anchor, date)
+        / DayCounter::Actual365.year_fraction(anchor, next_anchor)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::MakeUnitOfAccount;
+    use crate::{
+        cashflows::{
+            cashflow::{Cashflow, Side},
+            simplecashflow::SimpleCashflow,
+        },
+        core::marketstore::MarketStore,
+        currencies::enums::Currency,
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            enums::Compounding,
+            inflation::{
+                inflationindex::InflationIndex, zerocouponinflationcurve::ZeroCouponInflationCurve,
+            },
+            interestrate::RateDefinition,
+        },
+        time::{date::Date, daycounter::DayCounter, enums::Frequency},
+        utils::{errors::Result, testing::add_flat_index},
+        visitors::{
+            indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor,
+            traits::{ConstVisit, HasCashflows, Visit},
+        },
+    };
+
+    struct Flows(Vec<Cashflow>);
+
+    impl HasCashflows for Flows {
+        fn cashflows(&self) -> &[Cashflow] {
+            &self.0
+        }
+
+        fn mut_cashflows(&mut self) -> &mut [Cashflow] {
+            &mut self.0
+        }
+    }
+
+    fn cpi() -> Result<InflationIndex> {
+        let curve = Arc::new(ZeroCouponInflationCurve::new(
+            Date::new(2024, 3, 15),
+            Date::new(2024, 2, 1),
+            vec![Date::new(2025, 2, 1)],
+            vec![0.04],
+            DayCounter::Actual365,
+        )?);
+        let mut index = InflationIndex::new().with_term_structure(curve);
+        index.add_fixing(Date::new(2023, 12, 1), 100.0);
+        index.add_fixing(Date::new(2024, 1, 1), 100.7);
+        index.add_fixing(Date::new(2024, 2, 1), 101.3);
+        Ok(index)
+    }
+
+    #[test]
+    fn test_uf() -> Result<()> {
+        let cpi = cpi()?;
+        let uf = MakeUnitOfAccount::uf(&cpi)
+            .with_start(Date::new(2024, 3, 15), 37_000.0)
+            .with_end_date(Date::new(2024, 5, 31))
+            .build()?;
+        assert_eq!(uf.currency(), Currency::CLF);
+        assert_eq!(uf.local_currency(), Currency::CLP);
+        assert!((uf.value(Date::new(2024, 3, 15))? - 37_000.0).abs() < 1e-8);
+        assert!(uf.value(Date::new(2024, 6, 1)).is_err());
+
+        // 10 March to 9 April grows with the February CPI
+        let february: f64 = 101.3 / 100.7;
+        let uf_april = uf.value(Date::new(2024, 4, 9))?;
+        assert!((uf_april / 37_000.0 - february.powf(25.0 / 31.0)).abs() < 1e-12);
+
+        // 10 April to 9 May grows with the projected March CPI
+        let t = DayCounter::Actual365.year_fraction(Date::new(2024, 2, 1), Date::new(2024, 3, 1));
+        let march = 1.04f64.powf(t);
+        let uf_may = uf.value(Date::new(2024, 5, 9))?;
+        assert!((uf_may / uf_april - march).abs() < 1e-12);
+        let uf_mid = uf.value(Date::new(2024, 4, 24))?;
+        assert!((uf_mid / uf_april - march.sqrt()).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_uvr() -> Result<()> {
+        let cpi = cpi()?;
+        let uvr = MakeUnitOfAccount::uvr(&cpi)
+            .with_start(Date::new(2024, 2, 15), 360.0)
+            .with_end_date(Date::new(2024, 3, 15))
+            .build()?;
+        assert_eq!(uvr.currency(), Currency::COU);
+        let uvr_march = uvr.value(Date::new(2024, 3, 15))?;
+        assert!((uvr_march / 360.0 - 100.7 / 100.0).abs() < 1e-12);
+        assert!(MakeUnitOfAccount::uvr(&cpi).build().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clf_valuation_in_clp() -> Result<()> {
+        let reference_date = Date::new(2024, 3, 15);
+        let cpi = cpi()?;
+        let uf = MakeUnitOfAccount::uf(&cpi)
+            .with_start(reference_date, 37_000.0)
+            .with_end_date(Date::new(2025, 3, 15))
+            .build()?;
+
+        let mut market_store = MarketStore::new(reference_date, Currency::CLP);
+        add_flat_index(
+            &mut market_store,
+            0,
+            0.02,
+            RateDefinition::new(
+                DayCounter::Actual365,
+                Compounding::Continuous,
+                Frequency::Annual,
+            ),
+        )?;
+        market_store
+            .mut_index_store()
+            .add_currency_curve(Currency::CLF, 0);
+        market_store
+            .mut_exchange_rate_store()
+            .add_unit_of_account(&uf)?;
+
+        let payment_date = Date::new(2025, 3, 15);
+        let projected = market_store.get_forward_exchange_rate(
+            Currency::CLP,
+            Some(Currency::CLF),
+            payment_date,
+        )?;
+        assert!((projected - uf.value(payment_date)?).abs() < 1e-8);
+        let clf_per_clp =
+            market_store.get_forward_exchange_rate(Currency::CLF, None, payment_date)?;
+        assert!((clf_per_clp * projected - 1.0).abs() < 1e-12);
+
+        let mut flows = Flows(vec![Cashflow::Redemption(
+            SimpleCashflow::new(payment_date, Currency::CLF, Side::Receive)
+                .with_amount(1_000.0)
+                .with_discount_curve_id(0),
+        )]);
+        let indexing_visitor = IndexingVisitor::new();
+        indexing_visitor.visit(&mut flows)?;
+        let data = SimpleModel::new(&market_store)
+            .with_transform_currencies(true)
+            .gen_market_data(&indexing_visitor.request())?;
+        let npv = NPVConstVisitor::new(&data, true).visit(&flows)?;
+        let t = DayCounter::Actual365.year_fraction(reference_date, payment_date);
+        assert!((npv / (1_000.0 * 37_000.0) - (-0.02 * t).exp()).abs() < 1e-12);
+        Ok(())
+    }
+}
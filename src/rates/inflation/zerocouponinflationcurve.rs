@@ -0,0 +1,169 @@
+use crate::{
+    math::interpolation::enums::Interpolator,
+    rates::traits::HasReferenceDate,
+    time::{date::Date, daycounter::DayCounter},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::InflationTermStructure;
+
+/// # `ZeroCouponInflationCurve`
+/// Inflation curve built from zero-coupon inflation swap rates. Rates are annually compounded
+/// from the base date and interpolated on the year fraction to each pillar, flat outside the
+/// pillars.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let curve = ZeroCouponInflationCurve::new(
+///     Date::new(2025, 3, 15),
+///     Date::new(2025, 2, 1),
+///     vec![Date::new(2026, 2, 1), Date::new(2027, 2, 1)],
+///     vec![0.03, 0.035],
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+/// let growth = curve.growth_factor(Date::new(2026, 2, 1)).unwrap();
+/// assert!((growth - 1.03).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZeroCouponInflationCurve {
+    reference_date: Date,
+    base_date: Date,
+    dates: Vec<Date>,
+    rates: Vec<f64>,
+    day_counter: DayCounter,
+    interpolator: Interpolator,
+}
+
+impl ZeroCouponInflationCurve {
+    /// Creates a new `ZeroCouponInflationCurve` with linear interpolation.
+    ///
+    /// # Errors
+    /// Returns an error if there are no rates, if dates and rates differ in length or if the
+    /// dates are not strictly increasing after the base date.
+    pub fn new(
+        reference_date: Date,
+        base_date: Date,
+        dates: Vec<Date>,
+        rates: Vec<f64>,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if dates.is_empty() || dates.len() != rates.len() {
+            return Err(AtlasError::InvalidValueErr(
+                "Zero-coupon inflation curve needs one rate per date".to_string(),
+            ));
+        }
+        let mut previous = base_date;
+        for date in &dates {
+            if *date <= previous {
+                return Err(AtlasError::InvalidValueErr(format!(
+                    "Inflation curve dates must be increasing and after the base date ({date})"
+                )));
+            }
+            previous = *date;
+        }
+        Ok(Self {
+            reference_date,
+            base_date,
+            dates,
+            rates,
+            day_counter,
+            interpolator: Interpolator::Linear,
+        })
+    }
+
+    /// Sets the interpolation method of the zero-coupon rates.
+    #[must_use]
+    pub const fn with_interpolator(mut self, interpolator: Interpolator) -> Self {
+        self.interpolator = interpolator;
+        self
+    }
+
+    /// Returns the pillar dates.
+    #[must_use]
+    pub fn dates(&self) -> &[Date] {
+        &self.dates
+    }
+
+    /// Returns the zero-coupon rates of the pillars.
+    #[must_use]
+    pub fn rates(&self) -> &[f64] {
+        &self.rates
+    }
+}
+
+impl HasReferenceDate for ZeroCouponInflationCurve {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl InflationTermStructure for ZeroCouponInflationCurve {
+    fn base_date(&self) -> Date {
+        self.base_date
+    }
+
+    fn zero_rate(&self, date: Date) -> Result<f64> {
+        if self.rates.len() == 1 {
+            return Ok(self.rates[0]);
+        }
+        let x_: Vec<f64> = self
+            .dates
+            .iter()
+            .map(|d| self.day_counter.year_fraction(self.base_date, *d))
+            .collect();
+        let t = self
+            .day_counter
+            .year_fraction(self.base_date, date)
+            .clamp(x_[0], x_[x_.len() - 1]);
+        self.interpolator.interpolate(t, &x_, &self.rates, false)
+    }
+
+    fn growth_factor(&self, date: Date) -> Result<f64> {
+        let t = self.day_counter.year_fraction(self.base_date, date);
+        Ok((1.0 + self.zero_rate(date)?).powf(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZeroCouponInflationCurve;
+    use crate::{
+        rates::inflation::traits::InflationTermStructure,
+        time::{date::Date, daycounter::DayCounter},
+        utils::errors::Result,
+    };
+
+    #[test]
+    fn test_zero_rates() -> Result<()> {
+        let base_date = Date::new(2024, 1, 1);
+        let curve = ZeroCouponInflationCurve::new(
+            Date::new(2024, 2, 15),
+            base_date,
+            vec![Date::new(2025, 1, 1), Date::new(2027, 1, 1)],
+            vec![0.04, 0.03],
+            DayCounter::Actual365,
+        )?;
+        assert!((curve.zero_rate(Date::new(2024, 6, 1))? - 0.04).abs() < 1e-12);
+        assert!((curve.zero_rate(Date::new(2030, 1, 1))? - 0.03).abs() < 1e-12);
+        let mid = curve.zero_rate(Date::new(2026, 1, 1))?;
+        assert!(mid > 0.03 && mid < 0.04);
+
+        let date = Date::new(2027, 1, 1);
+        let t = DayCounter::Actual365.year_fraction(base_date, date);
+        assert!((curve.growth_factor(date)? - 1.03f64.powf(t)).abs() < 1e-12);
+        assert!((curve.growth_factor(base_date)? - 1.0).abs() < 1e-12);
+
+        assert!(ZeroCouponInflationCurve::new(
+            base_date,
+            base_date,
+            vec![base_date],
+            vec![0.03],
+            DayCounter::Actual365,
+        )
+        .is_err());
+        Ok(())
+    }
+}
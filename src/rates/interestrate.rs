@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    math::ad::traits::Real,
     time::{date::Date, daycounter::DayCounter, enums::Frequency},
     utils::errors::{AtlasError, Result},
 };
@@ -152,47 +153,63 @@ impl InterestRate {
         freq: Frequency,
         t: f64,
     ) -> Result<Self> {
-        if compound <= 0.0 {
+        let r = Self::implied_rate_from_compound_factor(compound, comp, freq, t)?;
+        Ok(Self::new(r, comp, freq, result_dc))
+    }
+
+    /// Calculates the implied rate from a compound factor given as any `Real`, e.g. a `Dual`
+    /// whose derivatives are carried to the rate.
+    ///
+    /// # Errors
+    /// Returns an error if the compound factor or time are invalid for the
+    /// requested compounding convention.
+    pub fn implied_rate_from_compound_factor<T: Real>(
+        compound: T,
+        comp: Compounding,
+        freq: Frequency,
+        t: f64,
+    ) -> Result<T> {
+        if compound.value() <= 0.0 {
             return Err(AtlasError::InvalidValueErr(
                 "Positive compound factor required".to_string(),
             ));
         }
-        let r: f64;
         let f = f64::from(freq as i32);
-        if (compound - 1.0).abs() < 1e-12 {
+        if (compound.value() - 1.0).abs() < 1e-12 {
             if t < 0.0 {
                 return Err(AtlasError::InvalidValueErr(
                     "Non-negative time required".to_string(),
                 ));
             }
-            r = 0.0;
-        } else {
-            if t <= 0.0 {
-                return Err(AtlasError::InvalidValueErr(
-                    "Positive time required".to_string(),
-                ));
-            }
-            match comp {
-                Compounding::Simple => r = (compound - 1.0) / t,
-                Compounding::Compounded => r = (compound.powf(1.0 / (f * t)) - 1.0) * f,
-                Compounding::Continuous => r = (compound).ln() / t,
-                Compounding::SimpleThenCompounded => {
-                    if t <= 1.0 / f {
-                        r = (compound - 1.0) / t;
-                    } else {
-                        r = (compound.powf(1.0 / (f * t)) - 1.0) * f;
-                    }
+            return Ok(compound * 0.0);
+        }
+        if t <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(
+                "Positive time required".to_string(),
+            ));
+        }
+        let simple = |compound: T| (compound - 1.0) / t;
+        let compounded = |compound: T| (compound.powf(1.0 / (f * t)) - 1.0) * f;
+        let r = match comp {
+            Compounding::Simple => simple(compound),
+            Compounding::Compounded => compounded(compound),
+            Compounding::Continuous => compound.ln() / t,
+            Compounding::SimpleThenCompounded => {
+                if t <= 1.0 / f {
+                    simple(compound)
+                } else {
+                    compounded(compound)
                 }
-                Compounding::CompoundedThenSimple => {
-                    if t > 1.0 / f {
-                        r = (compound - 1.0) / t;
-                    } else {
-                        r = (compound.powf(1.0 / (f * t)) - 1.0) * f;
-                    }
+            }
+            Compounding::CompoundedThenSimple => {
+                if t > 1.0 / f {
+                    simple(compound)
+                } else {
+                    compounded(compound)
                 }
             }
-        }
-        Ok(Self::new(r, comp, freq, result_dc))
+        };
+        Ok(r)
     }
 
     /// Calculates the compound factor between two dates using the day counter.
@@ -206,25 +223,42 @@ impl InterestRate {
     /// Calculates the compound factor from a year fraction.
     #[must_use]
     pub fn compound_factor_from_yf(&self, year_fraction: f64) -> f64 {
-        let rate = self.rate();
-        let compounding = self.compounding();
-        let f = f64::from(self.frequency() as i32);
+        Self::compound_factor_from_rate(
+            self.rate(),
+            self.compounding(),
+            self.frequency(),
+            year_fraction,
+        )
+    }
+
+    /// Calculates the compound factor from a year fraction for a rate given as any `Real`,
+    /// e.g. a `Dual` whose derivatives are carried to the compound factor.
+    #[must_use]
+    pub fn compound_factor_from_rate<T: Real>(
+        rate: T,
+        compounding: Compounding,
+        frequency: Frequency,
+        year_fraction: f64,
+    ) -> T {
+        let f = f64::from(frequency as i32);
+        let simple = |rate: T| rate * year_fraction + 1.0;
+        let compounded = |rate: T| (rate / f + 1.0).powf(f * year_fraction);
         match compounding {
-            Compounding::Simple => rate.mul_add(year_fraction, 1.0),
-            Compounding::Compounded => (1.0 + rate / f).powf(f * year_fraction),
+            Compounding::Simple => simple(rate),
+            Compounding::Compounded => compounded(rate),
             Compounding::Continuous => (rate * year_fraction).exp(),
             Compounding::SimpleThenCompounded => {
                 if year_fraction <= 1.0 / f {
-                    rate.mul_add(year_fraction, 1.0)
+                    simple(rate)
                 } else {
-                    (1.0 + rate / f).powf(year_fraction * f)
+                    compounded(rate)
                 }
             }
             Compounding::CompoundedThenSimple => {
                 if year_fraction > 1.0 / f {
-                    rate.mul_add(year_fraction, 1.0)
+                    simple(rate)
                 } else {
-                    (1.0 + rate / f).powf(year_fraction * f)
+                    compounded(rate)
                 }
             }
         }
@@ -196,6 +196,15 @@ impl RelinkableTermStructure for IborIndex {
     fn link_to(&mut self, term_structure: Arc<dyn YieldTermStructureTrait>) {
         self.term_structure = Some(term_structure);
     }
+
+    fn relinked_to(
+        &self,
+        term_structure: Arc<dyn YieldTermStructureTrait>,
+    ) -> Arc<RwLock<dyn InterestRateIndexTrait>> {
+        let mut index = self.clone();
+        index.link_to(term_structure);
+        Arc::new(RwLock::new(index))
+    }
 }
 
 impl InterestRateIndexTrait for IborIndex {}
@@ -280,7 +289,7 @@ mod tests {
     }
 
     #[test]
-    fn test_fixing_interpolation_ibor() {
+    fn test_fixing_interpolation_ibor() -> Result<()> {
         let fixing: HashMap<Date, f64> = [
             (Date::new(2023, 6, 1), 21938.71),
             (Date::new(2023, 6, 2), 21945.57),
@@ -291,7 +300,7 @@ mod tests {
         .copied()
         .collect();
         let mut ibor_index = IborIndex::new(Date::new(2023, 11, 6)).with_fixings(fixing);
-        ibor_index.fill_missing_fixings(Interpolator::Linear);
+        ibor_index.fill_missing_fixings(Interpolator::Linear)?;
         let interpolated = ibor_index
             .fixings()
             .get(&Date::new(2023, 6, 3))
@@ -301,6 +310,33 @@ mod tests {
                 )
             });
         assert!((*interpolated - 21952.4266666).abs() < 0.001);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixing_monotone_cubic_interpolation_ibor() -> Result<()> {
+        let fixing: HashMap<Date, f64> = [
+            (Date::new(2023, 6, 1), 21938.71),
+            (Date::new(2023, 6, 2), 21945.57),
+            (Date::new(2023, 6, 5), 21966.14),
+            (Date::new(2023, 6, 6), 21973.0),
+        ]
+        .iter()
+        .copied()
+        .collect();
+        let mut ibor_index = IborIndex::new(Date::new(2023, 11, 6)).with_fixings(fixing);
+        ibor_index.fill_missing_fixings(Interpolator::MonotoneCubic)?;
+
+        let values: Vec<f64> = (1..=6)
+            .map(|day| {
+                *ibor_index
+                    .fixings()
+                    .get(&Date::new(2023, 6, day))
+                    .unwrap_or_else(|| panic!("fixing for 2023-06-{day} should be filled"))
+            })
+            .collect();
+        assert!(values.windows(2).all(|w| w[1] > w[0]));
+        Ok(())
     }
 
     #[test]
@@ -73,7 +73,7 @@ pub fn compose_fixing_rate(
     rate_definition: RateDefinition,
 ) -> HashMap<Date, f64> {
     let mut fixings_rates = fixings_rates.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
-    fixings_rates.sort_by(|a, b| a.0.cmp(&b.0));
+    fixings_rates.sort_by_key(|a| a.0);
 
     let mut fixing_index = HashMap::new();
 
@@ -237,6 +237,15 @@ impl RelinkableTermStructure for OvernightCompoundedRateIndex {
     fn link_to(&mut self, term_structure: Arc<dyn YieldTermStructureTrait>) {
         self.overnight_index.link_to(term_structure);
     }
+
+    fn relinked_to(
+        &self,
+        term_structure: Arc<dyn YieldTermStructureTrait>,
+    ) -> Arc<RwLock<dyn InterestRateIndexTrait>> {
+        let mut index = self.clone();
+        index.link_to(term_structure);
+        Arc::new(RwLock::new(index))
+    }
 }
 
 impl InterestRateIndexTrait for OvernightCompoundedRateIndex {}
@@ -363,7 +372,7 @@ mod tests {
     }
 
     #[test]
-    fn test_fixing_provider_overnight() {
+    fn test_fixing_provider_overnight() -> Result<()> {
         let fixing: HashMap<Date, f64> =
             [(Date::new(2023, 6, 2), 2.5), (Date::new(2023, 6, 5), 3.0)]
                 .iter()
@@ -373,7 +382,7 @@ mod tests {
         let mut overnight_index =
             OvernightCompoundedRateIndex::new(Date::new(2023, 6, 5)).with_fixings_rates(fixing);
 
-        overnight_index.fill_missing_fixings(Interpolator::Linear);
+        overnight_index.fill_missing_fixings(Interpolator::Linear)?;
 
         assert!(
             (overnight_index
@@ -386,5 +395,6 @@ mod tests {
                 .abs()
                 < 0.001
         );
+        Ok(())
     }
 }
@@ -10,7 +10,7 @@ use crate::{
         yieldtermstructure::traits::YieldTermStructureTrait,
     },
     time::{date::Date, enums::TimeUnit, period::Period},
-    utils::errors::Result,
+    utils::errors::{AtlasError, Result},
 };
 
 /// # `FixingProvider`
@@ -27,13 +27,25 @@ pub trait FixingProvider {
     fn add_fixing(&mut self, date: Date, rate: f64);
 
     /// Fill missing fixings using interpolation.
-    fn fill_missing_fixings(&mut self, interpolator: Interpolator) {
+    ///
+    /// # Errors
+    /// Returns an error if the fixings span more days than fit in an `i32` or if the
+    /// interpolation fails (e.g. a single fixing or a non-positive one with `LogLinear`).
+    fn fill_missing_fixings(&mut self, interpolator: Interpolator) -> Result<()> {
         if !self.fixings().is_empty() {
             let (first_date, last_date) =
                 match (self.fixings().keys().min(), self.fixings().keys().max()) {
                     (Some(first), Some(last)) => (*first, *last),
-                    _ => return,
+                    _ => return Ok(()),
                 };
+            let day_count = |date: Date| -> Result<f64> {
+                let days = i32::try_from(date - first_date).map_err(|_| {
+                    AtlasError::InvalidValueErr(format!(
+                        "Day count between {first_date} and {date} does not fit in i32"
+                    ))
+                })?;
+                Ok(f64::from(days))
+            };
 
             let aux_btreemap = self
                 .fixings()
@@ -41,14 +53,10 @@ pub trait FixingProvider {
                 .map(|(k, v)| (*k, *v))
                 .collect::<BTreeMap<Date, f64>>();
 
-            let x: Vec<f64> = aux_btreemap
+            let x = aux_btreemap
                 .keys()
-                .map(|&d| {
-                    let days = i32::try_from(d - first_date)
-                        .unwrap_or_else(|_| panic!("fixing day count should fit in i32"));
-                    f64::from(days)
-                })
-                .collect::<Vec<f64>>();
+                .map(|&d| day_count(d))
+                .collect::<Result<Vec<f64>>>()?;
 
             let y = aux_btreemap.values().copied().collect::<Vec<f64>>();
 
@@ -56,15 +64,13 @@ pub trait FixingProvider {
 
             while current_date <= last_date {
                 if !self.fixings().contains_key(&current_date) {
-                    let days = i32::try_from(current_date - first_date)
-                        .unwrap_or_else(|_| panic!("fixing day count should fit in i32"));
-                    let days = f64::from(days);
-                    let rate = interpolator.interpolate(days, &x, &y, false);
+                    let rate = interpolator.interpolate(day_count(current_date)?, &x, &y, false)?;
                     self.add_fixing(current_date, rate);
                 }
                 current_date = current_date + Period::new(1, TimeUnit::Days);
             }
         }
+        Ok(())
     }
 }
 
@@ -136,6 +142,12 @@ pub trait HasName {
 pub trait RelinkableTermStructure {
     /// Links the index to a new yield term structure.
     fn link_to(&mut self, term_structure: Arc<dyn YieldTermStructureTrait>);
+    /// Returns a copy of the index linked to a new yield term structure. Unlike `link_to`,
+    /// the index is left untouched, so stores sharing it are not affected.
+    fn relinked_to(
+        &self,
+        term_structure: Arc<dyn YieldTermStructureTrait>,
+    ) -> Arc<RwLock<dyn InterestRateIndexTrait>>;
 }
 
 /// # `InterestRateIndexTrait`
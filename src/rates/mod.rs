@@ -1,12 +1,24 @@
+/// Curve bootstrapping from market quotes.
+pub mod bootstrap;
 /// Enumeration types for interest rate calculations.
 pub mod enums;
+/// Parametric curve fitting to bond prices or yields.
+pub mod fitting;
 /// Interest rate index storage and management.
 pub mod indexstore;
+/// Inflation indices, curves and units of account.
+pub mod inflation;
 /// Interest rate calculations and operations.
 pub mod interestrate;
 /// Interest rate index definitions and implementations.
 pub mod interestrateindex;
+/// Observation and compounding conventions of overnight rates.
+pub mod overnightobservation;
 /// Trait definitions for interest rate components.
 pub mod traits;
+/// Volatility surfaces of interest rates.
+pub mod volatility;
+/// Volatility surface storage and management.
+pub mod volatilitystore;
 /// Yield term structure and related calculations.
 pub mod yieldtermstructure;
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rates::{enums::Compounding, traits::YieldProvider},
+    time::{
+        calendar::{Calendar, CalendarId},
+        calendars::traits::IsCalendar,
+        date::Date,
+        daycounter::DayCounter,
+        enums::{Frequency, TimeUnit},
+        period::Period,
+    },
+    utils::errors::{AtlasError, Result},
+};
+
+/// A single business day of the observation schedule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ObservationDay {
+    /// The date the overnight rate is published for.
+    fixing: Date,
+    /// The day after the fixing date, when the overnight rate matures.
+    fixing_end: Date,
+    /// The period the rate is weighted with.
+    weight_start: Date,
+    weight_end: Date,
+}
+
+/// # `OvernightObservation`
+/// The conventions used to compound an overnight rate (SOFR, ESTR, SONIA...) over an accrual
+/// period, following ISDA 2021:
+///
+/// * `lookback_days` - Each rate is observed this many business days before the day it applies to.
+/// * `observation_shift` - If true, the rates are also weighted with the days of the shifted
+///   (observation) period instead of those of the accrual period.
+/// * `lockout_days` - The rate of the last business days of the period is frozen to the one
+///   observed this many business days before the end.
+///
+/// It is held by overnight indexed coupons and carried by their forward rate requests, so
+/// that models fix them with `compounded_rate` against the index of the forecast curve.
+///
+/// ## Parameters
+/// * `accrual_start_date` - The date from which the rate accrues
+/// * `accrual_end_date` - The date until which the rate accrues
+/// * `day_counter` - The day counter of the overnight rate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OvernightObservation {
+    accrual_start_date: Date,
+    accrual_end_date: Date,
+    day_counter: DayCounter,
+    calendar: CalendarId,
+    lookback_days: i32,
+    lockout_days: usize,
+    observation_shift: bool,
+}
+
+impl OvernightObservation {
+    /// Creates a new `OvernightObservation` with no lookback, lockout or observation shift,
+    /// observed on every calendar day.
+    #[must_use]
+    pub const fn new(
+        accrual_start_date: Date,
+        accrual_end_date: Date,
+        day_counter: DayCounter,
+    ) -> Self {
+        Self {
+            accrual_start_date,
+            accrual_end_date,
+            day_counter,
+            calendar: CalendarId::NullCalendar,
+            lookback_days: 0,
+            lockout_days: 0,
+            observation_shift: false,
+        }
+    }
+
+    /// Sets the calendar of the overnight index fixings.
+    #[must_use]
+    pub const fn with_calendar(mut self, calendar: CalendarId) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Sets the number of business days each rate is observed before the day it applies to.
+    #[must_use]
+    pub const fn with_lookback_days(mut self, lookback_days: i32) -> Self {
+        self.lookback_days = lookback_days;
+        self
+    }
+
+    /// Sets the number of business days at the end of the period using the same rate.
+    #[must_use]
+    pub const fn with_lockout_days(mut self, lockout_days: usize) -> Self {
+        self.lockout_days = lockout_days;
+        self
+    }
+
+    /// Sets whether the rates are weighted with the days of the observation period.
+    #[must_use]
+    pub const fn with_observation_shift(mut self, observation_shift: bool) -> Self {
+        self.observation_shift = observation_shift;
+        self
+    }
+
+    /// Returns the period the overnight rates are observed over, i.e. the accrual period
+    /// moved back by the lookback.
+    #[must_use]
+    pub fn observation_period(&self) -> (Date, Date) {
+        let calendar = Calendar::from(self.calendar);
+        (
+            self.shift_back(&calendar, self.accrual_start_date),
+            self.shift_back(&calendar, self.accrual_end_date),
+        )
+    }
+
+    /// Returns the compounded overnight rate. Rates fixed before the reference date of `index`
+    /// are taken from its fixings; the others are forecast from its discount factors.
+    ///
+    /// # Errors
+    /// Returns an error if a fixing or a discount factor is not available, or if the lockout
+    /// covers the whole period.
+    pub fn compounded_rate<I: YieldProvider + ?Sized>(&self, index: &I) -> Result<f64> {
+        let reference_date = index.reference_date();
+        let day_counter = self.day_counter;
+        let days = self.observation_days()?;
+        let growth = days.iter().try_fold(1.0, |acc, day| {
+            let rate = if day.fixing < reference_date {
+                index.forward_rate(
+                    day.fixing,
+                    day.fixing_end,
+                    Compounding::Simple,
+                    Frequency::Annual,
+                )?
+            } else {
+                let compound =
+                    index.discount_factor(day.fixing)? / index.discount_factor(day.fixing_end)?;
+                (compound - 1.0) / day_counter.year_fraction(day.fixing, day.fixing_end)
+            };
+            let weight = day_counter.year_fraction(day.weight_start, day.weight_end);
+            Ok::<f64, AtlasError>(acc * rate.mul_add(weight, 1.0))
+        })?;
+        let (start, end) = match (days.first(), days.last()) {
+            (Some(first), Some(last)) => (first.weight_start, last.weight_end),
+            _ => {
+                return Err(AtlasError::InvalidValueErr(
+                    "Overnight indexed coupon has no observation days".to_string(),
+                ))
+            }
+        };
+        Ok((growth - 1.0) / day_counter.year_fraction(start, end))
+    }
+
+    fn shift_back(&self, calendar: &Calendar, date: Date) -> Date {
+        calendar.advance(
+            date,
+            Period::new(-self.lookback_days, TimeUnit::Days),
+            None,
+            false,
+        )
+    }
+
+    fn next_business_day(calendar: &Calendar, date: Date) -> Date {
+        calendar.advance(date, Period::new(1, TimeUnit::Days), None, false)
+    }
+
+    /// Returns the business days of the accrual period, or of the observation period if it is
+    /// shifted, with their fixings and weights.
+    fn observation_days(&self) -> Result<Vec<ObservationDay>> {
+        let (start, end) = if self.observation_shift {
+            self.observation_period()
+        } else {
+            (self.accrual_start_date, self.accrual_end_date)
+        };
+        let calendar = Calendar::from(self.calendar);
+        let mut days = Vec::new();
+        let mut date = start;
+        while date < end {
+            let next = Self::next_business_day(&calendar, date).min(end);
+            let fixing = if self.observation_shift {
+                date
+            } else {
+                self.shift_back(&calendar, date)
+            };
+            days.push(ObservationDay {
+                fixing,
+                fixing_end: Self::next_business_day(&calendar, fixing),
+                weight_start: date,
+                weight_end: next,
+            });
+            date = next;
+        }
+
+        if self.lockout_days > 0 {
+            let cutoff = days.len().checked_sub(self.lockout_days + 1).ok_or(
+                AtlasError::InvalidValueErr(format!(
+                    "Lockout of {} days is longer than the {} observation days",
+                    self.lockout_days,
+                    days.len()
+                )),
+            )?;
+            let (fixing, fixing_end) = (days[cutoff].fixing, days[cutoff].fixing_end);
+            for day in days.iter_mut().skip(cutoff + 1) {
+                day.fixing = fixing;
+                day.fixing_end = fixing_end;
+            }
+        }
+        Ok(days)
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{enums::VolatilityType, traits::HasReferenceDate},
+    time::{date::Date, daycounter::DayCounter, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{AdvanceVolatilitySurfaceInTime, VolatilityProvider, VolatilitySurfaceTrait};
+
+/// # `ConstantVolatility`
+/// A volatility surface with the same volatility for every expiry and strike.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the surface
+/// * `volatility` - The volatility
+/// * `volatility_type` - The quoting convention of the volatility
+/// * `day_counter` - The day counter used to calculate the times to expiry
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let surface = ConstantVolatility::new(
+///     reference_date,
+///     0.0075,
+///     VolatilityType::Normal,
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+/// let std_dev = surface.std_dev(Date::new(2025, 1, 1), 0.03).unwrap();
+/// assert!((std_dev - 0.0075).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConstantVolatility {
+    reference_date: Date,
+    volatility: f64,
+    volatility_type: VolatilityType,
+    day_counter: DayCounter,
+}
+
+impl ConstantVolatility {
+    /// Creates a new `ConstantVolatility`.
+    ///
+    /// # Errors
+    /// Returns an error if the volatility is negative.
+    pub fn new(
+        reference_date: Date,
+        volatility: f64,
+        volatility_type: VolatilityType,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if volatility < 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Negative volatility ({volatility})"
+            )));
+        }
+        Ok(Self {
+            reference_date,
+            volatility,
+            volatility_type,
+            day_counter,
+        })
+    }
+
+    /// Returns the day counter.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+}
+
+impl HasReferenceDate for ConstantVolatility {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl VolatilityProvider for ConstantVolatility {
+    fn volatility(&self, _expiry: Date, _strike: f64) -> Result<f64> {
+        Ok(self.volatility)
+    }
+
+    fn volatility_type(&self) -> VolatilityType {
+        self.volatility_type
+    }
+
+    fn time_to_expiry(&self, expiry: Date) -> f64 {
+        self.day_counter
+            .year_fraction(self.reference_date, expiry)
+            .max(0.0)
+    }
+}
+
+impl AdvanceVolatilitySurfaceInTime for ConstantVolatility {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        self.advance_to_date(self.reference_date + period)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        Ok(Arc::new(Self {
+            reference_date: date,
+            ..*self
+        }))
+    }
+}
+
+impl VolatilitySurfaceTrait for ConstantVolatility {}
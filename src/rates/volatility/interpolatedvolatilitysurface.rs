@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use crate::{
+    math::interpolation::enums::Interpolator,
+    rates::{enums::VolatilityType, traits::HasReferenceDate},
+    time::{date::Date, daycounter::DayCounter, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{AdvanceVolatilitySurfaceInTime, VolatilityProvider, VolatilitySurfaceTrait};
+
+/// # `InterpolatedVolatilitySurface`
+/// A volatility surface built from a grid of quotes by expiry and strike. Volatilities are
+/// interpolated linearly in strike and then in time to expiry, and extrapolated flat outside
+/// the grid.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the surface
+/// * `expiries` - The expiries of the grid, as periods from the reference date
+/// * `strikes` - The strikes of the grid, in increasing order
+/// * `volatilities` - One row of volatilities by strike for each expiry
+/// * `volatility_type` - The quoting convention of the volatilities
+/// * `day_counter` - The day counter used to calculate the times to expiry
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let surface = InterpolatedVolatilitySurface::new(
+///     reference_date,
+///     vec![Period::new(1, TimeUnit::Years), Period::new(2, TimeUnit::Years)],
+///     vec![0.01, 0.03],
+///     vec![vec![0.30, 0.20], vec![0.40, 0.30]],
+///     VolatilityType::ShiftedLognormal(0.01),
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+///
+/// let volatility = surface.volatility(Date::new(2025, 1, 1), 0.02).unwrap();
+/// assert!((volatility - 0.25).abs() < 1e-12);
+/// // flat extrapolation beyond the last strike
+/// let volatility = surface.volatility(Date::new(2025, 1, 1), 0.05).unwrap();
+/// assert!((volatility - 0.20).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct InterpolatedVolatilitySurface {
+    reference_date: Date,
+    expiries: Vec<Period>,
+    times: Vec<f64>,
+    strikes: Vec<f64>,
+    volatilities: Vec<Vec<f64>>,
+    volatility_type: VolatilityType,
+    day_counter: DayCounter,
+}
+
+impl InterpolatedVolatilitySurface {
+    /// Creates a new `InterpolatedVolatilitySurface`.
+    ///
+    /// # Errors
+    /// Returns an error if the grid is empty, its expiries or strikes are not increasing, its
+    /// shape does not match the expiries and strikes, or it has negative volatilities.
+    pub fn new(
+        reference_date: Date,
+        expiries: Vec<Period>,
+        strikes: Vec<f64>,
+        volatilities: Vec<Vec<f64>>,
+        volatility_type: VolatilityType,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if expiries.is_empty() || strikes.is_empty() {
+            return Err(AtlasError::InsufficientPointsErr(
+                "Volatility surface needs at least one expiry and one strike".to_string(),
+            ));
+        }
+        let times: Vec<f64> = expiries
+            .iter()
+            .map(|expiry| day_counter.year_fraction(reference_date, reference_date + *expiry))
+            .collect();
+        if times.windows(2).any(|pair| pair[0] >= pair[1])
+            || strikes.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Volatility surface expiries and strikes must be increasing".to_string(),
+            ));
+        }
+        if volatilities.len() != expiries.len()
+            || volatilities.iter().any(|row| row.len() != strikes.len())
+        {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Volatility surface must have {} rows of {} volatilities",
+                expiries.len(),
+                strikes.len()
+            )));
+        }
+        if volatilities
+            .iter()
+            .flatten()
+            .any(|volatility| *volatility < 0.0)
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Volatility surface has negative volatilities".to_string(),
+            ));
+        }
+        Ok(Self {
+            reference_date,
+            expiries,
+            times,
+            strikes,
+            volatilities,
+            volatility_type,
+            day_counter,
+        })
+    }
+
+    /// Returns the expiries of the grid.
+    #[must_use]
+    pub fn expiries(&self) -> &[Period] {
+        &self.expiries
+    }
+
+    /// Returns the strikes of the grid.
+    #[must_use]
+    pub fn strikes(&self) -> &[f64] {
+        &self.strikes
+    }
+
+    /// Returns the volatilities of the grid, one row by expiry.
+    #[must_use]
+    pub fn volatilities(&self) -> &[Vec<f64>] {
+        &self.volatilities
+    }
+
+    /// Returns the day counter.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+}
+
+/// Linear interpolation with flat extrapolation, also valid for a single point.
+pub(crate) fn interpolate_flat(x: f64, x_: &[f64], y_: &[f64]) -> Result<f64> {
+    match (x_.first(), x_.last(), y_.first(), y_.last()) {
+        (Some(first), _, Some(y), _) if x <= *first => Ok(*y),
+        (_, Some(last), _, Some(y)) if x >= *last => Ok(*y),
+        _ => Interpolator::Linear.interpolate(x, x_, y_, false),
+    }
+}
+
+impl HasReferenceDate for InterpolatedVolatilitySurface {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl VolatilityProvider for InterpolatedVolatilitySurface {
+    fn volatility(&self, expiry: Date, strike: f64) -> Result<f64> {
+        let by_expiry = self
+            .volatilities
+            .iter()
+            .map(|row| interpolate_flat(strike, &self.strikes, row))
+            .collect::<Result<Vec<f64>>>()?;
+        interpolate_flat(self.time_to_expiry(expiry), &self.times, &by_expiry)
+    }
+
+    fn volatility_type(&self) -> VolatilityType {
+        self.volatility_type
+    }
+
+    fn time_to_expiry(&self, expiry: Date) -> f64 {
+        self.day_counter
+            .year_fraction(self.reference_date, expiry)
+            .max(0.0)
+    }
+}
+
+impl AdvanceVolatilitySurfaceInTime for InterpolatedVolatilitySurface {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        self.advance_to_date(self.reference_date + period)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        Ok(Arc::new(Self::new(
+            date,
+            self.expiries.clone(),
+            self.strikes.clone(),
+            self.volatilities.clone(),
+            self.volatility_type,
+            self.day_counter,
+        )?))
+    }
+}
+
+impl VolatilitySurfaceTrait for InterpolatedVolatilitySurface {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::enums::TimeUnit;
+
+    #[test]
+    fn test_interpolation() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let surface = InterpolatedVolatilitySurface::new(
+            reference_date,
+            vec![
+                Period::new(1, TimeUnit::Years),
+                Period::new(3, TimeUnit::Years),
+            ],
+            vec![0.0, 0.02, 0.04],
+            vec![vec![0.010, 0.008, 0.009], vec![0.012, 0.010, 0.011]],
+            VolatilityType::Normal,
+            DayCounter::Actual365,
+        )?;
+
+        // 2026-01-02 is halfway between the 1y and 3y expiries in days
+        let volatility = surface.volatility(Date::new(2026, 1, 2), 0.01)?;
+        assert!((volatility - 0.010).abs() < 1e-12);
+
+        // flat before the first expiry and after the last one
+        assert!((surface.volatility(reference_date, 0.02)? - 0.008).abs() < 1e-12);
+        assert!((surface.volatility(Date::new(2040, 1, 1), 0.04)? - 0.011).abs() < 1e-12);
+        assert!((surface.std_dev(reference_date, 0.02)?).abs() < 1e-12);
+
+        // the surface keeps its volatilities by time to expiry when advanced
+        let advanced = surface.advance_to_period(Period::new(1, TimeUnit::Years))?;
+        assert!(
+            (advanced.volatility(Date::new(2027, 1, 2), 0.02)?
+                - surface.volatility(Date::new(2026, 1, 2), 0.02)?)
+            .abs()
+                < 1e-12
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let reference_date = Date::new(2024, 1, 2);
+        let expiries = vec![Period::new(1, TimeUnit::Years)];
+        assert!(InterpolatedVolatilitySurface::new(
+            reference_date,
+            expiries.clone(),
+            vec![0.02, 0.01],
+            vec![vec![0.2, 0.2]],
+            VolatilityType::ShiftedLognormal(0.0),
+            DayCounter::Actual365,
+        )
+        .is_err());
+        assert!(InterpolatedVolatilitySurface::new(
+            reference_date,
+            expiries,
+            vec![0.01, 0.02],
+            vec![vec![0.2]],
+            VolatilityType::ShiftedLognormal(0.0),
+            DayCounter::Actual365,
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,8 @@
+/// Constant volatility module.
+pub mod constantvolatility;
+/// Interpolated volatility surface module.
+pub mod interpolatedvolatilitysurface;
+/// Swaption volatility cube module.
+pub mod swaptionvolatilitycube;
+/// Traits module.
+pub mod traits;
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{enums::VolatilityType, traits::HasReferenceDate},
+    time::{date::Date, daycounter::DayCounter, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    interpolatedvolatilitysurface::interpolate_flat,
+    traits::{
+        AdvanceSwaptionVolatilityInTime, SwaptionVolatilityCubeTrait, SwaptionVolatilityProvider,
+    },
+};
+
+/// # `SwaptionVolatilityCube`
+/// A swaption volatility cube built from quotes by expiry, tenor of the underlying swap and
+/// strike. Volatilities are interpolated linearly in strike, then in tenor and then in time to
+/// expiry, and extrapolated flat outside the grid. A grid with a single point is a constant
+/// volatility.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the cube
+/// * `expiries` - The expiries of the grid, as periods from the reference date
+/// * `tenors` - The tenors of the underlying swaps, in increasing order
+/// * `strikes` - The strikes of the grid, in increasing order
+/// * `volatilities` - For each expiry, one row of volatilities by strike for each tenor
+/// * `volatility_type` - The quoting convention of the volatilities
+/// * `day_counter` - The day counter used to calculate the times to expiry and swap tenors
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let cube = SwaptionVolatilityCube::new(
+///     reference_date,
+///     vec![Period::new(1, TimeUnit::Years)],
+///     vec![Period::new(5, TimeUnit::Years), Period::new(10, TimeUnit::Years)],
+///     vec![0.03],
+///     vec![vec![vec![0.008], vec![0.010]]],
+///     VolatilityType::Normal,
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+///
+/// let volatility = cube.volatility(Date::new(2025, 1, 2), 7.5, 0.03).unwrap();
+/// assert!((volatility - 0.009).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SwaptionVolatilityCube {
+    reference_date: Date,
+    expiries: Vec<Period>,
+    times: Vec<f64>,
+    tenors: Vec<Period>,
+    tenor_lengths: Vec<f64>,
+    strikes: Vec<f64>,
+    volatilities: Vec<Vec<Vec<f64>>>,
+    volatility_type: VolatilityType,
+    day_counter: DayCounter,
+}
+
+impl SwaptionVolatilityCube {
+    /// Creates a new `SwaptionVolatilityCube`.
+    ///
+    /// # Errors
+    /// Returns an error if the grid is empty, its expiries, tenors or strikes are not
+    /// increasing, its shape does not match them, or it has negative volatilities.
+    pub fn new(
+        reference_date: Date,
+        expiries: Vec<Period>,
+        tenors: Vec<Period>,
+        strikes: Vec<f64>,
+        volatilities: Vec<Vec<Vec<f64>>>,
+        volatility_type: VolatilityType,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if expiries.is_empty() || tenors.is_empty() || strikes.is_empty() {
+            return Err(AtlasError::InsufficientPointsErr(
+                "Swaption volatility cube needs at least one expiry, tenor and strike".to_string(),
+            ));
+        }
+        let times: Vec<f64> = expiries
+            .iter()
+            .map(|expiry| day_counter.year_fraction(reference_date, reference_date + *expiry))
+            .collect();
+        let tenor_lengths: Vec<f64> = tenors.iter().map(Period::period_in_year).collect();
+        if times.windows(2).any(|pair| pair[0] >= pair[1])
+            || tenor_lengths.windows(2).any(|pair| pair[0] >= pair[1])
+            || strikes.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Swaption volatility cube expiries, tenors and strikes must be increasing"
+                    .to_string(),
+            ));
+        }
+        if volatilities.len() != expiries.len()
+            || volatilities.iter().any(|matrix| {
+                matrix.len() != tenors.len() || matrix.iter().any(|row| row.len() != strikes.len())
+            })
+        {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Swaption volatility cube must have {} matrices of {} rows of {} volatilities",
+                expiries.len(),
+                tenors.len(),
+                strikes.len()
+            )));
+        }
+        if volatilities
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|volatility| *volatility < 0.0)
+        {
+            return Err(AtlasError::InvalidValueErr(
+                "Swaption volatility cube has negative volatilities".to_string(),
+            ));
+        }
+        Ok(Self {
+            reference_date,
+            expiries,
+            times,
+            tenors,
+            tenor_lengths,
+            strikes,
+            volatilities,
+            volatility_type,
+            day_counter,
+        })
+    }
+
+    /// Returns the expiries of the grid.
+    #[must_use]
+    pub fn expiries(&self) -> &[Period] {
+        &self.expiries
+    }
+
+    /// Returns the tenors of the grid.
+    #[must_use]
+    pub fn tenors(&self) -> &[Period] {
+        &self.tenors
+    }
+
+    /// Returns the strikes of the grid.
+    #[must_use]
+    pub fn strikes(&self) -> &[f64] {
+        &self.strikes
+    }
+
+    /// Returns the volatilities of the grid, one matrix by expiry.
+    #[must_use]
+    pub fn volatilities(&self) -> &[Vec<Vec<f64>>] {
+        &self.volatilities
+    }
+
+    /// Returns the day counter.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+}
+
+impl HasReferenceDate for SwaptionVolatilityCube {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl SwaptionVolatilityProvider for SwaptionVolatilityCube {
+    fn volatility(&self, expiry: Date, tenor: f64, strike: f64) -> Result<f64> {
+        let by_expiry = self
+            .volatilities
+            .iter()
+            .map(|matrix| {
+                let by_tenor = matrix
+                    .iter()
+                    .map(|row| interpolate_flat(strike, &self.strikes, row))
+                    .collect::<Result<Vec<f64>>>()?;
+                interpolate_flat(tenor, &self.tenor_lengths, &by_tenor)
+            })
+            .collect::<Result<Vec<f64>>>()?;
+        interpolate_flat(self.time_to_expiry(expiry), &self.times, &by_expiry)
+    }
+
+    fn volatility_type(&self) -> VolatilityType {
+        self.volatility_type
+    }
+
+    fn time_to_expiry(&self, expiry: Date) -> f64 {
+        self.day_counter
+            .year_fraction(self.reference_date, expiry)
+            .max(0.0)
+    }
+
+    fn swap_tenor(&self, start_date: Date, end_date: Date) -> f64 {
+        self.day_counter.year_fraction(start_date, end_date)
+    }
+}
+
+impl AdvanceSwaptionVolatilityInTime for SwaptionVolatilityCube {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>> {
+        self.advance_to_date(self.reference_date + period)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>> {
+        Ok(Arc::new(Self::new(
+            date,
+            self.expiries.clone(),
+            self.tenors.clone(),
+            self.strikes.clone(),
+            self.volatilities.clone(),
+            self.volatility_type,
+            self.day_counter,
+        )?))
+    }
+}
+
+impl SwaptionVolatilityCubeTrait for SwaptionVolatilityCube {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::enums::TimeUnit;
+
+    #[test]
+    fn test_interpolation() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let cube = SwaptionVolatilityCube::new(
+            reference_date,
+            vec![
+                Period::new(1, TimeUnit::Years),
+                Period::new(3, TimeUnit::Years),
+            ],
+            vec![
+                Period::new(2, TimeUnit::Years),
+                Period::new(10, TimeUnit::Years),
+            ],
+            vec![0.01, 0.03],
+            vec![
+                vec![vec![0.20, 0.30], vec![0.10, 0.20]],
+                vec![vec![0.40, 0.50], vec![0.30, 0.40]],
+            ],
+            VolatilityType::ShiftedLognormal(0.02),
+            DayCounter::Actual365,
+        )?;
+
+        // halfway in expiry, tenor and strike
+        let volatility = cube.volatility(Date::new(2026, 1, 2), 6.0, 0.02)?;
+        assert!((volatility - 0.30).abs() < 1e-12);
+
+        // flat outside the grid
+        assert!((cube.volatility(reference_date, 1.0, 0.0)? - 0.20).abs() < 1e-12);
+        assert!((cube.volatility(Date::new(2040, 1, 1), 30.0, 0.05)? - 0.40).abs() < 1e-12);
+        assert!(cube.std_dev(reference_date, 5.0, 0.02)?.abs() < 1e-12);
+
+        let advanced = cube.advance_to_period(Period::new(1, TimeUnit::Years))?;
+        assert!(
+            (advanced.volatility(Date::new(2027, 1, 2), 6.0, 0.02)?
+                - cube.volatility(Date::new(2026, 1, 2), 6.0, 0.02)?)
+            .abs()
+                < 1e-12
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let reference_date = Date::new(2024, 1, 2);
+        assert!(SwaptionVolatilityCube::new(
+            reference_date,
+            vec![Period::new(1, TimeUnit::Years)],
+            vec![
+                Period::new(10, TimeUnit::Years),
+                Period::new(5, TimeUnit::Years),
+            ],
+            vec![0.03],
+            vec![vec![vec![0.01], vec![0.01]]],
+            VolatilityType::Normal,
+            DayCounter::Actual365,
+        )
+        .is_err());
+        assert!(SwaptionVolatilityCube::new(
+            reference_date,
+            vec![Period::new(1, TimeUnit::Years)],
+            vec![Period::new(5, TimeUnit::Years)],
+            vec![0.03],
+            vec![vec![vec![0.01, 0.02]]],
+            VolatilityType::Normal,
+            DayCounter::Actual365,
+        )
+        .is_err());
+    }
+}
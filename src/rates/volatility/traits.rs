@@ -0,0 +1,136 @@
+use std::{panic::RefUnwindSafe, sync::Arc};
+
+use crate::{
+    rates::{enums::VolatilityType, traits::HasReferenceDate},
+    time::{date::Date, period::Period},
+    utils::errors::Result,
+};
+
+/// # `VolatilityProvider`
+/// Trait for objects that provide the volatilities of options on interest rates.
+pub trait VolatilityProvider {
+    /// Returns the volatility of an option with the given expiry and strike.
+    ///
+    /// # Errors
+    /// Returns an error if the volatility cannot be calculated.
+    fn volatility(&self, expiry: Date, strike: f64) -> Result<f64>;
+
+    /// Returns the quoting convention of the volatilities.
+    fn volatility_type(&self) -> VolatilityType;
+
+    /// Returns the time to the given expiry, zero for expiries on or before the reference date.
+    fn time_to_expiry(&self, expiry: Date) -> f64;
+
+    /// Returns the standard deviation of the rate up to the given expiry, the volatility times
+    /// the square root of the time to expiry.
+    ///
+    /// # Errors
+    /// Returns an error if the volatility cannot be calculated.
+    fn std_dev(&self, expiry: Date, strike: f64) -> Result<f64> {
+        let time = self.time_to_expiry(expiry);
+        if time == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(self.volatility(expiry, strike)? * time.sqrt())
+    }
+}
+
+/// # `AdvanceVolatilitySurfaceInTime`
+/// Trait for advancing a volatility surface in time. Surfaces keep their volatilities by time
+/// to expiry (sticky expiry), so an option expiring one year after the new reference date gets
+/// the volatility the one year expiry had before.
+pub trait AdvanceVolatilitySurfaceInTime {
+    /// Advances the surface by the given period.
+    ///
+    /// # Errors
+    /// Returns an error if the surface cannot be advanced.
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn VolatilitySurfaceTrait>>;
+    /// Advances the surface to the given date.
+    ///
+    /// # Errors
+    /// Returns an error if the surface cannot be advanced.
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn VolatilitySurfaceTrait>>;
+}
+
+/// # `VolatilitySurfaceTrait`
+/// Trait that defines a volatility surface by expiry and strike.
+///
+/// ## Note
+/// This trait is a combination of the following traits:
+/// - `VolatilityProvider`
+/// - `HasReferenceDate`
+/// - `AdvanceVolatilitySurfaceInTime`
+/// - Send and Sync, to share surfaces between threads.
+/// - `RefUnwindSafe`, so that stores holding surfaces can be used across `catch_unwind`.
+pub trait VolatilitySurfaceTrait:
+    VolatilityProvider + HasReferenceDate + AdvanceVolatilitySurfaceInTime + Send + Sync + RefUnwindSafe
+{
+}
+
+/// # `SwaptionVolatilityProvider`
+/// Trait for objects that provide the volatilities of swaptions, by expiry, tenor of the
+/// underlying swap and strike.
+pub trait SwaptionVolatilityProvider {
+    /// Returns the volatility of a swaption with the given expiry, underlying tenor (in years)
+    /// and strike.
+    ///
+    /// # Errors
+    /// Returns an error if the volatility cannot be calculated.
+    fn volatility(&self, expiry: Date, tenor: f64, strike: f64) -> Result<f64>;
+
+    /// Returns the quoting convention of the volatilities.
+    fn volatility_type(&self) -> VolatilityType;
+
+    /// Returns the time to the given expiry, zero for expiries on or before the reference date.
+    fn time_to_expiry(&self, expiry: Date) -> f64;
+
+    /// Returns the tenor, in years, of a swap running between the given dates.
+    fn swap_tenor(&self, start_date: Date, end_date: Date) -> f64;
+
+    /// Returns the standard deviation of the swap rate up to the given expiry.
+    ///
+    /// # Errors
+    /// Returns an error if the volatility cannot be calculated.
+    fn std_dev(&self, expiry: Date, tenor: f64, strike: f64) -> Result<f64> {
+        let time = self.time_to_expiry(expiry);
+        if time == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(self.volatility(expiry, tenor, strike)? * time.sqrt())
+    }
+}
+
+/// # `AdvanceSwaptionVolatilityInTime`
+/// Trait for advancing a swaption volatility cube in time, keeping its volatilities by time to
+/// expiry like `AdvanceVolatilitySurfaceInTime`.
+pub trait AdvanceSwaptionVolatilityInTime {
+    /// Advances the cube by the given period.
+    ///
+    /// # Errors
+    /// Returns an error if the cube cannot be advanced.
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>>;
+    /// Advances the cube to the given date.
+    ///
+    /// # Errors
+    /// Returns an error if the cube cannot be advanced.
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>>;
+}
+
+/// # `SwaptionVolatilityCubeTrait`
+/// Trait that defines a swaption volatility cube by expiry, tenor and strike.
+///
+/// ## Note
+/// This trait is a combination of the following traits:
+/// - `SwaptionVolatilityProvider`
+/// - `HasReferenceDate`
+/// - `AdvanceSwaptionVolatilityInTime`
+/// - Send, Sync and `RefUnwindSafe`, as for `VolatilitySurfaceTrait`.
+pub trait SwaptionVolatilityCubeTrait:
+    SwaptionVolatilityProvider
+    + HasReferenceDate
+    + AdvanceSwaptionVolatilityInTime
+    + Send
+    + Sync
+    + RefUnwindSafe
+{
+}
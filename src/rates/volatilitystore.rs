@@ -0,0 +1,144 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    time::{date::Date, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::volatility::traits::{SwaptionVolatilityCubeTrait, VolatilitySurfaceTrait};
+
+/// # `VolatilityStore`
+/// A store for the volatility surfaces of interest rate indices. Surfaces are stored under the
+/// ID of the index whose rates they describe, so options on coupons with forecast curve ID `i`
+/// are priced with the surface with ID `i`. Swaption volatility cubes are stored in the same way,
+/// under the forecast curve ID of the floating leg of the underlying swaps.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the volatility store
+#[derive(Clone)]
+pub struct VolatilityStore {
+    reference_date: Date,
+    surfaces: HashMap<usize, Arc<dyn VolatilitySurfaceTrait>>,
+    swaption_cubes: HashMap<usize, Arc<dyn SwaptionVolatilityCubeTrait>>,
+}
+
+impl VolatilityStore {
+    /// Creates a new `VolatilityStore` with the given reference date.
+    #[must_use]
+    pub fn new(reference_date: Date) -> Self {
+        Self {
+            reference_date,
+            surfaces: HashMap::new(),
+            swaption_cubes: HashMap::new(),
+        }
+    }
+
+    /// Returns the reference date of this volatility store.
+    #[must_use]
+    pub const fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+
+    /// Adds a surface to the store for the index with the given ID.
+    ///
+    /// # Errors
+    /// Returns an error if the surface reference date does not match or the ID already exists.
+    pub fn add_surface(
+        &mut self,
+        id: usize,
+        surface: Arc<dyn VolatilitySurfaceTrait>,
+    ) -> Result<()> {
+        if self.reference_date != surface.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Volatility surface reference date ({reference_date}) does not match volatility store reference date ({store_reference_date})",
+                reference_date = surface.reference_date(),
+                store_reference_date = self.reference_date
+            )));
+        }
+        if self.surfaces.contains_key(&id) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Volatility surface with id {id} already exists"
+            )));
+        }
+        self.surfaces.insert(id, surface);
+        Ok(())
+    }
+
+    /// Retrieves the surface of the index with the given ID.
+    ///
+    /// # Errors
+    /// Returns an error if no surface exists for the ID.
+    pub fn get_surface(&self, id: usize) -> Result<Arc<dyn VolatilitySurfaceTrait>> {
+        self.surfaces
+            .get(&id)
+            .cloned()
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "Volatility surface with id {id} not found"
+            )))
+    }
+
+    /// Adds a swaption volatility cube to the store for the index with the given ID.
+    ///
+    /// # Errors
+    /// Returns an error if the cube reference date does not match or the ID already exists.
+    pub fn add_swaption_cube(
+        &mut self,
+        id: usize,
+        cube: Arc<dyn SwaptionVolatilityCubeTrait>,
+    ) -> Result<()> {
+        if self.reference_date != cube.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Swaption volatility cube reference date ({reference_date}) does not match volatility store reference date ({store_reference_date})",
+                reference_date = cube.reference_date(),
+                store_reference_date = self.reference_date
+            )));
+        }
+        if self.swaption_cubes.contains_key(&id) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Swaption volatility cube with id {id} already exists"
+            )));
+        }
+        self.swaption_cubes.insert(id, cube);
+        Ok(())
+    }
+
+    /// Retrieves the swaption volatility cube of the index with the given ID.
+    ///
+    /// # Errors
+    /// Returns an error if no cube exists for the ID.
+    pub fn get_swaption_cube(&self, id: usize) -> Result<Arc<dyn SwaptionVolatilityCubeTrait>> {
+        self.swaption_cubes
+            .get(&id)
+            .cloned()
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "Swaption volatility cube with id {id} not found"
+            )))
+    }
+
+    /// Returns the number of surfaces and swaption cubes in the store.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.surfaces.len() + self.swaption_cubes.len()
+    }
+
+    /// Returns true if the store has no surfaces nor swaption cubes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.surfaces.is_empty() && self.swaption_cubes.is_empty()
+    }
+
+    /// Advances every surface and swaption cube of the store by the given period.
+    ///
+    /// # Errors
+    /// Returns an error if a surface cannot be advanced.
+    pub fn advance_to_period(&self, period: Period) -> Result<Self> {
+        let mut store = Self::new(self.reference_date + period);
+        for (id, surface) in &self.surfaces {
+            store.add_surface(*id, surface.advance_to_period(period)?)?;
+        }
+        for (id, cube) in &self.swaption_cubes {
+            store.add_swaption_cube(*id, cube.advance_to_period(period)?)?;
+        }
+        Ok(store)
+    }
+}
@@ -0,0 +1,437 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{
+        enums::Compounding,
+        interestrate::InterestRate,
+        traits::{HasReferenceDate, YieldProvider},
+    },
+    time::{date::Date, daycounter::DayCounter, enums::Frequency, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{AdvanceTermStructureInTime, YieldTermStructureTrait};
+
+/// # `Bump`
+/// A shock applied to a yield curve. Sizes are absolute rates (0.0001 is one basis point)
+/// and are applied on continuously compounded rates.
+///
+/// ## Variants
+/// * `Parallel` - Shifts every zero rate by the same amount.
+/// * `KeyRate` - Shifts zero rates with a triangular profile that peaks at `tenors[pillar]`
+///   and vanishes at the neighbouring tenors. The first and last tenors are flat towards the
+///   short and long ends, so the key-rate bumps of all pillars add up to a parallel bump.
+/// * `Forward` - Shifts the instantaneous forward rates between `start` and `end`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Bump {
+    /// Parallel zero rate shift.
+    Parallel(f64),
+    /// Triangular zero rate shift around one of the pillar tenors.
+    KeyRate {
+        /// Pillar tenors, in increasing order.
+        tenors: Vec<Period>,
+        /// Position of the bumped pillar in `tenors`.
+        pillar: usize,
+        /// Size of the shift at the bumped pillar.
+        size: f64,
+    },
+    /// Instantaneous forward rate shift over a window.
+    Forward {
+        /// Start of the bumped window.
+        start: Period,
+        /// End of the bumped window.
+        end: Period,
+        /// Size of the shift.
+        size: f64,
+    },
+}
+
+impl Bump {
+    /// Returns one key-rate bump of the given size for every pillar tenor.
+    #[must_use]
+    pub fn key_rates(tenors: &[Period], size: f64) -> Vec<Self> {
+        (0..tenors.len())
+            .map(|pillar| Self::KeyRate {
+                tenors: tenors.to_vec(),
+                pillar,
+                size,
+            })
+            .collect()
+    }
+
+    /// Returns the size of the bump.
+    #[must_use]
+    pub const fn size(&self) -> f64 {
+        match self {
+            Self::Parallel(size) | Self::KeyRate { size, .. } | Self::Forward { size, .. } => *size,
+        }
+    }
+}
+
+/// # `BumpedTermStructure`
+/// Term structure that applies a `Bump` on top of a base curve. Discount factors are
+///
+/// `df_{bumped}(t) = df_{base}(t) * e^{-dz(t) * t}`
+///
+/// where `dz(t)` is the zero rate shift implied by the bump and `t` is measured with the
+/// given day counter from the date the bump was set. Bump tenors are resolved against that
+/// same date, so an advanced curve keeps shocking the same calendar dates.
+///
+/// Forward rates are those of the base curve, under its own day counter, plus the forwards
+/// implied by the bump factors, so a zero bump leaves them unchanged.
+///
+/// ## Parameters
+/// * `base_curve` - The curve being bumped
+/// * `bump` - The bump to apply
+/// * `day_counter` - The day counter used to measure times (defaults to `Actual365`)
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+/// use std::sync::Arc;
+///
+/// let ref_date = Date::new(2023, 1, 1);
+/// let base_curve = Arc::new(FlatForwardTermStructure::new(
+///     ref_date,
+///     0.02,
+///     RateDefinition::new(DayCounter::Actual365, Compounding::Continuous, Frequency::Annual),
+/// ));
+/// let bumped_curve = BumpedTermStructure::new(base_curve.clone(), Bump::Parallel(0.0001)).unwrap();
+///
+/// let date = Date::new(2024, 1, 1);
+/// let df = bumped_curve.discount_factor(date).unwrap();
+/// let base_df = base_curve.discount_factor(date).unwrap();
+/// assert!((df - base_df * (-0.0001f64).exp()).abs() < 1e-12);
+/// ```
+#[derive(Clone)]
+pub struct BumpedTermStructure {
+    base_curve: Arc<dyn YieldTermStructureTrait>,
+    bump: Bump,
+    bump_date: Date,
+    times: Vec<f64>,
+    day_counter: DayCounter,
+}
+
+impl BumpedTermStructure {
+    /// Creates a new `BumpedTermStructure` that bumps `base_curve` from its reference date.
+    ///
+    /// # Errors
+    /// Returns an error if a key-rate bump has no tenors, its pillar is out of range or its
+    /// tenors are not increasing, or if a forward bump ends before it starts.
+    pub fn new(base_curve: Arc<dyn YieldTermStructureTrait>, bump: Bump) -> Result<Self> {
+        let bump_date = base_curve.reference_date();
+        Self::with_bump_date(base_curve, bump, bump_date, DayCounter::Actual365)
+    }
+
+    /// Sets the day counter used to measure times.
+    ///
+    /// # Errors
+    /// Returns an error if the bump tenors are not increasing under the new day counter.
+    pub fn with_day_counter(self, day_counter: DayCounter) -> Result<Self> {
+        Self::with_bump_date(self.base_curve, self.bump, self.bump_date, day_counter)
+    }
+
+    fn with_bump_date(
+        base_curve: Arc<dyn YieldTermStructureTrait>,
+        bump: Bump,
+        bump_date: Date,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        let time = |period: Period| day_counter.year_fraction(bump_date, bump_date + period);
+        let times: Vec<f64> = match &bump {
+            Bump::Parallel(_) => Vec::new(),
+            Bump::KeyRate { tenors, pillar, .. } => {
+                if *pillar >= tenors.len() {
+                    return Err(AtlasError::OutOfRangeErr(format!(
+                        "Key-rate pillar {pillar} is out of range for {} tenors",
+                        tenors.len()
+                    )));
+                }
+                tenors.iter().map(|tenor| time(*tenor)).collect()
+            }
+            Bump::Forward { start, end, .. } => vec![time(*start), time(*end)],
+        };
+        if times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Bump tenors must be increasing, got {bump:?}"
+            )));
+        }
+        Ok(Self {
+            base_curve,
+            bump,
+            bump_date,
+            times,
+            day_counter,
+        })
+    }
+
+    /// Returns a reference to the base curve.
+    #[must_use]
+    pub fn base_curve(&self) -> &dyn YieldTermStructureTrait {
+        self.base_curve.as_ref()
+    }
+
+    /// Returns the bump applied to the base curve.
+    #[must_use]
+    pub const fn bump(&self) -> &Bump {
+        &self.bump
+    }
+
+    /// Returns the zero rate shift at time `t`, measured in years from the date the bump
+    /// was set.
+    #[must_use]
+    pub fn zero_rate_shift(&self, t: f64) -> f64 {
+        match &self.bump {
+            Bump::Parallel(size) => *size,
+            Bump::KeyRate { pillar, size, .. } => size * self.key_rate_weight(t, *pillar),
+            Bump::Forward { size, .. } => {
+                if t <= 0.0 {
+                    return 0.0;
+                }
+                let overlap = t.min(self.times[1]) - t.min(self.times[0]);
+                size * overlap / t
+            }
+        }
+    }
+
+    fn key_rate_weight(&self, t: f64, pillar: usize) -> f64 {
+        let times = &self.times;
+        let pillar_time = times[pillar];
+        if t <= pillar_time {
+            match pillar.checked_sub(1).map(|i| times[i]) {
+                Some(previous) if t > previous => (t - previous) / (pillar_time - previous),
+                Some(_) => 0.0,
+                None => 1.0,
+            }
+        } else {
+            match times.get(pillar + 1) {
+                Some(next) if t < *next => (next - t) / (next - pillar_time),
+                Some(_) => 0.0,
+                None => 1.0,
+            }
+        }
+    }
+
+    fn bump_factor(&self, date: Date) -> f64 {
+        let t = self.day_counter.year_fraction(self.bump_date, date);
+        (-self.zero_rate_shift(t) * t).exp()
+    }
+}
+
+impl HasReferenceDate for BumpedTermStructure {
+    fn reference_date(&self) -> Date {
+        self.base_curve.reference_date()
+    }
+}
+
+impl YieldProvider for BumpedTermStructure {
+    fn discount_factor(&self, date: Date) -> Result<f64> {
+        let base_discount_factor = self.base_curve.discount_factor(date)?;
+        Ok(base_discount_factor * self.bump_factor(date) / self.bump_factor(self.reference_date()))
+    }
+
+    fn forward_rate(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        comp: Compounding,
+        freq: Frequency,
+    ) -> Result<f64> {
+        let base_forward = self
+            .base_curve
+            .forward_rate(start_date, end_date, comp, freq)?;
+        let bump_comp_factor = self.bump_factor(start_date) / self.bump_factor(end_date);
+        let t = self.day_counter.year_fraction(start_date, end_date);
+        let bump_forward =
+            InterestRate::implied_rate(bump_comp_factor, self.day_counter, comp, freq, t)?.rate();
+        Ok(base_forward + bump_forward)
+    }
+}
+
+/// # `AdvanceTermStructureInTime` for `BumpedTermStructure`
+impl AdvanceTermStructureInTime for BumpedTermStructure {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        let base_curve = self.base_curve.advance_to_period(period)?;
+        Ok(Arc::new(Self {
+            base_curve,
+            ..self.clone()
+        }))
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        let base_curve = self.base_curve.advance_to_date(date)?;
+        Ok(Arc::new(Self {
+            base_curve,
+            ..self.clone()
+        }))
+    }
+}
+
+impl YieldTermStructureTrait for BumpedTermStructure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rates::{
+            interestrate::RateDefinition,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::enums::TimeUnit,
+    };
+
+    fn base_curve() -> Arc<dyn YieldTermStructureTrait> {
+        Arc::new(FlatForwardTermStructure::new(
+            Date::new(2024, 1, 2),
+            0.03,
+            RateDefinition::new(
+                DayCounter::Actual365,
+                Compounding::Continuous,
+                Frequency::Annual,
+            ),
+        ))
+    }
+
+    fn zero_rate(curve: &dyn YieldTermStructureTrait, date: Date) -> Result<f64> {
+        let t = DayCounter::Actual365.year_fraction(curve.reference_date(), date);
+        Ok(-curve.discount_factor(date)?.ln() / t)
+    }
+
+    #[test]
+    fn test_key_rates_add_up_to_parallel() -> Result<()> {
+        let tenors = [2, 5, 10]
+            .iter()
+            .map(|years| Period::new(*years, TimeUnit::Years))
+            .collect::<Vec<Period>>();
+        let base = base_curve();
+        let reference_date = base.reference_date();
+
+        for years in [1, 3, 5, 7, 15] {
+            let date = reference_date + Period::new(years, TimeUnit::Years);
+            let base_rate = zero_rate(base.as_ref(), date)?;
+            let total = Bump::key_rates(&tenors, 0.0001)
+                .into_iter()
+                .map(|bump| {
+                    let curve = BumpedTermStructure::new(base.clone(), bump)?;
+                    Ok(zero_rate(&curve, date)? - base_rate)
+                })
+                .sum::<Result<f64>>()?;
+            assert!((total - 0.0001).abs() < 1e-12, "{years}y shift {total}");
+        }
+
+        // the 5y pillar is about half way up at 3.5y and has no effect beyond 10y
+        let curve = BumpedTermStructure::new(
+            base.clone(),
+            Bump::KeyRate {
+                tenors: tenors.clone(),
+                pillar: 1,
+                size: 0.0001,
+            },
+        )?;
+        let t = DayCounter::Actual365.year_fraction(
+            reference_date,
+            reference_date + Period::new(42, TimeUnit::Months),
+        );
+        let shift = curve.zero_rate_shift(t);
+        assert!((shift - 0.00005).abs() < 1e-6);
+        assert!(curve.zero_rate_shift(12.0).abs() < 1e-16);
+
+        assert!(BumpedTermStructure::new(
+            base,
+            Bump::KeyRate {
+                tenors,
+                pillar: 3,
+                size: 0.0001,
+            },
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_forward_bump() -> Result<()> {
+        let base = base_curve();
+        let reference_date = base.reference_date();
+        let bump = Bump::Forward {
+            start: Period::new(1, TimeUnit::Years),
+            end: Period::new(2, TimeUnit::Years),
+            size: 0.01,
+        };
+        let curve = BumpedTermStructure::new(base.clone(), bump)?;
+
+        let (one_year, two_years, three_years) = (
+            reference_date + Period::new(1, TimeUnit::Years),
+            reference_date + Period::new(2, TimeUnit::Years),
+            reference_date + Period::new(3, TimeUnit::Years),
+        );
+        let inside = curve.forward_rate(
+            one_year,
+            two_years,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )?;
+        let outside = curve.forward_rate(
+            two_years,
+            three_years,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )?;
+        assert!((inside - 0.04).abs() < 1e-12);
+        assert!((outside - 0.03).abs() < 1e-12);
+        assert!((curve.discount_factor(one_year)? - base.discount_factor(one_year)?).abs() < 1e-15);
+
+        // advancing keeps the bumped window on the same dates
+        let advanced = curve.advance_to_period(Period::new(6, TimeUnit::Months))?;
+        let forward = advanced.forward_rate(
+            one_year,
+            two_years,
+            Compounding::Continuous,
+            Frequency::Annual,
+        )?;
+        assert!((forward - 0.04).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_bump_keeps_base_forwards() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let base: Arc<dyn YieldTermStructureTrait> = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.03,
+            RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Compounded,
+                Frequency::Semiannual,
+            ),
+        ));
+        let tenors = vec![
+            Period::new(1, TimeUnit::Years),
+            Period::new(5, TimeUnit::Years),
+        ];
+        let bumps = [
+            Bump::Parallel(0.0),
+            Bump::KeyRate {
+                tenors,
+                pillar: 0,
+                size: 0.0,
+            },
+        ];
+        let start_date = reference_date + Period::new(6, TimeUnit::Months);
+        let end_date = reference_date + Period::new(9, TimeUnit::Months);
+        for bump in bumps {
+            let curve = BumpedTermStructure::new(base.clone(), bump)?;
+            for comp in [
+                Compounding::Simple,
+                Compounding::Compounded,
+                Compounding::Continuous,
+            ] {
+                let forward =
+                    curve.forward_rate(start_date, end_date, comp, Frequency::Quarterly)?;
+                let base_forward =
+                    base.forward_rate(start_date, end_date, comp, Frequency::Quarterly)?;
+                assert!((forward - base_forward).abs() < 1e-15);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    math::interpolation::enums::Interpolator,
+    math::{ad::traits::Real, interpolation::enums::Interpolator},
     rates::traits::HasReferenceDate,
     rates::{enums::Compounding, interestrate::InterestRate, traits::YieldProvider},
     time::{
@@ -82,7 +82,8 @@ impl DiscountTermStructure {
     ///
     /// # Errors
     ///
-    /// Returns an error if dates and discount factors have different lengths or if the first discount factor is not 1.0.
+    /// Returns an error if dates and discount factors have different lengths, if the first discount factor is not 1.0
+    /// or if the interpolator cannot interpolate the discount factors.
     pub fn new(
         dates: Vec<Date>,
         discount_factors: Vec<f64>,
@@ -99,7 +100,7 @@ impl DiscountTermStructure {
 
         // order dates y discount_factors
         let mut zipped = dates.into_iter().zip(discount_factors).collect::<Vec<_>>();
-        zipped.sort_by(|a, b| a.0.cmp(&b.0));
+        zipped.sort_by_key(|a| a.0);
         let (dates, discount_factors): (Vec<Date>, Vec<f64>) = zipped.into_iter().unzip();
 
         // discount_factors[0] needs to be 1.0
@@ -108,6 +109,7 @@ impl DiscountTermStructure {
                 "First discount factor needs to be 1.0".to_string(),
             ));
         }
+        interpolator.check_discount_factors(&discount_factors)?;
         let reference_date = dates[0];
         let year_fractions: Vec<f64> = dates
             .iter()
@@ -154,6 +156,40 @@ impl DiscountTermStructure {
     pub const fn interpolator(&self) -> Interpolator {
         self.interpolator
     }
+
+    /// Returns the discount factor at `date` interpolated from the given node values instead
+    /// of the curve's own, so that it can be evaluated with `Dual` numbers to get its
+    /// derivatives with respect to each node.
+    ///
+    /// # Errors
+    /// Returns an error if there is not one value per node, if the date is before the
+    /// reference date or if the interpolation fails.
+    pub fn discount_factor_with<T: Real>(&self, date: Date, discount_factors: &[T]) -> Result<T> {
+        if discount_factors.len() != self.dates.len() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Expected {} discount factors, got {}",
+                self.dates.len(),
+                discount_factors.len()
+            )));
+        }
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(
+                "Date needs to be greater than reference date".to_string(),
+            ));
+        }
+        if date == self.reference_date() {
+            return Ok(T::from_f64(1.0));
+        }
+        let year_fraction = self
+            .day_counter()
+            .year_fraction(self.reference_date(), date);
+        self.interpolator.interpolate_discount_factor_with(
+            year_fraction,
+            &self.year_fractions,
+            discount_factors,
+            self.enable_extrapolation,
+        )
+    }
 }
 
 impl HasReferenceDate for DiscountTermStructure {
@@ -177,12 +213,12 @@ impl YieldProvider for DiscountTermStructure {
             .day_counter()
             .year_fraction(self.reference_date(), date);
 
-        let discount_factor = self.interpolator.interpolate(
+        let discount_factor = self.interpolator.interpolate_discount_factor(
             year_fraction,
             &self.year_fractions,
             &self.discount_factors,
             self.enable_extrapolation,
-        );
+        )?;
         Ok(discount_factor)
     }
 
@@ -373,6 +409,46 @@ mod tests {
         assert!((df - 0.9832967032967033).abs() < 1e-8);
     }
 
+    #[test]
+    fn test_interpolation_errors() {
+        let dates = vec![
+            Date::new(2020, 1, 1),
+            Date::new(2020, 7, 1),
+            Date::new(2021, 1, 1),
+        ];
+        let discount_term_structure = DiscountTermStructure::new(
+            dates,
+            vec![1.0, 0.98, 0.96],
+            DayCounter::Actual360,
+            Interpolator::LogLinear,
+            false,
+        )
+        .unwrap_or_else(|e| panic!(
+            "DiscountTermStructure::new should succeed in test_interpolation_errors: {e}"
+        ));
+
+        // out of range dates are reported instead of panicking
+        let result = discount_term_structure.discount_factor(Date::new(2022, 1, 1));
+        assert!(matches!(result, Err(AtlasError::OutOfRangeErr(_))));
+        let result = discount_term_structure.forward_rate(
+            Date::new(2020, 6, 1),
+            Date::new(2022, 1, 1),
+            Compounding::Simple,
+            Frequency::Annual,
+        );
+        assert!(matches!(result, Err(AtlasError::OutOfRangeErr(_))));
+
+        // non-positive discount factors are rejected once, when the curve is built
+        let result = DiscountTermStructure::new(
+            vec![Date::new(2020, 1, 1), Date::new(2021, 1, 1), Date::new(2022, 1, 1)],
+            vec![1.0, 0.98, -0.1],
+            DayCounter::Actual360,
+            Interpolator::LogLinear,
+            false,
+        );
+        assert!(matches!(result, Err(AtlasError::NonPositiveValueErr(_))));
+    }
+
     #[test]
 
     fn test_forward_rate() {
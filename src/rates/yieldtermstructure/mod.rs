@@ -1,9 +1,17 @@
+/// Bumped term structure module.
+pub mod bumpedtermstructure;
 /// Composite term structure module.
 pub mod compositetermstructure;
 /// Discount term structure module.
 pub mod discounttermstructure;
 /// Flat forward term structure module.
 pub mod flatforwardtermstructure;
+/// Nelson-Siegel term structure module.
+pub mod nelsonsiegeltermstructure;
+/// Smith-Wilson term structure module.
+pub mod smithwilsontermstructure;
+/// Nelson-Siegel-Svensson term structure module.
+pub mod svenssontermstructure;
 /// Tenor-based zero rate term structure module.
 pub mod tenorbasedzeroratetermstructure;
 /// Traits module.
@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{
+        enums::Compounding,
+        interestrate::InterestRate,
+        traits::{HasReferenceDate, YieldProvider},
+    },
+    time::{date::Date, daycounter::DayCounter, enums::Frequency, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{AdvanceTermStructureInTime, YieldTermStructureTrait};
+
+/// Returns the Nelson-Siegel loadings `(1 - e^{-t/tau}) / (t/tau)` and that value minus
+/// `e^{-t/tau}`, i.e. the slope and curvature factors at time `t`.
+pub(crate) fn nelson_siegel_loadings(t: f64, tau: f64) -> (f64, f64) {
+    let x = t / tau;
+    if x < 1e-10 {
+        // limits as t -> 0
+        return (0.5f64.mul_add(-x, 1.0), 0.5 * x);
+    }
+    let decay = (-x).exp();
+    let slope = -(-x).exp_m1() / x;
+    (slope, slope - decay)
+}
+
+/// # `NelsonSiegelTermStructure`
+/// Parametric term structure with continuously compounded zero rates
+///
+/// `z(t) = b0 + b1 * (1 - e^{-t/tau}) / (t/tau) + b2 * ((1 - e^{-t/tau}) / (t/tau) - e^{-t/tau})`
+///
+/// where `b0` is the long term level, `b0 + b1` the instantaneous short rate, `b2` the size
+/// of the hump and `tau` its position in years. The parameters are usually obtained with a
+/// `NelsonSiegelFitter`.
+///
+/// When advanced in time the curve keeps its parameters and returns the forwards implied
+/// from the new reference date.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the curve
+/// * `parameters` - The parameters `[b0, b1, b2, tau]`
+/// * `day_counter` - The day counter used to measure `t`
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let reference_date = Date::new(2024, 1, 2);
+/// let curve = NelsonSiegelTermStructure::new(
+///     reference_date,
+///     [0.04, -0.01, 0.005, 1.5],
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+/// assert!((curve.zero_rate(0.0) - 0.03).abs() < 1e-12);
+/// assert!(curve.discount_factor(Date::new(2029, 1, 2)).unwrap() < 1.0);
+/// ```
+#[derive(Clone, Copy)]
+pub struct NelsonSiegelTermStructure {
+    reference_date: Date,
+    parameters_date: Date,
+    parameters: [f64; 4],
+    day_counter: DayCounter,
+}
+
+impl NelsonSiegelTermStructure {
+    /// Creates a new `NelsonSiegelTermStructure` with parameters `[b0, b1, b2, tau]`.
+    ///
+    /// # Errors
+    /// Returns an error if `tau` is not positive or a parameter is not finite.
+    pub fn new(
+        reference_date: Date,
+        parameters: [f64; 4],
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if parameters.iter().any(|p| !p.is_finite()) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Nelson-Siegel parameters must be finite, got {parameters:?}"
+            )));
+        }
+        if parameters[3] <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Nelson-Siegel tau must be positive, got {}",
+                parameters[3]
+            )));
+        }
+        Ok(Self {
+            reference_date,
+            parameters_date: reference_date,
+            parameters,
+            day_counter,
+        })
+    }
+
+    /// Returns the parameters `[b0, b1, b2, tau]`.
+    #[must_use]
+    pub const fn parameters(&self) -> [f64; 4] {
+        self.parameters
+    }
+
+    /// Returns the day counter of the curve.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+
+    /// Returns the continuously compounded zero rate of the parametric form at time `t`,
+    /// measured in years from the date the parameters were set.
+    #[must_use]
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        let [b0, b1, b2, tau] = self.parameters;
+        let (slope, curvature) = nelson_siegel_loadings(t, tau);
+        b2.mul_add(curvature, b1.mul_add(slope, b0))
+    }
+
+    fn parametric_discount_factor(&self, date: Date) -> f64 {
+        let t = self.day_counter.year_fraction(self.parameters_date, date);
+        (-self.zero_rate(t) * t).exp()
+    }
+}
+
+impl HasReferenceDate for NelsonSiegelTermStructure {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl YieldProvider for NelsonSiegelTermStructure {
+    fn discount_factor(&self, date: Date) -> Result<f64> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        Ok(self.parametric_discount_factor(date)
+            / self.parametric_discount_factor(self.reference_date))
+    }
+
+    fn forward_rate(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        comp: Compounding,
+        freq: Frequency,
+    ) -> Result<f64> {
+        let comp_factor = self.discount_factor(start_date)? / self.discount_factor(end_date)?;
+        let t = self.day_counter.year_fraction(start_date, end_date);
+        Ok(InterestRate::implied_rate(comp_factor, self.day_counter, comp, freq, t)?.rate())
+    }
+}
+
+/// # `AdvanceTermStructureInTime` for `NelsonSiegelTermStructure`
+impl AdvanceTermStructureInTime for NelsonSiegelTermStructure {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        let date = self
+            .reference_date()
+            .advance(period.length(), period.units());
+        self.advance_to_date(date)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        Ok(Arc::new(Self {
+            reference_date: date,
+            ..*self
+        }))
+    }
+}
+
+impl YieldTermStructureTrait for NelsonSiegelTermStructure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::enums::TimeUnit;
+
+    #[test]
+    fn test_limits() -> Result<()> {
+        let curve = NelsonSiegelTermStructure::new(
+            Date::new(2024, 1, 2),
+            [0.04, -0.015, 0.01, 2.0],
+            DayCounter::Actual365,
+        )?;
+        // short end tends to b0 + b1 and long end to b0
+        assert!((curve.zero_rate(0.0) - 0.025).abs() < 1e-12);
+        assert!((curve.zero_rate(1e-6) - 0.025).abs() < 1e-8);
+        assert!((curve.zero_rate(1000.0) - 0.04).abs() < 1e-4);
+
+        assert!(NelsonSiegelTermStructure::new(
+            Date::new(2024, 1, 2),
+            [0.04, -0.015, 0.01, 0.0],
+            DayCounter::Actual365,
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_advance_keeps_forwards() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let curve = NelsonSiegelTermStructure::new(
+            reference_date,
+            [0.04, -0.015, 0.01, 2.0],
+            DayCounter::Actual365,
+        )?;
+        let advanced = curve.advance_to_period(Period::new(1, TimeUnit::Years))?;
+        assert_eq!(advanced.reference_date(), Date::new(2025, 1, 2));
+
+        let (start, end) = (Date::new(2026, 1, 2), Date::new(2027, 1, 2));
+        let expected = curve.forward_rate(start, end, Compounding::Simple, Frequency::Annual)?;
+        let forward = advanced.forward_rate(start, end, Compounding::Simple, Frequency::Annual)?;
+        assert!((forward - expected).abs() < 1e-12);
+        assert!((advanced.discount_factor(Date::new(2025, 1, 2))? - 1.0).abs() < 1e-14);
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{
+        enums::Compounding,
+        interestrate::InterestRate,
+        traits::{HasReferenceDate, YieldProvider},
+    },
+    time::{date::Date, daycounter::DayCounter, enums::Frequency, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{AdvanceTermStructureInTime, YieldTermStructureTrait};
+
+/// Smith-Wilson kernel `W(t, u)` for a continuous ultimate forward intensity `omega` and
+/// convergence speed `alpha`.
+pub(crate) fn wilson_function(t: f64, u: f64, omega: f64, alpha: f64) -> f64 {
+    let (min, max) = if t < u { (t, u) } else { (u, t) };
+    (-omega * (t + u)).exp()
+        * (-alpha * max)
+            .exp()
+            .mul_add(-(alpha * min).sinh(), alpha * min)
+}
+
+/// # `SmithWilsonTermStructure`
+/// Smith-Wilson term structure as used for regulatory (Solvency II) extrapolation. Discount
+/// factors are
+///
+/// `P(t) = e^{-omega t} + sum_j w_j W(t, u_j)`
+///
+/// with `omega = ln(1 + ufr)`, `W` the Wilson kernel and `u_j` the cashflow times of the
+/// instruments the curve was calibrated to. Beyond the last liquid point the forwards
+/// converge to the ultimate forward rate at a pace set by the convergence speed `alpha`.
+/// The node weights are usually obtained with a `SmithWilsonFitter`.
+///
+/// When advanced in time the curve keeps its calibration and returns the forwards implied
+/// from the new reference date.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the curve
+/// * `ultimate_forward_rate` - The annually compounded ultimate forward rate
+/// * `convergence_speed` - The convergence speed `alpha`
+/// * `nodes` - The node times `u_j` in years
+/// * `weights` - The node weights `w_j`
+/// * `day_counter` - The day counter used to measure times
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// // without nodes the curve is flat at the ultimate forward rate
+/// let curve = SmithWilsonTermStructure::new(
+///     Date::new(2023, 1, 1),
+///     0.036,
+///     0.1,
+///     Vec::new(),
+///     Vec::new(),
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+/// let df = curve.discount_factor(Date::new(2024, 1, 1)).unwrap();
+/// assert!((df - 1.0 / 1.036).abs() < 1e-12);
+/// ```
+#[derive(Clone)]
+pub struct SmithWilsonTermStructure {
+    reference_date: Date,
+    parameters_date: Date,
+    ultimate_forward_rate: f64,
+    convergence_speed: f64,
+    nodes: Vec<f64>,
+    weights: Vec<f64>,
+    day_counter: DayCounter,
+}
+
+impl SmithWilsonTermStructure {
+    /// Creates a new `SmithWilsonTermStructure`.
+    ///
+    /// # Errors
+    /// Returns an error if nodes and weights have different lengths, if the convergence
+    /// speed is not positive or if the ultimate forward rate is not above -100%.
+    pub fn new(
+        reference_date: Date,
+        ultimate_forward_rate: f64,
+        convergence_speed: f64,
+        nodes: Vec<f64>,
+        weights: Vec<f64>,
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if nodes.len() != weights.len() {
+            return Err(AtlasError::InvalidValueErr(
+                "Nodes and weights need to have the same size".to_string(),
+            ));
+        }
+        if convergence_speed <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Convergence speed must be positive, got {convergence_speed}"
+            )));
+        }
+        if ultimate_forward_rate <= -1.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Ultimate forward rate must be above -100%, got {ultimate_forward_rate}"
+            )));
+        }
+        Ok(Self {
+            reference_date,
+            parameters_date: reference_date,
+            ultimate_forward_rate,
+            convergence_speed,
+            nodes,
+            weights,
+            day_counter,
+        })
+    }
+
+    /// Returns the annually compounded ultimate forward rate.
+    #[must_use]
+    pub const fn ultimate_forward_rate(&self) -> f64 {
+        self.ultimate_forward_rate
+    }
+
+    /// Returns the convergence speed `alpha`.
+    #[must_use]
+    pub const fn convergence_speed(&self) -> f64 {
+        self.convergence_speed
+    }
+
+    /// Returns the node times in years.
+    #[must_use]
+    pub const fn nodes(&self) -> &Vec<f64> {
+        &self.nodes
+    }
+
+    /// Returns the node weights.
+    #[must_use]
+    pub const fn weights(&self) -> &Vec<f64> {
+        &self.weights
+    }
+
+    /// Returns the day counter of the curve.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+
+    /// Returns the discount factor of the calibrated curve at time `t`, measured in years
+    /// from the date the curve was calibrated.
+    #[must_use]
+    pub fn discount_factor_from_yf(&self, t: f64) -> f64 {
+        let omega = self.ultimate_forward_rate.ln_1p();
+        self.nodes
+            .iter()
+            .zip(&self.weights)
+            .fold((-omega * t).exp(), |acc, (u, w)| {
+                w.mul_add(wilson_function(t, *u, omega, self.convergence_speed), acc)
+            })
+    }
+
+    fn parametric_discount_factor(&self, date: Date) -> f64 {
+        self.discount_factor_from_yf(self.day_counter.year_fraction(self.parameters_date, date))
+    }
+}
+
+impl HasReferenceDate for SmithWilsonTermStructure {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl YieldProvider for SmithWilsonTermStructure {
+    fn discount_factor(&self, date: Date) -> Result<f64> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        Ok(self.parametric_discount_factor(date)
+            / self.parametric_discount_factor(self.reference_date))
+    }
+
+    fn forward_rate(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        comp: Compounding,
+        freq: Frequency,
+    ) -> Result<f64> {
+        let comp_factor = self.discount_factor(start_date)? / self.discount_factor(end_date)?;
+        let t = self.day_counter.year_fraction(start_date, end_date);
+        Ok(InterestRate::implied_rate(comp_factor, self.day_counter, comp, freq, t)?.rate())
+    }
+}
+
+/// # `AdvanceTermStructureInTime` for `SmithWilsonTermStructure`
+impl AdvanceTermStructureInTime for SmithWilsonTermStructure {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        let date = self
+            .reference_date()
+            .advance(period.length(), period.units());
+        self.advance_to_date(date)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        let mut curve = self.clone();
+        curve.reference_date = date;
+        Ok(Arc::new(curve))
+    }
+}
+
+impl YieldTermStructureTrait for SmithWilsonTermStructure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::enums::TimeUnit;
+
+    #[test]
+    fn test_kernel_symmetry() {
+        let (omega, alpha) = (0.035_f64.ln_1p(), 0.1);
+        let w = wilson_function(3.0, 7.0, omega, alpha);
+        assert!((w - wilson_function(7.0, 3.0, omega, alpha)).abs() < 1e-16);
+        assert!(wilson_function(0.0, 7.0, omega, alpha).abs() < 1e-16);
+    }
+
+    #[test]
+    fn test_converges_to_ufr() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 1);
+        let curve = SmithWilsonTermStructure::new(
+            reference_date,
+            0.036,
+            0.15,
+            vec![1.0, 5.0, 10.0],
+            vec![0.3, -0.1, 0.05],
+            DayCounter::Actual365,
+        )?;
+        let start = reference_date + Period::new(120, TimeUnit::Years);
+        let end = reference_date + Period::new(121, TimeUnit::Years);
+        let forward = curve.forward_rate(start, end, Compounding::Compounded, Frequency::Annual)?;
+        assert!((forward - 0.036).abs() < 1e-4);
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use crate::{
+    rates::{
+        enums::Compounding,
+        interestrate::InterestRate,
+        traits::{HasReferenceDate, YieldProvider},
+    },
+    time::{date::Date, daycounter::DayCounter, enums::Frequency, period::Period},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    nelsonsiegeltermstructure::nelson_siegel_loadings,
+    traits::{AdvanceTermStructureInTime, YieldTermStructureTrait},
+};
+
+/// # `SvenssonTermStructure`
+/// Nelson-Siegel-Svensson parametric term structure. It extends the Nelson-Siegel form with
+/// a second hump, so the continuously compounded zero rates are
+///
+/// `z(t) = b0 + b1 * L(t, tau1) + b2 * C(t, tau1) + b3 * C(t, tau2)`
+///
+/// with `L(t, tau) = (1 - e^{-t/tau}) / (t/tau)` and `C(t, tau) = L(t, tau) - e^{-t/tau}`.
+/// The parameters are usually obtained with a `SvenssonFitter`.
+///
+/// When advanced in time the curve keeps its parameters and returns the forwards implied
+/// from the new reference date.
+///
+/// ## Parameters
+/// * `reference_date` - The reference date of the curve
+/// * `parameters` - The parameters `[b0, b1, b2, b3, tau1, tau2]`
+/// * `day_counter` - The day counter used to measure `t`
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let curve = SvenssonTermStructure::new(
+///     Date::new(2024, 1, 2),
+///     [0.04, -0.01, 0.005, -0.004, 1.5, 8.0],
+///     DayCounter::Actual365,
+/// )
+/// .unwrap();
+/// assert!((curve.zero_rate(0.0) - 0.03).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy)]
+pub struct SvenssonTermStructure {
+    reference_date: Date,
+    parameters_date: Date,
+    parameters: [f64; 6],
+    day_counter: DayCounter,
+}
+
+impl SvenssonTermStructure {
+    /// Creates a new `SvenssonTermStructure` with parameters `[b0, b1, b2, b3, tau1, tau2]`.
+    ///
+    /// # Errors
+    /// Returns an error if `tau1` or `tau2` is not positive or a parameter is not finite.
+    pub fn new(
+        reference_date: Date,
+        parameters: [f64; 6],
+        day_counter: DayCounter,
+    ) -> Result<Self> {
+        if parameters.iter().any(|p| !p.is_finite()) {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Svensson parameters must be finite, got {parameters:?}"
+            )));
+        }
+        if parameters[4] <= 0.0 || parameters[5] <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Svensson tau1 and tau2 must be positive, got {} and {}",
+                parameters[4], parameters[5]
+            )));
+        }
+        Ok(Self {
+            reference_date,
+            parameters_date: reference_date,
+            parameters,
+            day_counter,
+        })
+    }
+
+    /// Returns the parameters `[b0, b1, b2, b3, tau1, tau2]`.
+    #[must_use]
+    pub const fn parameters(&self) -> [f64; 6] {
+        self.parameters
+    }
+
+    /// Returns the day counter of the curve.
+    #[must_use]
+    pub const fn day_counter(&self) -> DayCounter {
+        self.day_counter
+    }
+
+    /// Returns the continuously compounded zero rate of the parametric form at time `t`,
+    /// measured in years from the date the parameters were set.
+    #[must_use]
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        let [b0, b1, b2, b3, tau1, tau2] = self.parameters;
+        let (slope, curvature) = nelson_siegel_loadings(t, tau1);
+        let (_, second_curvature) = nelson_siegel_loadings(t, tau2);
+        b3.mul_add(
+            second_curvature,
+            b2.mul_add(curvature, b1.mul_add(slope, b0)),
+        )
+    }
+
+    fn parametric_discount_factor(&self, date: Date) -> f64 {
+        let t = self.day_counter.year_fraction(self.parameters_date, date);
+        (-self.zero_rate(t) * t).exp()
+    }
+}
+
+impl HasReferenceDate for SvenssonTermStructure {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+}
+
+impl YieldProvider for SvenssonTermStructure {
+    fn discount_factor(&self, date: Date) -> Result<f64> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        Ok(self.parametric_discount_factor(date)
+            / self.parametric_discount_factor(self.reference_date))
+    }
+
+    fn forward_rate(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        comp: Compounding,
+        freq: Frequency,
+    ) -> Result<f64> {
+        let comp_factor = self.discount_factor(start_date)? / self.discount_factor(end_date)?;
+        let t = self.day_counter.year_fraction(start_date, end_date);
+        Ok(InterestRate::implied_rate(comp_factor, self.day_counter, comp, freq, t)?.rate())
+    }
+}
+
+/// # `AdvanceTermStructureInTime` for `SvenssonTermStructure`
+impl AdvanceTermStructureInTime for SvenssonTermStructure {
+    fn advance_to_period(&self, period: Period) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        let date = self
+            .reference_date()
+            .advance(period.length(), period.units());
+        self.advance_to_date(date)
+    }
+
+    fn advance_to_date(&self, date: Date) -> Result<Arc<dyn YieldTermStructureTrait>> {
+        if date < self.reference_date() {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Date {date:?} is before reference date {reference_date:?}",
+                reference_date = self.reference_date()
+            )));
+        }
+        Ok(Arc::new(Self {
+            reference_date: date,
+            ..*self
+        }))
+    }
+}
+
+impl YieldTermStructureTrait for SvenssonTermStructure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rates::yieldtermstructure::nelsonsiegeltermstructure::NelsonSiegelTermStructure;
+
+    #[test]
+    fn test_reduces_to_nelson_siegel() -> Result<()> {
+        let reference_date = Date::new(2024, 1, 2);
+        let svensson = SvenssonTermStructure::new(
+            reference_date,
+            [0.04, -0.015, 0.01, 0.0, 2.0, 8.0],
+            DayCounter::Actual365,
+        )?;
+        let nelson_siegel = NelsonSiegelTermStructure::new(
+            reference_date,
+            [0.04, -0.015, 0.01, 2.0],
+            DayCounter::Actual365,
+        )?;
+        let date = Date::new(2031, 7, 2);
+        assert!(
+            (svensson.discount_factor(date)? - nelson_siegel.discount_factor(date)?).abs() < 1e-14
+        );
+        Ok(())
+    }
+}
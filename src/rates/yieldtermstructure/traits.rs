@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{panic::RefUnwindSafe, sync::Arc};
 
 use crate::{
     rates::traits::{HasReferenceDate, YieldProvider},
@@ -54,9 +54,11 @@ pub trait AdvanceTermStructureInTime {
 /// - `HasReferenceDate`
 /// - `AdvanceTermStructureInTime`
 /// - Send
+/// - `RefUnwindSafe`
 ///
-/// Send is required to be able to send the trait to another thread.
+/// Send is required to be able to send the trait to another thread. `RefUnwindSafe` keeps
+/// stores that hold curves directly, such as FX forward curves, usable across `catch_unwind`.
 pub trait YieldTermStructureTrait:
-    YieldProvider + HasReferenceDate + AdvanceTermStructureInTime + Send + Sync
+    YieldProvider + HasReferenceDate + AdvanceTermStructureInTime + Send + Sync + RefUnwindSafe
 {
 }
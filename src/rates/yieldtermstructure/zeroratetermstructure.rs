@@ -65,7 +65,8 @@ impl ZeroRateTermStructure {
     ///
     /// # Errors
     ///
-    /// Returns an error if dates and rates have different lengths or if the first date is not the reference date.
+    /// Returns an error if dates and rates have different lengths, if the first date is not the reference date or if
+    /// the interpolator cannot interpolate the rates.
     pub fn new(
         reference_date: Date,
         dates: Vec<Date>,
@@ -87,6 +88,9 @@ impl ZeroRateTermStructure {
                 "First date needs to be equal to reference date".to_string(),
             ));
         }
+        if !interpolator.is_forward_based() {
+            interpolator.check_data(&rates)?;
+        }
 
         let year_fractions: Vec<f64> = dates
             .iter()
@@ -152,12 +156,31 @@ impl YieldProvider for ZeroRateTermStructure {
             .day_counter()
             .year_fraction(self.reference_date(), date);
 
+        // forward-based interpolators work on the node discount factors
+        if self.interpolator.is_forward_based() {
+            let discount_factors: Vec<f64> = self
+                .year_fractions
+                .iter()
+                .zip(&self.rates)
+                .map(|(yf, rate)| {
+                    1.0 / InterestRate::from_rate_definition(*rate, self.rate_definition())
+                        .compound_factor_from_yf(*yf)
+                })
+                .collect();
+            return self.interpolator.interpolate_discount_factor(
+                year_fraction,
+                &self.year_fractions,
+                &discount_factors,
+                self.enable_extrapolation,
+            );
+        }
+
         let rate = self.interpolator.interpolate(
             year_fraction,
             &self.year_fractions,
             &self.rates,
             self.enable_extrapolation,
-        );
+        )?;
         let rt = InterestRate::from_rate_definition(rate, self.rate_definition());
         let compound = rt.compound_factor_from_yf(year_fraction);
         Ok(1.0 / compound)
@@ -328,4 +351,60 @@ mod tests {
         println!("fr: {fr:?}");
         assert!((fr - 0.02972519115024655).abs() < 0.000000001);
     }
+
+    #[test]
+    fn test_forward_based_interpolators() -> Result<()> {
+        let reference_date = Date::new(2020, 1, 1);
+        let dates = vec![
+            Date::new(2020, 1, 1),
+            Date::new(2021, 1, 1),
+            Date::new(2022, 1, 1),
+            Date::new(2025, 1, 1),
+        ];
+        let rates = vec![0.0, 0.02, 0.025, 0.03];
+        let rate_definition =
+            RateDefinition::new(DayCounter::Actual365, Compounding::Continuous, Frequency::Annual);
+
+        for interpolator in [Interpolator::FlatForward, Interpolator::MonotoneConvex] {
+            let curve = ZeroRateTermStructure::new(
+                reference_date,
+                dates.clone(),
+                rates.clone(),
+                rate_definition,
+                interpolator,
+                true,
+            )?;
+
+            // node zero rates are preserved
+            for (date, rate) in dates.iter().zip(&rates).skip(1) {
+                let t = DayCounter::Actual365.year_fraction(reference_date, *date);
+                let df = curve.discount_factor(*date)?;
+                assert!((df - (-rate * t).exp()).abs() < 1e-12);
+            }
+        }
+
+        // flat forward keeps the instantaneous forward constant between nodes
+        let curve = ZeroRateTermStructure::new(
+            reference_date,
+            dates,
+            rates,
+            rate_definition,
+            Interpolator::FlatForward,
+            true,
+        )?;
+        let first = curve.forward_rate(
+            Date::new(2022, 2, 1),
+            Date::new(2022, 3, 1),
+            Compounding::Continuous,
+            Frequency::Annual,
+        )?;
+        let second = curve.forward_rate(
+            Date::new(2024, 6, 1),
+            Date::new(2024, 7, 1),
+            Compounding::Continuous,
+            Frequency::Annual,
+        )?;
+        assert!((first - second).abs() < 1e-10);
+        Ok(())
+    }
 }
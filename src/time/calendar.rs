@@ -1,12 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::calendars::{
-    brazil::Brazil,
-    chile::Chile,
+    brazil::{self, Brazil},
+    chile::{self, Chile},
     nullcalendar::NullCalendar,
     target::TARGET,
     traits::{ImplCalendar, IsCalendar},
-    unitedstates::UnitedStates,
+    unitedstates::{self, UnitedStates},
     weekendsonly::WeekendsOnly,
 };
 use crate::{
@@ -197,6 +197,61 @@ impl ImplCalendar for Calendar {
 
 impl IsCalendar for Calendar {}
 
+/// # `CalendarId`
+/// A lightweight identifier of a `Calendar` and its market, which is `Copy` and can be stored
+/// in cashflows. Holidays added to or removed from a calendar are not part of its id, so the
+/// calendar built back from it only has the holidays of its market.
+///
+/// ## Example
+/// ```
+/// use rustatlas::prelude::*;
+///
+/// let calendar = Calendar::UnitedStates(UnitedStates::default());
+/// let id = CalendarId::from(&calendar);
+/// assert_eq!(Calendar::from(id), calendar);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CalendarId {
+    /// A null calendar that considers all days as business days.
+    NullCalendar,
+    /// A calendar that considers only weekends as non-business days.
+    WeekendsOnly,
+    /// TARGET Eurosystem calendar for business days.
+    TARGET,
+    /// A calendar for a United States market.
+    UnitedStates(unitedstates::Market),
+    /// A calendar for a Brazilian market.
+    Brazil(brazil::Market),
+    /// A calendar for a Chilean market.
+    Chile(chile::Market),
+}
+
+impl From<&Calendar> for CalendarId {
+    fn from(calendar: &Calendar) -> Self {
+        match calendar {
+            Calendar::NullCalendar(_) => Self::NullCalendar,
+            Calendar::WeekendsOnly(_) => Self::WeekendsOnly,
+            Calendar::TARGET(_) => Self::TARGET,
+            Calendar::UnitedStates(cal) => Self::UnitedStates(cal.market()),
+            Calendar::Brazil(cal) => Self::Brazil(cal.market()),
+            Calendar::Chile(cal) => Self::Chile(cal.market()),
+        }
+    }
+}
+
+impl From<CalendarId> for Calendar {
+    fn from(id: CalendarId) -> Self {
+        match id {
+            CalendarId::NullCalendar => Self::NullCalendar(NullCalendar::new()),
+            CalendarId::WeekendsOnly => Self::WeekendsOnly(WeekendsOnly::new()),
+            CalendarId::TARGET => Self::TARGET(TARGET::new()),
+            CalendarId::UnitedStates(market) => Self::UnitedStates(UnitedStates::new(market)),
+            CalendarId::Brazil(market) => Self::Brazil(Brazil::new(market)),
+            CalendarId::Chile(market) => Self::Chile(Chile::new(market)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::time::{
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
 
 use super::traits::{easter_monday, ImplCalendar, IsCalendar};
 use crate::time::date::Date;
@@ -9,7 +10,7 @@ use crate::time::date::Date;
 /// A calendar for Brazil
 ///
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Market {
     /// Settlement market type.
     Settlement,
@@ -36,6 +37,12 @@ impl Brazil {
         }
     }
 
+    /// Returns the market of the calendar.
+    #[must_use]
+    pub const fn market(&self) -> Market {
+        self.market
+    }
+
     fn is_weekend(day: Weekday) -> bool {
         day == Weekday::Sat || day == Weekday::Sun
     }
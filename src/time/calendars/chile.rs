@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
 
 use super::traits::{easter_monday, ImplCalendar, IsCalendar};
 use crate::time::date::Date;
@@ -9,7 +10,7 @@ use crate::time::date::Date;
 /// A calendar for Chile
 ///
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Market {
     /// Santiago Stock Exchange
     SSE,
@@ -34,6 +35,12 @@ impl Chile {
         }
     }
 
+    /// Returns the market of the calendar.
+    #[must_use]
+    pub const fn market(&self) -> Market {
+        self.market
+    }
+
     fn is_weekend(day: Weekday) -> bool {
         day == Weekday::Sat || day == Weekday::Sun
     }
@@ -69,10 +76,7 @@ impl Chile {
         day == 21 && month == 6 && year >= 2021
     }
 
-    fn is_saint_peter_and_saint_paul_day(day: u32, month: u32) -> bool {
-        let w = NaiveDate::from_ymd_opt(2001, month, day)
-            .unwrap_or_else(|| panic!("valid date for Saint Peter and Saint Paul day rules"))
-            .weekday();
+    fn is_saint_peter_and_saint_paul_day(day: u32, month: u32, w: Weekday) -> bool {
         (26..=29).contains(&day) && month == 6 && w == Weekday::Mon
             || day == 2 && month == 7 && w == Weekday::Mon
     }
@@ -85,27 +89,18 @@ impl Chile {
         day == 15 && month == 8
     }
 
-    fn is_independence_day(day: u32, month: u32, year: i32) -> bool {
-        let w = NaiveDate::from_ymd_opt(1810, month, day)
-            .unwrap_or_else(|| panic!("valid date for Independence Day rules"))
-            .weekday();
+    fn is_independence_day(day: u32, month: u32, year: i32, w: Weekday) -> bool {
         (day == 17
             && month == 9
             && ((w == Weekday::Mon && year >= 2007) || (w == Weekday::Fri && year >= 2016)))
             || (day == 18 && month == 9)
     }
 
-    fn is_army_day(day: u32, month: u32, year: i32) -> bool {
-        let w = NaiveDate::from_ymd_opt(1810, month, day)
-            .unwrap_or_else(|| panic!("valid date for Army Day rules"))
-            .weekday();
+    fn is_army_day(day: u32, month: u32, year: i32, w: Weekday) -> bool {
         (day == 19 && month == 9) || (day == 20 && month == 9 && w == Weekday::Fri && year >= 2007)
     }
 
-    fn is_discovery_of_two_worlds(day: u32, month: u32) -> bool {
-        let w = NaiveDate::from_ymd_opt(1492, month, day)
-            .unwrap_or_else(|| panic!("valid date for Discovery of Two Worlds rules"))
-            .weekday();
+    fn is_discovery_of_two_worlds(day: u32, month: u32, w: Weekday) -> bool {
         !(month != 10 || w != Weekday::Mon || !(9..=12).contains(&day) && day != 15)
     }
 
@@ -154,12 +149,12 @@ impl Chile {
                     || Self::is_labour_day(day, month)
                     || Self::is_navy_day(day, month)
                     || Self::is_aboriginal_peoples_day(day, month, year)
-                    || Self::is_saint_peter_and_saint_paul_day(day, month)
+                    || Self::is_saint_peter_and_saint_paul_day(day, month, weekday)
                     || Self::is_our_lady_of_mount_carmel_day(day, month)
                     || Self::is_assumption_day(day, month)
-                    || Self::is_independence_day(day, month, year)
-                    || Self::is_army_day(day, month, year)
-                    || Self::is_discovery_of_two_worlds(day, month)
+                    || Self::is_independence_day(day, month, year, weekday)
+                    || Self::is_army_day(day, month, year, weekday)
+                    || Self::is_discovery_of_two_worlds(day, month, weekday)
                     || Self::is_reformation_day(day, month, year)
                     || Self::is_all_saints_day(day, month)
                     || Self::is_immaculate_conception(day, month)
@@ -266,4 +261,26 @@ mod tests {
             assert!(!cal.is_business_day(d.base_date()));
         }
     }
+
+    #[test]
+    fn test_chile_moving_holidays() {
+        let cal = Chile::new(Market::SSE);
+        let expected_hol = vec![
+            Date::new(2018, 9, 17),
+            Date::new(2024, 9, 20),
+            Date::new(2026, 6, 29),
+            Date::new(2026, 10, 12),
+        ];
+        for d in expected_hol {
+            assert!(!cal.is_business_day(d.base_date()));
+        }
+        let expected_bd = vec![
+            Date::new(2024, 9, 17),
+            Date::new(2024, 10, 14),
+            Date::new(2026, 9, 17),
+        ];
+        for d in expected_bd {
+            assert!(cal.is_business_day(d.base_date()));
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
 
 use crate::time::date::Date;
 
@@ -8,7 +9,7 @@ use super::traits::{ImplCalendar, IsCalendar};
 
 /// # Market
 /// Defines the relevant market for the United States calendar.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Market {
     /// Settlement market.
     Settlement,
@@ -46,6 +47,12 @@ impl UnitedStates {
         }
     }
 
+    /// Returns the market of the calendar.
+    #[must_use]
+    pub const fn market(&self) -> Market {
+        self.market
+    }
+
     fn is_weekend(day: Weekday) -> bool {
         day == Weekday::Sat || day == Weekday::Sun
     }
@@ -110,11 +117,11 @@ impl UnitedStates {
             | Market::Nerc
             | Market::FederalReserve
             | Market::Sofr => {
-                Self::is_washington_birthday(day, month, year, weekday)
+                !(Self::is_washington_birthday(day, month, year, weekday)
                     || Self::is_memorial_day(day, month, year, weekday)
                     || Self::is_independence_day(day, month, weekday)
                     || Self::is_thanksgiving(day, month, weekday)
-                    || Self::is_christmas(day, month, weekday)
+                    || Self::is_christmas(day, month, weekday))
             }
         }
     }
@@ -183,3 +190,41 @@ impl Default for UnitedStates {
         Self::new(Market::Sofr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::date::Date;
+
+    #[test]
+    fn test_united_states_holidays() {
+        for market in [
+            Market::Settlement,
+            Market::Nerc,
+            Market::FederalReserve,
+            Market::Sofr,
+        ] {
+            let cal = UnitedStates::new(market);
+            let expected_hol = vec![
+                Date::new(2024, 2, 19),
+                Date::new(2024, 5, 27),
+                Date::new(2024, 7, 4),
+                Date::new(2024, 11, 28),
+                Date::new(2024, 12, 25),
+                Date::new(2021, 12, 24),
+            ];
+            for d in expected_hol {
+                assert!(!cal.is_business_day(d.base_date()));
+            }
+            let expected_bd = vec![
+                Date::new(2024, 7, 5),
+                Date::new(2024, 11, 27),
+                Date::new(2024, 11, 29),
+                Date::new(2024, 12, 26),
+            ];
+            for d in expected_bd {
+                assert!(cal.is_business_day(d.base_date()));
+            }
+        }
+    }
+}
@@ -34,6 +34,15 @@ pub enum AtlasError {
     /// Error indicating that a provided value is invalid.
     #[error("Invalid value error: {0}")]
     InvalidValueErr(String),
+    /// Error indicating that a point lies outside the data range and extrapolation is disabled.
+    #[error("Out of range error: {0}")]
+    OutOfRangeErr(String),
+    /// Error indicating that there are not enough data points to perform an operation.
+    #[error("Insufficient points error: {0}")]
+    InsufficientPointsErr(String),
+    /// Error indicating that a value that must be strictly positive is not.
+    #[error("Non positive value error: {0}")]
+    NonPositiveValueErr(String),
     /// Error that occurs during solver operations.
     #[error("Solver error: {0}")]
     SolverErr(#[from] argmin::core::Error),
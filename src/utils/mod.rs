@@ -1,4 +1,7 @@
 /// Error types and utilities for the utils module.
 pub mod errors;
+/// Fixtures shared by the unit tests.
+#[cfg(test)]
+pub mod testing;
 /// Tools and helper functions for the utils module.
 pub mod tools;
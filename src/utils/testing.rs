@@ -0,0 +1,49 @@
+use std::sync::{Arc, RwLock};
+
+use crate::{
+    core::marketstore::MarketStore,
+    currencies::enums::Currency,
+    rates::{
+        interestrate::RateDefinition, interestrateindex::iborindex::IborIndex,
+        traits::HasReferenceDate,
+        yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+    },
+    time::date::Date,
+    utils::errors::Result,
+};
+
+/// Adds an Ibor index at `id` whose curve is flat at `rate`, quoted with `rate_definition`,
+/// from the reference date of the market store.
+///
+/// # Errors
+/// Returns an error if an index already exists at `id`.
+pub fn add_flat_index(
+    market_store: &mut MarketStore,
+    id: usize,
+    rate: f64,
+    rate_definition: RateDefinition,
+) -> Result<()> {
+    let reference_date = market_store.reference_date();
+    let curve = Arc::new(FlatForwardTermStructure::new(
+        reference_date,
+        rate,
+        rate_definition,
+    ));
+    market_store.mut_index_store().add_index(
+        id,
+        Arc::new(RwLock::new(
+            IborIndex::new(reference_date).with_term_structure(curve),
+        )),
+    )
+}
+
+/// Returns a USD market store on `reference_date` with an Ibor index at id 0 whose curve is
+/// flat at `rate`, quoted with the default rate definition.
+///
+/// # Errors
+/// Returns an error if the index cannot be added.
+pub fn flat_market_store(reference_date: Date, rate: f64) -> Result<MarketStore> {
+    let mut market_store = MarketStore::new(reference_date, Currency::USD);
+    add_flat_index(&mut market_store, 0, rate, RateDefinition::default())?;
+    Ok(market_store)
+}
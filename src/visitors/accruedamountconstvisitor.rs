@@ -78,7 +78,9 @@ impl<T: HasCurrency + HasCashflows> ConstVisit<T> for AccruedAmountConstVisitor
                     .iter()
                     .try_fold(0.0, |acc, cf| -> Result<f64> {
                         match cf {
-                            Cashflow::FixedRateCoupon(_) | Cashflow::FloatingRateCoupon(_) => {
+                            Cashflow::FixedRateCoupon(_)
+                            | Cashflow::FloatingRateCoupon(_)
+                            | Cashflow::OvernightIndexedCoupon(_) => {
                                 let accrual_start = cf.accrual_start_date()?;
                                 let accrual_end = cf.accrual_end_date()?;
                                 if accrual_start <= end_date && accrual_end >= start_date {
@@ -0,0 +1,341 @@
+use crate::{
+    cashflows::{
+        cashflow::Cashflow,
+        floatingratecoupon::FloatingRateCoupon,
+        traits::{InterestAccrual, Payable},
+    },
+    core::{
+        meta::MarketData,
+        traits::{HasForecastCurveId, Registrable},
+    },
+    instruments::{
+        capfloor::{CapFloor, CapFloorType},
+        floatingrateinstrument::FloatingRateInstrument,
+        instrument::Instrument,
+    },
+    math::optionpricing::{bachelier_formula, black_formula, OptionType},
+    rates::{enums::VolatilityType, volatilitystore::VolatilityStore},
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{ConstVisit, HasCashflows};
+
+/// The undiscounted value of a caplet or floorlet and its payoff at the forward rate.
+#[derive(Default)]
+struct Optionlet {
+    value: f64,
+    intrinsic: f64,
+}
+
+/// # `CapFloorNPVConstVisitor`
+/// `CapFloorNPVConstVisitor` is a visitor that calculates the NPV of instruments with caps and
+/// floors. Each caplet and floorlet is priced with the volatility surface stored under the
+/// forecast curve ID of its coupon, using Black-76 for shifted lognormal volatilities and
+/// Bachelier for normal volatilities, and expires on the fixing date of the coupon.
+///
+/// Floating rate instruments are valued with their embedded options: capped or floored coupons
+/// are worth their amount at the forward rate plus the time value of their options. A
+/// `CapFloor` is valued by its options only. Like `NPVConstVisitor`, it assumes that the
+/// cashflows have already been indexed and fixed.
+///
+/// ## Parameters
+/// * `market_data` - The market data to use for NPV calculation
+/// * `volatility_store` - The volatility surfaces of the forecast curves
+/// * `include_today_cashflows` - Flag to include cashflows with payment date equal to the reference date
+pub struct CapFloorNPVConstVisitor<'a> {
+    market_data: &'a [MarketData],
+    volatility_store: &'a VolatilityStore,
+    include_today_cashflows: bool,
+}
+
+impl<'a> CapFloorNPVConstVisitor<'a> {
+    /// Creates a new `CapFloorNPVConstVisitor` with the given market data, volatilities and flag.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        market_data: &'a [MarketData],
+        volatility_store: &'a VolatilityStore,
+        include_today_cashflows: bool,
+    ) -> Self {
+        Self {
+            market_data,
+            volatility_store,
+            include_today_cashflows,
+        }
+    }
+
+    /// Returns the factor that converts an amount of the cashflow to its present value, or
+    /// `None` if the cashflow is already paid.
+    fn discount(&self, cf: &Cashflow) -> Result<Option<f64>> {
+        let id = cf.id()?;
+        let cf_market_data = self
+            .market_data
+            .get(id)
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "Market data for cashflow with id {id}"
+            )))?;
+        if cf_market_data.reference_date() == cf.payment_date() && !self.include_today_cashflows
+            || cf.payment_date() < cf_market_data.reference_date()
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            cf_market_data.df()? / cf_market_data.fx()? * cf.side().sign()
+                / cf_market_data.numerarie(),
+        ))
+    }
+
+    fn optionlet(
+        &self,
+        coupon: &FloatingRateCoupon,
+        option_type: OptionType,
+        strike: Option<f64>,
+    ) -> Result<Optionlet> {
+        let Some(strike) = strike else {
+            return Ok(Optionlet::default());
+        };
+        let forward = coupon
+            .fixing_rate()
+            .ok_or(AtlasError::ValueNotSetErr("Fixing rate".to_string()))?
+            + coupon.spread();
+        let surface = self
+            .volatility_store
+            .get_surface(coupon.forecast_curve_id()?)?;
+        let std_dev = surface.std_dev(coupon.fixing_date(), strike)?;
+        let price = match surface.volatility_type() {
+            VolatilityType::ShiftedLognormal(shift) => {
+                black_formula(option_type, forward, strike, std_dev, shift)?
+            }
+            VolatilityType::Normal => bachelier_formula(option_type, forward, strike, std_dev)?,
+        };
+        let accrual = coupon.notional()
+            * coupon
+                .rate_definition()
+                .day_counter()
+                .year_fraction(coupon.accrual_start_date()?, coupon.accrual_end_date()?);
+        Ok(Optionlet {
+            value: accrual * price,
+            intrinsic: accrual * option_type.intrinsic_value(forward, strike),
+        })
+    }
+
+    /// NPV of the cashflows, with the time value of the options embedded in their coupons.
+    fn embedded_npv(&self, cashflows: &[Cashflow]) -> Result<f64> {
+        cashflows.iter().try_fold(0.0, |acc, cf| {
+            let Some(discount) = self.discount(cf)? else {
+                return Ok(acc);
+            };
+            let mut amount = cf.amount()?;
+            if let Cashflow::FloatingRateCoupon(coupon) = cf {
+                if coupon.has_optionality() {
+                    let caplet = self.optionlet(coupon, OptionType::Call, coupon.cap())?;
+                    let floorlet = self.optionlet(coupon, OptionType::Put, coupon.floor())?;
+                    amount +=
+                        (floorlet.value - floorlet.intrinsic) - (caplet.value - caplet.intrinsic);
+                }
+            }
+            Ok(discount.mul_add(amount, acc))
+        })
+    }
+}
+
+impl ConstVisit<FloatingRateInstrument> for CapFloorNPVConstVisitor<'_> {
+    type Output = Result<f64>;
+    fn visit(&self, instrument: &FloatingRateInstrument) -> Self::Output {
+        self.embedded_npv(instrument.cashflows())
+    }
+}
+
+impl ConstVisit<Instrument> for CapFloorNPVConstVisitor<'_> {
+    type Output = Result<f64>;
+    fn visit(&self, instrument: &Instrument) -> Self::Output {
+        self.embedded_npv(instrument.cashflows())
+    }
+}
+
+impl ConstVisit<CapFloor> for CapFloorNPVConstVisitor<'_> {
+    type Output = Result<f64>;
+    fn visit(&self, cap_floor: &CapFloor) -> Self::Output {
+        cap_floor.cashflows().iter().try_fold(0.0, |acc, cf| {
+            let (Cashflow::FloatingRateCoupon(coupon), Some(discount)) = (cf, self.discount(cf)?)
+            else {
+                return Ok(acc);
+            };
+            let caplet = self.optionlet(coupon, OptionType::Call, coupon.cap())?;
+            let floorlet = self.optionlet(coupon, OptionType::Put, coupon.floor())?;
+            let value = match cap_floor.cap_floor_type() {
+                CapFloorType::Cap => caplet.value,
+                CapFloorType::Floor => floorlet.value,
+                CapFloorType::Collar => caplet.value - floorlet.value,
+            };
+            Ok(discount.mul_add(value, acc))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        core::marketstore::MarketStore,
+        currencies::enums::Currency,
+        instruments::{
+            leg::Leg, makefloatingrateinstrument::MakeFloatingRateInstrument,
+            makefloatingrateleg::MakeFloatingRateLeg,
+        },
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            interestrate::RateDefinition, interestrateindex::iborindex::IborIndex,
+            volatility::constantvolatility::ConstantVolatility,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{date::Date, daycounter::DayCounter, enums::Frequency},
+        visitors::{
+            fixingvisitor::FixingVisitor, indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor, traits::Visit,
+        },
+    };
+
+    fn market_store(volatility: f64, volatility_type: VolatilityType) -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        let curve = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.03,
+            RateDefinition::default(),
+        ));
+        market_store.mut_index_store().add_index(
+            0,
+            Arc::new(RwLock::new(
+                IborIndex::new(reference_date).with_term_structure(curve),
+            )),
+        )?;
+        market_store.mut_volatility_store().add_surface(
+            0,
+            Arc::new(ConstantVolatility::new(
+                reference_date,
+                volatility,
+                volatility_type,
+                DayCounter::Actual365,
+            )?),
+        )?;
+        Ok(market_store)
+    }
+
+    fn leg() -> Result<Leg> {
+        MakeFloatingRateLeg::new()
+            .with_start_date(Date::new(2024, 4, 2))
+            .with_end_date(Date::new(2027, 4, 2))
+            .with_payment_frequency(Frequency::Quarterly)
+            .with_rate_definition(RateDefinition::default())
+            .with_spread(0.0)
+            .with_notional(1_000_000.0)
+            .with_currency(Currency::USD)
+            .with_side(Side::Receive)
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(0))
+            .bullet()
+            .build()
+    }
+
+    fn fix<T: HasCashflows>(
+        instrument: &mut T,
+        market_store: &MarketStore,
+    ) -> Result<Vec<MarketData>> {
+        let indexer = IndexingVisitor::new();
+        indexer.visit(instrument)?;
+        let market_data = SimpleModel::new(market_store).gen_market_data(&indexer.request())?;
+        FixingVisitor::new(&market_data).visit(instrument)?;
+        Ok(market_data)
+    }
+
+    #[test]
+    fn test_cap_floor_parity() -> Result<()> {
+        let strike = 0.032;
+        for (volatility, volatility_type) in [
+            (0.2, VolatilityType::ShiftedLognormal(0.01)),
+            (0.008, VolatilityType::Normal),
+        ] {
+            let market_store = market_store(volatility, volatility_type)?;
+            let mut cap = CapFloor::cap(&leg()?, strike)?;
+            let mut floor = CapFloor::floor(&leg()?, strike)?;
+            let mut collar = CapFloor::collar(&leg()?, strike, strike)?;
+            let market_data = fix(&mut cap, &market_store)?;
+            fix(&mut floor, &market_store)?;
+            fix(&mut collar, &market_store)?;
+
+            let visitor =
+                CapFloorNPVConstVisitor::new(&market_data, market_store.volatility_store(), false);
+            let cap_npv = visitor.visit(&cap)?;
+            let floor_npv = visitor.visit(&floor)?;
+            assert!(cap_npv > 0.0 && floor_npv > 0.0);
+
+            // a cap minus a floor at the same strike is a swap paying the forward minus the strike
+            let swap_npv = cap.cashflows().iter().try_fold(0.0, |acc, cf| {
+                let Cashflow::FloatingRateCoupon(coupon) = cf else {
+                    return Ok(acc);
+                };
+                let accrual = coupon.notional()
+                    * coupon
+                        .rate_definition()
+                        .day_counter()
+                        .year_fraction(coupon.accrual_start_date()?, coupon.accrual_end_date()?);
+                let forward = coupon.fixing_rate().unwrap_or_default();
+                let df = market_data[cf.id()?].df()?;
+                Ok::<f64, AtlasError>((df * accrual).mul_add(forward - strike, acc))
+            })?;
+            assert!((cap_npv - floor_npv - swap_npv).abs() < 1e-6);
+            assert!((visitor.visit(&collar)? - swap_npv).abs() < 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedded_floor() -> Result<()> {
+        let strike = 0.035;
+        let build = |floor: Option<f64>| {
+            MakeFloatingRateInstrument::new()
+                .with_start_date(Date::new(2024, 4, 2))
+                .with_end_date(Date::new(2027, 4, 2))
+                .with_payment_frequency(Frequency::Quarterly)
+                .with_rate_definition(RateDefinition::default())
+                .with_spread(0.0)
+                .with_notional(1_000_000.0)
+                .with_currency(Currency::USD)
+                .with_side(Side::Receive)
+                .with_discount_curve_id(Some(0))
+                .with_forecast_curve_id(Some(0))
+                .with_floor(floor)
+                .bullet()
+                .build()
+        };
+        let store = market_store(0.008, VolatilityType::Normal)?;
+        let mut floored = build(Some(strike))?;
+        let mut plain = build(None)?;
+        let market_data = fix(&mut floored, &store)?;
+        fix(&mut plain, &store)?;
+        let mut floor = CapFloor::floor(&leg()?, strike)?;
+        let floor_market_data = fix(&mut floor, &store)?;
+
+        let visitor = CapFloorNPVConstVisitor::new(&market_data, store.volatility_store(), false);
+        let floor_npv =
+            CapFloorNPVConstVisitor::new(&floor_market_data, store.volatility_store(), false)
+                .visit(&floor)?;
+        let plain_npv = NPVConstVisitor::new(&market_data, false).visit(&plain)?;
+        let floored_npv = visitor.visit(&floored)?;
+        assert!((floored_npv - plain_npv - floor_npv).abs() < 1e-6);
+
+        // the amounts of the floored coupons only hold the intrinsic value of the floor
+        let intrinsic_npv = NPVConstVisitor::new(&market_data, false).visit(&floored)?;
+        assert!(plain_npv < intrinsic_npv && intrinsic_npv < floored_npv);
+
+        // without volatility, only the intrinsic value is left
+        let store = market_store(0.0, VolatilityType::Normal)?;
+        let visitor = CapFloorNPVConstVisitor::new(&market_data, store.volatility_store(), false);
+        assert!((visitor.visit(&floored)? - intrinsic_npv).abs() < 1e-6);
+        Ok(())
+    }
+}
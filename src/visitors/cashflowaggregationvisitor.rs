@@ -8,7 +8,7 @@ use crate::{
         traits::Payable,
     },
     core::traits::HasCurrency,
-    currencies::enums::Currency,
+    currencies::{enums::Currency, money::Money},
     time::date::Date,
     utils::errors::{AtlasError, Result},
 };
@@ -72,6 +72,40 @@ impl CashflowsAggregatorConstVisitor {
             .unwrap_or_else(std::sync::PoisonError::into_inner)
             .clone()
     }
+
+    /// Returns the aggregated redemptions by date as `Money` in the validation currency.
+    ///
+    /// # Errors
+    /// Returns an error if no validation currency was set, as the amounts could mix currencies.
+    pub fn redemptions_money(&self) -> Result<BTreeMap<Date, Money>> {
+        self.to_money(self.redemptions())
+    }
+
+    /// Returns the aggregated disbursements by date as `Money` in the validation currency.
+    ///
+    /// # Errors
+    /// Returns an error if no validation currency was set, as the amounts could mix currencies.
+    pub fn disbursements_money(&self) -> Result<BTreeMap<Date, Money>> {
+        self.to_money(self.disbursements())
+    }
+
+    /// Returns the aggregated interest payments by date as `Money` in the validation currency.
+    ///
+    /// # Errors
+    /// Returns an error if no validation currency was set, as the amounts could mix currencies.
+    pub fn interest_money(&self) -> Result<BTreeMap<Date, Money>> {
+        self.to_money(self.interest())
+    }
+
+    fn to_money(&self, map: BTreeMap<Date, f64>) -> Result<BTreeMap<Date, Money>> {
+        let currency = self.validation_currency.ok_or(AtlasError::ValueNotSetErr(
+            "Validation currency of CashflowsAggregatorConstVisitor".to_string(),
+        ))?;
+        Ok(map
+            .into_iter()
+            .map(|(date, amount)| (date, Money::new(amount, currency)))
+            .collect())
+    }
 }
 
 impl Default for CashflowsAggregatorConstVisitor {
@@ -125,6 +159,17 @@ impl<T: HasCashflows> ConstVisit<T> for CashflowsAggregatorConstVisitor {
                             .and_modify(|e| *e += amount)
                             .or_insert(amount);
                     }
+                    Cashflow::OvernightIndexedCoupon(cashflow) => {
+                        let mut interest = self.interest.lock().map_err(|e| {
+                            AtlasError::EvaluationErr(format!(
+                                "Interest mutex poisoned in CashflowsAggregatorConstVisitor: {e}",
+                            ))
+                        })?;
+                        interest
+                            .entry(cashflow.payment_date())
+                            .and_modify(|e| *e += amount)
+                            .or_insert(amount);
+                    }
                     Cashflow::Disbursement(cashflow) => {
                         let mut disbursements =
                             self.disbursements.lock().map_err(|e| {
@@ -221,5 +266,13 @@ mod tests {
             .get(&end_date)
             .unwrap_or_else(|| panic!("redemptions map should contain end_date"));
         assert!((*redemption - 100.0).abs() < 1e-12);
+
+        let redemptions = visitor
+            .redemptions_money()
+            .unwrap_or_else(|e| panic!("redemptions should be in USD: {e}"));
+        assert_eq!(redemptions[&end_date], Money::new(100.0, Currency::USD));
+        assert!(CashflowsAggregatorConstVisitor::new()
+            .interest_money()
+            .is_err());
     }
 }
@@ -193,7 +193,8 @@ impl<T: HasCashflows> ConstVisit<T> for CashflowCompressorConstVisitor {
         visitable
             .cashflows()
             .iter()
-            .try_for_each(|&cf| -> Result<()> {
+            .copied()
+            .try_for_each(|cf| -> Result<()> {
                 // validate that the cashflow currency is the same as the instrument currency
                 if cf.currency()? != self.currency {
                     return Err(AtlasError::InvalidValueErr(format!(
@@ -354,6 +355,11 @@ impl<T: HasCashflows> ConstVisit<T> for CashflowCompressorConstVisitor {
                             *estimated_start_date = Some(accrual_start_date);
                         }
                     }
+                    Cashflow::OvernightIndexedCoupon(_) => {
+                        return Err(AtlasError::InvalidValueErr(
+                            "Overnight indexed coupons cannot be compressed".to_string(),
+                        ));
+                    }
                 }
                 Ok(())
             })?;
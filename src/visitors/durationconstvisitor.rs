@@ -7,15 +7,36 @@ use crate::{
 
 use super::traits::{ConstVisit, HasCashflows};
 
+/// NPVs below this magnitude, in currency units, are treated as zero by duration measures.
+const MIN_DURATION_NPV: f64 = 1e-8;
+
+/// Checks that an NPV can be used to scale a duration measure, i.e. that it is not zero
+/// (e.g. an at-par swap), which would give an infinite or undefined duration.
+///
+/// # Errors
+/// Returns an `InvalidValueErr` if the NPV is zero or not a number.
+pub(crate) fn check_duration_npv(npv: f64) -> Result<()> {
+    if npv.abs() < MIN_DURATION_NPV || npv.is_nan() {
+        return Err(AtlasError::InvalidValueErr(format!(
+            "Duration is undefined for a position with NPV {npv}"
+        )));
+    }
+    Ok(())
+}
+
 /// # `DurationConstVisitor`
 /// `DurationConstVisitor` is a visitor that calculates the Duration of an instrument.
 /// It assumes that the cashflows of the instrument have already been indexed and fixed.
 ///
+/// Cashflows paid on or before the reference date are ignored. Visiting a position whose
+/// NPV is zero returns an error, since its duration is undefined.
+///
 /// ## Parameters
 /// * `market_data` - The market data to use for Duration calculation
-/// * `include_today_cashflows` - Flag to include cashflows with payment date equal to the reference date
+/// * `day_counter` - The day counter used to measure times (defaults to `Actual365`)
 pub struct DurationConstVisitor<'a> {
     market_data: &'a [MarketData],
+    day_counter: DayCounter,
 }
 
 impl<'a> DurationConstVisitor<'a> {
@@ -26,7 +47,17 @@ impl<'a> DurationConstVisitor<'a> {
     #[allow(clippy::missing_const_for_fn)]
     #[must_use]
     pub fn new(market_data: &'a [MarketData]) -> Self {
-        DurationConstVisitor { market_data }
+        DurationConstVisitor {
+            market_data,
+            day_counter: DayCounter::Actual365,
+        }
+    }
+
+    /// Sets the day counter used to measure times.
+    #[must_use]
+    pub const fn with_day_counter(mut self, day_counter: DayCounter) -> Self {
+        self.day_counter = day_counter;
+        self
     }
 }
 
@@ -46,11 +77,12 @@ impl<T: HasCashflows> ConstVisit<T> for DurationConstVisitor<'_> {
                             "Market data for cashflow with id {id}"
                         )))?;
 
-                if cf_market_data.reference_date() <= cf.payment_date() {
+                if cf.payment_date() <= cf_market_data.reference_date() {
                     return Ok(acc);
                 }
 
-                let year_fraction = DayCounter::Actual365
+                let year_fraction = self
+                    .day_counter
                     .year_fraction(cf_market_data.reference_date(), cf.payment_date());
 
                 let df = cf_market_data.df()?;
@@ -66,7 +98,7 @@ impl<T: HasCashflows> ConstVisit<T> for DurationConstVisitor<'_> {
             });
 
         match duration {
-            Ok((d1, d2)) => Ok(d1 / d2),
+            Ok((d1, d2)) => check_duration_npv(d2).map(|()| d1 / d2),
             Err(e) => Err(e),
         }
     }
@@ -91,7 +123,7 @@ mod tests {
         currencies::enums::Currency,
         instruments::{
             fixedrateinstrument::FixedRateInstrument,
-            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefixedrateinstrument::MakeFixedRateInstrument, swap::Swap,
         },
         models::{simplemodel::SimpleModel, traits::Model},
         rates::{
@@ -243,4 +275,77 @@ mod tests {
             duration(chunk);
         });
     }
+
+    #[test]
+    fn test_zero_coupon_duration() -> Result<()> {
+        let market_store = create_store()?;
+        let ref_date = market_store.reference_date();
+        let mut instrument = MakeFixedRateInstrument::new()
+            .with_start_date(ref_date)
+            .with_end_date(ref_date + Period::new(5, TimeUnit::Years))
+            .with_rate(InterestRate::new(
+                0.05,
+                Compounding::Compounded,
+                Frequency::Annual,
+                DayCounter::Actual365,
+            ))
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .zero()
+            .with_discount_curve_id(Some(2))
+            .with_notional(100.0)
+            .build()?;
+
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+
+        // the disbursement at the reference date carries no duration
+        let duration = DurationConstVisitor::new(&data).visit(&instrument)?;
+        let expected = DayCounter::Actual365
+            .year_fraction(ref_date, ref_date + Period::new(5, TimeUnit::Years));
+        assert!((duration - expected).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_npv_duration() -> Result<()> {
+        let market_store = create_store()?;
+        let ref_date = market_store.reference_date();
+        let leg = |side| {
+            MakeFixedRateInstrument::new()
+                .with_start_date(ref_date)
+                .with_end_date(ref_date + Period::new(5, TimeUnit::Years))
+                .with_rate(InterestRate::new(
+                    0.05,
+                    Compounding::Simple,
+                    Frequency::Annual,
+                    DayCounter::Thirty360,
+                ))
+                .with_payment_frequency(Frequency::Semiannual)
+                .with_side(side)
+                .with_currency(Currency::USD)
+                .bullet()
+                .with_discount_curve_id(Some(2))
+                .with_notional(100.0)
+                .build()
+        };
+        // offsetting legs have a zero NPV and no duration
+        let mut instrument = leg(Side::Receive)?;
+        let mut offset = leg(Side::Pay)?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        indexer.visit(&mut offset)?;
+        let cashflows = instrument
+            .cashflows()
+            .iter()
+            .chain(offset.cashflows())
+            .copied()
+            .collect();
+        let position = Swap::new(cashflows, Vec::new(), None);
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+
+        assert!(DurationConstVisitor::new(&data).visit(&position).is_err());
+        Ok(())
+    }
 }
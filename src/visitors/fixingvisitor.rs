@@ -34,8 +34,8 @@ impl<T: HasCashflows> Visit<T> for FixingVisitor<'_> {
             .mut_cashflows()
             .iter_mut()
             .try_for_each(|cf| -> Result<()> {
-                if let Cashflow::FloatingRateCoupon(frcf) = cf {
-                    let id = frcf.id()?;
+                if let Cashflow::FloatingRateCoupon(_) | Cashflow::OvernightIndexedCoupon(_) = cf {
+                    let id = cf.id()?;
                     let cf_market_data =
                         self.market_data
                             .get(id)
@@ -43,7 +43,7 @@ impl<T: HasCashflows> Visit<T> for FixingVisitor<'_> {
                                 "Market data for cashflow with id {id}"
                             )))?;
                     let fixing_rate = cf_market_data.fwd()?;
-                    frcf.set_fixing_rate(fixing_rate);
+                    cf.set_fixing_rate(fixing_rate);
                 }
                 Ok(())
             })?;
@@ -0,0 +1,459 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    core::{marketstore::MarketStore, meta::MarketRequest},
+    models::{simplemodel::SimpleModel, traits::Model},
+    rates::yieldtermstructure::bumpedtermstructure::Bump,
+    time::period::Period,
+    utils::errors::{AtlasError, Result},
+};
+
+use super::{
+    durationconstvisitor::check_duration_npv,
+    fixingvisitor::FixingVisitor,
+    npvconstvisitor::NPVConstVisitor,
+    traits::{ConstVisit, HasCashflows, Visit},
+};
+
+/// Size of a basis point.
+const BASIS_POINT: f64 = 0.0001;
+
+/// # `KeyRateDurations`
+/// Sensitivities computed by the `KeyRateDurationConstVisitor`. DV01s are the change in NPV,
+/// in currency units, for a one basis point rise in rates, and durations are the same change
+/// per unit of NPV and per unit of rate, so they are undefined for a position with zero NPV.
+#[derive(Clone, Debug)]
+pub struct KeyRateDurations {
+    npv: f64,
+    dv01: f64,
+    gamma: f64,
+    key_rate_dv01s: BTreeMap<usize, Vec<(Period, f64)>>,
+}
+
+impl KeyRateDurations {
+    /// Returns the NPV of the unbumped instrument.
+    #[must_use]
+    pub const fn npv(&self) -> f64 {
+        self.npv
+    }
+
+    /// Returns the change in NPV for a one basis point parallel rise of every curve.
+    #[must_use]
+    pub const fn dv01(&self) -> f64 {
+        self.dv01
+    }
+
+    /// Returns the modified duration, `-dP / (P dy)`, for a parallel shift of every curve.
+    ///
+    /// # Errors
+    /// Returns an error if the NPV is zero.
+    pub fn modified_duration(&self) -> Result<f64> {
+        check_duration_npv(self.npv)?;
+        Ok(-self.dv01 / (self.npv * BASIS_POINT))
+    }
+
+    /// Returns the convexity, `d2P / (P dy2)`, for a parallel shift of every curve.
+    ///
+    /// # Errors
+    /// Returns an error if the NPV is zero.
+    pub fn convexity(&self) -> Result<f64> {
+        check_duration_npv(self.npv)?;
+        Ok(self.gamma / self.npv)
+    }
+
+    /// Returns the key-rate DV01s of every curve, by curve id and pillar tenor.
+    #[must_use]
+    pub const fn key_rate_dv01s(&self) -> &BTreeMap<usize, Vec<(Period, f64)>> {
+        &self.key_rate_dv01s
+    }
+
+    /// Returns the key-rate DV01s of a curve, by pillar tenor.
+    ///
+    /// # Errors
+    /// Returns an error if the instrument does not depend on the curve.
+    pub fn key_rate_dv01(&self, curve_id: usize) -> Result<&[(Period, f64)]> {
+        self.key_rate_dv01s
+            .get(&curve_id)
+            .map(Vec::as_slice)
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "Key-rate sensitivities for curve with id {curve_id}"
+            )))
+    }
+
+    /// Returns the key-rate durations of a curve, by pillar tenor. They add up to the
+    /// modified duration when the instrument depends on that curve only.
+    ///
+    /// # Errors
+    /// Returns an error if the instrument does not depend on the curve or if the NPV is zero.
+    pub fn key_rate_durations(&self, curve_id: usize) -> Result<Vec<(Period, f64)>> {
+        check_duration_npv(self.npv)?;
+        Ok(self
+            .key_rate_dv01(curve_id)?
+            .iter()
+            .map(|(tenor, dv01)| (*tenor, -dv01 / (self.npv * BASIS_POINT)))
+            .collect())
+    }
+}
+
+/// # `KeyRateDurationConstVisitor`
+/// `KeyRateDurationConstVisitor` is a visitor that calculates the DV01 of an instrument per
+/// curve and per pillar tenor, together with its modified duration and convexity. Every
+/// curve the instrument depends on is bumped with `MarketStore::with_bumped_index`, the
+/// market data is generated again with a `SimpleModel`, floating rate coupons are fixed
+/// again and the NPVs are differentiated with central differences.
+///
+/// It assumes that the cashflows of the instrument have already been indexed, and the
+/// market requests of the indexing are used to generate the market data.
+///
+/// ## Parameters
+/// * `market_store` - The market store with the unbumped curves
+/// * `market_request` - The market requests of the instrument
+/// * `tenors` - The pillar tenors of the key-rate bumps
+/// * `bump_size` - The size of the bumps (defaults to one basis point)
+/// * `include_today_cashflows` - Flag to include cashflows with payment date equal to the reference date
+pub struct KeyRateDurationConstVisitor<'a> {
+    market_store: &'a MarketStore,
+    market_request: &'a [MarketRequest],
+    tenors: Vec<Period>,
+    bump_size: f64,
+    include_today_cashflows: bool,
+}
+
+impl<'a> KeyRateDurationConstVisitor<'a> {
+    /// Creates a new `KeyRateDurationConstVisitor` with the given pillar tenors.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(
+        market_store: &'a MarketStore,
+        market_request: &'a [MarketRequest],
+        tenors: Vec<Period>,
+    ) -> Self {
+        Self {
+            market_store,
+            market_request,
+            tenors,
+            bump_size: BASIS_POINT,
+            include_today_cashflows: false,
+        }
+    }
+
+    /// Creates a new `KeyRateDurationConstVisitor` whose pillars are the ends of the given
+    /// tenor buckets, as used by the `NPVByTenorConstVisitor`.
+    #[must_use]
+    pub fn from_tenor_buckets(
+        market_store: &'a MarketStore,
+        market_request: &'a [MarketRequest],
+        tenors: &[(Period, Period)],
+    ) -> Self {
+        Self::new(
+            market_store,
+            market_request,
+            tenors.iter().map(|(_, end)| *end).collect(),
+        )
+    }
+
+    /// Sets the size of the bumps.
+    #[must_use]
+    pub const fn with_bump_size(mut self, bump_size: f64) -> Self {
+        self.bump_size = bump_size;
+        self
+    }
+
+    /// Sets whether to include cashflows with payment date equal to the reference date.
+    #[must_use]
+    pub const fn with_include_today_cashflows(mut self, include_today_cashflows: bool) -> Self {
+        self.include_today_cashflows = include_today_cashflows;
+        self
+    }
+
+    /// Returns the pillar tenors.
+    #[must_use]
+    pub fn tenors(&self) -> &[Period] {
+        &self.tenors
+    }
+
+    fn curve_ids(&self) -> BTreeSet<usize> {
+        self.market_request
+            .iter()
+            .flat_map(|request| {
+                [
+                    request.df().map(|df| df.provider_id()),
+                    request.fwd().map(|fwd| fwd.provider_id()),
+                ]
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn npv<T: HasCashflows + Clone>(
+        &self,
+        market_store: &MarketStore,
+        visitable: &T,
+    ) -> Result<f64> {
+        let data = SimpleModel::new(market_store).gen_market_data(self.market_request)?;
+        let mut instrument = visitable.clone();
+        FixingVisitor::new(&data).visit(&mut instrument)?;
+        NPVConstVisitor::new(&data, self.include_today_cashflows).visit(&instrument)
+    }
+
+    /// Returns the NPVs with every curve bumped up and down by `bump`.
+    fn bumped_npvs<T: HasCashflows + Clone>(
+        &self,
+        curve_ids: &BTreeSet<usize>,
+        bump: impl Fn(f64) -> Bump,
+        visitable: &T,
+    ) -> Result<(f64, f64)> {
+        let bumped_npv = |size: f64| {
+            let market_store = curve_ids
+                .iter()
+                .try_fold(self.market_store.clone(), |store, id| {
+                    store.with_bumped_index(*id, bump(size))
+                })?;
+            self.npv(&market_store, visitable)
+        };
+        Ok((bumped_npv(self.bump_size)?, bumped_npv(-self.bump_size)?))
+    }
+}
+
+impl<T: HasCashflows + Clone> ConstVisit<T> for KeyRateDurationConstVisitor<'_> {
+    type Output = Result<KeyRateDurations>;
+    fn visit(&self, visitable: &T) -> Self::Output {
+        if self.bump_size == 0.0 {
+            return Err(AtlasError::InvalidValueErr(
+                "Bump size must not be zero".to_string(),
+            ));
+        }
+        let h = self.bump_size;
+        let to_dv01 = |(up, down): (f64, f64)| (up - down) / (2.0 * h) * BASIS_POINT;
+
+        let curve_ids = self.curve_ids();
+        let npv = self.npv(self.market_store, visitable)?;
+        let (up, down) = self.bumped_npvs(&curve_ids, Bump::Parallel, visitable)?;
+
+        let key_rate_dv01s = curve_ids
+            .iter()
+            .map(|id| {
+                let curve_id = BTreeSet::from([*id]);
+                let dv01s = (0..self.tenors.len())
+                    .map(|pillar| {
+                        let bump = |size| Bump::KeyRate {
+                            tenors: self.tenors.clone(),
+                            pillar,
+                            size,
+                        };
+                        let npvs = self.bumped_npvs(&curve_id, bump, visitable)?;
+                        Ok((self.tenors[pillar], to_dv01(npvs)))
+                    })
+                    .collect::<Result<Vec<(Period, f64)>>>()?;
+                Ok((*id, dv01s))
+            })
+            .collect::<Result<BTreeMap<usize, Vec<(Period, f64)>>>>()?;
+
+        Ok(KeyRateDurations {
+            npv,
+            dv01: to_dv01((up, down)),
+            gamma: 2.0f64.mul_add(-npv, up + down) / (h * h),
+            key_rate_dv01s,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        currencies::enums::Currency,
+        instruments::{
+            makefixedrateinstrument::MakeFixedRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument, swap::Swap,
+        },
+        rates::{
+            enums::Compounding,
+            interestrate::{InterestRate, RateDefinition},
+            interestrateindex::iborindex::IborIndex,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{
+            date::Date,
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+        },
+        visitors::indexingvisitor::IndexingVisitor,
+    };
+
+    fn create_store() -> Result<MarketStore> {
+        let ref_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(ref_date, Currency::USD);
+        for (id, rate) in [(0, 0.04), (1, 0.045)] {
+            let curve = Arc::new(FlatForwardTermStructure::new(
+                ref_date,
+                rate,
+                RateDefinition::new(
+                    DayCounter::Actual365,
+                    Compounding::Continuous,
+                    Frequency::Annual,
+                ),
+            ));
+            let index = IborIndex::new(ref_date)
+                .with_term_structure(curve)
+                .with_frequency(Frequency::Semiannual);
+            market_store
+                .mut_index_store()
+                .add_index(id, Arc::new(RwLock::new(index)))?;
+        }
+        Ok(market_store)
+    }
+
+    fn tenors() -> Vec<Period> {
+        [1, 2, 5, 10, 30]
+            .iter()
+            .map(|years| Period::new(*years, TimeUnit::Years))
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_rate_bond() -> Result<()> {
+        let market_store = create_store()?;
+        let ref_date = market_store.index_store().reference_date();
+        let mut instrument = MakeFixedRateInstrument::new()
+            .with_start_date(ref_date)
+            .with_end_date(ref_date + Period::new(5, TimeUnit::Years))
+            .with_rate(InterestRate::new(
+                0.04,
+                Compounding::Simple,
+                Frequency::Annual,
+                DayCounter::Actual365,
+            ))
+            .with_payment_frequency(Frequency::Annual)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .with_discount_curve_id(Some(0))
+            .with_notional(1_000_000.0)
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let visitor = KeyRateDurationConstVisitor::new(&market_store, &requests, tenors());
+        let result = visitor.visit(&instrument)?;
+
+        // a 5y bullet bond has a modified duration a bit below 5 years under continuous rates
+        let modified_duration = result.modified_duration()?;
+        assert!(modified_duration > 4.0 && modified_duration < 5.0);
+        assert!(result.convexity()? > 0.0);
+        let expected_dv01 = -result.npv() * modified_duration * BASIS_POINT;
+        assert!((result.dv01() - expected_dv01).abs() < 1e-6);
+
+        let key_rate_dv01 = result.key_rate_dv01(0)?;
+        assert_eq!(key_rate_dv01.len(), 5);
+        let total: f64 = key_rate_dv01.iter().map(|(_, dv01)| dv01).sum();
+        assert!((total - result.dv01()).abs() < 1e-6);
+        // all the exposure sits between the 1y and 5y pillars
+        assert!(key_rate_dv01[2].1.abs() > key_rate_dv01[1].1.abs());
+        assert!(key_rate_dv01[3].1.abs() < 1e-9 && key_rate_dv01[4].1.abs() < 1e-9);
+
+        let durations: f64 = result.key_rate_durations(0)?.iter().map(|(_, d)| d).sum();
+        assert!((durations - modified_duration).abs() < 1e-8);
+        assert!(result.key_rate_dv01(1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_floating_rate_note_by_curve() -> Result<()> {
+        let market_store = create_store()?;
+        let ref_date = market_store.index_store().reference_date();
+        let mut instrument = MakeFloatingRateInstrument::new()
+            .with_start_date(ref_date)
+            .with_end_date(ref_date + Period::new(3, TimeUnit::Years))
+            .with_payment_frequency(Frequency::Semiannual)
+            .with_rate_definition(RateDefinition::new(
+                DayCounter::Actual360,
+                Compounding::Simple,
+                Frequency::Semiannual,
+            ))
+            .with_spread(0.0)
+            .with_side(Side::Receive)
+            .with_currency(Currency::USD)
+            .bullet()
+            .with_discount_curve_id(Some(0))
+            .with_forecast_curve_id(Some(1))
+            .with_notional(1_000_000.0)
+            .build()?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let requests = indexer.request();
+
+        let buckets = [
+            (
+                Period::new(0, TimeUnit::Days),
+                Period::new(1, TimeUnit::Years),
+            ),
+            (
+                Period::new(1, TimeUnit::Years),
+                Period::new(5, TimeUnit::Years),
+            ),
+        ];
+        let visitor =
+            KeyRateDurationConstVisitor::from_tenor_buckets(&market_store, &requests, &buckets);
+        let result = visitor.visit(&instrument)?;
+
+        // higher forecasts increase the coupons, higher discounting reduces the value
+        let forecast: f64 = result.key_rate_dv01(1)?.iter().map(|(_, dv01)| dv01).sum();
+        let discount: f64 = result.key_rate_dv01(0)?.iter().map(|(_, dv01)| dv01).sum();
+        assert!(forecast > 0.0);
+        assert!(discount < 0.0);
+        assert!((forecast + discount - result.dv01()).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_npv_position() -> Result<()> {
+        let market_store = create_store()?;
+        let ref_date = market_store.index_store().reference_date();
+        let leg = |side| {
+            MakeFixedRateInstrument::new()
+                .with_start_date(ref_date)
+                .with_end_date(ref_date + Period::new(5, TimeUnit::Years))
+                .with_rate(InterestRate::new(
+                    0.04,
+                    Compounding::Simple,
+                    Frequency::Annual,
+                    DayCounter::Actual365,
+                ))
+                .with_payment_frequency(Frequency::Annual)
+                .with_side(side)
+                .with_currency(Currency::USD)
+                .bullet()
+                .with_discount_curve_id(Some(0))
+                .with_notional(1_000_000.0)
+                .build()
+        };
+        let mut receive = leg(Side::Receive)?;
+        let mut pay = leg(Side::Pay)?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut receive)?;
+        indexer.visit(&mut pay)?;
+        let requests = indexer.request();
+        let cashflows = receive
+            .cashflows()
+            .iter()
+            .chain(pay.cashflows())
+            .copied()
+            .collect();
+        let position = Swap::new(cashflows, Vec::new(), None);
+
+        let visitor = KeyRateDurationConstVisitor::new(&market_store, &requests, tenors());
+        let result = visitor.visit(&position)?;
+
+        // the DV01s of offsetting legs cancel out, and durations are undefined
+        assert!(result.npv().abs() < 1e-8 && result.dv01().abs() < 1e-8);
+        assert!(result.modified_duration().is_err());
+        assert!(result.convexity().is_err());
+        assert!(result.key_rate_durations(0).is_err());
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 /// Accrued amount const visitor module.
 pub mod accruedamountconstvisitor;
+/// Cap and floor NPV visitor module.
+pub mod capfloornpvconstvisitor;
 /// Cash flow aggregation visitor module.
 pub mod cashflowaggregationvisitor;
 /// Cash flow compressor const visitor module.
@@ -10,6 +12,8 @@ pub mod durationconstvisitor;
 pub mod fixingvisitor;
 /// Indexing visitor module.
 pub mod indexingvisitor;
+/// Key-rate duration const visitor module.
+pub mod keyratedurationconstvisitor;
 /// NPV by date const visitor module.
 pub mod npvbydateconstvisitor;
 /// NPV by tenor const visitor module.
@@ -20,6 +24,8 @@ pub mod npvconstvisitor;
 pub mod parvaluevisitor;
 /// Par value visitor double rate instrument module.
 pub mod parvaluevisitordoublerateinstrument;
+/// Swaption NPV visitor module.
+pub mod swaptionnpvconstvisitor;
 /// Traits module.
 pub mod traits;
 /// Z-spread const visitor module.
@@ -1,6 +1,11 @@
 use crate::{
-    cashflows::traits::Payable,
-    core::{meta::MarketData, traits::Registrable},
+    cashflows::{cashflow::Cashflow, traits::Payable},
+    core::{
+        meta::MarketData,
+        traits::{HasCurrency, Registrable},
+    },
+    currencies::{enums::Currency, money::Money},
+    math::ad::traits::Real,
     utils::errors::{AtlasError, Result},
 };
 
@@ -9,20 +14,23 @@ use super::traits::{ConstVisit, HasCashflows};
 /// # `NPVConstVisitor`
 /// `NPVConstVisitor` is a visitor that calculates the NPV of an instrument.
 /// It assumes that the cashflows of the instrument have already been indexed and fixed.
+/// The market data can hold `Dual` numbers, in which case the NPV is returned with its
+/// derivatives with respect to the curve nodes; floating rate coupons whose forward depends
+/// on the nodes are then paid on that forward, so the NPV carries their projection too.
 ///
 /// ## Parameters
 /// * `market_data` - The market data to use for NPV calculation
 /// * `include_today_cashflows` - Flag to include cashflows with payment date equal to the reference date
-pub struct NPVConstVisitor<'a> {
-    market_data: &'a [MarketData],
+pub struct NPVConstVisitor<'a, R = f64> {
+    market_data: &'a [MarketData<R>],
     include_today_cashflows: bool,
 }
 
-impl<'a> NPVConstVisitor<'a> {
+impl<'a, R> NPVConstVisitor<'a, R> {
     /// Creates a new `NPVConstVisitor` with the given market data and flag.
     #[allow(clippy::missing_const_for_fn)]
     #[must_use]
-    pub fn new(market_data: &'a [MarketData], include_today_cashflows: bool) -> Self {
+    pub fn new(market_data: &'a [MarketData<R>], include_today_cashflows: bool) -> Self {
         NPVConstVisitor {
             market_data,
             include_today_cashflows,
@@ -34,34 +42,79 @@ impl<'a> NPVConstVisitor<'a> {
     }
 }
 
-impl<T: HasCashflows> ConstVisit<T> for NPVConstVisitor<'_> {
-    type Output = Result<f64>;
-    fn visit(&self, visitable: &T) -> Self::Output {
-        let npv = visitable.cashflows().iter().try_fold(0.0, |acc, cf| {
-            let id = cf.id()?;
-
-            let cf_market_data =
-                self.market_data
-                    .get(id)
-                    .ok_or(AtlasError::NotFoundErr(format!(
-                        "Market data for cashflow with id {id}"
-                    )))?;
-
-            if cf_market_data.reference_date() == cf.payment_date() && !self.include_today_cashflows
-                || cf.payment_date() < cf_market_data.reference_date()
-            {
-                return Ok(acc);
+impl<R: Real> NPVConstVisitor<'_, R> {
+    fn cashflow_npv(&self, cf: &Cashflow) -> Result<R> {
+        let id = cf.id()?;
+
+        let cf_market_data = self
+            .market_data
+            .get(id)
+            .ok_or(AtlasError::NotFoundErr(format!(
+                "Market data for cashflow with id {id}"
+            )))?;
+
+        if cf_market_data.reference_date() == cf.payment_date() && !self.include_today_cashflows
+            || cf.payment_date() < cf_market_data.reference_date()
+        {
+            return Ok(R::from_f64(0.0));
+        }
+
+        let df = cf_market_data.df()?;
+        let fx = cf_market_data.fx()?;
+        let flag = cf.side().sign();
+
+        let numerarie = cf_market_data.numerarie();
+        // a forward carrying derivatives is projected into the amount of a floating coupon
+        let amount = match (cf, cf_market_data.fwd()) {
+            (Cashflow::FloatingRateCoupon(coupon), Ok(fwd)) if !fwd.is_constant() => {
+                coupon.amount_with(fwd)
             }
+            _ => R::from_f64(cf.amount()?),
+        };
+        Ok(df * amount / fx * flag / numerarie)
+    }
+}
 
-            let df = cf_market_data.df()?;
-            let fx = cf_market_data.fx()?;
-            let flag = cf.side().sign();
+impl NPVConstVisitor<'_> {
+    /// Returns the NPV as `Money` in `currency`, or in the currency of the first cashflow if
+    /// `None`. Every cashflow must be in that currency; NPVs converted by the model to its local
+    /// currency are given by `visit`.
+    ///
+    /// # Errors
+    /// Returns an error if the market data is missing, if a cashflow is in another currency or
+    /// if there are no cashflows to take the currency from.
+    pub fn visit_money<T: HasCashflows>(
+        &self,
+        visitable: &T,
+        currency: Option<Currency>,
+    ) -> Result<Money> {
+        let currency = match currency {
+            Some(currency) => currency,
+            None => visitable
+                .cashflows()
+                .first()
+                .ok_or(AtlasError::NotFoundErr(
+                    "Cashflows to set the NPV currency".to_string(),
+                ))?
+                .currency()?,
+        };
+        visitable
+            .cashflows()
+            .iter()
+            .try_fold(Money::zero(currency), |acc, cf| {
+                acc.checked_add(Money::new(self.cashflow_npv(cf)?, cf.currency()?))
+            })
+    }
+}
 
-            let numerarie = cf_market_data.numerarie();
-            let amount = cf.amount()?;
-            Ok(acc + df * amount / fx * flag / numerarie)
-        });
-        npv
+impl<T: HasCashflows, R: Real> ConstVisit<T> for NPVConstVisitor<'_, R> {
+    type Output = Result<R>;
+    fn visit(&self, visitable: &T) -> Self::Output {
+        let zero = R::from_f64(0.0);
+        visitable
+            .cashflows()
+            .iter()
+            .try_fold(zero, |acc, cf| Ok(acc + self.cashflow_npv(cf)?))
     }
 }
 
@@ -83,7 +136,7 @@ mod tests {
         instruments::{
             fixedrateinstrument::FixedRateInstrument,
             makefixedrateinstrument::MakeFixedRateInstrument,
-            makefloatingrateinstrument::MakeFloatingRateInstrument,
+            makefloatingrateinstrument::MakeFloatingRateInstrument, swap::Swap,
         },
         models::{simplemodel::SimpleModel, traits::Model},
         prelude::Side,
@@ -259,6 +312,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_visit_money() -> Result<()> {
+        let market_store = create_store()?;
+        let fixed = |currency: Currency| {
+            MakeFixedRateInstrument::new()
+                .with_start_date(market_store.reference_date())
+                .with_end_date(market_store.reference_date() + Period::new(5, TimeUnit::Years))
+                .with_rate(InterestRate::new(
+                    0.04,
+                    Compounding::Compounded,
+                    Frequency::Annual,
+                    DayCounter::Thirty360,
+                ))
+                .with_payment_frequency(Frequency::Semiannual)
+                .with_side(Side::Receive)
+                .with_currency(currency)
+                .bullet()
+                .with_discount_curve_id(Some(2))
+                .with_notional(100_000.0)
+                .build()
+        };
+        let usd = fixed(Currency::USD)?;
+        let eur = fixed(Currency::EUR)?;
+        let mut instrument = Swap::new(usd.cashflows().to_vec(), Vec::new(), None);
+        let mut mixed = Swap::new(
+            [usd.cashflows(), eur.cashflows()].concat(),
+            Vec::new(),
+            None,
+        );
+
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut instrument)?;
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+        let npv_visitor = NPVConstVisitor::new(&data, true);
+        let npv = npv_visitor.visit(&instrument)?;
+
+        let money = npv_visitor.visit_money(&instrument, None)?;
+        assert_eq!(money.currency()?, Currency::USD);
+        assert!((money.amount() - npv).abs() < 1e-9);
+        let money = npv_visitor.visit_money(&instrument, Some(Currency::USD))?;
+        assert!((money.amount() - npv).abs() < 1e-9);
+        assert!(npv_visitor
+            .visit_money(&instrument, Some(Currency::EUR))
+            .is_err());
+
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut mixed)?;
+        let data = SimpleModel::new(&market_store).gen_market_data(&indexer.request())?;
+        let npv_visitor = NPVConstVisitor::new(&data, true);
+        assert!(npv_visitor.visit_money(&mixed, None).is_err());
+        assert!(npv_visitor
+            .visit_money(&mixed, Some(Currency::USD))
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_npv_floating_bullet() -> Result<()> {
         let market_store =
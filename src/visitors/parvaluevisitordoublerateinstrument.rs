@@ -41,6 +41,7 @@ impl TmpInstrument {
         self.mut_cashflows().iter_mut().for_each(|cf| match cf {
             Cashflow::FixedRateCoupon(coupon) => coupon.set_rate_value(rate),
             Cashflow::FloatingRateCoupon(coupon) => coupon.set_spread(rate),
+            Cashflow::OvernightIndexedCoupon(coupon) => coupon.set_spread(rate),
             _ => {}
         });
         self
@@ -0,0 +1,329 @@
+use crate::{
+    cashflows::{
+        cashflow::Cashflow,
+        traits::{InterestAccrual, Payable},
+    },
+    core::{meta::MarketData, traits::Registrable},
+    instruments::swaption::{SettlementType, Swaption, SwaptionType},
+    math::optionpricing::{bachelier_formula, black_formula, OptionType},
+    rates::{enums::VolatilityType, volatilitystore::VolatilityStore},
+    time::date::Date,
+    utils::errors::{AtlasError, Result},
+};
+
+use super::traits::{ConstVisit, HasCashflows};
+
+/// # `SwaptionNPVConstVisitor`
+/// `SwaptionNPVConstVisitor` is a visitor that calculates the NPV of European swaptions. The
+/// annuity of the underlying swap is the discounted value of its fixed coupons per unit of
+/// rate, and its forward swap rate is the fixed rate that gives it a zero NPV. Swaptions are
+/// priced with the swaption volatility cube stored under the forecast curve ID of the floating
+/// leg, using Black-76 for shifted lognormal volatilities and Bachelier for normal volatilities.
+///
+/// Physically settled swaptions are worth the annuity times the option price. Cash settled
+/// swaptions use the cash annuity at the forward swap rate instead, discounted from the start
+/// of the underlying swap, which must have a disbursement on that date. Swaptions past their
+/// expiry are worth zero. Like `NPVConstVisitor`, it assumes that the cashflows of the
+/// underlying swap have already been indexed and fixed.
+///
+/// ## Parameters
+/// * `market_data` - The market data to use for NPV calculation
+/// * `volatility_store` - The swaption volatility cubes of the forecast curves
+pub struct SwaptionNPVConstVisitor<'a> {
+    market_data: &'a [MarketData],
+    volatility_store: &'a VolatilityStore,
+}
+
+/// The annuity and the value of the underlying swap of a swaption.
+struct Underlying {
+    npv: f64,
+    annuity: f64,
+    fixed_leg_sign: f64,
+    accruals: Vec<(Date, f64, f64)>,
+    start_date: Option<Date>,
+    end_date: Option<Date>,
+    settlement_discount: Option<(Date, f64)>,
+}
+
+impl<'a> SwaptionNPVConstVisitor<'a> {
+    /// Creates a new `SwaptionNPVConstVisitor` with the given market data and volatilities.
+    #[must_use]
+    pub const fn new(market_data: &'a [MarketData], volatility_store: &'a VolatilityStore) -> Self {
+        Self {
+            market_data,
+            volatility_store,
+        }
+    }
+
+    /// Discounts the underlying swap, or returns `None` if the swaption has expired.
+    fn underlying(&self, swaption: &Swaption) -> Result<Option<Underlying>> {
+        let mut underlying = Underlying {
+            npv: 0.0,
+            annuity: 0.0,
+            fixed_leg_sign: 0.0,
+            accruals: Vec::new(),
+            start_date: None,
+            end_date: None,
+            settlement_discount: None,
+        };
+        for cf in swaption.cashflows() {
+            let id = cf.id()?;
+            let cf_market_data =
+                self.market_data
+                    .get(id)
+                    .ok_or(AtlasError::NotFoundErr(format!(
+                        "Market data for cashflow with id {id}"
+                    )))?;
+            if swaption.expiry() < cf_market_data.reference_date() {
+                return Ok(None);
+            }
+            let discount = cf_market_data.df()? / cf_market_data.fx()? / cf_market_data.numerarie();
+            underlying.npv = (discount * cf.side().sign()).mul_add(cf.amount()?, underlying.npv);
+            match cf {
+                Cashflow::FixedRateCoupon(coupon) => {
+                    let start_date = coupon.accrual_start_date()?;
+                    let end_date = coupon.accrual_end_date()?;
+                    let year_fraction = coupon
+                        .rate()
+                        .day_counter()
+                        .year_fraction(start_date, end_date);
+                    underlying.annuity =
+                        (discount * coupon.notional()).mul_add(year_fraction, underlying.annuity);
+                    underlying.fixed_leg_sign = cf.side().sign();
+                    underlying
+                        .accruals
+                        .push((end_date, coupon.notional(), year_fraction));
+                    underlying.start_date = Some(
+                        underlying
+                            .start_date
+                            .map_or(start_date, |date| date.min(start_date)),
+                    );
+                    underlying.end_date = underlying.end_date.max(Some(end_date));
+                }
+                Cashflow::Disbursement(_)
+                    if underlying
+                        .settlement_discount
+                        .is_none_or(|(date, _)| cf.payment_date() < date) =>
+                {
+                    underlying.settlement_discount = Some((cf.payment_date(), discount));
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(underlying))
+    }
+}
+
+impl ConstVisit<Swaption> for SwaptionNPVConstVisitor<'_> {
+    type Output = Result<f64>;
+    fn visit(&self, swaption: &Swaption) -> Self::Output {
+        let Some(mut underlying) = self.underlying(swaption)? else {
+            return Ok(0.0);
+        };
+        let (Some(start_date), Some(end_date)) = (underlying.start_date, underlying.end_date)
+        else {
+            return Err(AtlasError::InvalidValueErr(
+                "Swaption underlying has no fixed rate coupons".to_string(),
+            ));
+        };
+        if underlying.annuity <= 0.0 {
+            return Err(AtlasError::InvalidValueErr(format!(
+                "Swaption underlying has a non positive annuity ({})",
+                underlying.annuity
+            )));
+        }
+        let strike = swaption.strike();
+        let forward = strike - underlying.npv / (underlying.fixed_leg_sign * underlying.annuity);
+
+        let cube = self.volatility_store.get_swaption_cube(
+            swaption
+                .forecast_curve_id()
+                .ok_or(AtlasError::ValueNotSetErr("Forecast curve id".to_string()))?,
+        )?;
+        let tenor = cube.swap_tenor(start_date, end_date);
+        let std_dev = cube.std_dev(swaption.expiry(), tenor, strike)?;
+        let option_type = match swaption.swaption_type() {
+            SwaptionType::Payer => OptionType::Call,
+            SwaptionType::Receiver => OptionType::Put,
+        };
+        let price = match cube.volatility_type() {
+            VolatilityType::ShiftedLognormal(shift) => {
+                black_formula(option_type, forward, strike, std_dev, shift)?
+            }
+            VolatilityType::Normal => bachelier_formula(option_type, forward, strike, std_dev)?,
+        };
+
+        let annuity = match swaption.settlement_type() {
+            SettlementType::Physical => underlying.annuity,
+            SettlementType::Cash => {
+                let (_, settlement_discount) =
+                    underlying
+                        .settlement_discount
+                        .ok_or(AtlasError::ValueNotSetErr(
+                            "Disbursement of the underlying swap to settle in cash".to_string(),
+                        ))?;
+                // the fixed coupons are discounted at the forward swap rate
+                underlying.accruals.sort_by_key(|(date, _, _)| *date);
+                let (cash_annuity, _) = underlying.accruals.iter().fold(
+                    (0.0, 1.0),
+                    |(annuity, compound_factor): (f64, f64), (_, notional, year_fraction)| {
+                        let compound_factor =
+                            compound_factor * forward.mul_add(*year_fraction, 1.0);
+                        (
+                            annuity + notional * year_fraction / compound_factor,
+                            compound_factor,
+                        )
+                    },
+                );
+                settlement_discount * cash_annuity
+            }
+        };
+        Ok(swaption.side().sign() * annuity * price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::{
+        cashflows::cashflow::Side,
+        core::marketstore::MarketStore,
+        currencies::enums::Currency,
+        instruments::{instrument::RateType, makeswap::MakeSwap, traits::Structure},
+        models::{simplemodel::SimpleModel, traits::Model},
+        rates::{
+            interestrate::RateDefinition, interestrateindex::iborindex::IborIndex,
+            volatility::swaptionvolatilitycube::SwaptionVolatilityCube,
+            yieldtermstructure::flatforwardtermstructure::FlatForwardTermStructure,
+        },
+        time::{
+            daycounter::DayCounter,
+            enums::{Frequency, TimeUnit},
+            period::Period,
+        },
+        visitors::{
+            fixingvisitor::FixingVisitor, indexingvisitor::IndexingVisitor,
+            npvconstvisitor::NPVConstVisitor, traits::Visit,
+        },
+    };
+
+    fn market_store(volatility: f64, volatility_type: VolatilityType) -> Result<MarketStore> {
+        let reference_date = Date::new(2024, 1, 2);
+        let mut market_store = MarketStore::new(reference_date, Currency::USD);
+        let curve = Arc::new(FlatForwardTermStructure::new(
+            reference_date,
+            0.03,
+            RateDefinition::default(),
+        ));
+        market_store.mut_index_store().add_index(
+            0,
+            Arc::new(RwLock::new(
+                IborIndex::new(reference_date).with_term_structure(curve),
+            )),
+        )?;
+        market_store.mut_volatility_store().add_swaption_cube(
+            0,
+            Arc::new(SwaptionVolatilityCube::new(
+                reference_date,
+                vec![Period::new(1, TimeUnit::Years)],
+                vec![Period::new(5, TimeUnit::Years)],
+                vec![0.03],
+                vec![vec![vec![volatility]]],
+                volatility_type,
+                DayCounter::Actual365,
+            )?),
+        )?;
+        Ok(market_store)
+    }
+
+    fn swaption(
+        strike: f64,
+        fixed_leg_side: Side,
+        settlement_type: SettlementType,
+        market_store: &MarketStore,
+    ) -> Result<(Swaption, Vec<MarketData>)> {
+        let (start_date, end_date) = (Date::new(2025, 1, 2), Date::new(2030, 1, 2));
+        let swap = MakeSwap::new()
+            .with_first_leg_rate_type(RateType::Fixed)
+            .with_first_leg_rate_value(strike)
+            .with_first_leg_rate_definition(RateDefinition::default())
+            .with_first_leg_currency(Currency::USD)
+            .with_first_leg_side(fixed_leg_side)
+            .with_first_leg_structure(Structure::Bullet)
+            .with_first_leg_payment_frequency(Frequency::Semiannual)
+            .with_first_leg_start_date(start_date)
+            .with_first_leg_end_date(end_date)
+            .with_first_leg_notional(1_000_000.0)
+            .with_first_leg_discount_curve_id(Some(0))
+            .with_second_leg_rate_type(RateType::Floating)
+            .with_second_leg_rate_value(0.0)
+            .with_second_leg_rate_definition(RateDefinition::default())
+            .with_second_leg_currency(Currency::USD)
+            .with_second_leg_side(fixed_leg_side.inverse())
+            .with_second_leg_structure(Structure::Bullet)
+            .with_second_leg_payment_frequency(Frequency::Quarterly)
+            .with_second_leg_start_date(start_date)
+            .with_second_leg_end_date(end_date)
+            .with_second_leg_notional(1_000_000.0)
+            .with_second_leg_discount_curve_id(Some(0))
+            .with_second_leg_forecast_curve_id(Some(0))
+            .build()?;
+        let mut swaption = Swaption::new(swap, Date::new(2024, 12, 31), settlement_type)?;
+        let indexer = IndexingVisitor::new();
+        indexer.visit(&mut swaption)?;
+        let market_data = SimpleModel::new(market_store).gen_market_data(&indexer.request())?;
+        FixingVisitor::new(&market_data).visit(&mut swaption)?;
+        Ok((swaption, market_data))
+    }
+
+    #[test]
+    fn test_payer_receiver_parity() -> Result<()> {
+        let strike = 0.032;
+        for (volatility, volatility_type) in [
+            (0.2, VolatilityType::ShiftedLognormal(0.01)),
+            (0.008, VolatilityType::Normal),
+        ] {
+            let store = market_store(volatility, volatility_type)?;
+            let (payer, market_data) =
+                swaption(strike, Side::Pay, SettlementType::Physical, &store)?;
+            let (receiver, _) = swaption(strike, Side::Receive, SettlementType::Physical, &store)?;
+            assert_eq!(payer.swaption_type(), SwaptionType::Payer);
+            assert_eq!(receiver.swaption_type(), SwaptionType::Receiver);
+
+            let visitor = SwaptionNPVConstVisitor::new(&market_data, store.volatility_store());
+            let payer_npv = visitor.visit(&payer)?;
+            let receiver_npv = visitor.visit(&receiver)?;
+            assert!(payer_npv > 0.0 && receiver_npv > 0.0);
+
+            // a long payer and a short receiver swaption is the payer swap
+            let swap_npv = NPVConstVisitor::new(&market_data, true).visit(payer.underlying())?;
+            assert!((payer_npv - receiver_npv - swap_npv).abs() < 1e-6);
+            let short = receiver.with_side(Side::Pay);
+            assert!((visitor.visit(&short)? + receiver_npv).abs() < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cash_settlement() -> Result<()> {
+        let store = market_store(0.008, VolatilityType::Normal)?;
+        let (physical, market_data) = swaption(0.03, Side::Pay, SettlementType::Physical, &store)?;
+        let (cash, _) = swaption(0.03, Side::Pay, SettlementType::Cash, &store)?;
+        let visitor = SwaptionNPVConstVisitor::new(&market_data, store.volatility_store());
+        let physical_npv = visitor.visit(&physical)?;
+        let cash_npv = visitor.visit(&cash)?;
+
+        // on a flat curve, the cash annuity is close to the physical one
+        assert!(cash_npv > 0.0);
+        assert!(((cash_npv - physical_npv) / physical_npv).abs() < 1e-2);
+
+        // without volatility, only the intrinsic value is left
+        let store = market_store(0.0, VolatilityType::Normal)?;
+        let visitor = SwaptionNPVConstVisitor::new(&market_data, store.volatility_store());
+        let swap_npv = NPVConstVisitor::new(&market_data, true).visit(physical.underlying())?;
+        assert!((visitor.visit(&physical)? - swap_npv.max(0.0)).abs() < 1e-6);
+        Ok(())
+    }
+}
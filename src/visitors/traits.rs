@@ -38,11 +38,9 @@ pub trait HasCashflows {
     }
     /// Sets the forecast curve ID for all floating rate coupons.
     fn set_forecast_curve_id(&mut self, id: usize) {
-        self.mut_cashflows().iter_mut().for_each(|cf| {
-            if let Cashflow::FloatingRateCoupon(frcf) = cf {
-                frcf.set_forecast_curve_id(id);
-            }
-        });
+        self.mut_cashflows()
+            .iter_mut()
+            .for_each(|cf| cf.set_forecast_curve_id(id));
     }
 
     /// Finds the next cashflow of the specified type after the reference date.
@@ -77,6 +75,14 @@ pub trait HasCashflows {
                 .filter(|cf| cf.payment_date() > reference_date)
                 .min_by(|cf1, cf2| cf1.payment_date().cmp(&cf2.payment_date()))
                 .copied(),
+
+            CashflowType::OvernightIndexedCoupon => self
+                .cashflows()
+                .iter()
+                .filter(|cf| matches!(cf, Cashflow::OvernightIndexedCoupon(_)))
+                .filter(|cf| cf.payment_date() > reference_date)
+                .min_by(|cf1, cf2| cf1.payment_date().cmp(&cf2.payment_date()))
+                .copied(),
         }
     }
 }